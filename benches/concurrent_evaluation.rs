@@ -0,0 +1,67 @@
+//! Measures the evaluation throughput of a single client shared by concurrent tasks.
+//!
+//! Run with `cargo bench --bench concurrent_evaluation`.
+
+use std::{sync::Arc, time::Instant};
+
+use open_feature::{flags, EvaluationContext, OpenFeature};
+
+const TASKS: usize = 32;
+const EVALUATIONS_PER_TASK: usize = 20_000;
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! {
+            "checkout-v2" => bool: true,
+            "tier" => String: "gold",
+        })
+        .await
+        .unwrap();
+        api.set_evaluation_context(EvaluationContext::default().with_custom_field("region", "eu"))
+            .await;
+
+        let client = Arc::new(api.create_client());
+
+        // Warm up.
+        client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap();
+
+        let started_at = Instant::now();
+
+        let tasks: Vec<_> = (0..TASKS)
+            .map(|task| {
+                let client = client.clone();
+                let context = EvaluationContext::default().with_targeting_key(format!("user-{task}"));
+
+                tokio::spawn(async move {
+                    for _ in 0..EVALUATIONS_PER_TASK {
+                        client
+                            .get_bool_value("checkout-v2", Some(&context), None)
+                            .await
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let elapsed = started_at.elapsed();
+        let evaluations = TASKS * EVALUATIONS_PER_TASK;
+
+        println!(
+            "{evaluations} evaluations by {TASKS} concurrent tasks in {elapsed:?}: {:.0} evaluations/s",
+            evaluations as f64 / elapsed.as_secs_f64()
+        );
+    });
+}
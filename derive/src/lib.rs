@@ -0,0 +1,139 @@
+//! Derive macros for the conversion traits of the OpenFeature Rust SDK. Use them through the
+//! `derive` feature of `open-feature`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Fields, FieldsNamed, GenericArgument,
+    PathArguments, Type,
+};
+
+/// Implement `FromValue` and `TryFrom<StructValue>` for a struct with named fields, reading each
+/// field from the struct field of the same name.
+#[proc_macro_derive(FromValue)]
+pub fn derive_from_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    named_fields(&input)
+        .map(|fields| expand_from_value(&input, fields))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implement `From<T>` for `Value` and `StructValue` for a struct with named fields, writing
+/// each field to the struct field of the same name and leaving out `Option` fields set to `None`.
+#[proc_macro_derive(IntoValue)]
+pub fn derive_into_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    named_fields(&input)
+        .map(|fields| expand_into_value(&input, fields))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn named_fields(input: &DeriveInput) -> Result<&FieldsNamed, Error> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields),
+            _ => Err(Error::new_spanned(
+                &input.ident,
+                "Only structs with named fields are supported",
+            )),
+        },
+        _ => Err(Error::new_spanned(
+            &input.ident,
+            "Only structs with named fields are supported",
+        )),
+    }
+}
+
+fn expand_from_value(input: &DeriveInput, fields: &FieldsNamed) -> TokenStream2 {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref().expect("Named field");
+        let ty = &field.ty;
+        let name = ident.to_string();
+
+        quote! {
+            #ident: <#ty as ::open_feature::FromValue>::from_field(&mut value.fields, #name)?
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::core::convert::TryFrom<::open_feature::StructValue> for #ident #ty_generics #where_clause {
+            type Error = ::open_feature::EvaluationError;
+
+            fn try_from(mut value: ::open_feature::StructValue) -> ::core::result::Result<Self, Self::Error> {
+                ::core::result::Result::Ok(Self {
+                    #(#fields,)*
+                })
+            }
+        }
+
+        impl #impl_generics ::open_feature::FromValue for #ident #ty_generics #where_clause {
+            fn from_value(value: ::open_feature::Value) -> ::open_feature::EvaluationResult<Self> {
+                let value = <::open_feature::StructValue as ::open_feature::FromValue>::from_value(value)?;
+
+                <Self as ::core::convert::TryFrom<::open_feature::StructValue>>::try_from(value)
+            }
+        }
+    }
+}
+
+fn expand_into_value(input: &DeriveInput, fields: &FieldsNamed) -> TokenStream2 {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = fields.named.iter().map(|field| {
+        let ident = field.ident.as_ref().expect("Named field");
+        let name = ident.to_string();
+
+        if is_option(&field.ty) {
+            quote! {
+                if let ::core::option::Option::Some(field) = value.#ident {
+                    fields.add_field(#name, field);
+                }
+            }
+        } else {
+            quote! {
+                fields.add_field(#name, value.#ident);
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::open_feature::StructValue #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                let mut fields = ::open_feature::StructValue::default();
+                #(#fields)*
+                fields
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::open_feature::Value #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                ::open_feature::Value::Struct(::core::convert::From::from(value))
+            }
+        }
+    }
+}
+
+/// Return `true` if `ty` is spelled as an `Option<T>`.
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    path.path.segments.last().map_or(false, |segment| {
+        segment.ident == "Option"
+            && matches!(
+                &segment.arguments,
+                PathArguments::AngleBracketed(arguments)
+                    if matches!(arguments.args.first(), Some(GenericArgument::Type(_)))
+            )
+    })
+}
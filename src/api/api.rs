@@ -1,13 +1,16 @@
+use std::time::Duration;
+
 use lazy_static::lazy_static;
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use crate::{
     provider::{FeatureProvider, ProviderMetadata},
-    Client, EvaluationContext,
+    Client, EvaluationContext, Hook, KillSwitches, SdkError, StaticContextClient,
 };
 
 use super::{
-    global_evaluation_context::GlobalEvaluationContext, provider_registry::ProviderRegistry,
+    global_evaluation_context::GlobalEvaluationContext, global_hooks::GlobalHooks,
+    provider_registry::ProviderRegistry,
 };
 
 lazy_static! {
@@ -18,14 +21,31 @@ lazy_static! {
 
 /// THE struct of the OpenFeature API.
 /// Access it via the [`SINGLETON`] instance.
-#[derive(Default)]
 pub struct OpenFeature {
     evaluation_context: GlobalEvaluationContext,
 
+    hooks: GlobalHooks,
+
     provider_registry: ProviderRegistry,
+
+    kill_switches: KillSwitches,
+}
+
+impl Default for OpenFeature {
+    fn default() -> Self {
+        Self {
+            evaluation_context: GlobalEvaluationContext::default(),
+            hooks: GlobalHooks::default(),
+            provider_registry: ProviderRegistry::default(),
+            kill_switches: KillSwitches::from_env(),
+        }
+    }
 }
 
 impl OpenFeature {
+    /// The time [`Self::shutdown`] gives the providers and hooks to shut down.
+    pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
     /// Get the singleton of [`OpenFeature`].
     pub async fn singleton() -> RwLockReadGuard<'static, Self> {
         SINGLETON.read().await
@@ -37,47 +57,70 @@ impl OpenFeature {
     }
 
     /// Set the global evaluation context.
+    // Kept async for compatibility.
+    #[allow(clippy::unused_async)]
     pub async fn set_evaluation_context(&mut self, evaluation_context: EvaluationContext) {
-        let mut context = self.evaluation_context.get_mut().await;
+        self.evaluation_context.set(evaluation_context);
+    }
 
-        context.targeting_key = evaluation_context.targeting_key;
-        context.custom_fields = evaluation_context.custom_fields;
+    /// Add `hook` to the hooks run for the evaluations of all the clients, before the hooks of
+    /// the clients in the `before` stage.
+    ///
+    /// Hooks buffering data, such as telemetry or exposure events, are expected to be added here
+    /// so that [`Self::shutdown`] flushes them.
+    pub fn add_hook<T: Hook>(&mut self, hook: T) {
+        self.hooks.add(hook);
     }
 
-    /// Set the default provider.
-    pub async fn set_provider<T: FeatureProvider>(&mut self, provider: T) {
-        self.provider_registry.set_default(provider).await;
+    /// Initialize `provider`, and set it as the default provider.
+    /// The current provider is kept if it fails to initialize.
+    pub async fn set_provider<T: FeatureProvider>(&mut self, provider: T) -> Result<(), SdkError> {
+        self.provider_registry.set_default(provider).await
     }
 
-    /// Bind the given `provider` to the corresponding `name`.
-    pub async fn set_named_provider<T: FeatureProvider>(&mut self, name: &str, provider: T) {
-        self.provider_registry.set_named(name, provider).await;
+    /// Initialize `provider`, and bind it to the corresponding `name`.
+    /// The current provider is kept if it fails to initialize.
+    pub async fn set_named_provider<T: FeatureProvider>(
+        &mut self,
+        name: &str,
+        provider: T,
+    ) -> Result<(), SdkError> {
+        self.provider_registry.set_named(name, provider).await
     }
 
     /// Return the metadata of default (unnamed) provider.
+    // Kept async for compatibility.
+    #[allow(clippy::unused_async)]
     pub async fn provider_metadata(&self) -> ProviderMetadata {
         self.provider_registry
             .get_default()
-            .await
             .get()
             .metadata()
             .clone()
     }
 
     /// Return the metadata of named provider (a provider bound to clients with this name).
+    // Kept async for compatibility.
+    #[allow(clippy::unused_async)]
     pub async fn named_provider_metadata(&self, name: &str) -> Option<ProviderMetadata> {
         self.provider_registry
             .get_named(name)
-            .await
             .map(|provider| provider.get().metadata().clone())
     }
 
+    /// Return the kill switches forcing flags to fixed values for all the clients.
+    pub fn kill_switches(&self) -> &KillSwitches {
+        &self.kill_switches
+    }
+
     /// Create a new client with default name.
     pub fn create_client(&self) -> Client {
         Client::new(
             String::default(),
             self.evaluation_context.clone(),
+            self.hooks.clone(),
             self.provider_registry.clone(),
+            self.kill_switches.clone(),
         )
     }
 
@@ -87,13 +130,106 @@ impl OpenFeature {
         Client::new(
             name.to_string(),
             self.evaluation_context.clone(),
+            self.hooks.clone(),
+            self.provider_registry.clone(),
+            self.kill_switches.clone(),
+        )
+    }
+
+    /// Create a new [`StaticContextClient`] with default name, resolving all the flags for
+    /// `evaluation_context` up front.
+    pub async fn create_static_context_client(
+        &self,
+        evaluation_context: EvaluationContext,
+    ) -> StaticContextClient {
+        self.create_named_static_context_client("", evaluation_context)
+            .await
+    }
+
+    /// Create a new [`StaticContextClient`] with specific `name`, resolving all the flags for
+    /// `evaluation_context` up front.
+    /// It will use the provider bound to this name, if any.
+    pub async fn create_named_static_context_client(
+        &self,
+        name: &str,
+        evaluation_context: EvaluationContext,
+    ) -> StaticContextClient {
+        StaticContextClient::new(
+            name,
+            self.evaluation_context.clone(),
             self.provider_registry.clone(),
+            self.kill_switches.clone(),
+            evaluation_context,
         )
+        .await
     }
 
-    /// Drops all the registered providers.
+    /// Wait until the default provider is ready, or fail once `timeout` is up, as described by
+    /// [`Client::wait_for_ready`].
+    pub async fn wait_for_ready(&self, timeout: Duration) -> Result<(), SdkError> {
+        self.create_client().wait_for_ready(timeout).await
+    }
+
+    /// Same as [`Self::wait_for_ready`] for the provider bound to `name`, or the default one if
+    /// none is.
+    pub async fn wait_for_named_ready(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> Result<(), SdkError> {
+        self.create_named_client(name).wait_for_ready(timeout).await
+    }
+
+    /// Same as [`Self::wait_for_ready`], blocking the current thread, as described by
+    /// [`Client::wait_for_ready_blocking`].
+    pub fn wait_for_ready_blocking(&self, timeout: Duration) -> Result<(), SdkError> {
+        self.create_client().wait_for_ready_blocking(timeout)
+    }
+
+    /// Shut down and drop all the registered providers, cancelling their pollers, then shut down
+    /// and drop the hooks of the API, flushing their buffered data, giving them up to
+    /// [`Self::DEFAULT_SHUTDOWN_TIMEOUT`].
+    ///
+    /// Call it before the process exits, such as on pod termination, so that no data is lost.
     pub async fn shutdown(&mut self) {
-        self.provider_registry.clear().await;
+        // Whatever did not complete in time is given up on.
+        let _ = self
+            .shutdown_with_timeout(Self::DEFAULT_SHUTDOWN_TIMEOUT)
+            .await;
+    }
+
+    /// Same as [`Self::shutdown`], failing with [`SdkError::ShutdownTimeout`] if the providers
+    /// or the hooks did not shut down within `timeout`. Both are removed from the API at once,
+    /// and whatever did not shut down in time is given up on.
+    ///
+    /// Providers shut down at the same time, within `timeout`, and so do hooks, once all the
+    /// providers are or gave up, within `timeout` of their own, so that slow providers don't
+    /// keep hooks from flushing their buffered data.
+    pub async fn shutdown_with_timeout(&mut self, timeout: Duration) -> Result<(), SdkError> {
+        let providers = self.provider_registry.take();
+        let hooks = self.hooks.take();
+
+        let providers = tokio::time::timeout(timeout, ProviderRegistry::shut_down(providers)).await;
+        let hooks = tokio::time::timeout(timeout, GlobalHooks::shut_down(hooks)).await;
+
+        match (providers, hooks) {
+            (Ok(()), Ok(())) => Ok(()),
+            _ => Err(SdkError::ShutdownTimeout { timeout }),
+        }
+    }
+
+    /// Same as [`Self::shutdown_with_timeout`], blocking the current thread, such as in a
+    /// synchronous signal handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an asynchronous runtime.
+    pub fn shutdown_blocking(&mut self, timeout: Duration) -> Result<(), SdkError> {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("Failed to build a runtime to shut down")
+            .block_on(self.shutdown_with_timeout(timeout))
     }
 }
 
@@ -103,8 +239,12 @@ mod tests {
 
     use super::*;
     use crate::{
-        provider::{MockFeatureProvider, NoOpProvider, ResolutionDetails},
-        EvaluationContextFieldValue,
+        provider::{
+            MockFeatureProvider, NoOpProvider, ProviderEvent, ProviderEventType, ProviderStatus,
+            ResolutionDetails,
+        },
+        EvaluationContextFieldValue, EvaluationErrorCode, EvaluationResult, ProviderError,
+        ProviderErrorKind, StructValue,
     };
     use mockall::predicate;
     use spec::spec;
@@ -123,7 +263,8 @@ mod tests {
             OpenFeature::singleton_mut()
                 .await
                 .set_provider(NoOpProvider::default())
-                .await;
+                .await
+                .unwrap();
         });
 
         let reader2 = tokio::spawn(async move {
@@ -155,12 +296,15 @@ mod tests {
 
         // Set the new provider and ensure the value comes from it.
         let mut provider = MockFeatureProvider::new();
-        provider.expect_initialize().returning(|_| {});
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
         provider
             .expect_resolve_int_value()
             .return_const(Ok(ResolutionDetails::new(200)));
 
-        api.set_provider(provider).await;
+        api.set_provider(provider).await.unwrap();
 
         assert_eq!(
             client.get_int_value("some-key", None, None).await.unwrap(),
@@ -175,18 +319,133 @@ mod tests {
     #[tokio::test]
     async fn set_provider_invoke_initialize() {
         let mut provider = MockFeatureProvider::new();
-        provider.expect_initialize().returning(|_| {}).once();
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(())).once();
+        provider.expect_event_emitter().returning(|| None);
 
         let mut api = OpenFeature::default();
-        api.set_provider(provider).await;
+        api.set_provider(provider).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn set_provider_failing_to_initialize() {
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ()).once();
+        provider.expect_initialize().returning(|_| {
+            Err(ProviderError::new(
+                ProviderErrorKind::Unauthorized,
+                "Invalid API key",
+            ))
+        });
+
+        let mut api = OpenFeature::default();
+        let error = api.set_provider(provider).await.unwrap_err();
+
+        assert!(matches!(
+            &error,
+            SdkError::ProviderInitialization { source, .. }
+                if source.kind == ProviderErrorKind::Unauthorized
+                    && source.message == "Invalid API key"
+        ));
+
+        // The current provider is kept.
+        assert_eq!(api.provider_metadata().await.name, "No-op Provider");
+    }
+
+    #[tokio::test]
+    async fn set_provider_panicking_in_initialize() {
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider
+            .expect_initialize()
+            .returning(|_| panic!("Invalid API key"));
+
+        let mut api = OpenFeature::default();
+        let error = api.set_provider(provider).await.unwrap_err();
+
+        assert!(matches!(
+            &error,
+            SdkError::ProviderInitialization { source, .. }
+                if source.kind == ProviderErrorKind::Other("PANIC".to_string())
+                    && source.message == "Invalid API key"
+        ));
+        assert_eq!(api.provider_metadata().await.name, "No-op Provider");
+    }
+
+    #[tokio::test]
+    async fn track_provider_status() {
+        let mut api = OpenFeature::default();
+        let provider = crate::flags! { "enabled" => bool: true };
+        let events = provider.event_emitter().unwrap();
+        api.set_provider(provider).await.unwrap();
+
+        let client = api.create_client();
+        let provider = api.provider_registry.get_default().get();
+        let emit = |event_type, error_code| {
+            let mut event = ProviderEvent::builder()
+                .event_type(event_type)
+                .provider_name("In-memory Provider")
+                .build();
+            event.error_code = error_code;
+            events.emit(event);
+            tokio::task::yield_now()
+        };
+
+        assert_eq!(provider.status(), ProviderStatus::Ready);
+
+        emit(ProviderEventType::Error, None).await;
+        assert_eq!(provider.status(), ProviderStatus::Error);
+        assert!(client.get_bool_value("enabled", None, None).await.unwrap());
+
+        emit(ProviderEventType::Stale, None).await;
+        assert_eq!(provider.status(), ProviderStatus::STALE);
+
+        emit(ProviderEventType::Ready, None).await;
+        assert_eq!(provider.status(), ProviderStatus::Ready);
+
+        emit(
+            ProviderEventType::Error,
+            Some(EvaluationErrorCode::ProviderFatal),
+        )
+        .await;
+        assert_eq!(provider.status(), ProviderStatus::Fatal);
+        assert_eq!(
+            client
+                .get_bool_details("enabled", None, None)
+                .await
+                .unwrap_err()
+                .code,
+            EvaluationErrorCode::ProviderFatal
+        );
+
+        // A fatal provider stays so until replaced.
+        emit(ProviderEventType::Ready, None).await;
+        assert_eq!(provider.status(), ProviderStatus::Fatal);
+
+        api.set_provider(crate::flags! { "enabled" => bool: true })
+            .await
+            .unwrap();
+        assert_eq!(provider.status(), ProviderStatus::NotReady);
+        assert!(client.get_bool_value("enabled", None, None).await.unwrap());
     }
 
     #[spec(
         number = "1.1.2.3",
         text = "The provider mutator function MUST invoke the shutdown function on the previously registered provider once it's no longer being used to resolve flag values."
     )]
-    #[test]
-    fn invoke_shutdown_on_old_provider_checked_by_type_system() {}
+    #[tokio::test]
+    async fn invoke_shutdown_on_old_provider() {
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
+        provider.expect_shutdown().returning(|| ()).once();
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider).await.unwrap();
+        api.set_provider(NoOpProvider::default()).await.unwrap();
+    }
 
     #[spec(
         number = "1.1.3",
@@ -202,11 +461,14 @@ mod tests {
 
         // Bind provider to the same name.
         let mut provider = MockFeatureProvider::new();
-        provider.expect_initialize().returning(|_| {});
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
         provider
             .expect_resolve_int_value()
             .return_const(Ok(ResolutionDetails::new(30)));
-        api.set_named_provider("test", provider).await;
+        api.set_named_provider("test", provider).await.unwrap();
 
         // Ensure the new provider is used for existing clients.
         assert_eq!(client.get_int_value("", None, None).await, Ok(30));
@@ -223,9 +485,10 @@ mod tests {
     #[tokio::test]
     async fn provider_metadata() {
         let mut api = OpenFeature::default();
-        api.set_provider(NoOpProvider::default()).await;
+        api.set_provider(NoOpProvider::default()).await.unwrap();
         api.set_named_provider("test", NoOpProvider::default())
-            .await;
+            .await
+            .unwrap();
 
         assert_eq!(api.provider_metadata().await.name, "No-op Provider");
         assert_eq!(
@@ -245,19 +508,31 @@ mod tests {
         let mut api = OpenFeature::default();
 
         let mut default_provider = MockFeatureProvider::new();
-        default_provider.expect_initialize().returning(|_| {});
+        default_provider
+            .expect_status()
+            .returning(|| ProviderStatus::Ready);
+        default_provider.expect_shutdown().returning(|| ());
+        default_provider.expect_initialize().returning(|_| Ok(()));
+        default_provider.expect_event_emitter().returning(|| None);
         default_provider
             .expect_resolve_int_value()
             .return_const(Ok(ResolutionDetails::new(100)));
 
         let mut named_provider = MockFeatureProvider::new();
-        named_provider.expect_initialize().returning(|_| {});
+        named_provider
+            .expect_status()
+            .returning(|| ProviderStatus::Ready);
+        named_provider.expect_shutdown().returning(|| ());
+        named_provider.expect_initialize().returning(|_| Ok(()));
+        named_provider.expect_event_emitter().returning(|| None);
         named_provider
             .expect_resolve_int_value()
             .return_const(Ok(ResolutionDetails::new(200)));
 
-        api.set_provider(default_provider).await;
-        api.set_named_provider("test", named_provider).await;
+        api.set_provider(default_provider).await.unwrap();
+        api.set_named_provider("test", named_provider)
+            .await
+            .unwrap();
 
         let client = api.create_client();
         assert_eq!(client.get_int_value("key", None, None).await.unwrap(), 100);
@@ -283,10 +558,11 @@ mod tests {
     #[tokio::test]
     async fn set_provider_should_block() {
         let mut api = OpenFeature::default();
-        api.set_provider(NoOpProvider::default()).await;
+        api.set_provider(NoOpProvider::default()).await.unwrap();
 
         api.set_named_provider("named", NoOpProvider::default())
-            .await;
+            .await
+            .unwrap();
     }
 
     #[spec(
@@ -295,12 +571,151 @@ mod tests {
     )]
     #[tokio::test]
     async fn shutdown() {
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
+        provider.expect_shutdown().returning(|| ()).once();
+
         let mut api = OpenFeature::default();
-        api.set_provider(NoOpProvider::default()).await;
+        api.set_provider(provider).await.unwrap();
 
         api.shutdown().await;
     }
 
+    #[spec(
+        number = "4.4.1",
+        text = "The API, Client, Provider, and invocation MUST have a method for registering hooks."
+    )]
+    #[tokio::test]
+    async fn shutdown_drains_hooks() {
+        let mut hook = crate::MockHook::new();
+        hook.expect_before().returning(|_| Ok(None)).once();
+        hook.expect_after().returning(|_, _| Ok(())).once();
+        hook.expect_finally().returning(|_| ()).once();
+        hook.expect_shutdown().returning(|| ()).once();
+
+        let mut api = OpenFeature::default();
+        api.set_provider(crate::flags! { "enabled" => bool: true })
+            .await
+            .unwrap();
+        api.add_hook(hook);
+
+        let client = api.create_client();
+        assert!(client.get_bool_value("enabled", None, None).await.unwrap());
+
+        api.shutdown_with_timeout(Duration::from_secs(1))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn shutdown_timeout() {
+        struct SlowHook;
+
+        #[async_trait::async_trait]
+        impl Hook for SlowHook {
+            async fn shutdown(&self) {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+        }
+
+        let mut api = OpenFeature::default();
+        api.add_hook(SlowHook);
+
+        let error = api
+            .shutdown_with_timeout(Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, SdkError::ShutdownTimeout { .. }));
+
+        // The hooks are dropped regardless.
+        api.shutdown_with_timeout(Duration::from_millis(10))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn shutdown_hooks_after_slow_providers() {
+        struct SlowProvider(ProviderMetadata);
+
+        #[async_trait::async_trait]
+        impl FeatureProvider for SlowProvider {
+            async fn shutdown(&self) {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+
+            fn metadata(&self) -> &ProviderMetadata {
+                &self.0
+            }
+
+            async fn resolve_bool_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> EvaluationResult<ResolutionDetails<bool>> {
+                unimplemented!()
+            }
+
+            async fn resolve_int_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> EvaluationResult<ResolutionDetails<i64>> {
+                unimplemented!()
+            }
+
+            async fn resolve_float_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> EvaluationResult<ResolutionDetails<f64>> {
+                unimplemented!()
+            }
+
+            async fn resolve_string_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> EvaluationResult<ResolutionDetails<String>> {
+                unimplemented!()
+            }
+
+            async fn resolve_struct_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+                unimplemented!()
+            }
+        }
+
+        /// Flushes its buffer on shutdown.
+        struct BufferingHook(Arc<std::sync::Mutex<Vec<String>>>);
+
+        #[async_trait::async_trait]
+        impl Hook for BufferingHook {
+            async fn shutdown(&self) {
+                self.0.lock().unwrap().push("flushed".to_string());
+            }
+        }
+
+        let flushed = Arc::default();
+        let mut api = OpenFeature::default();
+        api.set_provider(SlowProvider(ProviderMetadata::new("Slow Provider")))
+            .await
+            .unwrap();
+        api.add_hook(BufferingHook(Arc::clone(&flushed)));
+
+        let error = api
+            .shutdown_with_timeout(Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, SdkError::ShutdownTimeout { .. }));
+
+        // The hooks were given time of their own.
+        assert_eq!(*flushed.lock().unwrap(), vec!["flushed".to_string()]);
+    }
+
     #[spec(
         number = "3.2.1.1",
         text = "The API, Client and invocation MUST have a method for supplying evaluation context."
@@ -313,7 +728,10 @@ mod tests {
     async fn evaluation_context() {
         // Setup expectations for different evaluation contexts.
         let mut provider = MockFeatureProvider::new();
-        provider.expect_initialize().returning(|_| {});
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
 
         provider
             .expect_resolve_int_value()
@@ -353,7 +771,7 @@ mod tests {
 
         // Register the provider.
         let mut api = OpenFeature::default();
-        api.set_provider(provider).await;
+        api.set_provider(provider).await.unwrap();
 
         // Set global client context and ensure its values are picked up.
         let global_evaluation_context = EvaluationContext::default()
@@ -413,7 +831,7 @@ mod tests {
         let mut api = OpenFeature::singleton_mut().await;
 
         // Set the default (unnamed) provider.
-        api.set_provider(NoOpProvider::default()).await;
+        api.set_provider(NoOpProvider::default()).await.unwrap();
 
         // Create an unnamed client.
         let client = api.create_client();
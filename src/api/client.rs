@@ -1,16 +1,37 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tokio::{sync::watch, task::JoinHandle, time::timeout};
 
 use crate::{
-    provider::{FeatureProvider, ResolutionDetails},
-    EvaluationContext, EvaluationDetails, EvaluationError, EvaluationErrorCode, EvaluationOptions,
-    EvaluationResult, StructValue,
+    provider::{
+        FeatureProvider, FlagType, FlagValue, ProviderEvent, ProviderEventType, ProviderStatus,
+        ResolutionDetails,
+    },
+    ContextSupplier, EvaluationContext, EvaluationDetails, EvaluationError, EvaluationErrorCode,
+    EvaluationOptions, EvaluationResult, EvaluationTrace, Hook, HookContext, HookData, HookHints,
+    HookStage, HookTrace, ProviderError, ProviderErrorKind, SdkError, StructValue,
+    TrackingEventDetails, TransactionContext, Value,
 };
 
 use super::{
-    global_evaluation_context::GlobalEvaluationContext, provider_registry::ProviderRegistry,
+    flag_batch::FlagBatch,
+    flag_cache::FlagCache,
+    flag_snapshot::FlagSnapshot,
+    flag_stats::{FlagStats, FlagStatsRecorder},
+    flag_watch::FlagWatch,
+    global_evaluation_context::GlobalEvaluationContext,
+    global_hooks::GlobalHooks,
+    kill_switches::KillSwitches,
+    provider_events::ProviderEventListener,
+    provider_registry::ProviderRegistry,
 };
 
 /// The metadata of OpenFeature client.
+#[derive(Clone, Debug)]
 pub struct ClientMetadata {
     /// The name of client.
     pub name: String,
@@ -18,25 +39,51 @@ pub struct ClientMetadata {
 
 /// The OpenFeature client.
 /// Create it through the [`OpenFeature`] struct.
+#[derive(Clone)]
 pub struct Client {
     metadata: ClientMetadata,
     provider_registry: ProviderRegistry,
     evaluation_context: EvaluationContext,
     global_evaluation_context: GlobalEvaluationContext,
+    global_hooks: GlobalHooks,
+    hooks: Vec<Arc<dyn Hook>>,
+    context_supplier: Option<Arc<dyn ContextSupplier>>,
+    stats: FlagStatsRecorder,
+    kill_switches: KillSwitches,
+    cache: Option<FlagCache>,
+    snapshot: Option<FlagSnapshot>,
+    timeout: Option<Duration>,
 }
 
 impl Client {
+    /// The time [`Self::on_flag_change`] waits without change before re-evaluating a flag.
+    pub const FLAG_CHANGE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+    /// The interval [`Self::wait_for_ready`] checks the status of the provider at, when it does
+    /// not emit events in between.
+    pub const READY_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
     /// Create a new [`Client`] instance.
     pub fn new(
         name: impl Into<String>,
         global_evaluation_context: GlobalEvaluationContext,
+        global_hooks: GlobalHooks,
         provider_registry: ProviderRegistry,
+        kill_switches: KillSwitches,
     ) -> Self {
         Self {
             metadata: ClientMetadata { name: name.into() },
             global_evaluation_context,
+            global_hooks,
             provider_registry,
             evaluation_context: EvaluationContext::default(),
+            hooks: Vec::new(),
+            context_supplier: None,
+            stats: FlagStatsRecorder::default(),
+            kill_switches,
+            cache: None,
+            snapshot: None,
+            timeout: None,
         }
     }
 
@@ -50,6 +97,353 @@ impl Client {
         self.evaluation_context = evaluation_context;
     }
 
+    /// Return the statistics of the flags evaluated so far by this client and its clones, sorted
+    /// by flag key.
+    pub fn stats(&self) -> Vec<FlagStats> {
+        self.stats.snapshot()
+    }
+
+    /// Set the supplier of the ambient evaluation context consulted on every evaluation, and
+    /// return the client.
+    #[must_use]
+    pub fn with_context_supplier<T: ContextSupplier>(mut self, context_supplier: T) -> Self {
+        self.set_context_supplier(context_supplier);
+        self
+    }
+
+    /// Set the supplier of the ambient evaluation context consulted on every evaluation.
+    /// Its context takes precedence over the global one, and is overridden by the client and
+    /// invocation ones.
+    pub fn set_context_supplier<T: ContextSupplier>(&mut self, context_supplier: T) {
+        self.context_supplier = Some(Arc::new(context_supplier));
+    }
+
+    /// Cache the successful resolutions of the client and its clones for `ttl`, up to
+    /// `max_entries`, and return the client.
+    #[must_use]
+    pub fn with_cache(mut self, ttl: Duration, max_entries: usize) -> Self {
+        self.set_cache(ttl, max_entries);
+        self
+    }
+
+    /// Cache the successful resolutions of the client and its clones for `ttl`, up to
+    /// `max_entries`, keyed by flag key and evaluation context. Cached resolutions are returned
+    /// with reason [`EvaluationReason::Cached`](crate::EvaluationReason::Cached), still running
+    /// the hooks.
+    ///
+    /// Entries are invalidated when the provider emits `PROVIDER_CONFIGURATION_CHANGED` for
+    /// their flag, or when it is replaced.
+    pub fn set_cache(&mut self, ttl: Duration, max_entries: usize) {
+        self.cache = Some(FlagCache::new(ttl, max_entries));
+    }
+
+    /// Fail the resolutions of the client and its clones taking longer than `timeout`, and
+    /// return the client.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.set_timeout(Some(timeout));
+        self
+    }
+
+    /// Fail the resolutions of the client and its clones taking longer than `timeout`, if any,
+    /// unless overridden by [`EvaluationOptions::timeout`], so that a slow flag backend cannot
+    /// stall request handling.
+    ///
+    /// Resolutions running out of time fail with a
+    /// [`ProviderErrorKind::Timeout`](crate::ProviderErrorKind::Timeout) provider error, reported
+    /// as `GENERAL`, for the caller to fall back to its default value. Hooks are not subject to
+    /// the timeout.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Remove all the cached resolutions, if caching is enabled.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Return a clone of the client evaluating flags against a frozen snapshot, so that a
+    /// single request sees consistent flag values even if the configuration changes meanwhile.
+    ///
+    /// The snapshot pins the current provider and global evaluation context, and keeps
+    /// resolving flags with the provider even once it is replaced. Every flag then resolves to
+    /// the same details, or the same error, as the first time it is resolved through the
+    /// snapshot or its clones for the same evaluation context. Hooks still run on every
+    /// evaluation.
+    #[must_use]
+    pub fn snapshot(&self) -> Self {
+        let provider = match &self.snapshot {
+            Some(snapshot) => snapshot.provider(),
+            // Not shut down along with the registered provider.
+            None => self.provider_registry.get(&self.metadata.name).untracked(),
+        };
+        let global_evaluation_context = match &self.snapshot {
+            Some(snapshot) => snapshot.global_evaluation_context().clone(),
+            None => (*self.global_evaluation_context.get()).clone(),
+        };
+
+        Self {
+            snapshot: Some(FlagSnapshot::new(provider, global_evaluation_context)),
+            ..self.clone()
+        }
+    }
+
+    /// Append given `hook` to the client and return it.
+    #[must_use]
+    pub fn with_hook<T: Hook>(mut self, hook: T) -> Self {
+        self.add_hook(hook);
+        self
+    }
+
+    /// Append given `hook` to the client.
+    /// Hooks run their `before` stage in the order they are added, after the hooks of the API,
+    /// and the other stages in reverse order.
+    pub fn add_hook<T: Hook>(&mut self, hook: T) {
+        self.hooks.push(Arc::new(hook));
+    }
+
+    /// Subscribe to the changes of given `flag_key`, as signaled by the provider bound to the
+    /// client.
+    pub fn watch(&self, flag_key: impl Into<String>) -> FlagWatch {
+        FlagWatch::new(flag_key.into(), self.event_listener())
+    }
+
+    /// Create an empty set of flags evaluated with `evaluation_context`, to be re-evaluated
+    /// together whenever the global evaluation context or the flag configuration changes.
+    pub fn flag_batch(&self, evaluation_context: Option<&EvaluationContext>) -> FlagBatch {
+        FlagBatch::new(
+            self.clone(),
+            evaluation_context.cloned(),
+            self.event_listener(),
+            self.global_evaluation_context.subscribe_changes(),
+        )
+    }
+
+    /// Evaluate given `flag_key` as a bool value, and keep the returned receiver up to date as
+    /// the flag changes. `default_value` is used whenever the evaluation fails.
+    ///
+    /// The flag is re-evaluated in a background task on every change signaled by the provider,
+    /// until all the receivers are dropped or the providers are shut down.
+    pub async fn bool_stream(
+        &self,
+        flag_key: impl Into<String>,
+        default_value: bool,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> watch::Receiver<bool> {
+        self.value_stream(flag_key.into(), default_value, evaluation_context)
+            .await
+    }
+
+    /// Invoke `callback` with the previous and the new value of given `flag_key` whenever it
+    /// changes, as evaluated as `T` with `evaluation_context`.
+    ///
+    /// The flag is re-evaluated in a background task after the changes signaled by the provider,
+    /// once no other change was signaled for [`Self::FLAG_CHANGE_DEBOUNCE`]. The previous value
+    /// is `None` if the flag never resolved successfully before, and failed evaluations are
+    /// ignored. The task ends once the providers are shut down, or when aborted through the
+    /// returned handle.
+    pub async fn on_flag_change<T, F>(
+        &self,
+        flag_key: impl Into<String>,
+        evaluation_context: Option<&EvaluationContext>,
+        callback: F,
+    ) -> JoinHandle<()>
+    where
+        T: FlagValue + PartialEq,
+        F: FnMut(Option<&T>, &T) + Send + 'static,
+    {
+        self.on_flag_change_debounced(
+            flag_key,
+            evaluation_context,
+            Self::FLAG_CHANGE_DEBOUNCE,
+            callback,
+        )
+        .await
+    }
+
+    /// Same as [`Self::on_flag_change`], waiting for `debounce` without change before
+    /// re-evaluating the flag.
+    pub async fn on_flag_change_debounced<T, F>(
+        &self,
+        flag_key: impl Into<String>,
+        evaluation_context: Option<&EvaluationContext>,
+        debounce: Duration,
+        mut callback: F,
+    ) -> JoinHandle<()>
+    where
+        T: FlagValue + PartialEq,
+        F: FnMut(Option<&T>, &T) + Send + 'static,
+    {
+        let flag_key = flag_key.into();
+        let mut flag_watch = self.watch(flag_key.clone());
+        flag_watch.listen();
+
+        let evaluation_context = evaluation_context.cloned();
+        let mut current = self
+            .evaluate::<T>(&flag_key, evaluation_context.as_ref(), None)
+            .await
+            .ok()
+            .map(|details| details.value);
+
+        let client = self.clone();
+
+        tokio::spawn(async move {
+            while flag_watch.changed().await.is_some() {
+                // Wait for a burst of changes to settle.
+                loop {
+                    match timeout(debounce, flag_watch.changed()).await {
+                        Ok(Some(_)) => {}
+                        Ok(None) => return,
+                        Err(_) => break,
+                    }
+                }
+
+                let Ok(details) = client
+                    .evaluate::<T>(&flag_key, evaluation_context.as_ref(), None)
+                    .await
+                else {
+                    continue;
+                };
+
+                if current.as_ref() != Some(&details.value) {
+                    callback(current.as_ref(), &details.value);
+                    current = Some(details.value);
+                }
+            }
+        })
+    }
+
+    /// Invoke `handler` with every event of given `event_type` emitted by the provider bound to
+    /// the client, following the provider when it is replaced.
+    ///
+    /// If `event_type` is `PROVIDER_READY` and the provider is already ready, `handler` is
+    /// invoked immediately. The task ends once the providers are shut down, or when aborted
+    /// through the returned handle.
+    // Kept async for compatibility.
+    #[allow(clippy::unused_async)]
+    pub async fn add_handler<F>(
+        &self,
+        event_type: ProviderEventType,
+        mut handler: F,
+    ) -> JoinHandle<()>
+    where
+        F: FnMut(&ProviderEvent) + Send + 'static,
+    {
+        let mut listener = self.event_listener();
+        listener.listen();
+
+        if event_type == ProviderEventType::Ready {
+            let provider = self.get_provider();
+
+            if provider.status() == ProviderStatus::Ready {
+                handler(
+                    &ProviderEvent::builder()
+                        .event_type(ProviderEventType::Ready)
+                        .provider_name(provider.metadata().name.clone())
+                        .build(),
+                );
+            }
+        }
+
+        tokio::spawn(async move {
+            while let Some(event) = listener.recv().await {
+                if event.event_type == event_type {
+                    handler(&event);
+                }
+            }
+        })
+    }
+
+    /// Wait until the provider bound to the client is ready, so that traffic can be gated on the
+    /// availability of flags at startup.
+    ///
+    /// Fail with [`SdkError::ReadyTimeout`] if the provider is still not ready after `timeout`,
+    /// or with [`SdkError::ProviderFatal`] as soon as it is in an irrecoverable error state. The
+    /// status is checked on every event of the provider, and every
+    /// [`Self::READY_POLL_INTERVAL`] for the providers not emitting events.
+    pub async fn wait_for_ready(&self, timeout: Duration) -> Result<(), SdkError> {
+        let mut listener = self.event_listener();
+        let listening = listener.listen();
+
+        let wait = async {
+            loop {
+                let provider = self.get_provider();
+
+                match provider.status() {
+                    ProviderStatus::Ready => return Ok(()),
+                    ProviderStatus::Fatal => {
+                        return Err(SdkError::ProviderFatal {
+                            provider_name: provider.metadata().name.clone(),
+                        })
+                    }
+                    _ => {}
+                }
+
+                if listening {
+                    // Either an event or the poll interval elapsed.
+                    let _ = tokio::time::timeout(Self::READY_POLL_INTERVAL, listener.recv()).await;
+                } else {
+                    tokio::time::sleep(Self::READY_POLL_INTERVAL).await;
+                }
+            }
+        };
+
+        tokio::time::timeout(timeout, wait)
+            .await
+            .unwrap_or_else(|_| {
+                let provider = self.get_provider();
+
+                Err(SdkError::ReadyTimeout {
+                    provider_name: provider.metadata().name.clone(),
+                    status: provider.status(),
+                    timeout,
+                })
+            })
+    }
+
+    /// Same as [`Self::wait_for_ready`], blocking the current thread, such as in a synchronous
+    /// `main` before the runtime serving traffic is started.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an asynchronous runtime.
+    pub fn wait_for_ready_blocking(&self, timeout: Duration) -> Result<(), SdkError> {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("Failed to build a runtime to wait for the provider")
+            .block_on(self.wait_for_ready(timeout))
+    }
+
+    /// Evaluate given `flag_key` with corresponding `evaluation_context` and `evaluation_options`
+    /// as a `T`, any of the [`FlagValue`] types: bool, int (i64), float (f64), string and
+    /// [`StructValue`].
+    pub async fn get_value<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+        evaluation_options: Option<&EvaluationOptions>,
+    ) -> EvaluationResult<T> {
+        Ok(self
+            .evaluate::<T>(flag_key, evaluation_context, evaluation_options)
+            .await?
+            .value)
+    }
+
+    /// Return the [`EvaluationDetails`] of given `flag_key` evaluated as a `T` with
+    /// `evaluation_context` and `evaluation_options`.
+    pub async fn get_details<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+        evaluation_options: Option<&EvaluationOptions>,
+    ) -> EvaluationResult<EvaluationDetails<T>> {
+        self.evaluate(flag_key, evaluation_context, evaluation_options)
+            .await
+    }
+
     /// Evaluate given `flag_key` with corresponding `evaluation_context` and `evaluation_options`
     /// as a bool value.
     #[allow(unused_variables)]
@@ -59,12 +453,8 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<bool> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
         Ok(self
-            .get_provider()
-            .await
-            .resolve_bool_value(flag_key, &context)
+            .evaluate::<bool>(flag_key, evaluation_context, evaluation_options)
             .await?
             .value)
     }
@@ -78,12 +468,8 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<i64> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
         Ok(self
-            .get_provider()
-            .await
-            .resolve_int_value(flag_key, &context)
+            .evaluate::<i64>(flag_key, evaluation_context, evaluation_options)
             .await?
             .value)
     }
@@ -98,12 +484,8 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<f64> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
         Ok(self
-            .get_provider()
-            .await
-            .resolve_float_value(flag_key, &context)
+            .evaluate::<f64>(flag_key, evaluation_context, evaluation_options)
             .await?
             .value)
     }
@@ -118,12 +500,8 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<String> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
         Ok(self
-            .get_provider()
-            .await
-            .resolve_string_value(flag_key, &context)
+            .evaluate::<String>(flag_key, evaluation_context, evaluation_options)
             .await?
             .value)
     }
@@ -139,12 +517,8 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<T> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
         let result = self
-            .get_provider()
-            .await
-            .resolve_struct_value(flag_key, &context)
+            .evaluate::<StructValue>(flag_key, evaluation_context, evaluation_options)
             .await?;
 
         match T::try_from(result.value) {
@@ -165,14 +539,8 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<EvaluationDetails<bool>> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
-        Ok(self
-            .get_provider()
+        self.evaluate(flag_key, evaluation_context, evaluation_options)
             .await
-            .resolve_bool_value(flag_key, &context)
-            .await?
-            .into_evaluation_details(flag_key))
     }
 
     /// Return the [`EvaluationDetails`] with given `flag_key`, `evaluation_context` and
@@ -184,14 +552,8 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<EvaluationDetails<i64>> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
-        Ok(self
-            .get_provider()
+        self.evaluate(flag_key, evaluation_context, evaluation_options)
             .await
-            .resolve_int_value(flag_key, &context)
-            .await?
-            .into_evaluation_details(flag_key))
     }
 
     /// Return the [`EvaluationDetails`] with given `flag_key`, `evaluation_context` and
@@ -203,14 +565,8 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<EvaluationDetails<f64>> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
-        Ok(self
-            .get_provider()
+        self.evaluate(flag_key, evaluation_context, evaluation_options)
             .await
-            .resolve_float_value(flag_key, &context)
-            .await?
-            .into_evaluation_details(flag_key))
     }
 
     /// Return the [`EvaluationDetails`] with given `flag_key`, `evaluation_context` and
@@ -222,14 +578,8 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<EvaluationDetails<String>> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
-        Ok(self
-            .get_provider()
+        self.evaluate(flag_key, evaluation_context, evaluation_options)
             .await
-            .resolve_string_value(flag_key, &context)
-            .await?
-            .into_evaluation_details(flag_key))
     }
 
     /// Return the [`EvaluationDetails`] with given `flag_key`, `evaluation_context` and
@@ -241,21 +591,17 @@ impl Client {
         evaluation_context: Option<&EvaluationContext>,
         evaluation_options: Option<&EvaluationOptions>,
     ) -> EvaluationResult<EvaluationDetails<T>> {
-        let context = self.merge_evaluation_context(evaluation_context).await;
-
         let result = self
-            .get_provider()
-            .await
-            .resolve_struct_value(flag_key, &context)
+            .evaluate::<StructValue>(flag_key, evaluation_context, evaluation_options)
             .await?;
 
         match T::try_from(result.value) {
             Ok(value) => Ok(EvaluationDetails {
-                flag_key: flag_key.to_string(),
+                flag_key: result.flag_key,
                 value,
                 reason: result.reason,
                 variant: result.variant,
-                flag_metadata: result.flag_metadata.unwrap_or_default(),
+                flag_metadata: result.flag_metadata,
             }),
             Err(error) => Err(EvaluationError {
                 code: EvaluationErrorCode::TypeMismatch,
@@ -264,33 +610,666 @@ impl Client {
         }
     }
 
-    async fn get_provider(&self) -> Arc<dyn FeatureProvider> {
-        self.provider_registry.get(&self.metadata.name).await.get()
+    /// Evaluate the given flags as their type at once for the same evaluation context, keyed by
+    /// flag key, letting the provider resolve them in a single pass with
+    /// [`FeatureProvider::resolve_bulk`].
+    ///
+    /// Unlike the `get_*_details` functions, hooks are not run, and the evaluations are neither
+    /// cached nor recorded in the statistics.
+    pub async fn evaluate_all(
+        &self,
+        flags: &[(&str, FlagType)],
+        evaluation_context: Option<&EvaluationContext>,
+        evaluation_options: Option<&EvaluationOptions>,
+    ) -> HashMap<String, EvaluationResult<EvaluationDetails<Value>>> {
+        let context = self
+            .build_evaluation_context(evaluation_context, evaluation_options)
+            .await;
+
+        let timeout = self.timeout(evaluation_options);
+
+        let Some(snapshot) = &self.snapshot else {
+            return self.evaluate_all_live(flags, &context, timeout).await;
+        };
+
+        let mut results = HashMap::new();
+        let mut unrecorded_flags = Vec::new();
+
+        for &(flag_key, flag_type) in flags {
+            match snapshot.get(flag_key, flag_type, &context) {
+                Some(result) => {
+                    results.insert(flag_key.to_string(), result);
+                }
+                None => unrecorded_flags.push((flag_key, flag_type)),
+            }
+        }
+
+        let mut live_results = self
+            .evaluate_all_live(&unrecorded_flags, &context, timeout)
+            .await;
+
+        for (flag_key, flag_type) in unrecorded_flags {
+            if let Some(result) = live_results.remove(flag_key) {
+                let result = snapshot.insert(flag_key, flag_type, &context, result);
+                results.insert(flag_key.to_string(), result);
+            }
+        }
+
+        results
+    }
+
+    /// Same as [`Self::evaluate_all`] with the merged `context`, ignoring the snapshot.
+    async fn evaluate_all_live(
+        &self,
+        flags: &[(&str, FlagType)],
+        context: &EvaluationContext,
+        timeout: Option<Duration>,
+    ) -> HashMap<String, EvaluationResult<EvaluationDetails<Value>>> {
+        let provider = self.get_provider();
+
+        if let Err(error) = check_status(provider.as_ref()) {
+            return flags
+                .iter()
+                .map(|(flag_key, _)| ((*flag_key).to_string(), Err(error.clone())))
+                .collect();
+        }
+
+        let mut results = HashMap::new();
+        let mut resolved_flags = Vec::new();
+
+        for (flag_key, flag_type) in flags {
+            match self.kill_switches.evaluate_value(flag_key, *flag_type) {
+                Some(result) => {
+                    results.insert((*flag_key).to_string(), result);
+                }
+                None => resolved_flags.push(((*flag_key).to_string(), *flag_type)),
+            }
+        }
+
+        let resolutions = provider.resolve_bulk(&resolved_flags, context);
+        let mut resolutions = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, resolutions)
+                .await
+                .unwrap_or_else(|_| {
+                    resolved_flags
+                        .iter()
+                        .map(|(flag_key, _)| (flag_key.clone(), Err(timeout_error(timeout))))
+                        .collect()
+                }),
+            None => resolutions.await,
+        };
+
+        for (flag_key, flag_type) in resolved_flags {
+            let result = resolutions.remove(&flag_key).unwrap_or_else(|| {
+                Err(EvaluationError::builder()
+                    .code(EvaluationErrorCode::FlagNotFound)
+                    .message(format!("Flag \"{}\" was not resolved", flag_key))
+                    .build())
+            });
+
+            let result = result.and_then(|details| match flag_type.cast(details.value) {
+                Some(value) => {
+                    Ok(ResolutionDetails { value, ..details }.into_evaluation_details(&flag_key))
+                }
+                None => Err(EvaluationError::builder()
+                    .code(EvaluationErrorCode::TypeMismatch)
+                    .message(format!("Expected a {:?} flag", flag_type))
+                    .build()),
+            });
+
+            results.insert(flag_key, result);
+        }
+
+        results
+    }
+
+    /// Evaluate given `flag_key` as `T` the same way the `get_*_details` functions do, and return
+    /// a trace of the whole evaluation for debugging.
+    pub async fn trace<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+        evaluation_options: Option<&EvaluationOptions>,
+    ) -> EvaluationTrace {
+        let provider = self.get_provider();
+        let mut context = self
+            .build_evaluation_context(evaluation_context, evaluation_options)
+            .await;
+        let mut hooks = Vec::new();
+
+        let result = self
+            .evaluate_with_hooks::<T>(
+                flag_key,
+                provider.as_ref(),
+                &mut context,
+                evaluation_options,
+                Some(&mut hooks),
+            )
+            .await;
+
+        EvaluationTrace {
+            flag_key: flag_key.to_string(),
+            flag_type: T::FLAG_TYPE,
+            client_name: self.metadata.name.clone(),
+            provider_name: provider.metadata().name.clone(),
+            evaluation_context: context,
+            hooks,
+            result: result.map(|details| value_details(&details)),
+        }
+    }
+
+    /// Track that the subject of the evaluation context performed the action `event_name`, such
+    /// as a conversion, so that the provider can associate it with flag evaluations.
+    ///
+    /// The evaluation context is merged the same way as for evaluations. Nothing is tracked when
+    /// the provider is not ready or fatal.
+    pub async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: Option<&EvaluationContext>,
+        tracking_event_details: Option<&TrackingEventDetails>,
+    ) {
+        let provider = self.get_provider();
+
+        if matches!(
+            provider.status(),
+            ProviderStatus::NotReady | ProviderStatus::Fatal
+        ) {
+            return;
+        }
+
+        let context = self.merge_evaluation_context(evaluation_context).await;
+
+        provider
+            .track(
+                event_name,
+                &context,
+                tracking_event_details.unwrap_or(&TrackingEventDetails::default()),
+            )
+            .await;
+    }
+
+    async fn value_stream<T: FlagValue + PartialEq>(
+        &self,
+        flag_key: String,
+        default_value: T,
+        evaluation_context: Option<&EvaluationContext>,
+    ) -> watch::Receiver<T> {
+        let mut flag_watch = self.watch(flag_key.clone());
+        let listening = flag_watch.listen();
+
+        let evaluation_context = evaluation_context.cloned();
+        let value = self
+            .evaluate::<T>(&flag_key, evaluation_context.as_ref(), None)
+            .await
+            .map_or_else(|_| default_value.clone(), |details| details.value);
+        let (sender, receiver) = watch::channel(value);
+
+        if !listening {
+            return receiver;
+        }
+
+        let client = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    () = sender.closed() => break,
+                    event = flag_watch.changed() => if event.is_none() {
+                        break;
+                    },
+                }
+
+                let value = client
+                    .evaluate::<T>(&flag_key, evaluation_context.as_ref(), None)
+                    .await
+                    .map_or_else(|_| default_value.clone(), |details| details.value);
+
+                sender.send_if_modified(|current| {
+                    if *current == value {
+                        false
+                    } else {
+                        *current = value;
+                        true
+                    }
+                });
+            }
+        });
+
+        receiver
+    }
+
+    fn event_listener(&self) -> ProviderEventListener {
+        ProviderEventListener::new(
+            self.provider_registry.clone(),
+            self.metadata.name.clone(),
+            &self.kill_switches,
+        )
+    }
+
+    fn get_provider(&self) -> Arc<dyn FeatureProvider> {
+        match &self.snapshot {
+            Some(snapshot) => snapshot.provider(),
+            None => self.provider_registry.get(&self.metadata.name).get(),
+        }
+    }
+
+    /// Merge provided `flag_evaluation_context` (that is passed when evaluating a flag) with
+    /// client, transaction, supplied and global evaluation context.
+    async fn merge_evaluation_context(
+        &self,
+        flag_evaluation_context: Option<&EvaluationContext>,
+    ) -> EvaluationContext {
+        let mut context = match flag_evaluation_context {
+            Some(c) => c.clone(),
+            None => EvaluationContext::default(),
+        };
+
+        context.merge_missing(&self.evaluation_context);
+
+        if let Some(transaction_context) = TransactionContext::current() {
+            context.merge_missing(&transaction_context);
+        }
+
+        if let Some(context_supplier) = &self.context_supplier {
+            context.merge_missing(&context_supplier.supply().await);
+        }
+
+        match &self.snapshot {
+            Some(snapshot) => context.merge_missing(snapshot.global_evaluation_context()),
+            None => context.merge_missing(&self.global_evaluation_context.get()),
+        }
+
+        context
+    }
+
+    /// Build the evaluation context passed to the hooks and the provider, out of the merged
+    /// evaluation contexts and `evaluation_options`.
+    async fn build_evaluation_context(
+        &self,
+        evaluation_context: Option<&EvaluationContext>,
+        evaluation_options: Option<&EvaluationOptions>,
+    ) -> EvaluationContext {
+        let mut context = self.merge_evaluation_context(evaluation_context).await;
+
+        if let Some(as_of) = evaluation_options.and_then(|options| options.as_of) {
+            context = context.with_as_of(as_of);
+        }
+
+        context
+    }
+
+    /// Resolve `flag_key` as `T` through the hooks and the provider, applying
+    /// `evaluation_options`.
+    async fn evaluate<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+        evaluation_options: Option<&EvaluationOptions>,
+    ) -> EvaluationResult<EvaluationDetails<T>> {
+        let started_at = Instant::now();
+
+        let result = self
+            .evaluate_unrecorded::<T>(flag_key, evaluation_context, evaluation_options)
+            .await;
+
+        self.stats.record(flag_key, started_at.elapsed(), &result);
+
+        result
+    }
+
+    /// Same as [`Self::evaluate`], without recording statistics.
+    async fn evaluate_unrecorded<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: Option<&EvaluationContext>,
+        evaluation_options: Option<&EvaluationOptions>,
+    ) -> EvaluationResult<EvaluationDetails<T>> {
+        let provider = self.get_provider();
+        let mut context = self
+            .build_evaluation_context(evaluation_context, evaluation_options)
+            .await;
+
+        if self.hooks.is_empty()
+            && self.global_hooks.get().is_empty()
+            && evaluation_options.map_or(true, |options| options.hooks.is_empty())
+        {
+            return self
+                .resolve(
+                    flag_key,
+                    provider.as_ref(),
+                    &context,
+                    self.timeout(evaluation_options),
+                )
+                .await;
+        }
+
+        self.evaluate_with_hooks::<T>(
+            flag_key,
+            provider.as_ref(),
+            &mut context,
+            evaluation_options,
+            None,
+        )
+        .await
+    }
+
+    /// Run all the hook stages of the API, the client and `evaluation_options` around the
+    /// provider, recording them into `trace` if given.
+    async fn evaluate_with_hooks<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        provider: &dyn FeatureProvider,
+        context: &mut EvaluationContext,
+        evaluation_options: Option<&EvaluationOptions>,
+        mut trace: Option<&mut Vec<HookTrace>>,
+    ) -> EvaluationResult<EvaluationDetails<T>> {
+        let no_hook_hints = HookHints::default();
+        let (invocation_hooks, hook_hints) = match evaluation_options {
+            Some(options) => (options.hooks.as_slice(), &options.hook_hints),
+            None => (&[][..], &no_hook_hints),
+        };
+
+        let global_hooks = self.global_hooks.get();
+        let hooks: Vec<_> = global_hooks
+            .iter()
+            .chain(&self.hooks)
+            .chain(invocation_hooks)
+            .map(|hook| (hook.as_ref(), HookData::default()))
+            .collect();
+
+        let result = self
+            .resolve_with_hooks::<T>(
+                flag_key,
+                provider,
+                context,
+                &hooks,
+                hook_hints,
+                self.timeout(evaluation_options),
+                trace.as_deref_mut(),
+            )
+            .await;
+
+        if let Err(error) = &result {
+            for (hook, hook_data) in hooks.iter().rev() {
+                let hook_context = HookContext {
+                    flag_key,
+                    flag_type: T::FLAG_TYPE,
+                    evaluation_context: context,
+                    client_metadata: &self.metadata,
+                    provider_metadata: provider.metadata(),
+                    hook_data,
+                    hook_hints,
+                };
+
+                hook.error(&hook_context, error).await;
+                record_hook(&mut trace, *hook, HookStage::Error, false, None);
+            }
+        }
+
+        for (hook, hook_data) in hooks.iter().rev() {
+            let hook_context = HookContext {
+                flag_key,
+                flag_type: T::FLAG_TYPE,
+                evaluation_context: context,
+                client_metadata: &self.metadata,
+                provider_metadata: provider.metadata(),
+                hook_data,
+                hook_hints,
+            };
+
+            hook.finally(&hook_context).await;
+            record_hook(&mut trace, *hook, HookStage::Finally, false, None);
+        }
+
+        result
+    }
+
+    /// Run the `before` stages, the provider, and the `after` stages.
+    /// The `error` and `finally` stages are left to the caller.
+    #[allow(clippy::too_many_arguments)]
+    async fn resolve_with_hooks<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        provider: &dyn FeatureProvider,
+        context: &mut EvaluationContext,
+        hooks: &[(&dyn Hook, HookData)],
+        hook_hints: &HookHints,
+        timeout: Option<Duration>,
+        mut trace: Option<&mut Vec<HookTrace>>,
+    ) -> EvaluationResult<EvaluationDetails<T>> {
+        for (hook, hook_data) in hooks {
+            let hook_context = HookContext {
+                flag_key,
+                flag_type: T::FLAG_TYPE,
+                evaluation_context: context,
+                client_metadata: &self.metadata,
+                provider_metadata: provider.metadata(),
+                hook_data,
+                hook_hints,
+            };
+
+            let result = hook.before(&hook_context).await;
+            record_hook(
+                &mut trace,
+                *hook,
+                HookStage::Before,
+                matches!(result, Ok(Some(_))),
+                result.as_ref().err(),
+            );
+
+            if let Some(mut hook_evaluation_context) = result? {
+                // Values returned by a hook take precedence over existing ones.
+                hook_evaluation_context.merge_missing(context);
+                *context = hook_evaluation_context;
+            }
+        }
+
+        let details = self.resolve(flag_key, provider, context, timeout).await?;
+        let value_details = value_details(&details);
+
+        for (hook, hook_data) in hooks.iter().rev() {
+            let hook_context = HookContext {
+                flag_key,
+                flag_type: T::FLAG_TYPE,
+                evaluation_context: context,
+                client_metadata: &self.metadata,
+                provider_metadata: provider.metadata(),
+                hook_data,
+                hook_hints,
+            };
+
+            let result = hook.after(&hook_context, &value_details).await;
+            record_hook(
+                &mut trace,
+                *hook,
+                HookStage::After,
+                false,
+                result.as_ref().err(),
+            );
+
+            result?;
+        }
+
+        Ok(details)
+    }
+
+    /// Resolve `flag_key` as `T` with `provider` within `timeout` if any, or return its
+    /// resolution recorded in the snapshot if any.
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        provider: &dyn FeatureProvider,
+        context: &EvaluationContext,
+        timeout: Option<Duration>,
+    ) -> EvaluationResult<EvaluationDetails<T>> {
+        let Some(snapshot) = &self.snapshot else {
+            return self
+                .resolve_live_within(flag_key, provider, context, timeout)
+                .await;
+        };
+
+        if let Some(result) = snapshot.get(flag_key, T::FLAG_TYPE, context) {
+            return result.and_then(typed_details);
+        }
+
+        let result = self
+            .resolve_live_within::<T>(flag_key, provider, context, timeout)
+            .await
+            .map(|details| value_details(&details));
+
+        snapshot
+            .insert(flag_key, T::FLAG_TYPE, context, result)
+            .and_then(typed_details)
+    }
+
+    /// Same as [`Self::resolve_live`], failing once `timeout` is up, if any.
+    async fn resolve_live_within<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        provider: &dyn FeatureProvider,
+        context: &EvaluationContext,
+        timeout: Option<Duration>,
+    ) -> EvaluationResult<EvaluationDetails<T>> {
+        let resolution = self.resolve_live(flag_key, provider, context);
+
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, resolution)
+                .await
+                .unwrap_or_else(|_| Err(timeout_error(timeout))),
+            None => resolution.await,
+        }
+    }
+
+    /// Return the timeout of the resolutions, as overridden by `evaluation_options`.
+    fn timeout(&self, evaluation_options: Option<&EvaluationOptions>) -> Option<Duration> {
+        evaluation_options
+            .and_then(|options| options.timeout)
+            .or(self.timeout)
     }
 
-    /// Merge provided `flag_evaluation_context` (that is passed when evaluating a flag) with
-    /// client and global evaluation context.
-    async fn merge_evaluation_context(
+    /// Resolve `flag_key` as `T` with `provider`, unless its kill switch is engaged or the
+    /// provider is not ready or fatal.
+    async fn resolve_live<T: FlagValue>(
         &self,
-        flag_evaluation_context: Option<&EvaluationContext>,
-    ) -> EvaluationContext {
-        let mut context = match flag_evaluation_context {
-            Some(c) => c.clone(),
-            None => EvaluationContext::default(),
+        flag_key: &str,
+        provider: &dyn FeatureProvider,
+        context: &EvaluationContext,
+    ) -> EvaluationResult<EvaluationDetails<T>> {
+        if let Some(result) = self.kill_switches.evaluate(flag_key) {
+            return result;
+        }
+
+        check_status(provider)?;
+
+        // Evaluations as of another time are previews, not worth caching.
+        let Some(cache) = self.cache.as_ref().filter(|_| context.as_of().is_none()) else {
+            return Ok(T::resolve(provider, flag_key, context)
+                .await?
+                .into_evaluation_details(flag_key));
         };
 
-        context.merge_missing(&self.evaluation_context);
+        cache.invalidate_on_events(|| self.event_listener());
+
+        if let Some(Ok(details)) = cache
+            .get(flag_key, T::FLAG_TYPE, context)
+            .map(typed_details)
+        {
+            return Ok(details);
+        }
 
-        let global_evaluation_context = self.global_evaluation_context.get().await;
+        let details = T::resolve(provider, flag_key, context)
+            .await?
+            .into_evaluation_details(flag_key);
 
-        context.merge_missing(&global_evaluation_context);
+        cache.insert(T::FLAG_TYPE, context, value_details(&details));
 
-        context
+        Ok(details)
+    }
+}
+
+fn value_details<T: FlagValue>(details: &EvaluationDetails<T>) -> EvaluationDetails<Value> {
+    EvaluationDetails {
+        flag_key: details.flag_key.clone(),
+        value: details.value.to_value(),
+        reason: details.reason.clone(),
+        variant: details.variant.clone(),
+        flag_metadata: details.flag_metadata.clone(),
+    }
+}
+
+/// Convert `details` back into `T`, failing if they hold another type.
+fn typed_details<T: FlagValue>(
+    details: EvaluationDetails<Value>,
+) -> EvaluationResult<EvaluationDetails<T>> {
+    let value = T::from_value(details.value).ok_or_else(|| {
+        EvaluationError::builder()
+            .code(EvaluationErrorCode::TypeMismatch)
+            .message(format!("Expected a {:?} flag", T::FLAG_TYPE))
+            .build()
+    })?;
+
+    Ok(EvaluationDetails {
+        flag_key: details.flag_key,
+        value,
+        reason: details.reason,
+        variant: details.variant,
+        flag_metadata: details.flag_metadata,
+    })
+}
+
+/// Return the error of a resolution running out of `timeout`.
+fn timeout_error(timeout: Duration) -> EvaluationError {
+    ProviderError::new(
+        ProviderErrorKind::Timeout,
+        format!("The resolution timed out after {:?}", timeout),
+    )
+    .into()
+}
+
+/// Fail if `provider` is not ready or fatal.
+fn check_status(provider: &dyn FeatureProvider) -> EvaluationResult<()> {
+    match provider.status() {
+        ProviderStatus::NotReady => Err(EvaluationError::builder()
+            .code(EvaluationErrorCode::ProviderNotReady)
+            .message(format!(
+                "Provider \"{}\" is not ready",
+                provider.metadata().name
+            ))
+            .build()),
+        ProviderStatus::Fatal => Err(EvaluationError::builder()
+            .code(EvaluationErrorCode::ProviderFatal)
+            .message(format!(
+                "Provider \"{}\" is in an irrecoverable error state",
+                provider.metadata().name
+            ))
+            .build()),
+        ProviderStatus::Ready | ProviderStatus::Error | ProviderStatus::STALE => Ok(()),
+    }
+}
+
+fn record_hook(
+    trace: &mut Option<&mut Vec<HookTrace>>,
+    hook: &dyn Hook,
+    stage: HookStage,
+    changed_evaluation_context: bool,
+    error: Option<&EvaluationError>,
+) {
+    if let Some(trace) = trace {
+        trace.push(HookTrace {
+            hook: hook.name().to_string(),
+            stage,
+            changed_evaluation_context,
+            error: error.cloned(),
+        });
     }
 }
 
 impl<T> ResolutionDetails<T> {
-    fn into_evaluation_details(self, flag_key: impl Into<String>) -> EvaluationDetails<T> {
+    pub(super) fn into_evaluation_details(
+        self,
+        flag_key: impl Into<String>,
+    ) -> EvaluationDetails<T> {
         EvaluationDetails {
             flag_key: flag_key.into(),
             value: self.value,
@@ -308,11 +1287,16 @@ mod tests {
 
     use crate::{
         api::{
-            global_evaluation_context::GlobalEvaluationContext, provider_registry::ProviderRegistry,
+            global_evaluation_context::GlobalEvaluationContext, global_hooks::GlobalHooks,
+            provider_registry::ProviderRegistry,
+        },
+        provider::{
+            FeatureProvider, FlagType, MockFeatureProvider, ProviderStatus, ResolutionDetails,
         },
-        provider::{FeatureProvider, MockFeatureProvider, ResolutionDetails},
-        Client, EvaluationReason, FlagMetadata, StructValue, Value,
+        Client, EvaluationContext, EvaluationErrorCode, EvaluationOptions, EvaluationReason,
+        FlagMetadata, KillSwitches, StructValue, TrackingEventDetails, Value,
     };
+    use time::{Duration, OffsetDateTime};
 
     #[spec(
         number = "1.2.2",
@@ -323,6 +1307,54 @@ mod tests {
         assert_eq!(create_default_client().metadata().name, "no_op");
     }
 
+    #[tokio::test]
+    async fn get_value_of_any_type() {
+        let mut api = crate::OpenFeature::default();
+        api.set_provider(crate::flags! {
+            "enabled" => bool: true,
+            "greeting" => String: "Hello",
+        })
+        .await
+        .unwrap();
+        let client = api.create_client();
+
+        assert!(client
+            .get_value::<bool>("enabled", None, None)
+            .await
+            .unwrap());
+        assert_eq!(
+            client
+                .get_value::<String>("greeting", None, None)
+                .await
+                .unwrap(),
+            "Hello"
+        );
+
+        let details = client
+            .get_details::<String>("greeting", None, None)
+            .await
+            .unwrap();
+        assert_eq!(details.value, "Hello");
+        assert_eq!(details.flag_key, "greeting");
+
+        assert_eq!(
+            client
+                .get_value::<i64>("greeting", None, None)
+                .await
+                .unwrap_err()
+                .code,
+            EvaluationErrorCode::TypeMismatch
+        );
+    }
+
+    #[test]
+    fn client_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+        assert_send_sync::<Client>();
+        assert_send_sync::<std::sync::Arc<dyn FeatureProvider>>();
+    }
+
     #[derive(PartialEq, Debug)]
     struct Student {
         id: i64,
@@ -363,7 +1395,10 @@ mod tests {
     async fn get_value() {
         // Test bool.
         let mut provider = MockFeatureProvider::new();
-        provider.expect_initialize().returning(|_| {});
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
 
         provider
             .expect_resolve_bool_value()
@@ -463,7 +1498,10 @@ mod tests {
     #[tokio::test]
     async fn get_details() {
         let mut provider = MockFeatureProvider::new();
-        provider.expect_initialize().returning(|_| {});
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
         provider
             .expect_resolve_int_value()
             .return_const(Ok(ResolutionDetails::builder()
@@ -515,7 +1553,10 @@ mod tests {
     #[tokio::test]
     async fn get_details_flag_metadata() {
         let mut provider = MockFeatureProvider::new();
-        provider.expect_initialize().returning(|_| {});
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
         provider
             .expect_resolve_bool_value()
             .return_const(Ok(ResolutionDetails::builder()
@@ -533,6 +1574,644 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn stream_bool_value() {
+        let provider = crate::flags! { "checkout-v2" => bool: true };
+
+        let mut api = crate::OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let client = api.create_client();
+        let mut receiver = client.bool_stream("checkout-v2", false, None).await;
+        assert!(*receiver.borrow());
+
+        // Fall back to the default value once the flag is broken.
+        provider.set_flag("checkout-v2", crate::provider::InMemoryFlag::new("missing"));
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), receiver.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!*receiver.borrow());
+
+        provider.set_flag(
+            "checkout-v2",
+            crate::provider::InMemoryFlag::with_value(true),
+        );
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), receiver.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(*receiver.borrow());
+    }
+
+    #[tokio::test]
+    async fn callback_on_flag_change() {
+        let provider = crate::flags! { "tier" => String: "gold" };
+
+        let mut api = crate::OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let client = api.create_client();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+        client
+            .on_flag_change_debounced(
+                "tier",
+                None,
+                std::time::Duration::from_millis(10),
+                move |old: Option<&String>, new: &String| {
+                    sender.send((old.cloned(), new.clone())).unwrap();
+                },
+            )
+            .await;
+
+        // A burst of changes is evaluated once.
+        for tier in ["silver", "bronze", "platinum"] {
+            provider.set_flag("tier", crate::provider::InMemoryFlag::with_value(tier));
+        }
+
+        let change = tokio::time::timeout(std::time::Duration::from_secs(1), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(change, (Some("gold".to_string()), "platinum".to_string()));
+
+        // Not invoked when the value is the same.
+        provider.set_flag(
+            "tier",
+            crate::provider::InMemoryFlag::with_value("platinum"),
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        provider.set_flag("tier", crate::provider::InMemoryFlag::with_value("gold"));
+
+        let change = tokio::time::timeout(std::time::Duration::from_secs(1), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(change, (Some("platinum".to_string()), "gold".to_string()));
+    }
+
+    #[tokio::test]
+    async fn fail_unless_provider_ready() {
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
+        provider.expect_shutdown().returning(|| ());
+        provider
+            .expect_metadata()
+            .return_const(crate::provider::ProviderMetadata::new("Test"));
+
+        let mut sequence = mockall::Sequence::new();
+        provider
+            .expect_status()
+            .returning(|| ProviderStatus::NotReady)
+            .once()
+            .in_sequence(&mut sequence);
+        provider
+            .expect_status()
+            .returning(|| ProviderStatus::Fatal)
+            .once()
+            .in_sequence(&mut sequence);
+
+        let mut api = crate::OpenFeature::default();
+        api.set_provider(provider).await.unwrap();
+
+        let client = api.create_client();
+
+        let error = client.get_bool_value("key", None, None).await.unwrap_err();
+        assert_eq!(error.code, crate::EvaluationErrorCode::ProviderNotReady);
+
+        let error = client.get_bool_value("key", None, None).await.unwrap_err();
+        assert_eq!(error.code, crate::EvaluationErrorCode::ProviderFatal);
+    }
+
+    #[tokio::test]
+    async fn wait_for_ready() {
+        let status_provider = |statuses: Vec<ProviderStatus>| {
+            let calls = std::sync::atomic::AtomicUsize::new(0);
+            let mut provider = MockFeatureProvider::new();
+            provider.expect_initialize().returning(|_| Ok(()));
+            provider.expect_shutdown().returning(|| ());
+            provider
+                .expect_metadata()
+                .return_const(crate::provider::ProviderMetadata::new("Test"));
+            provider.expect_event_emitter().returning(|| None);
+            provider.expect_status().returning(move || {
+                let call = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                statuses[call.min(statuses.len() - 1)]
+            });
+            provider
+        };
+        let timeout = std::time::Duration::from_millis(200);
+
+        let client = create_client(status_provider(vec![
+            ProviderStatus::NotReady,
+            ProviderStatus::NotReady,
+            ProviderStatus::Ready,
+        ]))
+        .await;
+        client.wait_for_ready(timeout).await.unwrap();
+
+        let client = create_client(status_provider(vec![ProviderStatus::NotReady])).await;
+        let error = client.wait_for_ready(timeout).await.unwrap_err();
+        assert!(matches!(
+            error,
+            crate::SdkError::ReadyTimeout {
+                status: ProviderStatus::NotReady,
+                ..
+            }
+        ));
+
+        let client = create_client(status_provider(vec![ProviderStatus::Fatal])).await;
+        let error = client.wait_for_ready(timeout).await.unwrap_err();
+        assert!(matches!(error, crate::SdkError::ProviderFatal { .. }));
+    }
+
+    #[test]
+    fn wait_for_ready_blocking() {
+        let client = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(create_client(crate::flags! {}));
+
+        client
+            .wait_for_ready_blocking(std::time::Duration::from_secs(1))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn handle_provider_events() {
+        let provider = crate::flags! { "tier" => String: "gold" };
+
+        let mut api = crate::OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let client = api.create_client();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+        client
+            .add_handler(crate::provider::ProviderEventType::Ready, {
+                let sender = sender.clone();
+                move |event| sender.send(event.event_type).unwrap()
+            })
+            .await;
+        client
+            .add_handler(
+                crate::provider::ProviderEventType::ConfigurationChanged,
+                move |event| sender.send(event.event_type).unwrap(),
+            )
+            .await;
+
+        // The provider is already ready.
+        assert_eq!(
+            receiver.recv().await,
+            Some(crate::provider::ProviderEventType::Ready)
+        );
+
+        provider.set_flag("tier", crate::provider::InMemoryFlag::with_value("silver"));
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(1), receiver.recv())
+            .await
+            .unwrap();
+        assert_eq!(
+            event,
+            Some(crate::provider::ProviderEventType::ConfigurationChanged)
+        );
+    }
+
+    #[tokio::test]
+    async fn merge_supplied_context() {
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
+        provider
+            .expect_resolve_bool_value()
+            .withf(|_, context| {
+                context.targeting_key == Some("alice".to_string())
+                    && context.custom_fields.get("tenant") == Some(&"acme".into())
+                    && context.custom_fields.get("plan") == Some(&"pro".into())
+            })
+            .return_const(Ok(ResolutionDetails::new(true)));
+
+        let client = create_client(provider).await.with_context_supplier(|| {
+            EvaluationContext::default()
+                .with_targeting_key("bob")
+                .with_custom_field("tenant", "acme")
+                .with_custom_field("plan", "free")
+        });
+
+        let result = client
+            .get_bool_value(
+                "key",
+                Some(
+                    &EvaluationContext::default()
+                        .with_targeting_key("alice")
+                        .with_custom_field("plan", "pro"),
+                ),
+                None,
+            )
+            .await;
+
+        assert!(result.unwrap());
+    }
+
+    #[tokio::test]
+    async fn track() {
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
+        provider
+            .expect_track()
+            .withf(|event_name, context, details| {
+                event_name == "checkout"
+                    && context.targeting_key == Some("alice".to_string())
+                    && details.custom_fields.get("currency") == Some(&"EUR".into())
+            })
+            .times(1)
+            .return_const(());
+
+        let mut sequence = mockall::Sequence::new();
+        provider
+            .expect_status()
+            .returning(|| ProviderStatus::Ready)
+            .once()
+            .in_sequence(&mut sequence);
+        provider
+            .expect_status()
+            .returning(|| ProviderStatus::NotReady)
+            .once()
+            .in_sequence(&mut sequence);
+
+        let mut client = create_client(provider).await;
+        client.set_evaluation_context(EvaluationContext::default().with_targeting_key("alice"));
+
+        let details = TrackingEventDetails::default()
+            .with_value(99.9)
+            .with_custom_field("currency", "EUR");
+
+        client.track("checkout", None, Some(&details)).await;
+
+        // Not tracked while the provider is not ready.
+        client.track("checkout", None, Some(&details)).await;
+    }
+
+    #[tokio::test]
+    async fn invocation_hooks_and_hints() {
+        struct RecordingHook {
+            name: &'static str,
+            stages: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+        }
+
+        #[async_trait::async_trait]
+        impl crate::Hook for RecordingHook {
+            async fn before<'a>(
+                &self,
+                context: &crate::HookContext<'a>,
+            ) -> Result<Option<EvaluationContext>, crate::EvaluationError> {
+                let quiet = context.hook_hints.get("quiet") == Some(&Value::Bool(true));
+                self.stages
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} before quiet={quiet}", self.name));
+
+                Ok(None)
+            }
+
+            async fn finally<'a>(&self, _context: &crate::HookContext<'a>) {
+                self.stages
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} finally", self.name));
+            }
+        }
+
+        let stages = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let mut api = crate::OpenFeature::default();
+        api.set_provider(crate::flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let client = api.create_client().with_hook(RecordingHook {
+            name: "client",
+            stages: stages.clone(),
+        });
+
+        let options = EvaluationOptions::default()
+            .with_hook(RecordingHook {
+                name: "invocation",
+                stages: stages.clone(),
+            })
+            .with_hook_hint("quiet", true);
+
+        assert!(client
+            .get_bool_value("checkout-v2", None, Some(&options))
+            .await
+            .unwrap());
+
+        assert_eq!(
+            *stages.lock().unwrap(),
+            vec![
+                "client before quiet=true",
+                "invocation before quiet=true",
+                "invocation finally",
+                "client finally",
+            ]
+        );
+
+        // Invocation hooks only apply to their evaluation.
+        stages.lock().unwrap().clear();
+        client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *stages.lock().unwrap(),
+            vec!["client before quiet=false", "client finally"]
+        );
+    }
+
+    #[tokio::test]
+    async fn evaluate_all() {
+        let mut api = crate::OpenFeature::default();
+        api.set_provider(crate::flags! {
+            "checkout-v2" => bool: true,
+            "tier" => String: "gold",
+            "limit" => i64: 10,
+        })
+        .await
+        .unwrap();
+
+        let client = api.create_client();
+        let results = client
+            .evaluate_all(
+                &[
+                    ("checkout-v2", FlagType::Bool),
+                    ("tier", FlagType::String),
+                    ("limit", FlagType::Float),
+                    ("missing", FlagType::Int),
+                ],
+                None,
+                None,
+            )
+            .await;
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results["checkout-v2"].as_ref().unwrap().value,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            results["tier"].as_ref().unwrap().value,
+            Value::String("gold".to_string())
+        );
+        assert_eq!(
+            results["limit"].as_ref().unwrap_err().code,
+            crate::EvaluationErrorCode::TypeMismatch
+        );
+        assert_eq!(
+            results["missing"].as_ref().unwrap_err().code,
+            crate::EvaluationErrorCode::FlagNotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn evaluate_all_in_single_pass() {
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
+        provider
+            .expect_resolve_bulk()
+            .withf(|flags, context| {
+                flags.len() == 2 && context.targeting_key == Some("alice".to_string())
+            })
+            .times(1)
+            .returning(|flags, _| {
+                flags
+                    .iter()
+                    .map(|(flag_key, _)| (flag_key.clone(), Ok(ResolutionDetails::new(1))))
+                    .collect()
+            });
+
+        let client = create_client(provider).await;
+        let results = client
+            .evaluate_all(
+                &[("limit", FlagType::Int), ("ratio", FlagType::Float)],
+                Some(&EvaluationContext::default().with_targeting_key("alice")),
+                None,
+            )
+            .await;
+
+        assert_eq!(results["limit"].as_ref().unwrap().value, Value::Int(1));
+        assert_eq!(results["ratio"].as_ref().unwrap().value, Value::Float(1.0));
+    }
+
+    #[tokio::test]
+    async fn evaluate_snapshot() {
+        let provider = crate::flags! {
+            "checkout-v2" => bool: false,
+            "tier" => String: "gold",
+        };
+
+        let mut api = crate::OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let client = api.create_client();
+        let snapshot = client.snapshot();
+
+        assert!(!snapshot
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+
+        provider.set_flag(
+            "checkout-v2",
+            crate::provider::InMemoryFlag::with_value(true),
+        );
+        provider.set_flag("tier", crate::provider::InMemoryFlag::with_value("silver"));
+        api.set_provider(crate::flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        // The pinned provider is still used, and the flag keeps its first value.
+        assert!(!snapshot
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+        assert_eq!(
+            snapshot.get_string_value("tier", None, None).await.unwrap(),
+            "silver"
+        );
+
+        let results = snapshot
+            .evaluate_all(
+                &[("checkout-v2", FlagType::Bool), ("tier", FlagType::String)],
+                None,
+                None,
+            )
+            .await;
+        assert_eq!(
+            results["checkout-v2"].as_ref().unwrap().value,
+            Value::Bool(false)
+        );
+        assert_eq!(
+            results["tier"].as_ref().unwrap().value,
+            Value::String("silver".to_string())
+        );
+
+        assert!(client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn kill_switch_takes_precedence() {
+        let mut api = crate::OpenFeature::default();
+        api.set_provider(crate::flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let client = api.create_client();
+        let mut receiver = client.bool_stream("checkout-v2", true, None).await;
+
+        api.kill_switches().engage("checkout-v2", false);
+
+        let details = client
+            .get_bool_details("checkout-v2", None, None)
+            .await
+            .unwrap();
+        assert!(!details.value);
+        assert_eq!(details.reason, Some(EvaluationReason::Static));
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), receiver.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!*receiver.borrow());
+
+        api.kill_switches().release("checkout-v2");
+        assert!(client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn time_out_slow_resolutions() {
+        #[derive(Default)]
+        struct SlowProvider {
+            metadata: crate::provider::ProviderMetadata,
+        }
+
+        #[async_trait::async_trait]
+        impl FeatureProvider for SlowProvider {
+            fn metadata(&self) -> &crate::provider::ProviderMetadata {
+                &self.metadata
+            }
+
+            async fn resolve_bool_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> crate::EvaluationResult<ResolutionDetails<bool>> {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                Ok(ResolutionDetails::new(true))
+            }
+
+            async fn resolve_int_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> crate::EvaluationResult<ResolutionDetails<i64>> {
+                unimplemented!()
+            }
+
+            async fn resolve_float_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> crate::EvaluationResult<ResolutionDetails<f64>> {
+                unimplemented!()
+            }
+
+            async fn resolve_string_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> crate::EvaluationResult<ResolutionDetails<String>> {
+                unimplemented!()
+            }
+
+            async fn resolve_struct_value(
+                &self,
+                _flag_key: &str,
+                _evaluation_context: &EvaluationContext,
+            ) -> crate::EvaluationResult<ResolutionDetails<StructValue>> {
+                unimplemented!()
+            }
+        }
+
+        let client = create_client(SlowProvider::default())
+            .await
+            .with_timeout(std::time::Duration::from_millis(10));
+
+        let error = client.get_bool_value("key", None, None).await.unwrap_err();
+        match error.code {
+            EvaluationErrorCode::Provider(error) => {
+                assert_eq!(error.kind, crate::ProviderErrorKind::Timeout);
+            }
+            code => panic!("Unexpected error code {:?}", code),
+        }
+        assert!(client
+            .get_bool_value("key", None, None)
+            .await
+            .unwrap_err()
+            .is_retryable());
+
+        let options = EvaluationOptions::default().with_timeout(std::time::Duration::from_secs(1));
+        assert!(client
+            .get_bool_value("key", None, Some(&options))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn evaluate_as_of() {
+        let as_of = OffsetDateTime::now_utc() + Duration::days(7);
+
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
+        provider
+            .expect_resolve_bool_value()
+            .withf(move |_, context| {
+                context.as_of() == Some(as_of) && context.custom_fields.is_empty()
+            })
+            .return_const(Ok(ResolutionDetails::new(true)));
+
+        let client = create_client(provider).await;
+
+        let result = client
+            .get_bool_value(
+                "key",
+                None,
+                Some(&EvaluationOptions::default().with_as_of(as_of)),
+            )
+            .await;
+
+        assert!(result.unwrap());
+    }
+
     #[spec(
         number = "1.3.2.1",
         text = "The client MUST provide methods for typed flag evaluation, including boolean, numeric, string, and structure, with parameters flag key (string, required), default value (boolean | number | string | structure, required), and evaluation options (optional), which returns the flag value."
@@ -548,18 +2227,25 @@ mod tests {
         Client::new(
             "no_op",
             GlobalEvaluationContext::default(),
+            GlobalHooks::default(),
             ProviderRegistry::default(),
+            KillSwitches::default(),
         )
     }
 
     async fn create_client(provider: impl FeatureProvider) -> Client {
         let provider_registry = ProviderRegistry::default();
-        provider_registry.set_named("custom", provider).await;
+        provider_registry
+            .set_named("custom", provider)
+            .await
+            .unwrap();
 
         Client::new(
             "custom",
             GlobalEvaluationContext::default(),
+            GlobalHooks::default(),
             provider_registry,
+            KillSwitches::default(),
         )
     }
 }
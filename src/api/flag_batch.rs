@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{
+    provider::{FlagType, FlagValue},
+    Client, EvaluationContext, EvaluationResult, StructValue, Value,
+};
+
+use super::provider_events::ProviderEventListener;
+
+// ============================================================
+//  FlagBatch
+// ============================================================
+
+/// A set of flags re-evaluated together, created with [`Client::flag_batch`].
+///
+/// Instead of a notification per flag, every re-evaluation reports all the flags whose value
+/// changed at once. [`Self::changed`] re-evaluates them whenever the global evaluation context
+/// changes, or the provider signals a change affecting any of them.
+///
+/// ```no_run
+/// use open_feature::{provider::FlagType, OpenFeature};
+///
+/// # async fn example() {
+/// let client = OpenFeature::singleton().await.create_client();
+/// let mut batch = client
+///     .flag_batch(None)
+///     .with_flag("checkout-v2", FlagType::Bool)
+///     .with_flag("tier", FlagType::String);
+///
+/// while let Some(changes) = batch.changed().await {
+///     println!("{} flags changed", changes.len());
+/// }
+/// # }
+/// ```
+pub struct FlagBatch {
+    client: Client,
+    evaluation_context: Option<EvaluationContext>,
+    flags: Vec<(String, FlagType)>,
+    values: HashMap<String, Option<Value>>,
+    listener: ProviderEventListener,
+    context_changes: broadcast::Receiver<()>,
+}
+
+/// A flag whose value changed between two evaluations of a [`FlagBatch`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct FlagChange {
+    /// The key of the flag.
+    pub flag_key: String,
+
+    /// The previous value, or `None` if the evaluation failed.
+    pub old_value: Option<Value>,
+
+    /// The new value, or `None` if the evaluation failed.
+    pub new_value: Option<Value>,
+}
+
+impl FlagBatch {
+    pub(crate) fn new(
+        client: Client,
+        evaluation_context: Option<EvaluationContext>,
+        listener: ProviderEventListener,
+        context_changes: broadcast::Receiver<()>,
+    ) -> Self {
+        Self {
+            client,
+            evaluation_context,
+            flags: Vec::new(),
+            values: HashMap::new(),
+            listener,
+            context_changes,
+        }
+    }
+
+    /// Add flag `flag_key` evaluated as `flag_type` to the batch.
+    #[must_use]
+    pub fn with_flag(mut self, flag_key: impl Into<String>, flag_type: FlagType) -> Self {
+        self.add_flag(flag_key, flag_type);
+        self
+    }
+
+    /// Add flag `flag_key` evaluated as `flag_type` to the batch.
+    pub fn add_flag(&mut self, flag_key: impl Into<String>, flag_type: FlagType) {
+        self.flags.push((flag_key.into(), flag_type));
+    }
+
+    /// Evaluate all the flags of the batch now, and return those whose value changed since the
+    /// previous evaluation. Flags evaluated for the first time are not reported.
+    pub async fn evaluate(&mut self) -> Vec<FlagChange> {
+        let mut changes = Vec::new();
+
+        for (flag_key, flag_type) in &self.flags {
+            let new_value = self.evaluate_flag(flag_key, *flag_type).await.ok();
+
+            match self.values.insert(flag_key.clone(), new_value.clone()) {
+                Some(old_value) if old_value != new_value => changes.push(FlagChange {
+                    flag_key: flag_key.clone(),
+                    old_value,
+                    new_value,
+                }),
+                _ => {}
+            }
+        }
+
+        changes
+    }
+
+    /// Wait until the value of some flags changed, and return all of them.
+    ///
+    /// The first call evaluates the flags to know their current values. Return `None` once the
+    /// providers are shut down.
+    pub async fn changed(&mut self) -> Option<Vec<FlagChange>> {
+        if self.values.is_empty() {
+            if !self.listener.listen() {
+                return None;
+            }
+
+            self.evaluate().await;
+        }
+
+        loop {
+            tokio::select! {
+                change = self.context_changes.recv() => if let Err(RecvError::Closed) = change {
+                    return None;
+                },
+                event = self.listener.recv() => {
+                    let event = event?;
+
+                    if !self.flags.iter().any(|(flag_key, _)| event.affects(flag_key)) {
+                        continue;
+                    }
+                }
+            }
+
+            let changes = self.evaluate().await;
+
+            if !changes.is_empty() {
+                return Some(changes);
+            }
+        }
+    }
+
+    async fn evaluate_flag(&self, flag_key: &str, flag_type: FlagType) -> EvaluationResult<Value> {
+        let context = self.evaluation_context.as_ref();
+
+        Ok(match flag_type {
+            FlagType::Bool => self
+                .client
+                .get_bool_value(flag_key, context, None)
+                .await?
+                .into(),
+            FlagType::Int => self
+                .client
+                .get_int_value(flag_key, context, None)
+                .await?
+                .into(),
+            FlagType::Float => self
+                .client
+                .get_float_value(flag_key, context, None)
+                .await?
+                .into(),
+            FlagType::String => self
+                .client
+                .get_string_value(flag_key, context, None)
+                .await?
+                .into(),
+            FlagType::Struct => self
+                .client
+                .get_struct_value::<StructValue>(flag_key, context, None)
+                .await?
+                .to_value(),
+        })
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::*;
+    use crate::{flags, provider::InMemoryFlag, OpenFeature};
+
+    #[tokio::test]
+    async fn report_changes_at_once() {
+        let mut provider = flags! {
+            "checkout-v2" => bool: false,
+            "tier" => String: "gold",
+            "limit" => i64: 10,
+        };
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let mut batch = api
+            .create_client()
+            .flag_batch(None)
+            .with_flag("checkout-v2", FlagType::Bool)
+            .with_flag("tier", FlagType::String)
+            .with_flag("limit", FlagType::Int);
+
+        assert!(batch.evaluate().await.is_empty());
+
+        provider.add_flag("checkout-v2", InMemoryFlag::with_value(true));
+        provider.add_flag("tier", InMemoryFlag::with_value("silver"));
+
+        let changes = batch.evaluate().await;
+        assert_eq!(
+            changes,
+            vec![
+                FlagChange {
+                    flag_key: "checkout-v2".to_string(),
+                    old_value: Some(Value::Bool(false)),
+                    new_value: Some(Value::Bool(true)),
+                },
+                FlagChange {
+                    flag_key: "tier".to_string(),
+                    old_value: Some("gold".into()),
+                    new_value: Some("silver".into()),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reevaluate_on_global_context_change() {
+        let mut provider = flags! { "checkout-v2" => bool: false };
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let mut batch = api
+            .create_client()
+            .flag_batch(None)
+            .with_flag("checkout-v2", FlagType::Bool)
+            .with_flag("missing", FlagType::Bool);
+
+        let watcher = tokio::spawn(async move { batch.changed().await });
+        tokio::task::yield_now().await;
+
+        // Changed without any event.
+        provider.add_flag("checkout-v2", InMemoryFlag::with_value(true));
+
+        api.set_evaluation_context(EvaluationContext::default().with_targeting_key("alice"))
+            .await;
+
+        let changes = timeout(Duration::from_secs(1), watcher)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            changes,
+            vec![FlagChange {
+                flag_key: "checkout-v2".to_string(),
+                old_value: Some(Value::Bool(false)),
+                new_value: Some(Value::Bool(true)),
+            }]
+        );
+    }
+}
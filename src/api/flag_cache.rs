@@ -0,0 +1,509 @@
+use std::{
+    collections::{hash_map::RandomState, BTreeMap, HashMap},
+    hash::{BuildHasher, Hash, Hasher},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+use tokio::task::JoinHandle;
+
+use crate::{
+    provider::{FlagType, ProviderEventType},
+    EvaluationContext, EvaluationContextFieldValue, EvaluationDetails, EvaluationReason, Value,
+};
+
+use super::provider_events::ProviderEventListener;
+
+// ============================================================
+//  FlagCache
+// ============================================================
+
+/// Memoizes the successful resolutions of a client and its clones, keyed by flag key, flag type
+/// and evaluation context.
+///
+/// Entries expire after a time to live, and are invalidated when the provider signals that
+/// their flag changed or when it is replaced.
+///
+/// Entries are spread over shards locked on their own, so that concurrent evaluations rarely
+/// contend, and only read-locked on hits. Each shard holds an equal share of the capacity, and
+/// indexes its entries by expiry time to evict the one closest to expiry in logarithmic time.
+#[derive(Clone)]
+pub struct FlagCache {
+    ttl: Duration,
+    shard_capacity: usize,
+    shards: Arc<[RwLock<Shard>]>,
+    hasher: RandomState,
+    invalidation: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+/// The key of an entry within the entries of its flag.
+type EntryKey = (FlagType, ContextKey);
+
+#[derive(Default)]
+struct Shard {
+    flags: HashMap<String, HashMap<EntryKey, CacheEntry>>,
+    /// The keys of the entries by expiry time, made unique by an insertion sequence number.
+    expiry: BTreeMap<(Instant, u64), (String, EntryKey)>,
+    sequence: u64,
+}
+
+struct CacheEntry {
+    details: EvaluationDetails<Value>,
+    expiry: (Instant, u64),
+}
+
+impl FlagCache {
+    /// The number of shards of a cache, unless it holds fewer entries.
+    const SHARDS: usize = 16;
+
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        let shards = max_entries.clamp(1, Self::SHARDS);
+
+        Self {
+            ttl,
+            shard_capacity: max_entries / shards,
+            shards: (0..shards).map(|_| RwLock::default()).collect(),
+            hasher: RandomState::new(),
+            invalidation: Arc::default(),
+        }
+    }
+
+    /// Start invalidating entries on the events received by the listener created by
+    /// `create_listener`, unless already done.
+    ///
+    /// The listener follows the provider when it is replaced, and ends once the API is shut
+    /// down, in which case a new one is started, for the provider set afterwards, with all the
+    /// entries removed.
+    pub fn invalidate_on_events(&self, create_listener: impl FnOnce() -> ProviderEventListener) {
+        let mut invalidation = self.invalidation.lock().unwrap();
+
+        let restarted = match &*invalidation {
+            Some(task) if !task.is_finished() => return,
+            Some(_) => true,
+            None => false,
+        };
+
+        let mut listener = create_listener();
+
+        if !listener.listen() {
+            return;
+        }
+
+        if restarted {
+            self.clear();
+        }
+
+        let shards = Arc::downgrade(&self.shards);
+
+        *invalidation = Some(tokio::spawn(async move {
+            while let Some(event) = listener.recv().await {
+                // The cache is dropped along with all its clients.
+                let Some(shards) = shards.upgrade() else {
+                    break;
+                };
+
+                match (event.event_type, event.flags_changed) {
+                    (ProviderEventType::ConfigurationChanged, Some(flags_changed)) => {
+                        for shard in shards.iter() {
+                            let mut shard = shard.write().unwrap();
+
+                            for flag_key in &flags_changed {
+                                shard.remove_flag(flag_key);
+                            }
+                        }
+                    }
+                    (ProviderEventType::Ready | ProviderEventType::ConfigurationChanged, _) => {
+                        for shard in shards.iter() {
+                            shard.write().unwrap().clear();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }));
+    }
+
+    /// Return the cached resolution of `flag_key` as `flag_type` with `context`, with reason
+    /// [`EvaluationReason::Cached`].
+    pub fn get(
+        &self,
+        flag_key: &str,
+        flag_type: FlagType,
+        context: &EvaluationContext,
+    ) -> Option<EvaluationDetails<Value>> {
+        let key = (flag_type, ContextKey::new(context));
+        let shard = self.shard(flag_key, &key).read().unwrap();
+
+        let entry = shard.flags.get(flag_key)?.get(&key)?;
+
+        if entry.expiry.0 <= Instant::now() {
+            return None;
+        }
+
+        Some(EvaluationDetails {
+            reason: Some(EvaluationReason::Cached),
+            ..entry.details.clone()
+        })
+    }
+
+    /// Cache the resolution `details` of their flag as `flag_type` with `context`, evicting the
+    /// entry closest to expiry if the cache is full.
+    pub fn insert(
+        &self,
+        flag_type: FlagType,
+        context: &EvaluationContext,
+        details: EvaluationDetails<Value>,
+    ) {
+        if self.shard_capacity == 0 || self.ttl.is_zero() {
+            return;
+        }
+
+        let flag_key = details.flag_key.clone();
+        let key = (flag_type, ContextKey::new(context));
+        let mut shard = self.shard(&flag_key, &key).write().unwrap();
+        let now = Instant::now();
+
+        shard.remove(&flag_key, &key);
+        shard.remove_expired(now);
+
+        if shard.expiry.len() >= self.shard_capacity {
+            shard.remove_closest_to_expiry();
+        }
+
+        shard.insert(flag_key, key, details, now + self.ttl);
+    }
+
+    /// Remove all the entries.
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            shard.write().unwrap().clear();
+        }
+    }
+
+    /// Return the shard of the entry of `flag_key` with `key`.
+    fn shard(&self, flag_key: &str, key: &EntryKey) -> &RwLock<Shard> {
+        let mut hasher = self.hasher.build_hasher();
+        flag_key.hash(&mut hasher);
+        key.hash(&mut hasher);
+
+        // Only the low bits of the hash pick the shard.
+        #[allow(clippy::cast_possible_truncation)]
+        let hash = hasher.finish() as usize;
+
+        &self.shards[hash % self.shards.len()]
+    }
+}
+
+impl Shard {
+    fn insert(
+        &mut self,
+        flag_key: String,
+        key: EntryKey,
+        details: EvaluationDetails<Value>,
+        expires_at: Instant,
+    ) {
+        let expiry = (expires_at, self.sequence);
+        self.sequence += 1;
+
+        self.expiry.insert(expiry, (flag_key.clone(), key.clone()));
+        self.flags
+            .entry(flag_key)
+            .or_default()
+            .insert(key, CacheEntry { details, expiry });
+    }
+
+    fn remove(&mut self, flag_key: &str, key: &EntryKey) {
+        let Some(entries) = self.flags.get_mut(flag_key) else {
+            return;
+        };
+
+        if let Some(entry) = entries.remove(key) {
+            self.expiry.remove(&entry.expiry);
+        }
+
+        if entries.is_empty() {
+            self.flags.remove(flag_key);
+        }
+    }
+
+    fn remove_flag(&mut self, flag_key: &str) {
+        if let Some(entries) = self.flags.remove(flag_key) {
+            for entry in entries.values() {
+                self.expiry.remove(&entry.expiry);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.flags.clear();
+        self.expiry.clear();
+    }
+
+    fn remove_expired(&mut self, now: Instant) {
+        while let Some(entry) = self.expiry.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+
+            let (flag_key, key) = entry.remove();
+            self.remove(&flag_key, &key);
+        }
+    }
+
+    fn remove_closest_to_expiry(&mut self) {
+        if let Some((_, (flag_key, key))) = self.expiry.pop_first() {
+            self.remove(&flag_key, &key);
+        }
+    }
+}
+
+/// An evaluation context as a key, equal to the contexts with the same targeting key, as-of time
+/// and custom fields, in any order. Opaque structs are only equal to themselves, and are kept alive by the
+/// key so that their address is not reused.
+#[derive(Clone)]
+pub(super) struct ContextKey(EvaluationContext);
+
+impl ContextKey {
+    pub fn new(context: &EvaluationContext) -> Self {
+        Self(context.clone())
+    }
+}
+
+impl PartialEq for ContextKey {
+    fn eq(&self, other: &Self) -> bool {
+        let (left, right) = (&self.0, &other.0);
+
+        left.targeting_key == right.targeting_key
+            && left.as_of() == right.as_of()
+            && left.custom_fields.len() == right.custom_fields.len()
+            && left.custom_fields.iter().all(|(key, value)| {
+                right
+                    .custom_fields
+                    .get(key)
+                    .map_or(false, |other| same_field_value(value, other))
+            })
+    }
+}
+
+impl Eq for ContextKey {}
+
+impl Hash for ContextKey {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.0.targeting_key.hash(hasher);
+        self.0.as_of().hash(hasher);
+
+        let mut custom_fields: Vec<_> = self.0.custom_fields.iter().collect();
+        custom_fields.sort_unstable_by_key(|(key, _)| *key);
+
+        for (key, value) in custom_fields {
+            key.hash(hasher);
+            hash_field_value(value, hasher);
+        }
+    }
+}
+
+fn same_field_value(
+    left: &EvaluationContextFieldValue,
+    right: &EvaluationContextFieldValue,
+) -> bool {
+    match (left, right) {
+        (EvaluationContextFieldValue::Float(left), EvaluationContextFieldValue::Float(right)) => {
+            left.to_bits() == right.to_bits()
+        }
+        (EvaluationContextFieldValue::List(left), EvaluationContextFieldValue::List(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right)
+                    .all(|(left, right)| same_field_value(left, right))
+        }
+        (EvaluationContextFieldValue::Struct(left), EvaluationContextFieldValue::Struct(right)) => {
+            Arc::ptr_eq(left, right)
+        }
+        (left, right) => left == right,
+    }
+}
+
+fn hash_field_value<H: Hasher>(value: &EvaluationContextFieldValue, hasher: &mut H) {
+    std::mem::discriminant(value).hash(hasher);
+
+    match value {
+        EvaluationContextFieldValue::Bool(value) => value.hash(hasher),
+        EvaluationContextFieldValue::Int(value) => value.hash(hasher),
+        EvaluationContextFieldValue::Float(value) => value.to_bits().hash(hasher),
+        EvaluationContextFieldValue::String(value) => value.hash(hasher),
+        EvaluationContextFieldValue::DateTime(value) => value.hash(hasher),
+        EvaluationContextFieldValue::List(values) => {
+            values.len().hash(hasher);
+
+            for value in values {
+                hash_field_value(value, hasher);
+            }
+        }
+        EvaluationContextFieldValue::Struct(value) => {
+            Arc::as_ptr(value).cast::<()>().hash(hasher);
+        }
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        flags, provider::InMemoryFlag, EvaluationContext, EvaluationContextFieldValue,
+        EvaluationReason, OpenFeature,
+    };
+
+    #[tokio::test]
+    async fn cache_resolutions() {
+        let provider = flags! {
+            "tier" => String: "gold",
+        };
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let client = api.create_client().with_cache(Duration::from_secs(60), 100);
+        let alice = EvaluationContext::default().with_targeting_key("alice");
+
+        let details = client
+            .get_string_details("tier", Some(&alice), None)
+            .await
+            .unwrap();
+        assert_eq!(details.reason, Some(EvaluationReason::Static));
+
+        let details = client
+            .get_string_details("tier", Some(&alice), None)
+            .await
+            .unwrap();
+        assert_eq!(details.value, "gold");
+        assert_eq!(details.reason, Some(EvaluationReason::Cached));
+
+        // Cached per evaluation context.
+        let details = client.get_string_details("tier", None, None).await.unwrap();
+        assert_eq!(details.reason, Some(EvaluationReason::Static));
+
+        // Invalidated when the flag changes.
+        provider.set_flag("tier", InMemoryFlag::with_value("silver"));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let details = client
+            .get_string_details("tier", Some(&alice), None)
+            .await
+            .unwrap();
+        assert_eq!(details.value, "silver");
+        assert_eq!(details.reason, Some(EvaluationReason::Static));
+    }
+
+    #[tokio::test]
+    async fn evict_entries() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! {
+            "tier" => String: "gold",
+        })
+        .await
+        .unwrap();
+
+        let client = api.create_client().with_cache(Duration::from_secs(60), 1);
+        let alice = EvaluationContext::default().with_targeting_key("alice");
+        let bob = EvaluationContext::default().with_targeting_key("bob");
+
+        for context in [&alice, &bob, &alice] {
+            let details = client
+                .get_string_details("tier", Some(context), None)
+                .await
+                .unwrap();
+            assert_eq!(details.reason, Some(EvaluationReason::Static));
+        }
+
+        let details = client
+            .get_string_details("tier", Some(&alice), None)
+            .await
+            .unwrap();
+        assert_eq!(details.reason, Some(EvaluationReason::Cached));
+    }
+
+    #[tokio::test]
+    async fn compare_contexts() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! {
+            "tier" => String: "gold",
+        })
+        .await
+        .unwrap();
+
+        let client = api.create_client().with_cache(Duration::from_secs(60), 100);
+        let reason = |context: EvaluationContext| {
+            let client = client.clone();
+            async move {
+                client
+                    .get_string_details("tier", Some(&context), None)
+                    .await
+                    .unwrap()
+                    .reason
+            }
+        };
+
+        let plan = EvaluationContext::default()
+            .with_custom_field("plan", EvaluationContextFieldValue::new_struct(1_u8));
+        assert_eq!(reason(plan.clone()).await, Some(EvaluationReason::Static));
+        assert_eq!(reason(plan).await, Some(EvaluationReason::Cached));
+
+        // Opaque structs are only equal to themselves.
+        let other_plan = EvaluationContext::default()
+            .with_custom_field("plan", EvaluationContextFieldValue::new_struct(1_u8));
+        assert_eq!(reason(other_plan).await, Some(EvaluationReason::Static));
+
+        let fields = EvaluationContext::default()
+            .with_custom_field("a", 1)
+            .with_custom_field("b", 2.5);
+        assert_eq!(reason(fields).await, Some(EvaluationReason::Static));
+
+        let reordered = EvaluationContext::default()
+            .with_custom_field("b", 2.5)
+            .with_custom_field("a", 1);
+        assert_eq!(reason(reordered).await, Some(EvaluationReason::Cached));
+    }
+
+    #[tokio::test]
+    async fn invalidate_after_shutdown() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! {
+            "tier" => String: "gold",
+        })
+        .await
+        .unwrap();
+
+        let client = api.create_client().with_cache(Duration::from_secs(60), 100);
+        assert_eq!(
+            client.get_string_value("tier", None, None).await.unwrap(),
+            "gold"
+        );
+
+        api.shutdown().await;
+        tokio::task::yield_now().await;
+
+        let provider = flags! {
+            "tier" => String: "silver",
+        };
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let details = client.get_string_details("tier", None, None).await.unwrap();
+        assert_eq!(details.value, "silver");
+        assert_eq!(details.reason, Some(EvaluationReason::Static));
+
+        // The events of the new provider are listened to.
+        provider.set_flag("tier", InMemoryFlag::with_value("bronze"));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(
+            client.get_string_value("tier", None, None).await.unwrap(),
+            "bronze"
+        );
+    }
+}
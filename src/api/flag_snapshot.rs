@@ -0,0 +1,80 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    provider::{FeatureProvider, FlagType},
+    EvaluationContext, EvaluationDetails, EvaluationResult, Value,
+};
+
+use super::flag_cache::ContextKey;
+
+// ============================================================
+//  FlagSnapshot
+// ============================================================
+
+/// The frozen state of a client created with [`Client::snapshot`](crate::Client::snapshot): the
+/// provider and global evaluation context at the time, and the first resolution of every flag,
+/// keyed by flag key, flag type and evaluation context.
+#[derive(Clone)]
+pub struct FlagSnapshot {
+    provider: Arc<dyn FeatureProvider>,
+    global_evaluation_context: EvaluationContext,
+    resolutions: Arc<Mutex<Resolutions>>,
+}
+
+type Resolutions =
+    HashMap<(String, FlagType, ContextKey), EvaluationResult<EvaluationDetails<Value>>>;
+
+impl FlagSnapshot {
+    pub fn new(
+        provider: Arc<dyn FeatureProvider>,
+        global_evaluation_context: EvaluationContext,
+    ) -> Self {
+        Self {
+            provider,
+            global_evaluation_context,
+            resolutions: Arc::default(),
+        }
+    }
+
+    pub fn provider(&self) -> Arc<dyn FeatureProvider> {
+        self.provider.clone()
+    }
+
+    pub fn global_evaluation_context(&self) -> &EvaluationContext {
+        &self.global_evaluation_context
+    }
+
+    /// Return the resolution of `flag_key` as `flag_type` with `context`, if already resolved.
+    pub fn get(
+        &self,
+        flag_key: &str,
+        flag_type: FlagType,
+        context: &EvaluationContext,
+    ) -> Option<EvaluationResult<EvaluationDetails<Value>>> {
+        self.resolutions
+            .lock()
+            .unwrap()
+            .get(&(flag_key.to_string(), flag_type, ContextKey::new(context)))
+            .cloned()
+    }
+
+    /// Record the resolution `result` of `flag_key` as `flag_type` with `context`, unless
+    /// resolved meanwhile, and return the recorded one.
+    pub fn insert(
+        &self,
+        flag_key: &str,
+        flag_type: FlagType,
+        context: &EvaluationContext,
+        result: EvaluationResult<EvaluationDetails<Value>>,
+    ) -> EvaluationResult<EvaluationDetails<Value>> {
+        self.resolutions
+            .lock()
+            .unwrap()
+            .entry((flag_key.to_string(), flag_type, ContextKey::new(context)))
+            .or_insert(result)
+            .clone()
+    }
+}
@@ -0,0 +1,180 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{EvaluationDetails, EvaluationResult};
+
+/// The number of most recent latencies kept per flag to compute percentiles.
+const MAX_LATENCY_SAMPLES: usize = 1000;
+
+// ============================================================
+//  FlagStats
+// ============================================================
+
+/// The statistics of the evaluations of a flag by a client, as returned by
+/// [`Client::stats`](crate::Client::stats).
+#[derive(Clone, PartialEq, Debug)]
+pub struct FlagStats {
+    /// The key of the flag.
+    pub flag_key: String,
+
+    /// The number of evaluations.
+    pub evaluation_count: u64,
+
+    /// The number of failed evaluations.
+    pub error_count: u64,
+
+    /// The median latency of the most recent evaluations.
+    pub p50_latency: Duration,
+
+    /// The 99th percentile latency of the most recent evaluations.
+    pub p99_latency: Duration,
+
+    /// The variant resolved by the last successful evaluation, if any.
+    pub last_variant: Option<String>,
+}
+
+impl FlagStats {
+    /// Return the ratio of failed evaluations, from `0.0` to `1.0`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn error_rate(&self) -> f64 {
+        if self.evaluation_count == 0 {
+            0.0
+        } else {
+            self.error_count as f64 / self.evaluation_count as f64
+        }
+    }
+}
+
+// ============================================================
+//  FlagStatsRecorder
+// ============================================================
+
+/// Records the statistics of the evaluations of a client and its clones.
+#[derive(Clone, Default)]
+pub struct FlagStatsRecorder(Arc<Mutex<HashMap<String, FlagStatsAccumulator>>>);
+
+#[derive(Default)]
+struct FlagStatsAccumulator {
+    evaluation_count: u64,
+    error_count: u64,
+    latencies: VecDeque<Duration>,
+    last_variant: Option<String>,
+}
+
+impl FlagStatsRecorder {
+    pub fn record<T>(
+        &self,
+        flag_key: &str,
+        latency: Duration,
+        result: &EvaluationResult<EvaluationDetails<T>>,
+    ) {
+        let mut stats = self.0.lock().unwrap();
+
+        let accumulator = match stats.get_mut(flag_key) {
+            Some(accumulator) => accumulator,
+            None => stats.entry(flag_key.to_string()).or_default(),
+        };
+
+        accumulator.evaluation_count += 1;
+
+        match result {
+            Ok(details) => {
+                if let Some(variant) = &details.variant {
+                    accumulator.last_variant = Some(variant.clone());
+                }
+            }
+            Err(_) => accumulator.error_count += 1,
+        }
+
+        if accumulator.latencies.len() == MAX_LATENCY_SAMPLES {
+            accumulator.latencies.pop_front();
+        }
+        accumulator.latencies.push_back(latency);
+    }
+
+    /// Return the statistics of all the evaluated flags, sorted by key.
+    pub fn snapshot(&self) -> Vec<FlagStats> {
+        let stats = self.0.lock().unwrap();
+
+        let mut snapshot: Vec<_> = stats
+            .iter()
+            .map(|(flag_key, accumulator)| {
+                let mut latencies: Vec<_> = accumulator.latencies.iter().copied().collect();
+                latencies.sort_unstable();
+
+                FlagStats {
+                    flag_key: flag_key.clone(),
+                    evaluation_count: accumulator.evaluation_count,
+                    error_count: accumulator.error_count,
+                    p50_latency: percentile(&latencies, 50),
+                    p99_latency: percentile(&latencies, 99),
+                    last_variant: accumulator.last_variant.clone(),
+                }
+            })
+            .collect();
+
+        snapshot.sort_by(|a, b| a.flag_key.cmp(&b.flag_key));
+        snapshot
+    }
+}
+
+/// Return the `percent`th percentile of sorted `latencies`.
+fn percentile(latencies: &[Duration], percent: usize) -> Duration {
+    if latencies.is_empty() {
+        return Duration::ZERO;
+    }
+
+    latencies[(latencies.len() - 1) * percent / 100]
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{flags, OpenFeature};
+
+    #[test]
+    fn latency_percentiles() {
+        let latencies: Vec<_> = (1..=100).map(Duration::from_millis).collect();
+
+        assert_eq!(percentile(&latencies, 50), Duration::from_millis(50));
+        assert_eq!(percentile(&latencies, 99), Duration::from_millis(99));
+        assert_eq!(percentile(&[], 99), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn collect_stats() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! {
+            "checkout-v2" => bool: true,
+            "tier" => variants { "gold" => "Gold" }, default "gold",
+        })
+        .await
+        .unwrap();
+
+        let client = api.create_client();
+
+        for _ in 0..3 {
+            let _ = client.get_bool_value("checkout-v2", None, None).await;
+        }
+        let _ = client.get_int_value("tier", None, None).await;
+        let _ = client.get_string_value("tier", None, None).await;
+
+        let stats = client.stats();
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].flag_key, "checkout-v2");
+        assert_eq!(stats[0].evaluation_count, 3);
+        assert_eq!(stats[0].last_variant, Some("true".to_string()));
+        assert_eq!(stats[1].flag_key, "tier");
+        assert_eq!(stats[1].evaluation_count, 2);
+        assert!((stats[1].error_rate() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(stats[1].last_variant, Some("gold".to_string()));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::provider::ProviderEvent;
+
+use super::provider_events::ProviderEventListener;
+
+// ============================================================
+//  FlagWatch
+// ============================================================
+
+/// A subscription to the changes of a single flag, created with [`Client::watch`].
+///
+/// It is driven by the events of the provider bound to the client: a
+/// `PROVIDER_CONFIGURATION_CHANGED` event listing the flag (or not listing any flag), and the
+/// provider being replaced.
+///
+/// ```no_run
+/// use open_feature::OpenFeature;
+///
+/// # async fn example() {
+/// let client = OpenFeature::singleton().await.create_client();
+/// let mut watch = client.watch("checkout-v2");
+///
+/// while watch.changed().await.is_some() {
+///     let enabled = client.get_bool_value("checkout-v2", None, None).await;
+///     // Reconfigure accordingly.
+/// }
+/// # }
+/// ```
+///
+/// [`Client::watch`]: crate::Client::watch
+pub struct FlagWatch {
+    flag_key: String,
+    listener: ProviderEventListener,
+}
+
+impl FlagWatch {
+    pub(crate) fn new(flag_key: String, listener: ProviderEventListener) -> Self {
+        Self { flag_key, listener }
+    }
+
+    /// Return the key of watched flag.
+    pub fn flag_key(&self) -> &str {
+        &self.flag_key
+    }
+
+    /// Start listening to the provider right away rather than on the first call to
+    /// [`Self::changed`].
+    pub(crate) fn listen(&mut self) -> bool {
+        self.listener.listen()
+    }
+
+    /// Wait until the value of the flag might have changed, and return the event telling so.
+    ///
+    /// Return `None` once the providers are shut down.
+    pub async fn changed(&mut self) -> Option<ProviderEvent> {
+        loop {
+            let event = self.listener.recv().await?;
+
+            if event.affects(&self.flag_key) {
+                return Some(event);
+            }
+        }
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use crate::{
+        flags,
+        provider::{InMemoryFlag, ProviderEventType},
+        OpenFeature,
+    };
+
+    #[tokio::test]
+    async fn watch_configuration_changes() {
+        let provider = flags! {
+            "checkout-v2" => bool: false,
+            "tier" => String: "gold",
+        };
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let client = api.create_client();
+        let mut watch = client.watch("checkout-v2");
+
+        let watcher = tokio::spawn(async move { watch.changed().await });
+        tokio::task::yield_now().await;
+
+        provider.set_flag("tier", InMemoryFlag::with_value("silver"));
+        provider.set_flag("checkout-v2", InMemoryFlag::with_value(true));
+
+        let event = timeout(Duration::from_secs(1), watcher)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(event.event_type, ProviderEventType::ConfigurationChanged);
+        assert_eq!(event.flags_changed, Some(vec!["checkout-v2".to_string()]));
+        assert!(client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn watch_provider_replacement() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! { "checkout-v2" => bool: false })
+            .await
+            .unwrap();
+
+        let client = api.create_client();
+        let mut watch = client.watch("checkout-v2");
+
+        let watcher = tokio::spawn(async move { watch.changed().await });
+        tokio::task::yield_now().await;
+
+        api.set_provider(flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let event = timeout(Duration::from_secs(1), watcher)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(event.event_type, ProviderEventType::Ready);
+        assert_eq!(event.provider_name, "In-memory Provider");
+    }
+
+    #[tokio::test]
+    async fn end_on_shutdown() {
+        let mut api = OpenFeature::default();
+        let mut watch = api.create_client().watch("checkout-v2");
+
+        api.shutdown().await;
+
+        assert!(watch.changed().await.is_none());
+    }
+}
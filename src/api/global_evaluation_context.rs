@@ -1,22 +1,45 @@
 use std::sync::Arc;
 
-use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use arc_swap::ArcSwap;
+use tokio::sync::broadcast;
 
 use crate::EvaluationContext;
 
-#[derive(Clone, Default)]
-pub struct GlobalEvaluationContext(Arc<RwLock<EvaluationContext>>);
+/// The global evaluation context, read without locking on every evaluation.
+#[derive(Clone)]
+pub struct GlobalEvaluationContext(Arc<ArcSwap<EvaluationContext>>, broadcast::Sender<()>);
+
+impl Default for GlobalEvaluationContext {
+    fn default() -> Self {
+        Self::new(EvaluationContext::default())
+    }
+}
 
 impl GlobalEvaluationContext {
     pub fn new(evaluation_context: EvaluationContext) -> Self {
-        Self(Arc::new(RwLock::new(evaluation_context)))
+        Self(
+            Arc::new(ArcSwap::from_pointee(evaluation_context)),
+            broadcast::channel(1).0,
+        )
+    }
+
+    pub fn get(&self) -> Arc<EvaluationContext> {
+        self.0.load_full()
+    }
+
+    /// Replace the evaluation context, and notify the subscribers.
+    pub fn set(&self, evaluation_context: EvaluationContext) {
+        self.0.store(Arc::new(evaluation_context));
+        self.notify_change();
     }
 
-    pub async fn get(&self) -> RwLockReadGuard<EvaluationContext> {
-        self.0.read().await
+    fn notify_change(&self) {
+        // An error only means nobody is listening.
+        let _ = self.1.send(());
     }
 
-    pub async fn get_mut(&self) -> RwLockWriteGuard<EvaluationContext> {
-        self.0.write().await
+    /// Return a receiver notified whenever the evaluation context changes.
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<()> {
+        self.1.subscribe()
     }
 }
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use tokio::task::JoinSet;
+
+use crate::Hook;
+
+/// The hooks of the API, run for the evaluations of all the clients, read without locking on
+/// every evaluation.
+#[derive(Clone)]
+pub struct GlobalHooks(Arc<ArcSwap<Vec<Arc<dyn Hook>>>>);
+
+impl Default for GlobalHooks {
+    fn default() -> Self {
+        Self(Arc::new(ArcSwap::from_pointee(Vec::new())))
+    }
+}
+
+impl GlobalHooks {
+    pub fn get(&self) -> Arc<Vec<Arc<dyn Hook>>> {
+        self.0.load_full()
+    }
+
+    pub fn add<T: Hook>(&self, hook: T) {
+        let hook: Arc<dyn Hook> = Arc::new(hook);
+        self.0.rcu(|hooks| {
+            let mut hooks = Vec::clone(hooks);
+            hooks.push(Arc::clone(&hook));
+            hooks
+        });
+    }
+
+    /// Remove all the hooks, and return them to be shut down with [`Self::shut_down`].
+    pub fn take(&self) -> Arc<Vec<Arc<dyn Hook>>> {
+        self.0.swap(Arc::default())
+    }
+
+    /// Shut `hooks` down at the same time.
+    pub async fn shut_down(hooks: Arc<Vec<Arc<dyn Hook>>>) {
+        let mut shutdowns = JoinSet::new();
+
+        for hook in hooks.iter() {
+            let hook = Arc::clone(hook);
+            shutdowns.spawn(async move { hook.shutdown().await });
+        }
+
+        while shutdowns.join_next().await.is_some() {}
+    }
+}
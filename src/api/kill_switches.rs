@@ -0,0 +1,479 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env, io,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use tokio::{sync::broadcast, task::JoinHandle};
+
+use crate::{
+    provider::{EventEmitter, FlagType, FlagValue, ProviderEvent, ProviderEventType},
+    EvaluationDetails, EvaluationError, EvaluationErrorCode, EvaluationReason, EvaluationResult,
+    FlagMetadata, SdkError, Value,
+};
+
+// ============================================================
+//  KillSwitches
+// ============================================================
+
+/// A process-wide registry of flags forced to fixed values, taking precedence over all the
+/// providers, so that operators can disable a misbehaving feature without touching the flag
+/// backend. Access it through [`OpenFeature::kill_switches`](crate::OpenFeature::kill_switches).
+///
+/// Kill switches are initially read from the [`Self::ENV_VAR`] environment variable, as a
+/// comma-separated list of `flag_key=value`. Values are parsed as bools, ints and floats when
+/// possible, and as strings otherwise:
+///
+/// ```sh
+/// OPENFEATURE_KILL_SWITCHES="new-checkout=false,max-retries=0"
+/// ```
+///
+/// On-call engineers can also force flags from an override file in the same format, one entry
+/// per line if preferred, watched with [`Self::watch_file`]. Its content is checked for changes
+/// every [`Self::FILE_POLL_INTERVAL`], and sending `SIGHUP` to the process reloads it
+/// immediately. The kill switches of the file are kept apart from the ones engaged through
+/// [`Self::engage`], which take precedence and are left alone by file reloads.
+///
+/// Forced values are resolved with the [`EvaluationReason::Static`] reason and a `kill_switch`
+/// flag metadata field. Engaging or releasing a kill switch emits a
+/// `PROVIDER_CONFIGURATION_CHANGED` event, so that flag watches pick the change up.
+#[derive(Clone, Default, Debug)]
+pub struct KillSwitches {
+    /// The flags forced through [`Self::engage`].
+    flags: Arc<RwLock<HashMap<String, Value>>>,
+    /// The flags forced by the override file.
+    file_flags: Arc<RwLock<HashMap<String, Value>>>,
+    events: EventEmitter,
+}
+
+impl KillSwitches {
+    /// The environment variable kill switches are initially read from.
+    pub const ENV_VAR: &'static str = "OPENFEATURE_KILL_SWITCHES";
+
+    /// The provider name of the events emitted by kill switches.
+    pub const PROVIDER_NAME: &'static str = "Kill Switches";
+
+    /// The interval the override file is checked for changes at.
+    pub const FILE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Create a registry with the kill switches of the [`Self::ENV_VAR`] environment variable,
+    /// if set.
+    pub fn from_env() -> Self {
+        let kill_switches = Self::default();
+
+        if let Ok(value) = env::var(Self::ENV_VAR) {
+            kill_switches.parse(&value);
+        }
+
+        kill_switches
+    }
+
+    /// Engage the kill switches of `value`, a comma or newline-separated list of
+    /// `flag_key=value`. Malformed entries and lines starting with `#` are ignored.
+    pub fn parse(&self, value: &str) {
+        for (flag_key, value) in parse_entries(value) {
+            self.engage(flag_key, value);
+        }
+    }
+
+    /// Replace the kill switches forced by the override file with the ones of the file at `path`,
+    /// in the format of [`Self::parse`]. A missing file releases them all.
+    pub async fn load_file(&self, path: impl Into<PathBuf>) -> Result<(), SdkError> {
+        let overrides = read_file(&path.into()).await?;
+        self.apply_file(&overrides);
+        Ok(())
+    }
+
+    /// Load the override file at `path` now and whenever its content changes or the process
+    /// receives `SIGHUP`, until the returned task is aborted. Files failing to load are skipped,
+    /// keeping the current kill switches.
+    pub fn watch_file(&self, path: impl Into<PathBuf>) -> JoinHandle<()> {
+        self.watch_file_every(path.into(), Self::FILE_POLL_INTERVAL)
+    }
+
+    fn watch_file_every(&self, path: PathBuf, poll_interval: Duration) -> JoinHandle<()> {
+        let kill_switches = self.clone();
+
+        tokio::spawn(async move {
+            let mut hangup = Hangup::new();
+            let mut interval = tokio::time::interval(poll_interval);
+            let mut loaded: Option<String> = None;
+
+            loop {
+                let signaled = tokio::select! {
+                    _ = interval.tick() => false,
+                    () = hangup.recv() => true,
+                };
+
+                let Ok(overrides) = read_file(&path).await else {
+                    continue;
+                };
+
+                if signaled || loaded.as_ref() != Some(&overrides) {
+                    kill_switches.apply_file(&overrides);
+                    loaded = Some(overrides);
+                }
+            }
+        })
+    }
+
+    /// Replace the kill switches forced by the override file with the ones of `overrides`.
+    fn apply_file(&self, overrides: &str) {
+        let entries: HashMap<_, _> = parse_entries(overrides).into_iter().collect();
+        let previous = std::mem::replace(&mut *self.file_flags.write().unwrap(), entries.clone());
+
+        for flag_key in previous.keys() {
+            if !entries.contains_key(flag_key) {
+                self.emit(flag_key.clone(), "Kill switch released");
+            }
+        }
+
+        for (flag_key, value) in entries {
+            if previous.get(&flag_key) != Some(&value) {
+                self.emit(flag_key, "Kill switch engaged");
+            }
+        }
+    }
+
+    /// Force `flag_key` to `value`, replacing its current forced value if any.
+    pub fn engage(&self, flag_key: impl Into<String>, value: impl Into<Value>) {
+        let flag_key = flag_key.into();
+
+        self.flags
+            .write()
+            .unwrap()
+            .insert(flag_key.clone(), value.into());
+
+        self.emit(flag_key, "Kill switch engaged");
+    }
+
+    /// Stop forcing the value of `flag_key` through [`Self::engage`], leaving the override file
+    /// alone. Return `false` if its kill switch was not engaged.
+    pub fn release(&self, flag_key: &str) -> bool {
+        let released = self.flags.write().unwrap().remove(flag_key).is_some();
+
+        if released {
+            self.emit(flag_key.to_string(), "Kill switch released");
+        }
+
+        released
+    }
+
+    /// Return the value `flag_key` is forced to, if any.
+    pub fn get(&self, flag_key: &str) -> Option<Value> {
+        let engaged = self.flags.read().unwrap().get(flag_key).cloned();
+        engaged.or_else(|| self.file_flags.read().unwrap().get(flag_key).cloned())
+    }
+
+    /// Return the keys of the flags with an engaged kill switch, sorted.
+    pub fn engaged(&self) -> Vec<String> {
+        let mut flag_keys: Vec<_> = self
+            .flags
+            .read()
+            .unwrap()
+            .keys()
+            .chain(self.file_flags.read().unwrap().keys())
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        flag_keys.sort();
+        flag_keys
+    }
+
+    /// Return a receiver of the events emitted from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ProviderEvent> {
+        self.events.subscribe()
+    }
+
+    /// Return the details of `flag_key` as `T` if its kill switch is engaged.
+    pub(crate) fn evaluate<T: FlagValue>(
+        &self,
+        flag_key: &str,
+    ) -> Option<EvaluationResult<EvaluationDetails<T>>> {
+        let value = self.get(flag_key)?;
+
+        Some(match T::FLAG_TYPE.cast(value).and_then(T::from_value) {
+            Some(value) => Ok(kill_switch_details(flag_key, value)),
+            None => Err(type_mismatch(flag_key, T::FLAG_TYPE)),
+        })
+    }
+
+    /// Return the details of `flag_key` as `flag_type` if its kill switch is engaged.
+    pub(crate) fn evaluate_value(
+        &self,
+        flag_key: &str,
+        flag_type: FlagType,
+    ) -> Option<EvaluationResult<EvaluationDetails<Value>>> {
+        let value = self.get(flag_key)?;
+
+        Some(match flag_type.cast(value) {
+            Some(value) => Ok(kill_switch_details(flag_key, value)),
+            None => Err(type_mismatch(flag_key, flag_type)),
+        })
+    }
+
+    fn emit(&self, flag_key: String, message: &str) {
+        self.events.emit(
+            ProviderEvent::builder()
+                .event_type(ProviderEventType::ConfigurationChanged)
+                .provider_name(Self::PROVIDER_NAME)
+                .flags_changed(vec![flag_key])
+                .message(message)
+                .build(),
+        );
+    }
+}
+
+fn kill_switch_details<T>(flag_key: &str, value: T) -> EvaluationDetails<T> {
+    EvaluationDetails {
+        flag_key: flag_key.to_string(),
+        value,
+        reason: Some(EvaluationReason::Static),
+        variant: None,
+        flag_metadata: FlagMetadata::default().with_value("kill_switch", true),
+    }
+}
+
+fn type_mismatch(flag_key: &str, flag_type: FlagType) -> EvaluationError {
+    EvaluationError::builder()
+        .code(EvaluationErrorCode::TypeMismatch)
+        .message(format!(
+            "Kill switch of flag \"{}\" is not a {:?} value",
+            flag_key, flag_type
+        ))
+        .build()
+}
+
+/// Read the override file at `path`, empty if missing.
+async fn read_file(path: &PathBuf) -> Result<String, SdkError> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(overrides) => Ok(overrides),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(error) => Err(SdkError::Io {
+            path: path.display().to_string(),
+            source: error,
+        }),
+    }
+}
+
+fn parse_entries(value: &str) -> Vec<(String, Value)> {
+    value
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|entry| !entry.starts_with('#'))
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(flag_key, value)| (flag_key.trim(), value.trim()))
+        .filter(|(flag_key, _)| !flag_key.is_empty())
+        .map(|(flag_key, value)| (flag_key.to_string(), parse_value(value)))
+        .collect()
+}
+
+fn parse_value(value: &str) -> Value {
+    if let Ok(value) = value.parse::<bool>() {
+        Value::Bool(value)
+    } else if let Ok(value) = value.parse::<i64>() {
+        Value::Int(value)
+    } else if let Ok(value) = value.parse::<f64>() {
+        Value::Float(value)
+    } else {
+        Value::String(value.to_string())
+    }
+}
+
+/// The `SIGHUP` signals received by the process, never received on other platforms.
+struct Hangup {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Hangup {
+    fn new() -> Self {
+        Self {
+            #[cfg(unix)]
+            signal: tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()).ok(),
+        }
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            if signal.recv().await.is_some() {
+                return;
+            }
+        }
+
+        std::future::pending::<()>().await;
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_kill_switches() {
+        let kill_switches = KillSwitches::default();
+        kill_switches.parse("new-checkout=false, max-retries=0,ratio=0.5,theme=dark,malformed");
+
+        assert_eq!(
+            kill_switches.engaged(),
+            vec!["max-retries", "new-checkout", "ratio", "theme"]
+        );
+        assert_eq!(kill_switches.get("new-checkout"), Some(Value::Bool(false)));
+        assert_eq!(kill_switches.get("max-retries"), Some(Value::Int(0)));
+        assert_eq!(kill_switches.get("ratio"), Some(Value::Float(0.5)));
+        assert_eq!(
+            kill_switches.get("theme"),
+            Some(Value::String("dark".to_string()))
+        );
+    }
+
+    #[test]
+    fn evaluate_kill_switch() {
+        let kill_switches = KillSwitches::default();
+        kill_switches.engage("max-retries", 0);
+
+        assert_eq!(
+            kill_switches
+                .evaluate::<f64>("max-retries")
+                .unwrap()
+                .unwrap()
+                .value
+                .to_value(),
+            Value::Float(0.0)
+        );
+        assert_eq!(
+            kill_switches
+                .evaluate::<bool>("max-retries")
+                .unwrap()
+                .unwrap_err()
+                .code,
+            EvaluationErrorCode::TypeMismatch
+        );
+        assert!(kill_switches.evaluate::<bool>("new-checkout").is_none());
+    }
+
+    #[tokio::test]
+    async fn reload_override_file() {
+        let path = env::temp_dir().join(format!("kill-switches-{}", std::process::id()));
+        let kill_switches = KillSwitches::default();
+        kill_switches.engage("theme", "dark");
+
+        tokio::fs::write(&path, "# Incident 42\nnew-checkout=false\nmax-retries=0\n")
+            .await
+            .unwrap();
+        kill_switches.load_file(&path).await.unwrap();
+        assert_eq!(
+            kill_switches.engaged(),
+            vec!["max-retries", "new-checkout", "theme"]
+        );
+
+        tokio::fs::write(&path, "max-retries=1").await.unwrap();
+        kill_switches.load_file(&path).await.unwrap();
+        assert_eq!(kill_switches.engaged(), vec!["max-retries", "theme"]);
+        assert_eq!(kill_switches.get("max-retries"), Some(Value::Int(1)));
+
+        // Kill switches engaged through the API are kept over the ones of the file.
+        kill_switches.engage("max-retries", 5);
+        assert_eq!(kill_switches.get("max-retries"), Some(Value::Int(5)));
+
+        // Removing the file releases its kill switches only.
+        tokio::fs::remove_file(&path).await.unwrap();
+        kill_switches.load_file(&path).await.unwrap();
+        assert_eq!(kill_switches.engaged(), vec!["max-retries", "theme"]);
+        assert_eq!(kill_switches.get("max-retries"), Some(Value::Int(5)));
+    }
+
+    /// Return the value of `flag_key` once it is `value`, or after 5 seconds, yielding without
+    /// advancing a paused clock meanwhile.
+    async fn wait_for(kill_switches: &KillSwitches, flag_key: &str, value: Value) -> Option<Value> {
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+
+        while kill_switches.get(flag_key).as_ref() != Some(&value)
+            && std::time::Instant::now() < deadline
+        {
+            tokio::task::yield_now().await;
+        }
+
+        kill_switches.get(flag_key)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn watch_override_file() {
+        let path = env::temp_dir().join(format!("kill-switches-watch-{}", std::process::id()));
+        let kill_switches = KillSwitches::default();
+
+        tokio::fs::write(&path, "new-checkout=false").await.unwrap();
+        let watcher = kill_switches.watch_file(&path);
+        assert_eq!(
+            wait_for(&kill_switches, "new-checkout", Value::Bool(false)).await,
+            Some(Value::Bool(false))
+        );
+
+        // Rewritten within the same second, with content of the same length.
+        let rewritten = Value::String("true!".to_string());
+        tokio::fs::write(&path, "new-checkout=true!").await.unwrap();
+        tokio::time::advance(KillSwitches::FILE_POLL_INTERVAL).await;
+        assert_eq!(
+            wait_for(&kill_switches, "new-checkout", rewritten.clone()).await,
+            Some(rewritten)
+        );
+
+        watcher.abort();
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(start_paused = true)]
+    async fn reload_override_file_on_hangup() {
+        let path = env::temp_dir().join(format!("kill-switches-hangup-{}", std::process::id()));
+        let kill_switches = KillSwitches::default();
+
+        tokio::fs::write(&path, "new-checkout=false").await.unwrap();
+        let watcher = kill_switches.watch_file_every(path.clone(), Duration::from_secs(3600));
+        assert_eq!(
+            wait_for(&kill_switches, "new-checkout", Value::Bool(false)).await,
+            Some(Value::Bool(false))
+        );
+
+        // Only the signal reloads the file before the next poll, an hour later.
+        tokio::fs::write(&path, "new-checkout=true").await.unwrap();
+        let status = std::process::Command::new("kill")
+            .args(["-HUP", &std::process::id().to_string()])
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert_eq!(
+            wait_for(&kill_switches, "new-checkout", Value::Bool(true)).await,
+            Some(Value::Bool(true))
+        );
+
+        watcher.abort();
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn emit_on_change() {
+        let kill_switches = KillSwitches::default();
+        let mut events = kill_switches.subscribe();
+
+        kill_switches.engage("new-checkout", false);
+        assert!(!kill_switches.release("max-retries"));
+        assert!(kill_switches.release("new-checkout"));
+
+        let event = events.recv().await.unwrap();
+        assert_eq!(event.event_type, ProviderEventType::ConfigurationChanged);
+        assert_eq!(event.flags_changed, Some(vec!["new-checkout".to_string()]));
+        assert_eq!(event.message, Some("Kill switch engaged".to_string()));
+
+        let event = events.recv().await.unwrap();
+        assert_eq!(event.message, Some("Kill switch released".to_string()));
+    }
+}
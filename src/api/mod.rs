@@ -4,6 +4,32 @@ pub use api::OpenFeature;
 mod client;
 pub use client::{Client, ClientMetadata};
 
+mod sdk_error;
+pub use sdk_error::SdkError;
+
+mod static_context_client;
+pub use static_context_client::StaticContextClient;
+
+mod flag_cache;
+
+mod flag_snapshot;
+
+mod flag_stats;
+pub use flag_stats::FlagStats;
+
+mod flag_batch;
+pub use flag_batch::{FlagBatch, FlagChange};
+
+mod kill_switches;
+pub use kill_switches::KillSwitches;
+
+mod flag_watch;
+pub use flag_watch::FlagWatch;
+
+mod provider_events;
+
 mod provider_registry;
 
 mod global_evaluation_context;
+
+mod global_hooks;
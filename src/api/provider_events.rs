@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::provider::{FeatureProvider, ProviderEvent, ProviderEventType};
+
+use super::{kill_switches::KillSwitches, provider_registry::ProviderRegistry};
+
+// ============================================================
+//  ProviderEventListener
+// ============================================================
+
+/// Listens to the events of the provider bound to a client name, following the provider when it
+/// is replaced, along with the events of kill switches.
+///
+/// The provider is only asked for its event emitter once the first event is awaited, so that
+/// nothing is requested from providers nobody listens to.
+pub struct ProviderEventListener {
+    registry: ProviderRegistry,
+    name: String,
+    registry_changes: broadcast::Receiver<()>,
+    provider: Option<Arc<dyn FeatureProvider>>,
+    provider_events: Option<broadcast::Receiver<ProviderEvent>>,
+    kill_switch_events: broadcast::Receiver<ProviderEvent>,
+}
+
+impl ProviderEventListener {
+    pub fn new(
+        registry: ProviderRegistry,
+        name: impl Into<String>,
+        kill_switches: &KillSwitches,
+    ) -> Self {
+        Self {
+            registry_changes: registry.subscribe_changes(),
+            registry,
+            name: name.into(),
+            provider: None,
+            provider_events: None,
+            kill_switch_events: kill_switches.subscribe(),
+        }
+    }
+
+    /// Wait for the next event. A `PROVIDER_READY` event is produced whenever the provider is
+    /// replaced, as it is initialized by then.
+    ///
+    /// Return `None` once the providers are shut down.
+    pub async fn recv(&mut self) -> Option<ProviderEvent> {
+        if !self.listen() {
+            return None;
+        }
+
+        loop {
+            tokio::select! {
+                change = self.registry_changes.recv() => match change {
+                    Ok(()) | Err(RecvError::Lagged(_)) => {
+                        let provider = self.registry.find(&self.name)?.get();
+
+                        if !self.is_attached_to(&provider) {
+                            let event = ProviderEvent::builder()
+                                .event_type(ProviderEventType::Ready)
+                                .provider_name(provider.metadata().name.clone())
+                                .build();
+
+                            self.attach(provider);
+
+                            return Some(event);
+                        }
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+                event = recv_event(&mut self.provider_events) => match event {
+                    Ok(event) => return Some(event),
+                    // Some events were dropped, so any flag might have changed.
+                    Err(RecvError::Lagged(_)) => {
+                        return Some(
+                            ProviderEvent::builder()
+                                .event_type(ProviderEventType::ConfigurationChanged)
+                                .provider_name(self.provider_name())
+                                .build(),
+                        );
+                    }
+                    Err(RecvError::Closed) => self.provider_events = None,
+                },
+                event = self.kill_switch_events.recv() => match event {
+                    Ok(event) => return Some(event),
+                    Err(RecvError::Lagged(_)) => {
+                        return Some(
+                            ProviderEvent::builder()
+                                .event_type(ProviderEventType::ConfigurationChanged)
+                                .provider_name(KillSwitches::PROVIDER_NAME)
+                                .build(),
+                        );
+                    }
+                    // The API and all its clients are dropped.
+                    Err(RecvError::Closed) => return None,
+                },
+            }
+        }
+    }
+
+    /// Start listening to the current provider if not done yet, so that no event emitted from
+    /// now on is missed.
+    ///
+    /// Return `false` once the providers are shut down.
+    pub fn listen(&mut self) -> bool {
+        if self.provider.is_none() {
+            match self.registry.find(&self.name) {
+                Some(provider) => self.attach(provider.get()),
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    fn attach(&mut self, provider: Arc<dyn FeatureProvider>) {
+        self.provider_events = provider.event_emitter().map(|emitter| emitter.subscribe());
+        self.provider = Some(provider);
+    }
+
+    fn is_attached_to(&self, provider: &Arc<dyn FeatureProvider>) -> bool {
+        self.provider
+            .as_ref()
+            .map_or(false, |attached| Arc::ptr_eq(attached, provider))
+    }
+
+    fn provider_name(&self) -> String {
+        self.provider
+            .as_ref()
+            .map(|provider| provider.metadata().name.clone())
+            .unwrap_or_default()
+    }
+}
+
+async fn recv_event(
+    receiver: &mut Option<broadcast::Receiver<ProviderEvent>>,
+) -> Result<ProviderEvent, RecvError> {
+    match receiver {
+        Some(receiver) => receiver.recv().await,
+        None => std::future::pending().await,
+    }
+}
@@ -1,20 +1,34 @@
-use std::sync::Arc;
-use std::{borrow::Borrow, collections::HashMap};
+use std::sync::{Arc, Mutex};
+use std::{any::type_name, any::Any, collections::HashMap};
 
-use tokio::sync::RwLock;
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::{JoinHandle, JoinSet},
+};
 
-use crate::provider::{FeatureProvider, NoOpProvider};
+use crate::{
+    provider::{
+        EventEmitter, FeatureProvider, FlagType, NoOpProvider, ProviderEvent, ProviderEventType,
+        ProviderMetadata, ProviderStatus, ResolutionDetails,
+    },
+    EvaluationContext, EvaluationErrorCode, EvaluationResult, ProviderError, ProviderErrorKind,
+    StructValue, TrackingEventDetails, Value,
+};
 
-use super::global_evaluation_context::GlobalEvaluationContext;
+use super::{global_evaluation_context::GlobalEvaluationContext, sdk_error::SdkError};
 
 // ============================================================
 //  ProviderRegistry
 // ============================================================
 
+/// The providers bound to client names, read without locking on every evaluation.
 #[derive(Clone)]
 pub struct ProviderRegistry {
     global_evaluation_context: GlobalEvaluationContext,
-    providers: Arc<RwLock<HashMap<String, FeatureProviderWrapper>>>,
+    providers: Arc<ArcSwap<HashMap<String, FeatureProviderWrapper>>>,
+    changes: broadcast::Sender<()>,
 }
 
 impl ProviderRegistry {
@@ -27,54 +41,136 @@ impl ProviderRegistry {
 
         Self {
             global_evaluation_context: evaluation_context,
-            providers: Arc::new(RwLock::new(providers)),
+            providers: Arc::new(ArcSwap::from_pointee(providers)),
+            changes: broadcast::channel(1).0,
         }
     }
 
-    pub async fn set_default<T: FeatureProvider>(&self, mut provider: T) {
-        let mut map = self.providers.write().await;
-        map.remove("");
-
-        provider
-            .initialize(self.global_evaluation_context.get().await.borrow())
-            .await;
+    pub async fn set_default<T: FeatureProvider>(&self, provider: T) -> Result<(), SdkError> {
+        self.set("", provider).await
+    }
 
-        map.insert(String::default(), FeatureProviderWrapper::new(provider));
+    pub async fn set_named<T: FeatureProvider>(
+        &self,
+        name: &str,
+        provider: T,
+    ) -> Result<(), SdkError> {
+        self.set(name, provider).await
     }
 
-    pub async fn set_named<T: FeatureProvider>(&self, name: &str, mut provider: T) {
-        // Drop the already registered provider if any.
-        if self.get_named(name).await.is_some() {
-            self.providers.write().await.remove(name);
+    /// Initialize `provider` and bind it to `name`, replacing the current one only once
+    /// initialized. The replaced provider is shut down.
+    async fn set<T: FeatureProvider>(&self, name: &str, mut provider: T) -> Result<(), SdkError> {
+        let context = self.global_evaluation_context.get();
+
+        // Initialized in a task of its own, so that even a panic is reported as an error.
+        let (provider, result) = tokio::spawn(async move {
+            let result = provider.initialize(&context).await;
+            (provider, result)
+        })
+        .await
+        .map_err(|error| SdkError::ProviderInitialization {
+            provider: type_name::<T>().to_string(),
+            source: if error.is_panic() {
+                ProviderError::new(
+                    ProviderErrorKind::Other("PANIC".to_string()),
+                    panic_message(error.into_panic()),
+                )
+            } else {
+                ProviderError::new(
+                    ProviderErrorKind::Other("CANCELLED".to_string()),
+                    "Initialization was cancelled",
+                )
+            },
+        })?;
+
+        if let Err(error) = result {
+            // Release whatever the provider started before failing.
+            provider.shutdown().await;
+
+            return Err(SdkError::ProviderInitialization {
+                provider: type_name::<T>().to_string(),
+                source: error,
+            });
         }
 
-        provider
-            .initialize(self.global_evaluation_context.get().await.borrow())
-            .await;
+        let provider = FeatureProviderWrapper::initialized(provider);
+        let previous = self.providers.rcu(|providers| {
+            let mut providers = HashMap::clone(providers);
+            providers.insert(name.to_string(), provider.clone());
+            providers
+        });
 
-        self.providers
-            .write()
-            .await
-            .insert(name.to_string(), FeatureProviderWrapper::new(provider));
+        self.notify_change();
+
+        if let Some(replaced) = previous.get(name) {
+            replaced.get().shutdown().await;
+        }
+
+        Ok(())
     }
 
-    pub async fn get(&self, name: &str) -> FeatureProviderWrapper {
-        match self.get_named(name).await {
+    pub fn get(&self, name: &str) -> FeatureProviderWrapper {
+        match self.get_named(name) {
             Some(provider) => provider,
-            None => self.get_default().await,
+            None => self.get_default(),
         }
     }
 
-    pub async fn get_default(&self) -> FeatureProviderWrapper {
-        self.providers.read().await.get("").unwrap().clone()
+    /// Same as [`Self::get`], except `None` is returned once the registry is cleared.
+    pub fn find(&self, name: &str) -> Option<FeatureProviderWrapper> {
+        let providers = self.providers.load();
+
+        providers.get(name).or_else(|| providers.get("")).cloned()
+    }
+
+    pub fn get_default(&self) -> FeatureProviderWrapper {
+        self.providers.load().get("").unwrap().clone()
     }
 
-    pub async fn get_named(&self, name: &str) -> Option<FeatureProviderWrapper> {
-        self.providers.read().await.get(name).cloned()
+    pub fn get_named(&self, name: &str) -> Option<FeatureProviderWrapper> {
+        self.providers.load().get(name).cloned()
     }
 
-    pub async fn clear(&self) {
-        self.providers.write().await.clear();
+    /// Remove all the providers, and return them to be shut down with [`Self::shut_down`].
+    pub fn take(&self) -> Arc<HashMap<String, FeatureProviderWrapper>> {
+        let providers = self.providers.swap(Arc::default());
+
+        self.notify_change();
+
+        providers
+    }
+
+    /// Shut `providers` down at the same time.
+    pub async fn shut_down(providers: Arc<HashMap<String, FeatureProviderWrapper>>) {
+        let mut shutdowns = JoinSet::new();
+
+        for provider in providers.values() {
+            let provider = provider.get();
+            shutdowns.spawn(async move { provider.shutdown().await });
+        }
+
+        while shutdowns.join_next().await.is_some() {}
+    }
+
+    /// Return a receiver notified whenever a provider is set or removed.
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<()> {
+        self.changes.subscribe()
+    }
+
+    fn notify_change(&self) {
+        // An error only means nobody is listening.
+        let _ = self.changes.send(());
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => (*message).to_string(),
+            Err(_) => "Initialization panicked".to_string(),
+        },
     }
 }
 
@@ -88,15 +184,196 @@ impl Default for ProviderRegistry {
 //  FeatureProviderWrapper
 // ============================================================
 
+/// A registered provider, whose status is tracked by the SDK: `READY` once initialized, then
+/// following the events the provider emits, and `NOT_READY` once shut down.
 #[derive(Clone)]
-pub struct FeatureProviderWrapper(Arc<dyn FeatureProvider>);
+pub struct FeatureProviderWrapper(Arc<TrackedProvider>);
 
 impl FeatureProviderWrapper {
     pub fn new(provider: impl FeatureProvider) -> Self {
-        Self(Arc::new(provider))
+        Self(Arc::new(TrackedProvider {
+            provider: Arc::new(provider),
+            status: Arc::new(Mutex::new(ProviderStatus::Ready)),
+            events: Mutex::new(None),
+        }))
+    }
+
+    /// Wrap `provider`, once initialized, and follow its status through the events it emits.
+    pub fn initialized(provider: impl FeatureProvider) -> Self {
+        let wrapper = Self::new(provider);
+
+        if let Some(emitter) = wrapper.0.provider.event_emitter() {
+            let mut receiver = emitter.subscribe();
+            let status = wrapper.0.status.clone();
+
+            // Only holds the status, so that the provider can be dropped.
+            let events = tokio::spawn(async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) => track(&status, &event),
+                        Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            });
+
+            *wrapper.0.events.lock().unwrap() = Some(events);
+        }
+
+        wrapper
     }
 
     pub fn get(&self) -> Arc<dyn FeatureProvider> {
         self.0.clone()
     }
+
+    /// Return the provider itself, whose status is not tracked.
+    pub fn untracked(&self) -> Arc<dyn FeatureProvider> {
+        self.0.provider.clone()
+    }
+}
+
+/// Update `status` after `event`. `PROVIDER_ERROR` events with the `PROVIDER_FATAL` error code
+/// put the provider in the `FATAL` status for good.
+fn track(status: &Mutex<ProviderStatus>, event: &ProviderEvent) {
+    let mut status = status.lock().unwrap();
+
+    if *status == ProviderStatus::Fatal {
+        return;
+    }
+
+    *status = match event.event_type {
+        ProviderEventType::Ready | ProviderEventType::ContextChanged => ProviderStatus::Ready,
+        ProviderEventType::Error
+            if matches!(event.error_code, Some(EvaluationErrorCode::ProviderFatal)) =>
+        {
+            ProviderStatus::Fatal
+        }
+        ProviderEventType::Error => ProviderStatus::Error,
+        ProviderEventType::Stale => ProviderStatus::STALE,
+        ProviderEventType::ConfigurationChanged | ProviderEventType::Reconciling => return,
+    };
+}
+
+/// A provider along with the status tracked for it.
+struct TrackedProvider {
+    provider: Arc<dyn FeatureProvider>,
+    status: Arc<Mutex<ProviderStatus>>,
+    events: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl TrackedProvider {
+    fn stop_tracking(&self) {
+        if let Some(events) = self.events.lock().unwrap().take() {
+            events.abort();
+        }
+    }
+}
+
+impl Drop for TrackedProvider {
+    fn drop(&mut self) {
+        self.stop_tracking();
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for TrackedProvider {
+    async fn shutdown(&self) {
+        self.stop_tracking();
+        *self.status.lock().unwrap() = ProviderStatus::NotReady;
+
+        self.provider.shutdown().await;
+    }
+
+    /// The tracked status, unless the provider reports a status of its own while ready.
+    fn status(&self) -> ProviderStatus {
+        match *self.status.lock().unwrap() {
+            ProviderStatus::Ready => self.provider.status(),
+            status => status,
+        }
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.provider.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.provider.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.provider
+            .track(event_name, evaluation_context, tracking_event_details)
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.provider
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.provider
+            .resolve_int_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.provider
+            .resolve_float_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.provider
+            .resolve_string_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.provider
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_bulk(
+        &self,
+        flags: &[(String, FlagType)],
+        evaluation_context: &EvaluationContext,
+    ) -> HashMap<String, EvaluationResult<ResolutionDetails<Value>>> {
+        self.provider.resolve_bulk(flags, evaluation_context).await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.provider.resolve_all(evaluation_context).await
+    }
 }
@@ -0,0 +1,68 @@
+use std::{io, time::Duration};
+
+use crate::{provider::ProviderStatus, ProviderError};
+
+// ============================================================
+//  SdkError
+// ============================================================
+
+/// A failure of the SDK itself, as opposed to the failure of a flag evaluation reported by an
+/// [`EvaluationError`](crate::EvaluationError).
+#[derive(Debug, thiserror::Error)]
+pub enum SdkError {
+    /// The provider failed to initialize, and was not registered.
+    #[error("Provider {provider} failed to initialize: {source}")]
+    ProviderInitialization {
+        /// The type name of the provider.
+        provider: String,
+
+        /// The error of the provider, of kind `PANIC` if it panicked.
+        #[source]
+        source: ProviderError,
+    },
+
+    /// The provider was not ready in time.
+    #[error("Provider \"{provider_name}\" was not ready within {timeout:?}: {status:?}")]
+    ReadyTimeout {
+        /// The name of the provider, as in its metadata.
+        provider_name: String,
+
+        /// The status of the provider once the time was up.
+        status: ProviderStatus,
+
+        /// The time the provider was waited for.
+        timeout: Duration,
+    },
+
+    /// The provider is in an irrecoverable error state, and will not be ready until replaced.
+    #[error("Provider \"{provider_name}\" is in an irrecoverable error state")]
+    ProviderFatal {
+        /// The name of the provider, as in its metadata.
+        provider_name: String,
+    },
+
+    /// The providers and hooks did not shut down in time.
+    #[error("Shutdown did not complete within {timeout:?}")]
+    ShutdownTimeout {
+        /// The time the providers, and then the hooks, were each given to shut down.
+        timeout: Duration,
+    },
+
+    /// The configuration of the SDK is invalid.
+    #[error("Invalid configuration: {message}")]
+    Configuration {
+        /// A message describing the invalid configuration.
+        message: String,
+    },
+
+    /// A file could not be read.
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        /// The path of the file.
+        path: String,
+
+        /// The underlying error.
+        #[source]
+        source: io::Error,
+    },
+}
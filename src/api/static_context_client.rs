@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use tokio::sync::broadcast;
+
+use crate::{
+    provider::{EventEmitter, FlagValue, ProviderEvent, ProviderEventType, ResolutionDetails},
+    EvaluationContext, EvaluationDetails, EvaluationError, EvaluationErrorCode, EvaluationResult,
+    StructValue, Value,
+};
+
+use super::{
+    client::ClientMetadata, global_evaluation_context::GlobalEvaluationContext,
+    kill_switches::KillSwitches, provider_registry::ProviderRegistry,
+};
+
+// ============================================================
+//  StaticContextClient
+// ============================================================
+
+/// A client for applications evaluating flags for a single subject, such as desktop or
+/// frontend applications. Create it through the [`OpenFeature`](crate::OpenFeature) struct.
+///
+/// The evaluation context is set once, and the provider resolves all the flags for it up front
+/// with [`FeatureProvider::resolve_all`](crate::provider::FeatureProvider::resolve_all).
+/// Evaluations are then synchronous lookups.
+///
+/// Changing the evaluation context emits a `PROVIDER_RECONCILING` event, followed by
+/// `PROVIDER_CONTEXT_CHANGED` once the flags are resolved again, or `PROVIDER_ERROR` if it
+/// failed.
+pub struct StaticContextClient {
+    metadata: ClientMetadata,
+    provider_registry: ProviderRegistry,
+    global_evaluation_context: GlobalEvaluationContext,
+    kill_switches: KillSwitches,
+    evaluation_context: EvaluationContext,
+    flags: EvaluationResult<HashMap<String, ResolutionDetails<Value>>>,
+    events: EventEmitter,
+}
+
+impl StaticContextClient {
+    /// Create a new [`StaticContextClient`] instance, resolving all the flags for
+    /// `evaluation_context`.
+    pub async fn new(
+        name: impl Into<String>,
+        global_evaluation_context: GlobalEvaluationContext,
+        provider_registry: ProviderRegistry,
+        kill_switches: KillSwitches,
+        evaluation_context: EvaluationContext,
+    ) -> Self {
+        let mut client = Self {
+            metadata: ClientMetadata { name: name.into() },
+            provider_registry,
+            global_evaluation_context,
+            kill_switches,
+            evaluation_context,
+            flags: Ok(HashMap::new()),
+            events: EventEmitter::default(),
+        };
+
+        client.flags = client.resolve_all().await;
+
+        client
+    }
+
+    /// Return the metadata of current client.
+    pub fn metadata(&self) -> &ClientMetadata {
+        &self.metadata
+    }
+
+    /// Return the evaluation context flags are resolved for.
+    pub fn evaluation_context(&self) -> &EvaluationContext {
+        &self.evaluation_context
+    }
+
+    /// Replace the evaluation context and resolve all the flags again for it.
+    ///
+    /// Evaluations keep using the previous flags until they are resolved.
+    pub async fn set_evaluation_context(&mut self, evaluation_context: EvaluationContext) {
+        self.evaluation_context = evaluation_context;
+
+        self.emit(ProviderEventType::Reconciling, None);
+
+        self.flags = self.resolve_all().await;
+
+        let error = self.flags.as_ref().err().cloned();
+        match error {
+            None => self.emit(ProviderEventType::ContextChanged, None),
+            Some(error) => self.emit(ProviderEventType::Error, Some(error)),
+        }
+    }
+
+    /// Resolve all the flags again for the current evaluation context, such as after the flag
+    /// configuration changed.
+    pub async fn refresh(&mut self) {
+        self.flags = self.resolve_all().await;
+    }
+
+    /// Return a receiver of the events emitted by this client from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ProviderEvent> {
+        self.events.subscribe()
+    }
+
+    /// Return the value of given `flag_key` as a bool.
+    pub fn get_bool_value(&self, flag_key: &str) -> EvaluationResult<bool> {
+        Ok(self.evaluate::<bool>(flag_key)?.value)
+    }
+
+    /// Return the value of given `flag_key` as an int (i64).
+    pub fn get_int_value(&self, flag_key: &str) -> EvaluationResult<i64> {
+        Ok(self.evaluate::<i64>(flag_key)?.value)
+    }
+
+    /// Return the value of given `flag_key` as a float (f64).
+    pub fn get_float_value(&self, flag_key: &str) -> EvaluationResult<f64> {
+        Ok(self.evaluate::<f64>(flag_key)?.value)
+    }
+
+    /// Return the value of given `flag_key` as a string.
+    pub fn get_string_value(&self, flag_key: &str) -> EvaluationResult<String> {
+        Ok(self.evaluate::<String>(flag_key)?.value)
+    }
+
+    /// Return the value of given `flag_key` as a struct.
+    /// The required type should implement [`TryFrom<StructValue>`] trait.
+    pub fn get_struct_value<T: TryFrom<StructValue>>(&self, flag_key: &str) -> EvaluationResult<T> {
+        let result = self.evaluate::<StructValue>(flag_key)?;
+
+        T::try_from(result.value).map_err(|_| EvaluationError {
+            code: EvaluationErrorCode::TypeMismatch,
+            message: Some("Unable to cast value to required type".to_string()),
+        })
+    }
+
+    /// Return the evaluation details of given `flag_key` as a bool.
+    pub fn get_bool_details(&self, flag_key: &str) -> EvaluationResult<EvaluationDetails<bool>> {
+        self.evaluate(flag_key)
+    }
+
+    /// Return the evaluation details of given `flag_key` as an int (i64).
+    pub fn get_int_details(&self, flag_key: &str) -> EvaluationResult<EvaluationDetails<i64>> {
+        self.evaluate(flag_key)
+    }
+
+    /// Return the evaluation details of given `flag_key` as a float (f64).
+    pub fn get_float_details(&self, flag_key: &str) -> EvaluationResult<EvaluationDetails<f64>> {
+        self.evaluate(flag_key)
+    }
+
+    /// Return the evaluation details of given `flag_key` as a string.
+    pub fn get_string_details(
+        &self,
+        flag_key: &str,
+    ) -> EvaluationResult<EvaluationDetails<String>> {
+        self.evaluate(flag_key)
+    }
+
+    fn evaluate<T: FlagValue>(&self, flag_key: &str) -> EvaluationResult<EvaluationDetails<T>> {
+        if let Some(result) = self.kill_switches.evaluate(flag_key) {
+            return result;
+        }
+
+        let details = self
+            .flags
+            .as_ref()
+            .map_err(Clone::clone)?
+            .get(flag_key)
+            .ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::FlagNotFound)
+                    .message(format!("Flag \"{}\" was not resolved", flag_key))
+                    .build()
+            })?
+            .clone();
+
+        let value = T::from_value(details.value).ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::TypeMismatch)
+                .message(format!("Expected a {:?} flag", T::FLAG_TYPE))
+                .build()
+        })?;
+
+        Ok(ResolutionDetails {
+            value,
+            variant: details.variant,
+            reason: details.reason,
+            flag_metadata: details.flag_metadata,
+        }
+        .into_evaluation_details(flag_key))
+    }
+
+    async fn resolve_all(&self) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let provider = self.provider_registry.get(&self.metadata.name).get();
+
+        let mut context = self.evaluation_context.clone();
+
+        let global_evaluation_context = self.global_evaluation_context.get();
+
+        context.merge_missing(&global_evaluation_context);
+
+        provider.resolve_all(&context).await
+    }
+
+    fn emit(&self, event_type: ProviderEventType, error: Option<EvaluationError>) {
+        let provider = self.provider_registry.get(&self.metadata.name).get();
+
+        let mut event = ProviderEvent::builder()
+            .event_type(event_type)
+            .provider_name(provider.metadata().name.clone())
+            .build();
+
+        if let Some(error) = error {
+            event.message = error.message;
+            event.error_code = Some(error.code);
+        }
+
+        self.events.emit(event);
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{flags, provider::ProviderEventType, EvaluationContext, OpenFeature};
+
+    #[tokio::test]
+    async fn evaluate_flag_set() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! {
+            "checkout-v2" => bool: true,
+            "tier" => String: "gold",
+        })
+        .await
+        .unwrap();
+
+        let client = api
+            .create_static_context_client(EvaluationContext::default().with_targeting_key("alice"))
+            .await;
+
+        assert!(client.get_bool_value("checkout-v2").unwrap());
+        assert_eq!(client.get_string_value("tier").unwrap(), "gold");
+        assert!(client.get_int_value("tier").is_err());
+        assert!(client.get_bool_value("missing").is_err());
+    }
+
+    #[tokio::test]
+    async fn reconcile_on_context_change() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let mut client = api
+            .create_static_context_client(EvaluationContext::default().with_targeting_key("alice"))
+            .await;
+        let mut events = client.subscribe();
+
+        client
+            .set_evaluation_context(EvaluationContext::default().with_targeting_key("bob"))
+            .await;
+
+        assert_eq!(
+            events.recv().await.unwrap().event_type,
+            ProviderEventType::Reconciling
+        );
+        assert_eq!(
+            events.recv().await.unwrap().event_type,
+            ProviderEventType::ContextChanged
+        );
+        assert_eq!(
+            client.evaluation_context().targeting_key,
+            Some("bob".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_provider() {
+        let api = OpenFeature::default();
+        let client = api
+            .create_static_context_client(EvaluationContext::default())
+            .await;
+
+        let error = client.get_bool_value("checkout-v2").unwrap_err();
+        assert!(matches!(error.code, crate::EvaluationErrorCode::General(_)));
+    }
+}
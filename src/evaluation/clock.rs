@@ -0,0 +1,56 @@
+use time::OffsetDateTime;
+
+use crate::EvaluationContext;
+
+/// The source of the current time for time-based flag rules, so that it can be injected in tests
+/// and previews.
+pub trait Clock: Send + Sync + 'static {
+    /// Return the current time.
+    fn now(&self) -> OffsetDateTime;
+
+    /// Return the time `evaluation_context` is to be evaluated at: the as-of time it carries if
+    /// any (see [`EvaluationContext::as_of`]), and the current time otherwise.
+    fn evaluation_time(&self, evaluation_context: &EvaluationContext) -> OffsetDateTime {
+        evaluation_context.as_of().unwrap_or_else(|| self.now())
+    }
+}
+
+/// The clock of the system, in UTC.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
+
+/// A clock frozen at a given time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FixedClock(pub OffsetDateTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> OffsetDateTime {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn evaluation_time_honors_as_of() {
+        let now = OffsetDateTime::now_utc();
+        let next_week = now + Duration::weeks(1);
+        let clock = FixedClock(now);
+
+        assert_eq!(clock.evaluation_time(&EvaluationContext::default()), now);
+        assert_eq!(
+            clock.evaluation_time(&EvaluationContext::default().with_as_of(next_week)),
+            next_week
+        );
+    }
+}
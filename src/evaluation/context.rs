@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use time::OffsetDateTime;
+
 use crate::EvaluationContextFieldValue;
 
 /// The evaluation context provides ambient information for the purposes of flag evaluation.
@@ -12,6 +14,8 @@ use crate::EvaluationContextFieldValue;
 /// time of day. The context provides this information. The context can be optionally provided at
 /// evaluation, and mutated in before hooks.
 #[derive(Clone, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct EvaluationContext {
     /// The targeting key uniquely identifies the subject (end-user, or client service) of a flag
     /// evaluation. Providers may require this field for fractional flag evaluation, rules, or
@@ -22,6 +26,11 @@ pub struct EvaluationContext {
     /// The evaluation context MUST support the inclusion of custom fields, having keys of type
     /// string, and values of type boolean | string | number | datetime | structure.
     pub custom_fields: HashMap<String, EvaluationContextFieldValue>,
+
+    /// The time the flag is evaluated at, when it is not now. Kept apart from the custom fields,
+    /// so that it is not sent to remote flag management systems, and not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    as_of: Option<OffsetDateTime>,
 }
 
 impl EvaluationContext {
@@ -52,6 +61,20 @@ impl EvaluationContext {
         self.custom_fields.insert(key.into(), value.into());
     }
 
+    /// Set the time the flag is evaluated at, instead of now.
+    /// Providers with time-based rules are expected to honor it, typically through
+    /// [`Clock::evaluation_time`](crate::Clock::evaluation_time).
+    #[must_use]
+    pub fn with_as_of(mut self, as_of: OffsetDateTime) -> Self {
+        self.as_of = Some(as_of);
+        self
+    }
+
+    /// Return the time the flag is evaluated at, if it is not now.
+    pub fn as_of(&self) -> Option<OffsetDateTime> {
+        self.as_of
+    }
+
     /// Merge `other` into `self` if corresponding field is not set.
     /// Meaning values set into `self` has higher precedence.
     pub fn merge_missing(&mut self, other: &Self) {
@@ -66,6 +89,10 @@ impl EvaluationContext {
                 self.custom_fields.insert(key.clone(), value.clone());
             }
         });
+
+        if self.as_of.is_none() {
+            self.as_of = other.as_of;
+        }
     }
 }
 
@@ -209,4 +236,56 @@ mod tests {
             struct_value
         );
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde_json::json;
+
+        let context = EvaluationContext::default()
+            .with_targeting_key("user-1")
+            .with_custom_field("beta", true)
+            .with_custom_field("plan", "pro")
+            .with_custom_field("seats", vec![1, 2]);
+
+        let json = serde_json::to_value(&context).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "targeting_key": "user-1",
+                "custom_fields": { "beta": true, "plan": "pro", "seats": [1, 2] },
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<EvaluationContext>(json).unwrap(),
+            context
+        );
+
+        let context: EvaluationContext =
+            serde_json::from_value(json!({ "custom_fields": { "address": { "city": "Bern" } } }))
+                .unwrap();
+        assert_eq!(context.targeting_key, None);
+        assert_eq!(
+            context.custom_fields["address"]
+                .as_struct()
+                .unwrap()
+                .downcast_ref::<crate::StructValue>(),
+            Some(&crate::StructValue::default().with_field("city", "Bern"))
+        );
+        assert_eq!(
+            serde_json::to_value(&context).unwrap()["custom_fields"]["address"],
+            json!({ "city": "Bern" })
+        );
+
+        let now = OffsetDateTime::UNIX_EPOCH;
+        assert_eq!(
+            serde_json::to_value(EvaluationContext::default().with_custom_field("now", now))
+                .unwrap()["custom_fields"]["now"],
+            json!("1970-01-01T00:00:00Z")
+        );
+        assert!(serde_json::to_value(
+            EvaluationContext::default().with_custom_field("opaque", Arc::new(1))
+        )
+        .is_err());
+    }
 }
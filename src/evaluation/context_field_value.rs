@@ -2,6 +2,9 @@ use std::{any::Any, sync::Arc};
 
 use time::OffsetDateTime;
 
+#[cfg(feature = "serde")]
+use crate::{StructValue, Value};
+
 /// Value type of evaluation context custom fields.
 #[derive(Clone, Debug)]
 #[allow(missing_docs)]
@@ -11,6 +14,7 @@ pub enum EvaluationContextFieldValue {
     Float(f64),
     String(String),
     DateTime(OffsetDateTime),
+    List(Vec<EvaluationContextFieldValue>),
     Struct(Arc<dyn Any + Send + Sync>),
 }
 
@@ -88,6 +92,19 @@ impl EvaluationContextFieldValue {
         }
     }
 
+    /// Return `true` if this is a list value.
+    pub fn is_list(&self) -> bool {
+        matches!(self, Self::List(_))
+    }
+
+    /// Try to convert `self` to list.
+    pub fn as_list(&self) -> Option<&[EvaluationContextFieldValue]> {
+        match self {
+            Self::List(value) => Some(value),
+            _ => None,
+        }
+    }
+
     /// Return `true` if this is a struct value.
     pub fn is_struct(&self) -> bool {
         matches!(self, Self::Struct(_))
@@ -180,6 +197,12 @@ impl From<OffsetDateTime> for EvaluationContextFieldValue {
     }
 }
 
+impl<T: Into<EvaluationContextFieldValue>> From<Vec<T>> for EvaluationContextFieldValue {
+    fn from(value: Vec<T>) -> Self {
+        Self::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
 impl<T: Any + Send + Sync> From<Arc<T>> for EvaluationContextFieldValue {
     fn from(value: Arc<T>) -> Self {
         Self::Struct(value)
@@ -207,11 +230,69 @@ impl PartialEq for EvaluationContextFieldValue {
                 EvaluationContextFieldValue::DateTime(left),
                 EvaluationContextFieldValue::DateTime(right),
             ) => left == right,
+            (EvaluationContextFieldValue::List(left), EvaluationContextFieldValue::List(right)) => {
+                left == right
+            }
             (_, _) => false,
         }
     }
 }
 
+/// Serialize as the JSON-like counterpart of the value, with date-times formatted as RFC 3339
+/// strings. Only struct values holding a [`StructValue`] or a [`Value`] can be serialized.
+#[cfg(feature = "serde")]
+impl serde::Serialize for EvaluationContextFieldValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+
+        match self {
+            Self::Bool(value) => serializer.serialize_bool(*value),
+            Self::Int(value) => serializer.serialize_i64(*value),
+            Self::Float(value) => serializer.serialize_f64(*value),
+            Self::String(value) => serializer.serialize_str(value),
+            Self::DateTime(value) => serializer.serialize_str(
+                &value
+                    .format(&time::format_description::well_known::Rfc3339)
+                    .map_err(S::Error::custom)?,
+            ),
+            Self::List(values) => serializer.collect_seq(values),
+            Self::Struct(value) => {
+                if let Some(value) = value.downcast_ref::<StructValue>() {
+                    value.serialize(serializer)
+                } else if let Some(value) = value.downcast_ref::<Value>() {
+                    value.serialize(serializer)
+                } else {
+                    Err(S::Error::custom(
+                        "Only struct fields holding a StructValue or a Value can be serialized",
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Deserialize a JSON-like value, with objects held as [`StructValue`]. Date-times are not told
+/// apart from strings.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for EvaluationContextFieldValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        fn field_value(value: Value) -> EvaluationContextFieldValue {
+            match value {
+                Value::Bool(value) => EvaluationContextFieldValue::Bool(value),
+                Value::Int(value) => EvaluationContextFieldValue::Int(value),
+                Value::Float(value) => EvaluationContextFieldValue::Float(value),
+                Value::String(value) => EvaluationContextFieldValue::String(value),
+                Value::Array(values) => {
+                    EvaluationContextFieldValue::List(values.into_iter().map(field_value).collect())
+                }
+                Value::Struct(value) => EvaluationContextFieldValue::new_struct(value),
+            }
+        }
+
+        Value::deserialize(deserializer).map(field_value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -229,6 +310,7 @@ mod tests {
             .with_custom_field("Float", 42.0)
             .with_custom_field("String", "StringValue")
             .with_custom_field("DateTime", now.clone())
+            .with_custom_field("List", vec!["beta", "staff"])
             .with_custom_field(
                 "Struct",
                 EvaluationContextFieldValue::new_struct(EvaluationReason::Cached),
@@ -276,6 +358,12 @@ mod tests {
             panic!()
         }
 
+        // Assert list.
+        assert_eq!(
+            context.custom_fields.get("List").unwrap().as_list(),
+            Some(&["beta".into(), "staff".into()][..])
+        );
+
         // Assert struct.
         if let EvaluationContextFieldValue::Struct(value) =
             context.custom_fields.get("Struct").unwrap().clone()
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+
+use crate::EvaluationContext;
+
+/// Supplies the ambient evaluation context of the current call, such as the user of the current
+/// session or the tenant stored in a task-local, so that it does not need to be passed at every
+/// call site.
+///
+/// The client consults it on every evaluation. Its context takes precedence over the global one,
+/// and is overridden by the client and invocation ones.
+///
+/// Plain closures returning an [`EvaluationContext`] are suppliers:
+///
+/// ```
+/// use open_feature::{EvaluationContext, OpenFeature};
+///
+/// let client = OpenFeature::default()
+///     .create_client()
+///     .with_context_supplier(|| EvaluationContext::default().with_targeting_key("alice"));
+/// ```
+#[async_trait]
+pub trait ContextSupplier: Send + Sync + 'static {
+    /// Return the ambient evaluation context.
+    async fn supply(&self) -> EvaluationContext;
+}
+
+#[async_trait]
+impl<F> ContextSupplier for F
+where
+    F: Fn() -> EvaluationContext + Send + Sync + 'static,
+{
+    async fn supply(&self) -> EvaluationContext {
+        self()
+    }
+}
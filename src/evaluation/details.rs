@@ -12,6 +12,7 @@ pub type EvaluationResult<T> = Result<T, EvaluationError>;
 /// The result of the flag evaluation process, and made available in the detailed flag resolution
 /// functions.
 #[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EvaluationDetails<T> {
     /// The flag key argument passed to the detailed flag evaluation method.
     pub flag_key: String,
@@ -27,6 +28,7 @@ pub struct EvaluationDetails<T> {
 
     /// The optional flag metadata returned by the configured provider.
     /// If the provider returns nothing, it is set to the default value.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub flag_metadata: FlagMetadata,
 }
 
@@ -36,6 +38,8 @@ pub struct EvaluationDetails<T> {
 
 /// Reason for evaluation.
 #[derive(Clone, Default, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
 pub enum EvaluationReason {
     /// The resolved value is static (no dynamic evaluation).
     Static,
@@ -95,11 +99,23 @@ impl ToString for EvaluationReason {
 /// This structure is populated by a provider for use by an Application Author (via the Evaluation
 /// API) or an Application Integrator (via hooks).
 #[derive(Clone, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct FlagMetadata {
     /// The fields of the metadata.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
     pub values: HashMap<String, FlagMetadataValue>,
 }
 
+/// Serialize `values` sorted by key, so that the output is stable, such as for snapshots.
+#[cfg(feature = "serde")]
+fn serialize_sorted<S: serde::Serializer>(
+    values: &HashMap<String, FlagMetadataValue>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(values.iter().collect::<std::collections::BTreeMap<_, _>>())
+}
+
 impl FlagMetadata {
     /// Append givne `key` and `value` to the fields of metadata.
     #[must_use]
@@ -124,6 +140,8 @@ impl FlagMetadata {
 
 /// Supported values of flag metadata fields.
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
 #[allow(missing_docs)]
 pub enum FlagMetadataValue {
     Bool(bool),
@@ -203,3 +221,67 @@ impl From<&str> for FlagMetadataValue {
         Self::String(value.into())
     }
 }
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde_json::json;
+
+        use super::*;
+        use crate::{EvaluationErrorCode, ProviderError, ProviderErrorKind};
+
+        let details = EvaluationDetails {
+            flag_key: "tier".to_string(),
+            value: 3,
+            reason: Some(EvaluationReason::TargetingMatch),
+            variant: Some("gold".to_string()),
+            flag_metadata: FlagMetadata::default().with_value("owner", "billing"),
+        };
+
+        let json = serde_json::to_value(&details).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "flag_key": "tier",
+                "value": 3,
+                "reason": "TARGETING_MATCH",
+                "variant": "gold",
+                "flag_metadata": { "owner": "billing" },
+            })
+        );
+
+        let metadata = FlagMetadata::default()
+            .with_value("b", 2)
+            .with_value("c", 3)
+            .with_value("a", 1);
+        assert_eq!(
+            serde_json::to_string(&metadata).unwrap(),
+            r#"{"a":1,"b":2,"c":3}"#
+        );
+
+        let round_trip: EvaluationDetails<i64> = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.value, 3);
+        assert_eq!(round_trip.reason, details.reason);
+        assert_eq!(round_trip.flag_metadata, details.flag_metadata);
+
+        for error in [
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .message("No such flag")
+                .build(),
+            ProviderError::new(ProviderErrorKind::Timeout, "Backend timed out").into(),
+        ] {
+            let json = serde_json::to_string(&error).unwrap();
+            assert_eq!(
+                serde_json::from_str::<EvaluationError>(&json).unwrap(),
+                error
+            );
+        }
+    }
+}
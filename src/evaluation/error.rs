@@ -2,10 +2,13 @@
 //  EvaluationError
 // ============================================================
 
+use std::{error::Error, fmt, sync::Arc};
+
 use typed_builder::TypedBuilder;
 
 /// Struct representing error
 #[derive(Clone, Eq, PartialEq, TypedBuilder, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EvaluationError {
     /// The error code of abnormal evaluation.
     pub code: EvaluationErrorCode,
@@ -15,16 +18,67 @@ pub struct EvaluationError {
     pub message: Option<String>,
 }
 
+impl EvaluationError {
+    /// Return `true` if retrying the evaluation might succeed, such as when the provider is not
+    /// ready yet or its backend is temporarily unavailable. Errors about the flag or the
+    /// evaluation context, and unclassified errors, are terminal.
+    pub fn is_retryable(&self) -> bool {
+        match &self.code {
+            EvaluationErrorCode::ProviderNotReady => true,
+            EvaluationErrorCode::Provider(error) => error.is_retryable(),
+            EvaluationErrorCode::ProviderFatal
+            | EvaluationErrorCode::FlagNotFound
+            | EvaluationErrorCode::ParseError
+            | EvaluationErrorCode::TypeMismatch
+            | EvaluationErrorCode::TargetingKeyMissing
+            | EvaluationErrorCode::InvalidContext
+            | EvaluationErrorCode::General(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {}", self.code, message),
+            None => write!(f, "{}", self.code),
+        }
+    }
+}
+
+impl Error for EvaluationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.code {
+            EvaluationErrorCode::Provider(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ProviderError> for EvaluationError {
+    fn from(error: ProviderError) -> Self {
+        Self {
+            message: Some(error.message.clone()),
+            code: EvaluationErrorCode::Provider(error),
+        }
+    }
+}
+
 // ============================================================
 //  EvaluationErrorCode
 // ============================================================
 
 /// An enumerated error code represented idiomatically in the implementation language.
 #[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
 pub enum EvaluationErrorCode {
     /// The value was resolved before the provider was initialized.
     ProviderNotReady,
 
+    /// The provider is in an irrecoverable error state.
+    ProviderFatal,
+
     /// The flag could not be found.
     FlagNotFound,
 
@@ -40,20 +94,235 @@ pub enum EvaluationErrorCode {
     /// The evaluation context does not meet provider requirements.
     InvalidContext,
 
+    /// The provider failed, such as when its backend is unreachable. Reported as `GENERAL`.
+    Provider(ProviderError),
+
     /// The error was for a reason not enumerated above.
     General(String),
 }
 
-impl ToString for EvaluationErrorCode {
-    fn to_string(&self) -> String {
-        match self {
-            Self::ProviderNotReady => "PROVIDER_NOT_READY".to_string(),
-            Self::FlagNotFound => "FLAG_NOT_FOUND".to_string(),
-            Self::ParseError => "PARSE_ERROR".to_string(),
-            Self::TypeMismatch => "TYPE_MISMATCH".to_string(),
-            Self::TargetingKeyMissing => "TARGETING_KEY_MISSING".to_string(),
-            Self::InvalidContext => "INVALID_CONTEXT".to_string(),
-            Self::General(message) => message.clone(),
+impl fmt::Display for EvaluationErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ProviderNotReady => "PROVIDER_NOT_READY",
+            Self::ProviderFatal => "PROVIDER_FATAL",
+            Self::FlagNotFound => "FLAG_NOT_FOUND",
+            Self::ParseError => "PARSE_ERROR",
+            Self::TypeMismatch => "TYPE_MISMATCH",
+            Self::TargetingKeyMissing => "TARGETING_KEY_MISSING",
+            Self::InvalidContext => "INVALID_CONTEXT",
+            Self::Provider(_) => "GENERAL",
+            Self::General(message) => message,
+        })
+    }
+}
+
+// ============================================================
+//  ProviderError
+// ============================================================
+
+/// The stable sub-code of a [`ProviderError`], so that callers can tell faults apart without
+/// parsing messages.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+pub enum ProviderErrorKind {
+    /// The backend did not answer in time.
+    Timeout,
+
+    /// The backend could not be reached.
+    Network,
+
+    /// The backend is temporarily unable to serve requests, such as with an HTTP 503.
+    Unavailable,
+
+    /// The backend rejected the request because too many were sent.
+    RateLimited,
+
+    /// The backend rejected the credentials of the provider.
+    Unauthorized,
+
+    /// The provider is configured incorrectly.
+    Misconfigured,
+
+    /// The backend answered with data the provider does not understand.
+    InvalidResponse,
+
+    /// A fault not enumerated above, with a provider-specific sub-code.
+    Other(String),
+}
+
+impl ProviderErrorKind {
+    /// Return `true` if faults of this kind are usually transient.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::Timeout | Self::Network | Self::Unavailable | Self::RateLimited
+        )
+    }
+}
+
+impl fmt::Display for ProviderErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Timeout => "TIMEOUT",
+            Self::Network => "NETWORK",
+            Self::Unavailable => "UNAVAILABLE",
+            Self::RateLimited => "RATE_LIMITED",
+            Self::Unauthorized => "UNAUTHORIZED",
+            Self::Misconfigured => "MISCONFIGURED",
+            Self::InvalidResponse => "INVALID_RESPONSE",
+            Self::Other(code) => code,
+        })
+    }
+}
+
+/// A provider fault, carried by [`EvaluationErrorCode::Provider`].
+///
+/// ```
+/// use open_feature::{EvaluationError, ProviderError, ProviderErrorKind};
+///
+/// let io_error = std::io::Error::new(std::io::ErrorKind::TimedOut, "connect timed out");
+/// let error: EvaluationError = ProviderError::new(ProviderErrorKind::Timeout, "Backend timed out")
+///     .with_source(io_error)
+///     .into();
+/// ```
+///
+/// Errors compare equal regardless of their source.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProviderError {
+    /// The sub-code of the fault.
+    pub kind: ProviderErrorKind,
+
+    /// Whether retrying the resolution might succeed. Defaults to
+    /// [`ProviderErrorKind::is_transient`].
+    pub retryable: bool,
+
+    /// A message describing the fault.
+    pub message: String,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    source: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+impl ProviderError {
+    /// Create an error of given `kind`, retryable if the kind is transient.
+    pub fn new(kind: ProviderErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            retryable: kind.is_transient(),
+            kind,
+            message: message.into(),
+            source: None,
         }
     }
+
+    /// Set whether retrying the resolution might succeed.
+    #[must_use]
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Return `true` if retrying the resolution might succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.retryable
+    }
+
+    /// Set the underlying error.
+    #[must_use]
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+}
+
+impl PartialEq for ProviderError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.retryable == other.retryable
+            && self.message == other.message
+    }
+}
+
+impl Eq for ProviderError {}
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+impl Error for ProviderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn source_chain() {
+        let error: EvaluationError =
+            ProviderError::new(ProviderErrorKind::Timeout, "Backend timed out")
+                .with_source(io::Error::new(io::ErrorKind::TimedOut, "connect timed out"))
+                .into();
+
+        assert_eq!(error.to_string(), "GENERAL: Backend timed out");
+        assert_eq!(
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .build()
+                .to_string(),
+            "FLAG_NOT_FOUND"
+        );
+
+        let provider_error = error.source().unwrap();
+        assert_eq!(provider_error.to_string(), "TIMEOUT: Backend timed out");
+        assert_eq!(
+            provider_error.source().unwrap().to_string(),
+            "connect timed out"
+        );
+    }
+
+    #[test]
+    fn retryable_by_kind() {
+        assert!(ProviderError::new(ProviderErrorKind::Unavailable, "503").is_retryable());
+        assert!(!ProviderError::new(ProviderErrorKind::Unauthorized, "401").is_retryable());
+        assert!(
+            ProviderError::new(ProviderErrorKind::Other("QUOTA".to_string()), "Quota")
+                .with_retryable(true)
+                .is_retryable()
+        );
+    }
+
+    #[test]
+    fn retryable_evaluation_errors() {
+        let error = |code| EvaluationError::builder().code(code).build();
+
+        assert!(error(EvaluationErrorCode::ProviderNotReady).is_retryable());
+        assert!(!error(EvaluationErrorCode::ProviderFatal).is_retryable());
+        assert!(!error(EvaluationErrorCode::FlagNotFound).is_retryable());
+        assert!(!error(EvaluationErrorCode::General("Unknown".to_string())).is_retryable());
+
+        assert!(
+            EvaluationError::from(ProviderError::new(ProviderErrorKind::Timeout, "Timed out"))
+                .is_retryable()
+        );
+        assert!(!EvaluationError::from(ProviderError::new(
+            ProviderErrorKind::Unauthorized,
+            "Bad credentials"
+        ))
+        .is_retryable());
+    }
 }
@@ -4,16 +4,34 @@ pub use details::{
 };
 
 mod error;
-pub use error::{EvaluationError, EvaluationErrorCode};
+pub use error::{EvaluationError, EvaluationErrorCode, ProviderError, ProviderErrorKind};
 
 mod context;
 pub use context::EvaluationContext;
 
+mod context_supplier;
+pub use context_supplier::ContextSupplier;
+
 mod context_field_value;
 pub use context_field_value::EvaluationContextFieldValue;
 
+mod transaction_context;
+pub use transaction_context::TransactionContext;
+
+mod trace;
+pub use trace::{EvaluationTrace, HookTrace};
+
+mod tracking_event_details;
+pub use tracking_event_details::TrackingEventDetails;
+
 mod value;
 pub use value::{StructValue, Value};
 
+mod value_conversion;
+pub use value_conversion::{FromValue, IntoValue};
+
+mod clock;
+pub use clock::{Clock, FixedClock, SystemClock};
+
 mod options;
 pub use options::EvaluationOptions;
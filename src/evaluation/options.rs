@@ -1,2 +1,68 @@
-/// Contain hooks.
-pub struct EvaluationOptions {}
+use std::{fmt, sync::Arc, time::Duration};
+
+use time::OffsetDateTime;
+
+use crate::{Hook, HookHints, Value};
+
+/// Options applying to a single flag evaluation.
+#[derive(Clone, Default)]
+pub struct EvaluationOptions {
+    /// Evaluate the flag as if it was this time instead of now, in order to preview scheduled
+    /// changes. The time is passed to the provider through the evaluation context, see
+    /// [`EvaluationContext::as_of`](crate::EvaluationContext::as_of).
+    pub as_of: Option<OffsetDateTime>,
+
+    /// The time the provider is given to resolve the flag, overriding the timeout of the client,
+    /// see [`Client::set_timeout`](crate::Client::set_timeout).
+    pub timeout: Option<Duration>,
+
+    /// Hooks run for this evaluation only, after the hooks of the client in the `before` stage.
+    pub hooks: Vec<Arc<dyn Hook>>,
+
+    /// Hints given to every stage of all the hooks.
+    pub hook_hints: HookHints,
+}
+
+impl EvaluationOptions {
+    /// Set the time the flag is evaluated at.
+    #[must_use]
+    pub fn with_as_of(mut self, as_of: OffsetDateTime) -> Self {
+        self.as_of = Some(as_of);
+        self
+    }
+
+    /// Set the time the provider is given to resolve the flag.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Add `hook` to the hooks run for this evaluation.
+    #[must_use]
+    pub fn with_hook<T: Hook>(mut self, hook: T) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Set the hook hint `key` to `value`.
+    #[must_use]
+    pub fn with_hook_hint(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.hook_hints = self.hook_hints.with_hint(key, value);
+        self
+    }
+}
+
+impl fmt::Debug for EvaluationOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvaluationOptions")
+            .field("as_of", &self.as_of)
+            .field("timeout", &self.timeout)
+            .field(
+                "hooks",
+                &self.hooks.iter().map(Hook::name).collect::<Vec<_>>(),
+            )
+            .field("hook_hints", &self.hook_hints)
+            .finish()
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+
+use crate::{
+    provider::FlagType, EvaluationContext, EvaluationContextFieldValue, EvaluationDetails,
+    EvaluationError, EvaluationResult, FlagMetadataValue, HookStage, Value,
+};
+
+// ============================================================
+//  EvaluationTrace
+// ============================================================
+
+/// A record of a flag evaluation from end to end, for debugging why a subject got a value.
+///
+/// Obtain it with [`Client::trace`](crate::Client::trace). Its [`Display`](fmt::Display) renders
+/// it as an indented block suitable for logs:
+///
+/// ```text
+/// Evaluation of "checkout-v2" (Bool) by client "checkout"
+///   provider: In-memory Provider
+///   evaluation context:
+///     targeting_key: "alice"
+///     country: "FR"
+///   hooks:
+///     before my_app::EnrichHook (evaluation context changed)
+///     after my_app::EnrichHook
+///     finally my_app::EnrichHook
+///   result:
+///     value: true
+///     reason: TARGETING_MATCH
+///     variant: "on"
+/// ```
+///
+/// Providers exposing the trace of their rules are expected to do so through flag metadata, which
+/// is rendered along with the result.
+#[derive(Clone, Debug)]
+pub struct EvaluationTrace {
+    /// The key of evaluated flag.
+    pub flag_key: String,
+
+    /// The type of evaluated flag.
+    pub flag_type: FlagType,
+
+    /// The name of the client evaluating the flag.
+    pub client_name: String,
+
+    /// The name of the provider resolving the flag.
+    pub provider_name: String,
+
+    /// The evaluation context passed to the provider, once merged and changed by hooks.
+    pub evaluation_context: EvaluationContext,
+
+    /// The hook stages run, in order.
+    pub hooks: Vec<HookTrace>,
+
+    /// The outcome of the evaluation.
+    pub result: EvaluationResult<EvaluationDetails<Value>>,
+}
+
+/// A hook stage run during a traced evaluation.
+#[derive(Clone, Debug)]
+pub struct HookTrace {
+    /// The name of the hook.
+    pub hook: String,
+
+    /// The stage run.
+    pub stage: HookStage,
+
+    /// Whether a `before` stage changed the evaluation context.
+    pub changed_evaluation_context: bool,
+
+    /// The error returned by the stage, if any.
+    pub error: Option<EvaluationError>,
+}
+
+impl fmt::Display for EvaluationTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Evaluation of {:?} ({:?}) by client {:?}",
+            self.flag_key, self.flag_type, self.client_name
+        )?;
+        writeln!(f, "  provider: {}", self.provider_name)?;
+
+        writeln!(f, "  evaluation context:")?;
+        match &self.evaluation_context.targeting_key {
+            Some(targeting_key) => writeln!(f, "    targeting_key: {:?}", targeting_key)?,
+            None => writeln!(f, "    targeting_key: none")?,
+        }
+
+        let mut custom_fields: Vec<_> = self.evaluation_context.custom_fields.iter().collect();
+        custom_fields.sort_by_key(|(key, _)| *key);
+
+        for (key, value) in custom_fields {
+            write!(f, "    {}: ", key)?;
+            write_field_value(f, value)?;
+            writeln!(f)?;
+        }
+
+        if !self.hooks.is_empty() {
+            writeln!(f, "  hooks:")?;
+
+            for hook in &self.hooks {
+                write!(f, "    {} {}", hook.stage, hook.hook)?;
+
+                if hook.changed_evaluation_context {
+                    write!(f, " (evaluation context changed)")?;
+                }
+
+                if let Some(error) = &hook.error {
+                    write!(f, " (failed: ")?;
+                    write_error(f, error)?;
+                    write!(f, ")")?;
+                }
+
+                writeln!(f)?;
+            }
+        }
+
+        writeln!(f, "  result:")?;
+        match &self.result {
+            Ok(details) => {
+                write!(f, "    value: ")?;
+                write_value(f, &details.value)?;
+                writeln!(f)?;
+
+                if let Some(reason) = &details.reason {
+                    writeln!(f, "    reason: {}", reason.to_string())?;
+                }
+
+                if let Some(variant) = &details.variant {
+                    writeln!(f, "    variant: {:?}", variant)?;
+                }
+
+                let mut metadata: Vec<_> = details.flag_metadata.values.iter().collect();
+                metadata.sort_by_key(|(key, _)| *key);
+
+                if !metadata.is_empty() {
+                    writeln!(f, "    flag_metadata:")?;
+
+                    for (key, value) in metadata {
+                        write!(f, "      {}: ", key)?;
+                        match value {
+                            FlagMetadataValue::Bool(value) => writeln!(f, "{}", value)?,
+                            FlagMetadataValue::Int(value) => writeln!(f, "{}", value)?,
+                            FlagMetadataValue::Float(value) => writeln!(f, "{}", value)?,
+                            FlagMetadataValue::String(value) => writeln!(f, "{:?}", value)?,
+                        }
+                    }
+                }
+            }
+            Err(error) => {
+                write!(f, "    error: ")?;
+                write_error(f, error)?;
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn write_value(f: &mut fmt::Formatter<'_>, value: &Value) -> fmt::Result {
+    match value {
+        Value::Bool(value) => write!(f, "{}", value),
+        Value::Int(value) => write!(f, "{}", value),
+        Value::Float(value) => write!(f, "{}", value),
+        Value::String(value) => write!(f, "{:?}", value),
+        Value::Array(_) | Value::Struct(_) => write!(f, "{:?}", value),
+    }
+}
+
+fn write_field_value(
+    f: &mut fmt::Formatter<'_>,
+    value: &EvaluationContextFieldValue,
+) -> fmt::Result {
+    match value {
+        EvaluationContextFieldValue::Bool(value) => write!(f, "{}", value),
+        EvaluationContextFieldValue::Int(value) => write!(f, "{}", value),
+        EvaluationContextFieldValue::Float(value) => write!(f, "{}", value),
+        EvaluationContextFieldValue::String(value) => write!(f, "{:?}", value),
+        EvaluationContextFieldValue::DateTime(value) => write!(f, "{}", value),
+        EvaluationContextFieldValue::List(values) => {
+            write!(f, "[")?;
+
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+
+                write_field_value(f, value)?;
+            }
+
+            write!(f, "]")
+        }
+        EvaluationContextFieldValue::Struct(_) => write!(f, "<struct>"),
+    }
+}
+
+fn write_error(f: &mut fmt::Formatter<'_>, error: &EvaluationError) -> fmt::Result {
+    write!(f, "{}", error.code)?;
+
+    if let Some(message) = &error.message {
+        write!(f, " ({})", message)?;
+    }
+
+    Ok(())
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use crate::{flags, Hook, HookContext, OpenFeature};
+
+    use super::*;
+
+    struct EnrichHook;
+
+    #[async_trait]
+    impl Hook for EnrichHook {
+        async fn before<'a>(
+            &self,
+            _context: &HookContext<'a>,
+        ) -> Result<Option<EvaluationContext>, EvaluationError> {
+            Ok(Some(
+                EvaluationContext::default().with_custom_field("country", "FR"),
+            ))
+        }
+
+        fn name(&self) -> &'static str {
+            "EnrichHook"
+        }
+    }
+
+    #[tokio::test]
+    async fn render_trace() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let client = api.create_named_client("checkout").with_hook(EnrichHook);
+
+        let trace = client
+            .trace::<bool>(
+                "checkout-v2",
+                Some(&EvaluationContext::default().with_targeting_key("alice")),
+                None,
+            )
+            .await;
+
+        assert_eq!(
+            trace.to_string(),
+            r#"Evaluation of "checkout-v2" (Bool) by client "checkout"
+  provider: In-memory Provider
+  evaluation context:
+    targeting_key: "alice"
+    country: "FR"
+  hooks:
+    before EnrichHook (evaluation context changed)
+    after EnrichHook
+    finally EnrichHook
+  result:
+    value: true
+    reason: STATIC
+    variant: "true"
+"#
+        );
+    }
+
+    #[tokio::test]
+    async fn render_error() {
+        let api = OpenFeature::default();
+        let client = api.create_client();
+
+        let trace = client.trace::<i64>("missing", None, None).await;
+
+        assert!(trace.hooks.is_empty());
+        assert!(trace.to_string().ends_with(
+            "  result:\n    error: PROVIDER_NOT_READY (Provider \"No-op Provider\" is not ready)\n"
+        ));
+    }
+}
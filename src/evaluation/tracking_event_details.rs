@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use crate::EvaluationContextFieldValue;
+
+/// The details of a tracking event, passed to [`Client::track`](crate::Client::track).
+///
+/// See the [spec](https://openfeature.dev/specification/sections/tracking).
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct TrackingEventDetails {
+    /// A numeric value associated with the event, such as the amount of a purchase.
+    pub value: Option<f64>,
+
+    /// Custom fields describing the event.
+    pub custom_fields: HashMap<String, EvaluationContextFieldValue>,
+}
+
+impl TrackingEventDetails {
+    /// Set the numeric `value` associated with the event.
+    #[must_use]
+    pub fn with_value(mut self, value: f64) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Add `key` and `value` to the custom fields of the event.
+    #[must_use]
+    pub fn with_custom_field(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<EvaluationContextFieldValue>,
+    ) -> Self {
+        self.add_custom_field(key, value);
+        self
+    }
+
+    /// Add `key` and `value` to the custom fields of the event.
+    pub fn add_custom_field(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<EvaluationContextFieldValue>,
+    ) {
+        self.custom_fields.insert(key.into(), value.into());
+    }
+}
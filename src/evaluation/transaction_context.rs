@@ -0,0 +1,166 @@
+use std::{cell::RefCell, future::Future};
+
+use crate::EvaluationContext;
+
+tokio::task_local! {
+    static TASK_CONTEXT: EvaluationContext;
+}
+
+thread_local! {
+    static THREAD_CONTEXT: RefCell<Option<EvaluationContext>> = const { RefCell::new(None) };
+}
+
+// ============================================================
+//  TransactionContext
+// ============================================================
+
+/// The evaluation context of the current transaction, such as a request, set once by middleware
+/// and included in every evaluation made within it, without passing it at every call site.
+///
+/// The context is scoped to a future with [`Self::scope`], or to a closure on the current
+/// thread with [`Self::sync_scope`]. Nested scopes are merged, inner attributes taking
+/// precedence. Clients merge the transaction context above the global one and the one of their
+/// context supplier, and below their own and the invocation one.
+///
+/// ```
+/// use open_feature::{EvaluationContext, TransactionContext};
+///
+/// # async fn example() {
+/// let context = EvaluationContext::default()
+///     .with_targeting_key("alice")
+///     .with_custom_field("region", "eu-west");
+///
+/// TransactionContext::scope(context, async {
+///     // Every evaluation in here includes the targeting key and the region.
+/// })
+/// .await;
+/// # }
+/// ```
+pub struct TransactionContext;
+
+impl TransactionContext {
+    /// Run `future` with `context` as the transaction context, merged with the current one.
+    pub async fn scope<F: Future>(context: EvaluationContext, future: F) -> F::Output {
+        TASK_CONTEXT.scope(Self::merged(context), future).await
+    }
+
+    /// Run `f` on the current thread with `context` as the transaction context, merged with
+    /// the current one.
+    pub fn sync_scope<R>(context: EvaluationContext, f: impl FnOnce() -> R) -> R {
+        // Restores the previous context even if `f` panics.
+        struct Restore(Option<EvaluationContext>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                THREAD_CONTEXT.with(|thread_context| thread_context.replace(self.0.take()));
+            }
+        }
+
+        let previous = THREAD_CONTEXT
+            .with(|thread_context| thread_context.replace(Some(Self::merged(context))));
+        let _restore = Restore(previous);
+
+        f()
+    }
+
+    /// Return the current transaction context, if any. The one of the current task takes
+    /// precedence over the one of the current thread.
+    pub fn current() -> Option<EvaluationContext> {
+        TASK_CONTEXT
+            .try_with(Clone::clone)
+            .ok()
+            .or_else(|| THREAD_CONTEXT.with(|thread_context| thread_context.borrow().clone()))
+    }
+
+    fn merged(mut context: EvaluationContext) -> EvaluationContext {
+        if let Some(current) = Self::current() {
+            context.merge_missing(&current);
+        }
+
+        context
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{flags, provider::InMemoryFlag, OpenFeature};
+
+    #[tokio::test]
+    async fn merge_nested_scopes() {
+        assert!(TransactionContext::current().is_none());
+
+        let outer = EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("region", "eu-west");
+        let inner = EvaluationContext::default().with_custom_field("region", "us-east");
+
+        TransactionContext::scope(outer, async {
+            TransactionContext::scope(inner, async {
+                let context = TransactionContext::current().unwrap();
+
+                assert_eq!(context.targeting_key, Some("alice".to_string()));
+                assert_eq!(
+                    context.custom_fields.get("region").unwrap().as_str(),
+                    Some("us-east")
+                );
+            })
+            .await;
+        })
+        .await;
+
+        assert!(TransactionContext::current().is_none());
+    }
+
+    #[test]
+    fn sync_scope() {
+        let context = EvaluationContext::default().with_targeting_key("bob");
+
+        TransactionContext::sync_scope(context, || {
+            assert_eq!(
+                TransactionContext::current().unwrap().targeting_key,
+                Some("bob".to_string())
+            );
+        });
+
+        assert!(TransactionContext::current().is_none());
+    }
+
+    #[tokio::test]
+    async fn evaluate_with_transaction_context() {
+        let mut api = OpenFeature::default();
+        api.set_provider(
+            flags! {}.with_flag(
+                "checkout-v2",
+                InMemoryFlag::new("off")
+                    .with_variant("on", true)
+                    .with_variant("off", false)
+                    .with_resolver(|context| {
+                        (context.targeting_key.as_deref() == Some("alice"))
+                            .then(|| "on".to_string())
+                    }),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let client = api.create_client();
+
+        let context = EvaluationContext::default().with_targeting_key("alice");
+        let enabled = TransactionContext::scope(context, async {
+            client.get_bool_value("checkout-v2", None, None).await
+        })
+        .await
+        .unwrap();
+
+        assert!(enabled);
+        assert!(!client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+    }
+}
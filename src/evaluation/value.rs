@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 /// Hold a value in the evaluation result of supported types.
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
 #[allow(missing_docs)]
 pub enum Value {
     Bool(bool),
@@ -15,6 +17,8 @@ pub enum Value {
 /// Represent a structure value as defined in the
 /// [spec](https://openfeature.dev/specification/types#structure).
 #[derive(Clone, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct StructValue {
     /// The fields of struct as key-value pairs.
     pub fields: HashMap<String, Value>,
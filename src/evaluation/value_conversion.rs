@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+
+use crate::{EvaluationError, EvaluationErrorCode, EvaluationResult, StructValue, Value};
+
+// ============================================================
+//  FromValue
+// ============================================================
+
+/// A type that can be built out of a [`Value`], such as the value of an object flag.
+///
+/// It is implemented for the primitive types, `String`, [`Value`], [`StructValue`], and for
+/// `Vec<T>`, `HashMap<String, T>` and `Option<T>` of such types. With the `derive` feature,
+/// `#[derive(FromValue)]` implements it for structs with named fields, along with
+/// `TryFrom<StructValue>` so that they can be passed to
+/// [`Client::get_struct_value`](crate::Client::get_struct_value):
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # mod example {
+/// use open_feature::FromValue;
+///
+/// #[derive(FromValue)]
+/// struct Checkout {
+///     max_items: u32,
+///     banner: Option<String>,
+/// }
+/// # }
+/// ```
+///
+/// Each field is read from the struct field of the same name. `Option` fields may be missing.
+pub trait FromValue: Sized {
+    /// Convert `value` into `Self`, failing with [`EvaluationErrorCode::TypeMismatch`] if it
+    /// holds another type.
+    fn from_value(value: Value) -> EvaluationResult<Self>;
+
+    /// Remove the field `name` from `fields` and convert it into `Self`, failing if it is missing.
+    fn from_field(fields: &mut HashMap<String, Value>, name: &str) -> EvaluationResult<Self> {
+        let value = fields.remove(name).ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::TypeMismatch)
+                .message(format!("Missing field `{}`", name))
+                .build()
+        })?;
+
+        Self::from_value(value).map_err(|error| EvaluationError {
+            message: Some(format!(
+                "Invalid field `{}`: {}",
+                name,
+                error.message.unwrap_or_default()
+            )),
+            ..error
+        })
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        Ok(value)
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        match value {
+            Value::Bool(value) => Ok(value),
+            value => Err(type_mismatch("a bool", &value)),
+        }
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        match value {
+            Value::Int(value) => Ok(value),
+            value => Err(type_mismatch("an int", &value)),
+        }
+    }
+}
+
+macro_rules! impl_from_value_for_int {
+    ($($ty:ty),*) => {
+        $(
+            impl FromValue for $ty {
+                fn from_value(value: Value) -> EvaluationResult<Self> {
+                    let value = i64::from_value(value)?;
+
+                    Self::try_from(value).map_err(|_| {
+                        EvaluationError::builder()
+                            .code(EvaluationErrorCode::TypeMismatch)
+                            .message(format!(
+                                "{} is out of the range of {}",
+                                value,
+                                stringify!($ty)
+                            ))
+                            .build()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_for_int!(i8, i16, i32, isize, u8, u16, u32, u64, usize);
+
+impl FromValue for f64 {
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        match value {
+            Value::Float(value) => Ok(value),
+            #[allow(clippy::cast_precision_loss)]
+            Value::Int(value) => Ok(value as f64),
+            value => Err(type_mismatch("a float", &value)),
+        }
+    }
+}
+
+impl FromValue for f32 {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        f64::from_value(value).map(|value| value as f32)
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        match value {
+            Value::String(value) => Ok(value),
+            value => Err(type_mismatch("a string", &value)),
+        }
+    }
+}
+
+impl FromValue for StructValue {
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        match value {
+            Value::Struct(value) => Ok(value),
+            value => Err(type_mismatch("a struct", &value)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        match value {
+            Value::Array(values) => values.into_iter().map(T::from_value).collect(),
+            value => Err(type_mismatch("an array", &value)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for HashMap<String, T> {
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        StructValue::from_value(value)?
+            .fields
+            .into_iter()
+            .map(|(key, value)| Ok((key, T::from_value(value)?)))
+            .collect()
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: Value) -> EvaluationResult<Self> {
+        T::from_value(value).map(Some)
+    }
+
+    fn from_field(fields: &mut HashMap<String, Value>, name: &str) -> EvaluationResult<Self> {
+        if fields.contains_key(name) {
+            T::from_field(fields, name).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn type_mismatch(expected: &str, value: &Value) -> EvaluationError {
+    let actual = match value {
+        Value::Bool(_) => "a bool",
+        Value::Int(_) => "an int",
+        Value::Float(_) => "a float",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Struct(_) => "a struct",
+    };
+
+    EvaluationError::builder()
+        .code(EvaluationErrorCode::TypeMismatch)
+        .message(format!("Expected {}, got {}", expected, actual))
+        .build()
+}
+
+// ============================================================
+//  IntoValue
+// ============================================================
+
+/// A type that can be converted into a [`Value`], such as the value of an in-memory flag.
+///
+/// It is implemented for every type implementing `Into<Value>`. With the `derive` feature,
+/// `#[derive(IntoValue)]` implements `From<T>` for [`Value`] and [`StructValue`] for structs
+/// with named fields, holding one struct field per field. `Option` fields set to `None` are left
+/// out.
+pub trait IntoValue: Into<Value> {
+    /// Convert `self` into a [`Value`].
+    fn into_value(self) -> Value {
+        self.into()
+    }
+}
+
+impl<T: Into<Value>> IntoValue for T {}
+
+impl<T: Into<Value>> From<HashMap<String, T>> for Value {
+    fn from(value: HashMap<String, T>) -> Self {
+        Self::Struct(StructValue {
+            fields: value
+                .into_iter()
+                .map(|(key, value)| (key, value.into()))
+                .collect(),
+        })
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_primitives() {
+        assert!(bool::from_value(Value::Bool(true)).unwrap());
+        assert_eq!(u8::from_value(Value::Int(42)).unwrap(), 42);
+        assert_eq!(
+            Value::Float(f64::from_value(Value::Int(2)).unwrap()),
+            Value::Float(2.0)
+        );
+        assert_eq!(
+            String::from_value("Hello".into()).unwrap(),
+            "Hello".to_string()
+        );
+        assert_eq!(
+            Vec::<i64>::from_value(vec![1, 2].into()).unwrap(),
+            vec![1, 2]
+        );
+
+        assert_eq!(
+            u8::from_value(Value::Int(-1)).unwrap_err().message.unwrap(),
+            "-1 is out of the range of u8"
+        );
+        assert_eq!(
+            i64::from_value(Value::Bool(true))
+                .unwrap_err()
+                .message
+                .unwrap(),
+            "Expected an int, got a bool"
+        );
+    }
+
+    #[test]
+    fn convert_fields() {
+        let mut fields = StructValue::default()
+            .with_field("max_items", 3)
+            .with_field("banner", true)
+            .fields;
+
+        assert_eq!(u32::from_field(&mut fields, "max_items").unwrap(), 3);
+        assert_eq!(
+            Option::<String>::from_field(&mut fields, "missing").unwrap(),
+            None
+        );
+        assert_eq!(
+            String::from_field(&mut fields, "missing")
+                .unwrap_err()
+                .message
+                .unwrap(),
+            "Missing field `missing`"
+        );
+
+        let error = Option::<String>::from_field(&mut fields, "banner").unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::TypeMismatch);
+        assert_eq!(
+            error.message.unwrap(),
+            "Invalid field `banner`: Expected a string, got a bool"
+        );
+    }
+
+    #[test]
+    fn convert_maps() {
+        let value = HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]).into_value();
+
+        assert_eq!(
+            HashMap::<String, i64>::from_value(value).unwrap(),
+            HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)])
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derive() {
+        use crate::{FromValue, IntoValue};
+
+        #[derive(FromValue, IntoValue, PartialEq, Debug)]
+        struct Banner {
+            text: String,
+        }
+
+        #[derive(FromValue, IntoValue, PartialEq, Debug)]
+        struct Checkout {
+            max_items: u32,
+            discount: f64,
+            banner: Option<Banner>,
+            tags: Vec<String>,
+        }
+
+        let value = StructValue::from(Checkout {
+            max_items: 3,
+            discount: 0.1,
+            banner: None,
+            tags: Vec::new(),
+        });
+        assert!(!value.fields.contains_key("banner"));
+        assert_eq!(
+            Checkout::try_from(value).unwrap(),
+            Checkout {
+                max_items: 3,
+                discount: 0.1,
+                banner: None,
+                tags: Vec::new(),
+            }
+        );
+
+        let value = StructValue::default()
+            .with_field("max_items", 5)
+            .with_field("discount", 0)
+            .with_field("banner", StructValue::default().with_field("text", "Sale"))
+            .with_field("tags", vec!["summer"]);
+        assert_eq!(
+            Checkout::from_value(value.into()).unwrap(),
+            Checkout {
+                max_items: 5,
+                discount: 0.0,
+                banner: Some(Banner {
+                    text: "Sale".to_string()
+                }),
+                tags: vec!["summer".to_string()],
+            }
+        );
+
+        assert_eq!(
+            Checkout::from_value(StructValue::default().with_field("max_items", 5).into())
+                .unwrap_err()
+                .message
+                .unwrap(),
+            "Missing field `discount`"
+        );
+    }
+}
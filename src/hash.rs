@@ -0,0 +1,22 @@
+use std::fmt::Write as _;
+
+use sha2::{Digest, Sha256};
+
+/// Return the hex-encoded SHA-256 of `value` salted with `salt`, to pseudonymize it.
+pub(crate) fn salted_sha256_hex(salt: &[u8], value: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(value);
+
+    hex(&hasher.finalize())
+}
+
+/// Return `bytes` hex-encoded, in lower case.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+
+    hex
+}
@@ -0,0 +1,401 @@
+use std::{
+    io::{self, BufRead, Write},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Map, Value as JsonValue};
+use sha2::Sha256;
+use time::OffsetDateTime;
+
+use crate::{
+    hash::hex, provider::ProviderEvent, EvaluationDetails, EvaluationError, EvaluationErrorCode,
+    FlagMetadataValue, Value,
+};
+
+use super::{Hook, HookContext};
+
+/// The previous hash of the first entry of a log.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+// ============================================================
+//  AuditLog
+// ============================================================
+
+/// A hook writing a tamper-evident, append-only log of evaluations to a writer, such as a file
+/// opened in append mode, for flags whose use must be accounted for, such as those gating
+/// payments.
+///
+/// Every entry is a line of JSON telling who evaluated what and when: the client, the provider,
+/// the flag, the targeting key, and the resolved value, variant and reason or error code.
+/// Provider configuration changes are logged with [`Self::record_configuration_change`], along
+/// with the hash of the configuration sent by polling providers.
+///
+/// Entries are chained: each one holds its sequence number, the hash of the previous entry, and
+/// its own hash, the hex-encoded HMAC-SHA256 of the previous hash and of the entry without its
+/// hash, keyed with a secret key. Without the key, altering, removing or reordering entries
+/// breaks the chain, as [`AuditLog::verify`] reports. Removing the last entries leaves a valid
+/// chain though: the head of the log, as returned by [`AuditLog::head`], is to be anchored
+/// outside of it, such as in a separate store updated periodically, and compared with the one
+/// returned by [`AuditLog::verify`].
+///
+/// Evaluations are written on the blocking thread pool, so that a slow writer does not stall the
+/// runtime. A failed write is ignored, unless the log fails closed, in which case the evaluation
+/// fails rather than going unaccounted for.
+///
+/// ```no_run
+/// use std::{fs::OpenOptions, sync::Arc};
+///
+/// use open_feature::{provider::ProviderEventType, AuditLog, OpenFeature};
+///
+/// # async fn example() -> std::io::Result<()> {
+/// # let secret_key = b"secret".to_vec();
+/// # let api = OpenFeature::singleton().await;
+/// let file = OpenOptions::new().create(true).append(true).open("audit.log")?;
+/// let audit_log = Arc::new(AuditLog::new(file, secret_key).with_fail_closed(true));
+///
+/// let client = api.create_client().with_hook(audit_log.clone());
+/// client
+///     .add_handler(ProviderEventType::ConfigurationChanged, move |event| {
+///         let _ = audit_log.record_configuration_change(event);
+///     })
+///     .await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AuditLog<W> {
+    chain: Arc<Mutex<Chain<W>>>,
+    fail_closed: bool,
+}
+
+/// The writer of a log, and where its chain is at.
+struct Chain<W> {
+    writer: W,
+    key: Vec<u8>,
+    sequence: u64,
+    previous_hash: String,
+}
+
+impl<W: Write + Send + 'static> AuditLog<W> {
+    /// Create a log writing a new chain to `writer`, hashing entries with secret `key`.
+    pub fn new(writer: W, key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            chain: Arc::new(Mutex::new(Chain {
+                writer,
+                key: key.into(),
+                sequence: 0,
+                previous_hash: GENESIS_HASH.to_string(),
+            })),
+            fail_closed: false,
+        }
+    }
+
+    /// Continue the chain of an existing log of `entries` entries, the last one with hash
+    /// `last_hash`, as returned by [`AuditLog::verify`].
+    #[must_use]
+    pub fn with_chain(self, entries: u64, last_hash: impl Into<String>) -> Self {
+        {
+            let mut chain = self.chain.lock().unwrap();
+            chain.sequence = entries;
+            chain.previous_hash = last_hash.into();
+        }
+        self
+    }
+
+    /// Set whether evaluations fail when their entry can't be written, rather than proceeding.
+    #[must_use]
+    pub fn with_fail_closed(mut self, fail_closed: bool) -> Self {
+        self.fail_closed = fail_closed;
+        self
+    }
+
+    /// Return the number of entries of the log and the hash of the last one, to be anchored
+    /// outside of the log.
+    pub fn head(&self) -> (u64, String) {
+        let chain = self.chain.lock().unwrap();
+        (chain.sequence, chain.previous_hash.clone())
+    }
+
+    /// Log the configuration change of `event`, a `PROVIDER_CONFIGURATION_CHANGED` event: the
+    /// provider, the flags changed, and the `configurationHash` metadata of the event, if any.
+    pub fn record_configuration_change(&self, event: &ProviderEvent) -> io::Result<()> {
+        let configuration_hash = match event.event_metadata.values.get("configurationHash") {
+            Some(FlagMetadataValue::String(hash)) => Some(hash.as_str()),
+            _ => None,
+        };
+
+        self.chain.lock().unwrap().append(json!({
+            "kind": "configurationChange",
+            "provider": event.provider_name,
+            "flagsChanged": event.flags_changed,
+            "configurationHash": configuration_hash,
+        }))
+    }
+
+    /// Return the log written so far, once written, such as an in-memory buffer.
+    pub fn into_writer(self) -> W {
+        Arc::try_unwrap(self.chain)
+            .ok()
+            .expect("Entries are not being written")
+            .into_inner()
+            .unwrap()
+            .writer
+    }
+
+    /// Log an evaluation, failing it if the log fails closed and the entry can't be written.
+    async fn record(
+        &self,
+        context: &HookContext<'_>,
+        outcome: Map<String, JsonValue>,
+    ) -> Result<(), EvaluationError> {
+        let mut entry = json!({
+            "kind": "evaluation",
+            "client": context.client_metadata.name,
+            "provider": context.provider_metadata.name,
+            "flagKey": context.flag_key,
+            "targetingKey": context.evaluation_context.targeting_key,
+        });
+        entry.as_object_mut().unwrap().extend(outcome);
+
+        let chain = self.chain.clone();
+        let written = tokio::task::spawn_blocking(move || chain.lock().unwrap().append(entry))
+            .await
+            .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)));
+
+        match written {
+            Err(error) if self.fail_closed => Err(EvaluationError::builder()
+                .code(EvaluationErrorCode::General(
+                    "The evaluation could not be audited".to_string(),
+                ))
+                .message(error.to_string())
+                .build()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<W: Write> Chain<W> {
+    /// Chain `entry`, and write it as a line.
+    fn append(&mut self, mut entry: JsonValue) -> io::Result<()> {
+        let fields = entry.as_object_mut().expect("Entries are objects");
+        fields.insert("sequence".to_string(), self.sequence.into());
+        fields.insert(
+            "timestamp".to_string(),
+            OffsetDateTime::now_utc().unix_timestamp().into(),
+        );
+        fields.insert(
+            "previousHash".to_string(),
+            self.previous_hash.clone().into(),
+        );
+
+        let hash = hash_entry(&self.key, &self.previous_hash, fields);
+        fields.insert("hash".to_string(), hash.clone().into());
+
+        let mut line = entry.to_string();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()?;
+
+        self.sequence += 1;
+        self.previous_hash = hash;
+        Ok(())
+    }
+}
+
+impl AuditLog<()> {
+    /// Check the chain of the log read from `reader`, hashed with `key`, and return its number
+    /// of entries and the hash of the last one, to compare with the anchored head of the log,
+    /// and to continue it with [`AuditLog::with_chain`].
+    ///
+    /// Fail with [`io::ErrorKind::InvalidData`] at the first entry that was altered, removed or
+    /// reordered. Entries removed from the end of the log are only detected by comparing the
+    /// result with the anchored head.
+    pub fn verify(reader: impl BufRead, key: &[u8]) -> io::Result<(u64, String)> {
+        let mut entries = 0;
+        let mut previous_hash = GENESIS_HASH.to_string();
+
+        for line in reader.lines() {
+            let line = line?;
+            let invalid = |message: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Audit log entry {entries} {message}"),
+                )
+            };
+
+            let mut entry: Map<String, JsonValue> =
+                serde_json::from_str(&line).map_err(|_| invalid("is not valid JSON"))?;
+            let hash = match entry.remove("hash") {
+                Some(JsonValue::String(hash)) => hash,
+                _ => return Err(invalid("has no hash")),
+            };
+
+            if entry.get("sequence").and_then(JsonValue::as_u64) != Some(entries) {
+                return Err(invalid("is out of sequence"));
+            }
+            if entry.get("previousHash").and_then(JsonValue::as_str) != Some(&previous_hash) {
+                return Err(invalid("does not follow the previous entry"));
+            }
+            if hash_entry(key, &previous_hash, &entry) != hash {
+                return Err(invalid("was altered"));
+            }
+
+            entries += 1;
+            previous_hash = hash;
+        }
+
+        Ok((entries, previous_hash))
+    }
+}
+
+#[async_trait]
+impl<W: Write + Send + 'static> Hook for AuditLog<W> {
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        details: &EvaluationDetails<Value>,
+    ) -> Result<(), EvaluationError> {
+        let outcome = json!({
+            "value": JsonValue::from(&details.value),
+            "variant": details.variant,
+            "reason": details.reason.as_ref().map(ToString::to_string),
+        });
+
+        self.record(context, into_map(outcome)).await
+    }
+
+    async fn error<'a>(&self, context: &HookContext<'a>, error: &EvaluationError) {
+        let outcome = json!({
+            "errorCode": error.code.to_string(),
+            "errorMessage": error.message,
+        });
+
+        // The evaluation failed already.
+        let _ = self.record(context, into_map(outcome)).await;
+    }
+}
+
+fn into_map(value: JsonValue) -> Map<String, JsonValue> {
+    match value {
+        JsonValue::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Return the hex-encoded HMAC-SHA256 of `previous_hash` and of `entry`, keyed with `key`.
+fn hash_entry(key: &[u8], previous_hash: &str, entry: &Map<String, JsonValue>) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any size");
+    mac.update(previous_hash.as_bytes());
+    mac.update(JsonValue::Object(entry.clone()).to_string().as_bytes());
+
+    hex(&mac.finalize().into_bytes())
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{flags, provider::ProviderEventType, EvaluationContext, OpenFeature};
+
+    const KEY: &[u8] = b"secret-key";
+
+    #[tokio::test]
+    async fn chain_entries() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! { "payments-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let audit_log = Arc::new(AuditLog::new(Vec::new(), KEY));
+        let client = api.create_client().with_hook(audit_log.clone());
+
+        let context = EvaluationContext::default().with_targeting_key("alice");
+        client
+            .get_bool_value("payments-v2", Some(&context), None)
+            .await
+            .unwrap();
+        let _ = client.get_int_value("payments-v2", None, None).await;
+        audit_log
+            .record_configuration_change(
+                &ProviderEvent::builder()
+                    .event_type(ProviderEventType::ConfigurationChanged)
+                    .provider_name("In-memory Provider")
+                    .flags_changed(vec!["payments-v2".to_string()])
+                    .build(),
+            )
+            .unwrap();
+
+        drop(client);
+        let log = Arc::try_unwrap(audit_log).ok().unwrap().into_writer();
+        let lines: Vec<JsonValue> = log
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+
+        assert_eq!(lines[0]["targetingKey"], "alice");
+        assert_eq!(lines[0]["value"], true);
+        assert_eq!(lines[1]["errorCode"], "TYPE_MISMATCH");
+        assert_eq!(lines[2]["flagsChanged"], json!(["payments-v2"]));
+
+        let (entries, last_hash) = AuditLog::verify(log.as_slice(), KEY).unwrap();
+        assert_eq!(entries, 3);
+        assert_eq!(lines[2]["hash"], last_hash.as_str());
+
+        // Continuing the chain keeps it valid.
+        let audit_log = AuditLog::new(log, KEY).with_chain(entries, last_hash);
+        audit_log
+            .record_configuration_change(
+                &ProviderEvent::builder()
+                    .event_type(ProviderEventType::ConfigurationChanged)
+                    .provider_name("In-memory Provider")
+                    .build(),
+            )
+            .unwrap();
+        let head = audit_log.head();
+        let log = audit_log.into_writer();
+        assert_eq!(AuditLog::verify(log.as_slice(), KEY).unwrap(), head);
+        assert_eq!(head.0, 4);
+    }
+
+    #[test]
+    fn detect_tampering() {
+        let audit_log = AuditLog::new(Vec::new(), KEY);
+        for provider_name in ["a", "b", "c"] {
+            audit_log
+                .record_configuration_change(
+                    &ProviderEvent::builder()
+                        .event_type(ProviderEventType::ConfigurationChanged)
+                        .provider_name(provider_name)
+                        .build(),
+                )
+                .unwrap();
+        }
+        let head = audit_log.head();
+        let log = String::from_utf8(audit_log.into_writer()).unwrap();
+        let lines: Vec<_> = log.lines().collect();
+
+        let altered = log.replace("\"provider\":\"b\"", "\"provider\":\"x\"");
+        let error = AuditLog::verify(altered.as_bytes(), KEY).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "Audit log entry 1 was altered");
+
+        let removed = format!("{}\n{}\n", lines[0], lines[2]);
+        let error = AuditLog::verify(removed.as_bytes(), KEY).unwrap_err();
+        assert_eq!(error.to_string(), "Audit log entry 1 is out of sequence");
+
+        // Without the key, the chain cannot be rebuilt.
+        let error = AuditLog::verify(log.as_bytes(), b"guessed-key").unwrap_err();
+        assert_eq!(error.to_string(), "Audit log entry 0 was altered");
+
+        // Truncating the log keeps the chain valid, but not the head.
+        let truncated = format!("{}\n{}\n", lines[0], lines[1]);
+        assert_ne!(AuditLog::verify(truncated.as_bytes(), KEY).unwrap(), head);
+    }
+}
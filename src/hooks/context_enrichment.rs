@@ -0,0 +1,304 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use crate::{EvaluationContext, EvaluationError, EvaluationErrorCode};
+
+use super::{Hook, HookContext};
+
+/// The number of cached contexts above which expired ones are evicted.
+const MAX_CACHED_CONTEXTS: usize = 10_000;
+
+// ============================================================
+//  ContextEnricher
+// ============================================================
+
+/// Looks up additional attributes of an evaluation context from an external service, such as
+/// the plan of the user from a profile service.
+#[async_trait]
+pub trait ContextEnricher: Send + Sync + 'static {
+    /// Return the attributes to add to `evaluation_context`.
+    async fn enrich(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> Result<EvaluationContext, EvaluationError>;
+}
+
+// ============================================================
+//  ContextEnrichmentHook
+// ============================================================
+
+/// A hook adding the attributes returned by a [`ContextEnricher`] to the evaluation context in
+/// its `before` stage. Attributes already present in the evaluation context are kept.
+///
+/// The enricher is given [`Self::DEFAULT_TIMEOUT`] to answer, after which the evaluation
+/// proceeds without its attributes, unless [`Self::with_skip_on_timeout`] says otherwise. Its
+/// answers are cached by targeting key for [`Self::DEFAULT_CACHE_TTL`], and failed lookups are
+/// skipped.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use open_feature::{async_trait, ContextEnricher, ContextEnrichmentHook, OpenFeature};
+/// # use open_feature::{EvaluationContext, EvaluationError};
+/// #
+/// # struct ProfileService;
+/// #
+/// # impl ProfileService {
+/// #     fn new() -> Self {
+/// #         Self
+/// #     }
+/// # }
+/// #
+/// # #[async_trait]
+/// # impl ContextEnricher for ProfileService {
+/// #     async fn enrich(
+/// #         &self,
+/// #         _evaluation_context: &EvaluationContext,
+/// #     ) -> Result<EvaluationContext, EvaluationError> {
+/// #         Ok(EvaluationContext::default())
+/// #     }
+/// # }
+///
+/// # async fn example() {
+/// # let api = OpenFeature::singleton().await;
+/// let client = api.create_client().with_hook(
+///     ContextEnrichmentHook::new(ProfileService::new())
+///         .with_timeout(Duration::from_millis(50))
+///         .with_cache_ttl(Duration::from_secs(300)),
+/// );
+/// # }
+/// ```
+pub struct ContextEnrichmentHook<E> {
+    enricher: E,
+    timeout: Duration,
+    cache_ttl: Duration,
+    skip_on_timeout: bool,
+    cache: Mutex<HashMap<String, (Instant, EvaluationContext)>>,
+}
+
+impl<E: ContextEnricher> ContextEnrichmentHook<E> {
+    /// The time given to the enricher by default.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(200);
+
+    /// The time enriched attributes are cached by default.
+    pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+    /// Create a hook enriching evaluation contexts with `enricher`.
+    pub fn new(enricher: E) -> Self {
+        Self {
+            enricher,
+            timeout: Self::DEFAULT_TIMEOUT,
+            cache_ttl: Self::DEFAULT_CACHE_TTL,
+            skip_on_timeout: true,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the time given to the enricher.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the time enriched attributes are cached. Zero disables caching.
+    #[must_use]
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Set whether the evaluation proceeds without the attributes when the enricher times out,
+    /// rather than failing.
+    #[must_use]
+    pub fn with_skip_on_timeout(mut self, skip_on_timeout: bool) -> Self {
+        self.skip_on_timeout = skip_on_timeout;
+        self
+    }
+
+    fn cached(&self, targeting_key: &str) -> Option<EvaluationContext> {
+        let cache = self.cache.lock().unwrap();
+        let (cached_at, attributes) = cache.get(targeting_key)?;
+
+        (cached_at.elapsed() < self.cache_ttl).then(|| attributes.clone())
+    }
+
+    fn cache(&self, targeting_key: String, attributes: EvaluationContext) {
+        let mut cache = self.cache.lock().unwrap();
+
+        if cache.len() >= MAX_CACHED_CONTEXTS {
+            cache.retain(|_, (cached_at, _)| cached_at.elapsed() < self.cache_ttl);
+        }
+
+        cache.insert(targeting_key, (Instant::now(), attributes));
+    }
+}
+
+#[async_trait]
+impl<E: ContextEnricher> Hook for ContextEnrichmentHook<E> {
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        let targeting_key = context
+            .evaluation_context
+            .targeting_key
+            .as_ref()
+            .filter(|_| !self.cache_ttl.is_zero());
+
+        let cached = targeting_key.and_then(|targeting_key| self.cached(targeting_key));
+
+        let attributes = match cached {
+            Some(attributes) => attributes,
+            None => match tokio::time::timeout(
+                self.timeout,
+                self.enricher.enrich(context.evaluation_context),
+            )
+            .await
+            {
+                Ok(Ok(attributes)) => {
+                    if let Some(targeting_key) = targeting_key {
+                        self.cache(targeting_key.clone(), attributes.clone());
+                    }
+
+                    attributes
+                }
+                Ok(Err(_)) => return Ok(None),
+                Err(_) if self.skip_on_timeout => return Ok(None),
+                Err(_) => {
+                    return Err(EvaluationError::builder()
+                        .code(EvaluationErrorCode::General(
+                            "Context enrichment timed out".to_string(),
+                        ))
+                        .message(format!("No attributes received within {:?}", self.timeout))
+                        .build())
+                }
+            },
+        };
+
+        let mut evaluation_context = context.evaluation_context.clone();
+        evaluation_context.merge_missing(&attributes);
+
+        Ok(Some(evaluation_context))
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{
+        provider::{MockFeatureProvider, ProviderMetadata, ProviderStatus, ResolutionDetails},
+        OpenFeature,
+    };
+
+    struct ProfileService {
+        delay: Duration,
+        lookups: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ContextEnricher for ProfileService {
+        async fn enrich(
+            &self,
+            _evaluation_context: &EvaluationContext,
+        ) -> Result<EvaluationContext, EvaluationError> {
+            self.lookups.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(self.delay).await;
+
+            Ok(EvaluationContext::default()
+                .with_custom_field("plan", "pro")
+                .with_custom_field("country", "FR"))
+        }
+    }
+
+    fn create_provider(expected_plan: Option<&'static str>) -> MockFeatureProvider {
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider.expect_shutdown().returning(|| ());
+        provider
+            .expect_metadata()
+            .return_const(ProviderMetadata::new("Test Provider"));
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_event_emitter().returning(|| None);
+        provider
+            .expect_resolve_bool_value()
+            .withf(move |_, context| {
+                context.custom_fields.get("plan") == expected_plan.map(Into::into).as_ref()
+                    && context.custom_fields.get("country") != Some(&"FR".into())
+            })
+            .return_const(Ok(ResolutionDetails::new(true)));
+        provider
+    }
+
+    #[tokio::test]
+    async fn enrich_and_cache() {
+        let mut api = OpenFeature::default();
+        api.set_provider(create_provider(Some("pro")))
+            .await
+            .unwrap();
+
+        let hook = std::sync::Arc::new(ContextEnrichmentHook::new(ProfileService {
+            delay: Duration::ZERO,
+            lookups: AtomicUsize::new(0),
+        }));
+        let client = api.create_client().with_hook(hook.clone());
+
+        let context = EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("country", "US");
+
+        for _ in 0..2 {
+            assert!(client
+                .get_bool_value("key", Some(&context), None)
+                .await
+                .unwrap());
+        }
+
+        assert_eq!(hook.enricher.lookups.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn skip_on_timeout() {
+        let mut api = OpenFeature::default();
+        api.set_provider(create_provider(None)).await.unwrap();
+
+        let client = api.create_client().with_hook(
+            ContextEnrichmentHook::new(ProfileService {
+                delay: Duration::from_secs(1),
+                lookups: AtomicUsize::new(0),
+            })
+            .with_timeout(Duration::from_millis(10)),
+        );
+
+        assert!(client.get_bool_value("key", None, None).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn fail_on_timeout() {
+        let mut api = OpenFeature::default();
+        api.set_provider(create_provider(None)).await.unwrap();
+
+        let client = api.create_client().with_hook(
+            ContextEnrichmentHook::new(ProfileService {
+                delay: Duration::from_secs(1),
+                lookups: AtomicUsize::new(0),
+            })
+            .with_timeout(Duration::from_millis(10))
+            .with_skip_on_timeout(false),
+        );
+
+        let error = client.get_bool_value("key", None, None).await.unwrap_err();
+        assert!(matches!(error.code, EvaluationErrorCode::General(_)));
+    }
+}
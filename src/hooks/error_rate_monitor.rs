@@ -0,0 +1,216 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use crate::{EvaluationDetails, EvaluationError, Value};
+
+use super::{Hook, HookContext};
+
+type AlertHandler = Box<dyn Fn(&ErrorRateAlert) + Send + Sync>;
+
+// ============================================================
+//  ErrorRateAlert
+// ============================================================
+
+/// The error rate of a flag crossing the threshold of an [`ErrorRateMonitor`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct ErrorRateAlert {
+    /// The name of the provider resolving the flag.
+    pub provider_name: String,
+
+    /// The key of the flag.
+    pub flag_key: String,
+
+    /// The ratio of failed evaluations within the window, from `0.0` to `1.0`.
+    pub error_rate: f64,
+
+    /// The number of evaluations within the window.
+    pub evaluation_count: usize,
+}
+
+// ============================================================
+//  ErrorRateMonitor
+// ============================================================
+
+/// A hook tracking the rolling error rate of every flag per provider, and calling a handler when
+/// it rises above a threshold, so that a broken flag backend is noticed before users do.
+///
+/// The handler is called once per crossing: the error rate has to fall back below the threshold
+/// before it is called again for the same flag.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use open_feature::{ErrorRateMonitor, OpenFeature};
+///
+/// # async fn example() {
+/// # let api = OpenFeature::singleton().await;
+/// let client = api.create_client().with_hook(
+///     ErrorRateMonitor::new(0.2, |alert| eprintln!("Flag {} is failing", alert.flag_key))
+///         .with_window(Duration::from_secs(300)),
+/// );
+/// # }
+/// ```
+pub struct ErrorRateMonitor {
+    threshold: f64,
+    window: Duration,
+    min_evaluations: usize,
+    handler: AlertHandler,
+    flags: Mutex<HashMap<(String, String), FlagOutcomes>>,
+}
+
+#[derive(Default)]
+struct FlagOutcomes {
+    outcomes: VecDeque<(Instant, bool)>,
+    alerting: bool,
+}
+
+impl ErrorRateMonitor {
+    /// The window the error rate is computed over by default.
+    pub const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+    /// The number of evaluations within the window required to alert by default.
+    pub const DEFAULT_MIN_EVALUATIONS: usize = 10;
+
+    /// Create a monitor calling `handler` when the error rate of a flag rises above `threshold`
+    /// (from `0.0` to `1.0`).
+    pub fn new<F>(threshold: f64, handler: F) -> Self
+    where
+        F: Fn(&ErrorRateAlert) + Send + Sync + 'static,
+    {
+        Self {
+            threshold,
+            window: Self::DEFAULT_WINDOW,
+            min_evaluations: Self::DEFAULT_MIN_EVALUATIONS,
+            handler: Box::new(handler),
+            flags: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the window the error rate is computed over.
+    #[must_use]
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set the number of evaluations within the window required to alert, so that a single
+    /// failure does not alert.
+    #[must_use]
+    pub fn with_min_evaluations(mut self, min_evaluations: usize) -> Self {
+        self.min_evaluations = min_evaluations;
+        self
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn record(&self, context: &HookContext<'_>, failed: bool) {
+        let now = Instant::now();
+        let provider_name = &context.provider_metadata.name;
+
+        let alert = {
+            let mut flags = self.flags.lock().unwrap();
+            let flag = flags
+                .entry((provider_name.clone(), context.flag_key.to_string()))
+                .or_default();
+
+            flag.outcomes.push_back((now, failed));
+            while flag
+                .outcomes
+                .front()
+                .map_or(false, |(at, _)| now.duration_since(*at) > self.window)
+            {
+                flag.outcomes.pop_front();
+            }
+
+            let evaluation_count = flag.outcomes.len();
+            let error_count = flag.outcomes.iter().filter(|(_, failed)| *failed).count();
+            let error_rate = error_count as f64 / evaluation_count as f64;
+
+            let above = evaluation_count >= self.min_evaluations && error_rate > self.threshold;
+            let crossed = above && !flag.alerting;
+            flag.alerting = above;
+
+            crossed.then(|| ErrorRateAlert {
+                provider_name: provider_name.clone(),
+                flag_key: context.flag_key.to_string(),
+                error_rate,
+                evaluation_count,
+            })
+        };
+
+        // Called without the lock, so that the handler may evaluate flags.
+        if let Some(alert) = alert {
+            (self.handler)(&alert);
+        }
+    }
+}
+
+#[async_trait]
+impl Hook for ErrorRateMonitor {
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        _details: &EvaluationDetails<Value>,
+    ) -> Result<(), EvaluationError> {
+        self.record(context, false);
+        Ok(())
+    }
+
+    async fn error<'a>(&self, context: &HookContext<'a>, _error: &EvaluationError) {
+        self.record(context, true);
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{flags, OpenFeature};
+
+    #[tokio::test]
+    async fn alert_once_per_crossing() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let alerts = Arc::new(Mutex::new(Vec::new()));
+        let client = api.create_client().with_hook(
+            ErrorRateMonitor::new(0.5, {
+                let alerts = alerts.clone();
+                move |alert| alerts.lock().unwrap().push(alert.clone())
+            })
+            .with_min_evaluations(4),
+        );
+
+        // 2 successes, then failures: alerting at 3 failures out of 5.
+        for _ in 0..2 {
+            client
+                .get_bool_value("checkout-v2", None, None)
+                .await
+                .unwrap();
+        }
+        for _ in 0..5 {
+            let _ = client.get_int_value("checkout-v2", None, None).await;
+        }
+
+        assert_eq!(
+            *alerts.lock().unwrap(),
+            vec![ErrorRateAlert {
+                provider_name: "In-memory Provider".to_string(),
+                flag_key: "checkout-v2".to_string(),
+                error_rate: 0.6,
+                evaluation_count: 5,
+            }]
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use metrics::{counter, histogram};
+
+use crate::{EvaluationContext, EvaluationError, EvaluationErrorCode};
+
+use super::{Hook, HookContext};
+
+/// The key of the hook data holding the time the evaluation started.
+const STARTED_AT_KEY: &str = "started_at";
+
+// ============================================================
+//  MetricsHook
+// ============================================================
+
+/// A hook recording flag evaluations through the [`metrics`] facade, exported by whichever
+/// recorder the application installed:
+///
+/// * `feature_flag_evaluations_total`, a counter of evaluations.
+/// * `feature_flag_evaluation_errors_total`, a counter of failed evaluations, also labeled by
+///   `error_code`.
+/// * `feature_flag_evaluation_duration_seconds`, a histogram of the latency of evaluations, from
+///   the `before` stage of the hook to its `finally` stage.
+///
+/// All of them are labeled by `flag_key` and `provider`.
+///
+/// ```
+/// use open_feature::{MetricsHook, OpenFeature};
+///
+/// # async fn example() {
+/// let api = OpenFeature::singleton().await;
+/// let client = api.create_client().with_hook(MetricsHook);
+/// # }
+/// ```
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MetricsHook;
+
+#[async_trait]
+impl Hook for MetricsHook {
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        context.hook_data.set(STARTED_AT_KEY, Instant::now());
+
+        Ok(None)
+    }
+
+    async fn error<'a>(&self, context: &HookContext<'a>, error: &EvaluationError) {
+        counter!(
+            "feature_flag_evaluation_errors_total",
+            "flag_key" => context.flag_key.to_string(),
+            "provider" => context.provider_metadata.name.clone(),
+            "error_code" => error_code_label(&error.code),
+        )
+        .increment(1);
+    }
+
+    async fn finally<'a>(&self, context: &HookContext<'a>) {
+        counter!(
+            "feature_flag_evaluations_total",
+            "flag_key" => context.flag_key.to_string(),
+            "provider" => context.provider_metadata.name.clone(),
+        )
+        .increment(1);
+
+        if let Some(started_at) = context.hook_data.get::<Instant>(STARTED_AT_KEY) {
+            histogram!(
+                "feature_flag_evaluation_duration_seconds",
+                "flag_key" => context.flag_key.to_string(),
+                "provider" => context.provider_metadata.name.clone(),
+            )
+            .record(started_at.elapsed().as_secs_f64());
+        }
+    }
+}
+
+/// Return the label of `code`, reporting unclassified errors as `GENERAL` rather than by their
+/// message, so that labels keep a low cardinality.
+fn error_code_label(code: &EvaluationErrorCode) -> String {
+    match code {
+        EvaluationErrorCode::General(_) => "GENERAL".to_string(),
+        code => code.to_string(),
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProviderError, ProviderErrorKind};
+
+    #[test]
+    fn error_code_labels() {
+        assert_eq!(
+            error_code_label(&EvaluationErrorCode::FlagNotFound),
+            "FLAG_NOT_FOUND"
+        );
+        assert_eq!(
+            error_code_label(&EvaluationErrorCode::General(
+                "Backend exploded".to_string()
+            )),
+            "GENERAL"
+        );
+        assert_eq!(
+            error_code_label(&EvaluationErrorCode::Provider(ProviderError::new(
+                ProviderErrorKind::Timeout,
+                "Timed out"
+            ))),
+            "GENERAL"
+        );
+    }
+}
@@ -0,0 +1,589 @@
+use std::{
+    collections::VecDeque,
+    io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use serde_json::{json, Value as JsonValue};
+use time::OffsetDateTime;
+use tokio::{io::AsyncWriteExt, task::JoinHandle, time::Instant};
+
+use crate::{
+    hash::salted_sha256_hex, provider::backoff, EvaluationDetails, EvaluationError,
+    EvaluationReason, Value,
+};
+
+use super::{Hook, HookContext};
+
+// ============================================================
+//  Exposure
+// ============================================================
+
+/// The record of a single flag evaluation, as sent to an [`ExposureSink`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Exposure {
+    /// The key of the evaluated flag.
+    pub flag_key: String,
+
+    /// The variant resolved, if any.
+    pub variant: Option<String>,
+
+    /// The reason of the resolution, `ERROR` for failed evaluations.
+    pub reason: Option<EvaluationReason>,
+
+    /// The time of the evaluation.
+    pub timestamp: OffsetDateTime,
+
+    /// The hex-encoded salted SHA-256 of the targeting key, if any, so that exposures of a
+    /// subject can be joined without the key leaving the process in clear.
+    pub targeting_key_hash: Option<String>,
+}
+
+impl Exposure {
+    /// Return the exposure as JSON, with the timestamp as a Unix timestamp.
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "flagKey": self.flag_key,
+            "variant": self.variant,
+            "reason": self.reason.as_ref().map(ToString::to_string),
+            "timestamp": self.timestamp.unix_timestamp(),
+            "targetingKeyHash": self.targeting_key_hash,
+        })
+    }
+}
+
+// ============================================================
+//  ExposureSink
+// ============================================================
+
+/// Where batches of exposures are flushed to, such as an analytics pipeline.
+#[async_trait]
+pub trait ExposureSink: Send + Sync + 'static {
+    /// Write `exposures`, oldest first. On failure, they are buffered again and retried with the
+    /// next flush.
+    async fn write(&self, exposures: &[Exposure]) -> io::Result<()>;
+}
+
+/// A sink appending exposures to a file, one JSON object per line.
+pub struct FileExposureSink {
+    path: PathBuf,
+}
+
+impl FileExposureSink {
+    /// Create a sink appending to the file at `path`, created if missing.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl ExposureSink for FileExposureSink {
+    async fn write(&self, exposures: &[Exposure]) -> io::Result<()> {
+        let mut lines = String::new();
+        for exposure in exposures {
+            lines.push_str(&exposure.to_json().to_string());
+            lines.push('\n');
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(lines.as_bytes()).await?;
+        file.flush().await
+    }
+}
+
+/// A sink posting exposures to an HTTP endpoint, as a JSON array.
+pub struct HttpExposureSink {
+    url: String,
+    headers: Vec<(String, String)>,
+    client: reqwest::Client,
+}
+
+impl HttpExposureSink {
+    /// The time after which a request is abandoned by default.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Create a sink posting to `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            headers: Vec::new(),
+            client: create_client(Self::DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// Send header `name` with `value`, such as an API key.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Abandon requests taking longer than `timeout`.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client = create_client(timeout);
+        self
+    }
+}
+
+fn create_client(timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .expect("The HTTP client can be built")
+}
+
+#[async_trait]
+impl ExposureSink for HttpExposureSink {
+    async fn write(&self, exposures: &[Exposure]) -> io::Result<()> {
+        let body: Vec<_> = exposures.iter().map(Exposure::to_json).collect();
+
+        let mut request = self.client.post(&self.url).json(&body);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+
+        request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map(|_| ())
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+    }
+}
+
+// ============================================================
+//  ExposureHook
+// ============================================================
+
+/// A hook recording every evaluation as an [`Exposure`], and flushing them in batches to an
+/// [`ExposureSink`], so that experiments can be analyzed by who saw which variant.
+///
+/// Exposures are buffered in memory, up to a capacity past which the oldest ones are dropped,
+/// and flushed in the background every flush interval, as soon as a batch is full, and on
+/// shutdown. Batches failing to write are buffered again, and background flushes are held off
+/// for the flush interval, doubled with every consecutive failure up to
+/// [`MAX_BACKOFF`](Self::MAX_BACKOFF).
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use open_feature::{ExposureHook, HttpExposureSink, OpenFeature};
+///
+/// # async fn example() {
+/// let mut api = OpenFeature::singleton_mut().await;
+/// api.add_hook(
+///     ExposureHook::new(
+///         HttpExposureSink::new("https://analytics.example.com/exposures"),
+///         "my-salt",
+///     )
+///     .with_flush_interval(Duration::from_secs(30)),
+/// );
+/// # }
+/// ```
+pub struct ExposureHook<S> {
+    buffer: Arc<ExposureBuffer<S>>,
+    salt: String,
+    capacity: usize,
+    batch_size: usize,
+    flush_interval: Duration,
+    flusher: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl<S: ExposureSink> ExposureHook<S> {
+    /// The number of exposures buffered by default.
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    /// The number of exposures written at once by default.
+    pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+    /// The interval exposures are flushed at by default.
+    pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// The longest background flushes are held off after failures of the sink.
+    pub const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+    /// Create a hook flushing exposures to `sink`, hashing targeting keys with `salt`, which
+    /// should be secret so that the keys cannot be recovered from the hashes by brute force.
+    pub fn new(sink: S, salt: impl Into<String>) -> Self {
+        Self {
+            buffer: Arc::new(ExposureBuffer {
+                sink,
+                exposures: Mutex::new(VecDeque::new()),
+                dropped: AtomicU64::new(0),
+                flushing: tokio::sync::Mutex::new(()),
+                flush_pending: AtomicBool::new(false),
+                retry: Mutex::new(None),
+            }),
+            salt: salt.into(),
+            capacity: Self::DEFAULT_CAPACITY,
+            batch_size: Self::DEFAULT_BATCH_SIZE,
+            flush_interval: Self::DEFAULT_FLUSH_INTERVAL,
+            flusher: Mutex::new(None),
+        }
+    }
+
+    /// Set the number of exposures buffered, past which the oldest ones are dropped.
+    #[must_use]
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Set the number of exposures written at once.
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set the interval exposures are flushed at.
+    #[must_use]
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    /// Write the buffered exposures to the sink now.
+    pub async fn flush(&self) {
+        self.buffer.flush(self.batch_size, self.capacity).await;
+    }
+
+    /// Return the number of exposures dropped so far, for want of capacity.
+    pub fn dropped(&self) -> u64 {
+        self.buffer.dropped.load(Ordering::Relaxed)
+    }
+
+    fn record(&self, context: &HookContext<'_>, variant: Option<String>, reason: EvaluationReason) {
+        let exposure = Exposure {
+            flag_key: context.flag_key.to_string(),
+            variant,
+            reason: Some(reason),
+            timestamp: OffsetDateTime::now_utc(),
+            targeting_key_hash: context.evaluation_context.targeting_key.as_deref().map(
+                |targeting_key| salted_sha256_hex(self.salt.as_bytes(), targeting_key.as_bytes()),
+            ),
+        };
+
+        let buffered = self.buffer.push(exposure, self.capacity);
+        self.start_flusher();
+
+        if buffered >= self.batch_size && !self.buffer.flush_pending.swap(true, Ordering::AcqRel) {
+            let buffer = self.buffer.clone();
+            let (batch_size, capacity) = (self.batch_size, self.capacity);
+            let flush_interval = self.flush_interval;
+
+            tokio::spawn(async move {
+                buffer
+                    .flush_with_backoff(batch_size, capacity, flush_interval)
+                    .await;
+                buffer.flush_pending.store(false, Ordering::Release);
+            });
+        }
+    }
+
+    /// Spawn the task flushing every flush interval, unless running. The task holds the buffer
+    /// weakly, so that it ends with the hook.
+    fn start_flusher(&self) {
+        let mut flusher = self.flusher.lock().unwrap();
+        if flusher.is_some() {
+            return;
+        }
+
+        let buffer = Arc::downgrade(&self.buffer);
+        let (batch_size, capacity) = (self.batch_size, self.capacity);
+        let flush_interval = self.flush_interval;
+
+        *flusher = Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(flush_interval);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                let Some(buffer) = Weak::upgrade(&buffer) else {
+                    return;
+                };
+                buffer
+                    .flush_with_backoff(batch_size, capacity, flush_interval)
+                    .await;
+            }
+        }));
+    }
+}
+
+#[async_trait]
+impl<S: ExposureSink> Hook for ExposureHook<S> {
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        details: &EvaluationDetails<Value>,
+    ) -> Result<(), EvaluationError> {
+        self.record(
+            context,
+            details.variant.clone(),
+            details.reason.clone().unwrap_or_default(),
+        );
+        Ok(())
+    }
+
+    async fn error<'a>(&self, context: &HookContext<'a>, _error: &EvaluationError) {
+        self.record(context, None, EvaluationReason::Error);
+    }
+
+    async fn shutdown(&self) {
+        if let Some(flusher) = self.flusher.lock().unwrap().take() {
+            flusher.abort();
+        }
+
+        self.flush().await;
+    }
+}
+
+impl<S> Drop for ExposureHook<S> {
+    fn drop(&mut self) {
+        if let Some(flusher) = self.flusher.lock().unwrap().take() {
+            flusher.abort();
+        }
+    }
+}
+
+// ============================================================
+//  ExposureBuffer
+// ============================================================
+
+/// The ring buffer of exposures, shared with the tasks flushing it.
+struct ExposureBuffer<S> {
+    sink: S,
+    exposures: Mutex<VecDeque<Exposure>>,
+    dropped: AtomicU64,
+    /// Held while flushing, so that batches are written in order.
+    flushing: tokio::sync::Mutex<()>,
+    /// Set while a flush of a full batch is pending, so that there is at most one.
+    flush_pending: AtomicBool,
+    /// The consecutive failures of the sink, and the time background flushes are held off
+    /// until, after a failure.
+    retry: Mutex<Option<(u32, Instant)>>,
+}
+
+impl<S: ExposureSink> ExposureBuffer<S> {
+    /// Buffer `exposure`, dropping the oldest exposures past `capacity`, and return the number
+    /// of exposures buffered.
+    fn push(&self, exposure: Exposure, capacity: usize) -> usize {
+        let mut exposures = self.exposures.lock().unwrap();
+        exposures.push_back(exposure);
+        self.truncate(&mut exposures, capacity);
+
+        exposures.len()
+    }
+
+    /// Write the buffered exposures in batches of `batch_size`, stopping at the first failure.
+    /// Return `false` on failure.
+    async fn flush(&self, batch_size: usize, capacity: usize) -> bool {
+        let _flushing = self.flushing.lock().await;
+
+        loop {
+            let batch: Vec<_> = {
+                let mut exposures = self.exposures.lock().unwrap();
+                let len = exposures.len().min(batch_size);
+                exposures.drain(..len).collect()
+            };
+            if batch.is_empty() {
+                return true;
+            }
+
+            if self.sink.write(&batch).await.is_err() {
+                let mut exposures = self.exposures.lock().unwrap();
+                for exposure in batch.into_iter().rev() {
+                    exposures.push_front(exposure);
+                }
+                self.truncate(&mut exposures, capacity);
+                return false;
+            }
+        }
+    }
+
+    /// Flush, unless held off after a failure, holding further flushes off for `flush_interval`
+    /// doubled with every consecutive failure.
+    async fn flush_with_backoff(
+        &self,
+        batch_size: usize,
+        capacity: usize,
+        flush_interval: Duration,
+    ) {
+        let held_off = self
+            .retry
+            .lock()
+            .unwrap()
+            .map_or(false, |(_, retry_at)| Instant::now() < retry_at);
+        if held_off {
+            return;
+        }
+
+        let written = self.flush(batch_size, capacity).await;
+
+        let mut retry = self.retry.lock().unwrap();
+        *retry = if written {
+            None
+        } else {
+            let failures = retry.map_or(0, |(failures, _)| failures + 1);
+            let delay = backoff(flush_interval, failures, ExposureHook::<S>::MAX_BACKOFF);
+            Some((failures, Instant::now() + delay))
+        };
+    }
+
+    fn truncate(&self, exposures: &mut VecDeque<Exposure>, capacity: usize) {
+        while exposures.len() > capacity {
+            exposures.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{flags, OpenFeature};
+
+    #[derive(Clone, Default)]
+    struct MemorySink {
+        batches: Arc<Mutex<Vec<Vec<Exposure>>>>,
+        failing: Arc<AtomicBool>,
+        attempts: Arc<AtomicU64>,
+    }
+
+    #[async_trait]
+    impl ExposureSink for MemorySink {
+        async fn write(&self, exposures: &[Exposure]) -> io::Result<()> {
+            self.attempts.fetch_add(1, Ordering::Relaxed);
+
+            if self.failing.load(Ordering::Relaxed) {
+                return Err(io::Error::new(io::ErrorKind::Other, "unavailable"));
+            }
+
+            self.batches.lock().unwrap().push(exposures.to_vec());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn record_exposures() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let sink = MemorySink::default();
+        let hook = Arc::new(ExposureHook::new(sink.clone(), "salt").with_batch_size(10));
+        let client = api.create_client().with_hook(hook.clone());
+
+        let context = crate::EvaluationContext::default().with_targeting_key("alice");
+        client
+            .get_bool_value("checkout-v2", Some(&context), None)
+            .await
+            .unwrap();
+        let _ = client.get_int_value("checkout-v2", None, None).await;
+
+        sink.failing.store(true, Ordering::Relaxed);
+        hook.flush().await;
+        assert!(sink.batches.lock().unwrap().is_empty());
+
+        sink.failing.store(false, Ordering::Relaxed);
+        hook.shutdown().await;
+
+        let batches = sink.batches.lock().unwrap();
+        assert_eq!(batches.len(), 1);
+
+        let exposures = &batches[0];
+        assert_eq!(exposures[0].flag_key, "checkout-v2");
+        assert_eq!(
+            exposures[0].targeting_key_hash,
+            Some(salted_sha256_hex(b"salt", b"alice"))
+        );
+        assert_eq!(exposures[1].reason, Some(EvaluationReason::Error));
+        assert_eq!(exposures[1].targeting_key_hash, None);
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_past_capacity() {
+        let sink = MemorySink::default();
+        let hook = ExposureHook::new(sink.clone(), "salt")
+            .with_capacity(2)
+            .with_batch_size(10);
+
+        let exposure = |flag_key: &str| Exposure {
+            flag_key: flag_key.to_string(),
+            variant: None,
+            reason: None,
+            timestamp: OffsetDateTime::UNIX_EPOCH,
+            targeting_key_hash: None,
+        };
+        for flag_key in ["a", "b", "c"] {
+            hook.buffer.push(exposure(flag_key), hook.capacity);
+        }
+        hook.flush().await;
+
+        assert_eq!(hook.dropped(), 1);
+        assert_eq!(
+            *sink.batches.lock().unwrap(),
+            vec![vec![exposure("b"), exposure("c")]]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn back_off_after_failures() {
+        let sink = MemorySink::default();
+        let hook = ExposureHook::new(sink.clone(), "salt");
+        let interval = Duration::from_secs(10);
+
+        hook.buffer.push(
+            Exposure {
+                flag_key: "checkout-v2".to_string(),
+                variant: None,
+                reason: None,
+                timestamp: OffsetDateTime::UNIX_EPOCH,
+                targeting_key_hash: None,
+            },
+            hook.capacity,
+        );
+        sink.failing.store(true, Ordering::Relaxed);
+
+        let flush = || hook.buffer.flush_with_backoff(1, 10, interval);
+        let attempts = || sink.attempts.load(Ordering::Relaxed);
+
+        flush().await;
+        flush().await;
+        assert_eq!(attempts(), 1);
+
+        tokio::time::advance(interval).await;
+        flush().await;
+        assert_eq!(attempts(), 2);
+
+        // Held off twice as long after a second failure.
+        tokio::time::advance(interval).await;
+        flush().await;
+        assert_eq!(attempts(), 2);
+
+        tokio::time::advance(interval).await;
+        sink.failing.store(false, Ordering::Relaxed);
+        flush().await;
+        flush().await;
+        assert_eq!(attempts(), 3);
+        assert_eq!(sink.batches.lock().unwrap().len(), 1);
+        assert!(hook.buffer.retry.lock().unwrap().is_none());
+    }
+}
@@ -0,0 +1,292 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    provider::{FlagType, ProviderMetadata},
+    ClientMetadata, EvaluationContext, EvaluationDetails, EvaluationError, Value,
+};
+
+// ============================================================
+//  Hook
+// ============================================================
+
+/// Hooks are a mechanism whereby application developers can add arbitrary behavior to flag
+/// evaluation. They operate similarly to middleware in many web frameworks.
+///
+/// Hooks add their logic at any of the following points of flag evaluation:
+/// * `before`: immediately before flag evaluation.
+/// * `after`: immediately after successful flag evaluation.
+/// * `error`: immediately after an unsuccessful flag evaluation.
+/// * `finally`: unconditionally after flag evaluation.
+///
+/// All the stages have a default implementation doing nothing, so a hook only implements the
+/// stages it needs.
+///
+/// See the [spec](https://openfeature.dev/specification/sections/hooks).
+#[cfg_attr(feature = "test-util", mockall::automock)]
+#[async_trait]
+pub trait Hook: Send + Sync + 'static {
+    /// Run before flag evaluation.
+    ///
+    /// The returned evaluation context, if any, is merged into the existing one with higher
+    /// precedence, and is passed to subsequent stages and to the provider.
+    ///
+    /// Returning an error stops the evaluation: the provider is not called, and the `error` and
+    /// `finally` stages are run.
+    #[allow(unused_variables)]
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        Ok(None)
+    }
+
+    /// Run after successful flag evaluation with the resolved details.
+    ///
+    /// Returning an error turns the evaluation into an error, and the `error` stage is run.
+    #[allow(unused_variables)]
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        details: &EvaluationDetails<Value>,
+    ) -> Result<(), EvaluationError> {
+        Ok(())
+    }
+
+    /// Run when an error occurred in the `before` or `after` stages, or in the provider.
+    #[allow(unused_variables)]
+    async fn error<'a>(&self, context: &HookContext<'a>, error: &EvaluationError) {}
+
+    /// Run unconditionally after flag evaluation.
+    #[allow(unused_variables)]
+    async fn finally<'a>(&self, context: &HookContext<'a>) {}
+
+    /// Flush any data the hook buffers, such as telemetry or exposure events, and release its
+    /// resources. Run once by [`OpenFeature::shutdown`](crate::OpenFeature::shutdown) for the
+    /// hooks added to the API.
+    async fn shutdown(&self) {}
+
+    /// Return the name of the hook, as shown in an [`EvaluationTrace`](crate::EvaluationTrace).
+    /// Default to the name of the type.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+// ============================================================
+//  HookContext
+// ============================================================
+
+/// The context passed to every stage of a [`Hook`].
+#[derive(Clone, Copy, Debug)]
+pub struct HookContext<'a> {
+    /// The key of evaluated flag.
+    pub flag_key: &'a str,
+
+    /// The type of evaluated flag.
+    pub flag_type: FlagType,
+
+    /// The evaluation context, including the changes made by `before` stages run so far.
+    pub evaluation_context: &'a EvaluationContext,
+
+    /// The metadata of the client evaluating the flag.
+    pub client_metadata: &'a ClientMetadata,
+
+    /// The metadata of the provider resolving the flag.
+    pub provider_metadata: &'a ProviderMetadata,
+
+    /// The data of the hook being run, shared by its stages during a single evaluation.
+    pub hook_data: &'a HookData,
+
+    /// The hints given by the caller of the evaluation.
+    pub hook_hints: &'a HookHints,
+}
+
+// ============================================================
+//  HookData
+// ============================================================
+
+/// Arbitrary data a hook passes from a stage to the next ones of the same evaluation, such as
+/// the time the evaluation started. Every hook has its own data for every evaluation.
+///
+/// See the [spec](https://openfeature.dev/specification/sections/hooks#46-hook-data).
+#[derive(Default, Debug)]
+pub struct HookData(Mutex<HashMap<String, Box<dyn Any + Send + Sync>>>);
+
+impl HookData {
+    /// Set `key` to `value`, replacing its current value if any.
+    pub fn set<T: Any + Send + Sync>(&self, key: impl Into<String>, value: T) {
+        self.0.lock().unwrap().insert(key.into(), Box::new(value));
+    }
+
+    /// Return a clone of the value of `key`, or `None` if it is not set or is not a `T`.
+    pub fn get<T: Any + Clone>(&self, key: &str) -> Option<T> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(key)
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+}
+
+// ============================================================
+//  HookHints
+// ============================================================
+
+/// Immutable hints given to all the hooks of an evaluation through
+/// [`EvaluationOptions`](crate::EvaluationOptions), so that callers can tune the behavior of hooks
+/// for a single evaluation, such as skipping logging.
+///
+/// See the [spec](https://openfeature.dev/specification/sections/hooks#42-hook-hints).
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct HookHints(HashMap<String, Value>);
+
+impl HookHints {
+    /// Set the hint `key` to `value`.
+    #[must_use]
+    pub fn with_hint(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.0.insert(key.into(), value.into());
+        self
+    }
+
+    /// Return the value of the hint `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+}
+
+// ============================================================
+//  HookStage
+// ============================================================
+
+/// A stage of the hook pipeline.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum HookStage {
+    /// The `before` stage.
+    Before,
+
+    /// The `after` stage.
+    After,
+
+    /// The `error` stage.
+    Error,
+
+    /// The `finally` stage.
+    Finally,
+}
+
+impl fmt::Display for HookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Before => "before",
+            Self::After => "after",
+            Self::Error => "error",
+            Self::Finally => "finally",
+        })
+    }
+}
+
+/// A shared hook, so that the same instance can be registered at multiple places.
+#[async_trait]
+impl<T: Hook + ?Sized> Hook for Arc<T> {
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        self.as_ref().before(context).await
+    }
+
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        details: &EvaluationDetails<Value>,
+    ) -> Result<(), EvaluationError> {
+        self.as_ref().after(context, details).await
+    }
+
+    async fn error<'a>(&self, context: &HookContext<'a>, error: &EvaluationError) {
+        self.as_ref().error(context, error).await;
+    }
+
+    async fn finally<'a>(&self, context: &HookContext<'a>) {
+        self.as_ref().finally(context).await;
+    }
+
+    async fn shutdown(&self) {
+        self.as_ref().shutdown().await;
+    }
+
+    fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{flags, OpenFeature};
+
+    /// Counts its `before` stages, and checks that `finally` sees the count of the same
+    /// evaluation.
+    #[derive(Default)]
+    struct CountingHook {
+        evaluations: AtomicUsize,
+        checked: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Hook for CountingHook {
+        async fn before<'a>(
+            &self,
+            context: &HookContext<'a>,
+        ) -> Result<Option<EvaluationContext>, EvaluationError> {
+            let evaluation = self.evaluations.fetch_add(1, Ordering::SeqCst);
+            context.hook_data.set("evaluation", evaluation);
+
+            Ok(None)
+        }
+
+        async fn finally<'a>(&self, context: &HookContext<'a>) {
+            assert_eq!(context.hook_data.get::<String>("evaluation"), None);
+
+            if context.hook_data.get::<usize>("evaluation")
+                == Some(self.checked.load(Ordering::SeqCst))
+            {
+                self.checked.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn hook_data() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let hook = Arc::new(CountingHook::default());
+        let client = api.create_client().with_hook(hook.clone());
+
+        for _ in 0..3 {
+            client
+                .get_bool_value("checkout-v2", None, None)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(hook.checked.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use tracing::{debug, field, info, warn};
+
+use crate::{EvaluationContext, EvaluationDetails, EvaluationError, Value};
+
+use super::{Hook, HookContext};
+
+// ============================================================
+//  LoggingHook
+// ============================================================
+
+/// A hook emitting a structured [`tracing`] event for every stage of an evaluation:
+///
+/// * A `DEBUG` event before the evaluation.
+/// * An `INFO` event after a successful evaluation, with the variant and reason.
+/// * A `WARN` event after a failed evaluation, with the error code and message.
+///
+/// All of them carry the `flag_key` and `provider` fields. Resolved values are never logged, and
+/// the evaluation context is only logged when enabled with
+/// [`with_evaluation_context`](Self::with_evaluation_context), as it often holds personal data.
+///
+/// ```
+/// use open_feature::{LoggingHook, OpenFeature};
+///
+/// # async fn example() {
+/// let api = OpenFeature::singleton().await;
+/// let client = api
+///     .create_client()
+///     .with_hook(LoggingHook::default().with_evaluation_context(true));
+/// # }
+/// ```
+#[derive(Clone, Copy, Default, Debug)]
+pub struct LoggingHook {
+    include_evaluation_context: bool,
+}
+
+impl LoggingHook {
+    /// Set whether events include the evaluation context.
+    #[must_use]
+    pub fn with_evaluation_context(mut self, include_evaluation_context: bool) -> Self {
+        self.include_evaluation_context = include_evaluation_context;
+        self
+    }
+
+    fn evaluation_context<'a>(
+        self,
+        context: &HookContext<'a>,
+    ) -> Option<field::DebugValue<&'a EvaluationContext>> {
+        self.include_evaluation_context
+            .then(|| field::debug(context.evaluation_context))
+    }
+}
+
+#[async_trait]
+impl Hook for LoggingHook {
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        debug!(
+            flag_key = context.flag_key,
+            flag_type = ?context.flag_type,
+            provider = %context.provider_metadata.name,
+            evaluation_context = self.evaluation_context(context),
+            "Evaluating flag"
+        );
+
+        Ok(None)
+    }
+
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        details: &EvaluationDetails<Value>,
+    ) -> Result<(), EvaluationError> {
+        let reason = details.reason.as_ref().map(ToString::to_string);
+
+        info!(
+            flag_key = context.flag_key,
+            provider = %context.provider_metadata.name,
+            variant = details.variant.as_deref(),
+            reason = reason.as_deref(),
+            evaluation_context = self.evaluation_context(context),
+            "Flag evaluated"
+        );
+
+        Ok(())
+    }
+
+    async fn error<'a>(&self, context: &HookContext<'a>, error: &EvaluationError) {
+        warn!(
+            flag_key = context.flag_key,
+            provider = %context.provider_metadata.name,
+            error_code = %error.code,
+            error_message = error.message.as_deref(),
+            evaluation_context = self.evaluation_context(context),
+            "Flag evaluation failed"
+        );
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{flags, OpenFeature};
+
+    #[tokio::test]
+    async fn evaluate_without_subscriber() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let client = api
+            .create_client()
+            .with_hook(LoggingHook::default().with_evaluation_context(true));
+
+        assert!(client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+        assert!(client.get_int_value("missing", None, None).await.is_err());
+    }
+}
@@ -0,0 +1,43 @@
+/// Hook trait and its context.
+mod hook;
+#[cfg(feature = "test-util")]
+pub use hook::MockHook;
+pub use hook::{Hook, HookContext, HookData, HookHints, HookStage};
+
+/// Hook writing a tamper-evident log of evaluations.
+#[cfg(feature = "audit")]
+mod audit_log;
+#[cfg(feature = "audit")]
+pub use audit_log::AuditLog;
+
+/// Hook enriching evaluation contexts from external services.
+mod context_enrichment;
+pub use context_enrichment::{ContextEnricher, ContextEnrichmentHook};
+
+/// Hook recording evaluations on OpenTelemetry spans.
+#[cfg(feature = "opentelemetry")]
+mod open_telemetry;
+#[cfg(feature = "opentelemetry")]
+pub use open_telemetry::OpenTelemetryHook;
+
+/// Hook emitting structured `tracing` events.
+#[cfg(feature = "tracing")]
+mod logging;
+#[cfg(feature = "tracing")]
+pub use logging::LoggingHook;
+
+/// Hook recording evaluation metrics.
+#[cfg(feature = "metrics")]
+mod evaluation_metrics;
+#[cfg(feature = "metrics")]
+pub use evaluation_metrics::MetricsHook;
+
+/// Hook recording flag exposures for analytics.
+#[cfg(feature = "analytics")]
+mod exposure;
+#[cfg(feature = "analytics")]
+pub use exposure::{Exposure, ExposureHook, ExposureSink, FileExposureSink, HttpExposureSink};
+
+/// Hook alerting on rising error rates.
+mod error_rate_monitor;
+pub use error_rate_monitor::{ErrorRateAlert, ErrorRateMonitor};
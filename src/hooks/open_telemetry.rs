@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use opentelemetry::{trace::get_active_span, KeyValue};
+
+use crate::{EvaluationDetails, EvaluationError, Value};
+
+use super::{Hook, HookContext};
+
+/// The name of the span event recorded for every successful evaluation.
+const EVENT_NAME: &str = "feature_flag";
+
+const KEY_ATTRIBUTE: &str = "feature_flag.key";
+const PROVIDER_NAME_ATTRIBUTE: &str = "feature_flag.provider_name";
+const VARIANT_ATTRIBUTE: &str = "feature_flag.variant";
+
+// ============================================================
+//  OpenTelemetryHook
+// ============================================================
+
+/// A hook recording flag evaluations on the active OpenTelemetry span, following the
+/// [semantic conventions](https://opentelemetry.io/docs/specs/semconv/feature-flags/).
+///
+/// Every successful evaluation adds a `feature_flag` event with the `feature_flag.key`,
+/// `feature_flag.provider_name` and `feature_flag.variant` attributes. The variant falls back to
+/// the resolved value when the provider does not report one, except for arrays and structs. Failed
+/// evaluations record their error on the span.
+///
+/// ```
+/// use open_feature::{OpenFeature, OpenTelemetryHook};
+///
+/// # async fn example() {
+/// let api = OpenFeature::singleton().await;
+/// let client = api.create_client().with_hook(OpenTelemetryHook);
+/// # }
+/// ```
+#[derive(Clone, Copy, Default, Debug)]
+pub struct OpenTelemetryHook;
+
+#[async_trait]
+impl Hook for OpenTelemetryHook {
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        details: &EvaluationDetails<Value>,
+    ) -> Result<(), EvaluationError> {
+        get_active_span(|span| span.add_event(EVENT_NAME, event_attributes(context, details)));
+
+        Ok(())
+    }
+
+    async fn error<'a>(&self, _context: &HookContext<'a>, error: &EvaluationError) {
+        get_active_span(|span| span.record_error(error));
+    }
+}
+
+fn event_attributes(
+    context: &HookContext<'_>,
+    details: &EvaluationDetails<Value>,
+) -> Vec<KeyValue> {
+    let mut attributes = vec![
+        KeyValue::new(KEY_ATTRIBUTE, context.flag_key.to_string()),
+        KeyValue::new(
+            PROVIDER_NAME_ATTRIBUTE,
+            context.provider_metadata.name.clone(),
+        ),
+    ];
+
+    let variant = details.variant.clone().or_else(|| match &details.value {
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Int(value) => Some(value.to_string()),
+        Value::Float(value) => Some(value.to_string()),
+        Value::String(value) => Some(value.clone()),
+        Value::Array(_) | Value::Struct(_) => None,
+    });
+
+    if let Some(variant) = variant {
+        attributes.push(KeyValue::new(VARIANT_ATTRIBUTE, variant));
+    }
+
+    attributes
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        flags,
+        hooks::{HookData, HookHints},
+        provider::{FlagType, ProviderMetadata},
+        ClientMetadata, EvaluationContext, FlagMetadata, OpenFeature,
+    };
+
+    #[test]
+    fn attributes() {
+        let evaluation_context = EvaluationContext::default();
+        let client_metadata = ClientMetadata {
+            name: "checkout".to_string(),
+        };
+        let provider_metadata = ProviderMetadata::new("In Memory Provider");
+        let context = HookContext {
+            flag_key: "tier",
+            flag_type: FlagType::String,
+            evaluation_context: &evaluation_context,
+            client_metadata: &client_metadata,
+            provider_metadata: &provider_metadata,
+            hook_data: &HookData::default(),
+            hook_hints: &HookHints::default(),
+        };
+
+        let details = EvaluationDetails {
+            flag_key: "tier".to_string(),
+            value: Value::String("gold".to_string()),
+            reason: None,
+            variant: None,
+            flag_metadata: FlagMetadata::default(),
+        };
+
+        assert_eq!(
+            event_attributes(&context, &details),
+            vec![
+                KeyValue::new("feature_flag.key", "tier"),
+                KeyValue::new("feature_flag.provider_name", "In Memory Provider"),
+                KeyValue::new("feature_flag.variant", "gold"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn evaluate_without_active_span() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! { "checkout-v2" => bool: true })
+            .await
+            .unwrap();
+
+        let client = api.create_client().with_hook(OpenTelemetryHook);
+
+        assert!(client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+    }
+}
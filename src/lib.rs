@@ -25,10 +25,33 @@ pub use api::*;
 mod evaluation;
 pub use evaluation::*;
 
+/// Hooks related.
+mod hooks;
+pub use hooks::*;
+
+/// Hashing shared by providers and hooks.
+mod hash;
+
+/// Derive macros for [`FromValue`] and [`IntoValue`].
+#[cfg(feature = "derive")]
+pub use open_feature_derive::{FromValue, IntoValue};
+
+// Let the derive macros refer to this crate by name in its own tests.
+#[cfg(test)]
+extern crate self as open_feature;
+
 /// Feature provider related.
 pub mod provider;
 pub use async_trait::async_trait;
 
+/// Integrations with web frameworks.
+#[cfg(any(feature = "actix", feature = "tower"))]
+pub mod middleware;
+
+/// Utilities for testing code that evaluates flags.
+#[cfg(feature = "test-util")]
+pub mod testing;
+
 /// Optional support for [`serde_json::Value`].
 #[cfg(feature = "serde_json")]
 pub mod serde_json;
@@ -0,0 +1,209 @@
+use std::{
+    future::{ready, Future, Ready},
+    pin::Pin,
+    sync::Arc,
+};
+
+use actix_web::{
+    dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    error::ErrorInternalServerError,
+    Error, FromRequest, HttpMessage, HttpRequest,
+};
+
+use crate::{Client, TransactionContext};
+
+use super::{Flags, HeaderContext};
+
+// ============================================================
+//  OpenFeatureMiddleware
+// ============================================================
+
+/// An actix-web middleware deriving the evaluation context of every request from its headers,
+/// running the request within a [`TransactionContext`] scope of it, and adding [`Flags`] to its
+/// extensions, as the tower layer does.
+///
+/// Handlers take the flags as an extractor:
+///
+/// ```no_run
+/// use actix_web::{web, App};
+/// use open_feature::{
+///     middleware::{Flags, OpenFeatureMiddleware},
+///     OpenFeature,
+/// };
+///
+/// # async fn example() {
+/// # let api = OpenFeature::singleton().await;
+/// let app = App::new()
+///     .wrap(OpenFeatureMiddleware::new(api.create_client()))
+///     .route("/checkout", web::get().to(checkout));
+///
+/// async fn checkout(flags: Flags) -> String {
+///     // The evaluation includes the targeting key, locale and tenant of the request.
+///     let enabled = flags.get_bool_value("checkout-v2", None, None).await;
+///     // ...
+/// #   String::new()
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct OpenFeatureMiddleware {
+    client: Arc<Client>,
+    headers: Arc<HeaderContext>,
+}
+
+impl OpenFeatureMiddleware {
+    /// Create a middleware handing `client` to the requests, with the default
+    /// [`HeaderContext`].
+    pub fn new(client: Client) -> Self {
+        Self {
+            client: Arc::new(client),
+            headers: Arc::default(),
+        }
+    }
+
+    /// Derive the evaluation context of the requests with `headers`.
+    #[must_use]
+    pub fn with_headers(mut self, headers: HeaderContext) -> Self {
+        self.headers = Arc::new(headers);
+        self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for OpenFeatureMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = OpenFeatureMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, ()>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(OpenFeatureMiddlewareService {
+            service,
+            client: self.client.clone(),
+            headers: self.headers.clone(),
+        }))
+    }
+}
+
+// ============================================================
+//  OpenFeatureMiddlewareService
+// ============================================================
+
+/// The service wrapped by an [`OpenFeatureMiddleware`].
+pub struct OpenFeatureMiddlewareService<S> {
+    service: S,
+    client: Arc<Client>,
+    headers: Arc<HeaderContext>,
+}
+
+impl<S, B> Service<ServiceRequest> for OpenFeatureMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<ServiceResponse<B>, Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let context = self.headers.context(|name| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        });
+
+        request
+            .extensions_mut()
+            .insert(Flags::new(self.client.clone(), context.clone()));
+
+        Box::pin(TransactionContext::scope(
+            context,
+            self.service.call(request),
+        ))
+    }
+}
+
+/// Take the [`Flags`] of the request in handlers, which requires an [`OpenFeatureMiddleware`].
+impl FromRequest for Flags {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    fn from_request(request: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            request
+                .extensions()
+                .get::<Flags>()
+                .cloned()
+                .ok_or_else(|| ErrorInternalServerError("The OpenFeature middleware is missing")),
+        )
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test, web, App};
+
+    use super::*;
+    use crate::{
+        provider::{InMemoryFlag, InMemoryProvider},
+        OpenFeature,
+    };
+
+    async fn greet(flags: Flags) -> String {
+        flags
+            .get_string_value("greeting", None, None)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn evaluate_with_request_context() {
+        let provider = InMemoryProvider::default().with_flag(
+            "greeting",
+            InMemoryFlag::new("hello")
+                .with_variant("hello", "Hello")
+                .with_variant("bonjour", "Bonjour")
+                .with_resolver(|context| {
+                    let locale = context.custom_fields.get("locale")?.as_str()?;
+                    locale.starts_with("fr").then(|| "bonjour".to_string())
+                }),
+        );
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider).await.unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .wrap(OpenFeatureMiddleware::new(api.create_client()))
+                .route("/", web::get().to(greet)),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .insert_header(("Accept-Language", "fr-FR"))
+            .to_request();
+        assert_eq!(test::call_and_read_body(&app, request).await, "Bonjour");
+
+        let request = test::TestRequest::get().to_request();
+        assert_eq!(test::call_and_read_body(&app, request).await, "Hello");
+    }
+
+    #[tokio::test]
+    async fn reject_without_middleware() {
+        let app = test::init_service(App::new().route("/", web::get().to(greet))).await;
+
+        let response = test::call_service(&app, test::TestRequest::get().to_request()).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
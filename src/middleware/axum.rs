@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use axum_core::extract::FromRequestParts;
+use http::{request::Parts, StatusCode};
+
+use super::Flags;
+
+/// Take the [`Flags`] of the request in axum handlers, which requires an
+/// [`OpenFeatureLayer`](super::OpenFeatureLayer).
+///
+/// ```no_run
+/// use open_feature::middleware::Flags;
+///
+/// async fn checkout(flags: Flags) -> String {
+///     // The evaluation includes the targeting key, locale and tenant of the request.
+///     let enabled = flags.get_bool_value("checkout-v2", None, None).await;
+///     // ...
+/// #   String::new()
+/// }
+/// ```
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Flags {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Flags>().cloned().ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "The OpenFeature layer is missing",
+        ))
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use http::Request;
+
+    use super::*;
+    use crate::{EvaluationContext, OpenFeature};
+
+    #[tokio::test]
+    async fn extract_flags() {
+        let (mut parts, ()) = Request::builder().body(()).unwrap().into_parts();
+
+        let error = Flags::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+        assert_eq!(error.0, StatusCode::INTERNAL_SERVER_ERROR);
+
+        let context = EvaluationContext::default().with_targeting_key("alice");
+        parts.extensions.insert(Flags::new(
+            Arc::new(OpenFeature::default().create_client()),
+            context.clone(),
+        ));
+
+        let flags = Flags::from_request_parts(&mut parts, &()).await.unwrap();
+        assert_eq!(*flags.context(), context);
+    }
+}
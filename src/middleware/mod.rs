@@ -0,0 +1,204 @@
+use std::{fmt, ops::Deref, sync::Arc};
+
+use crate::{Client, EvaluationContext};
+
+/// An actix-web middleware.
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "actix")]
+pub use self::actix::{OpenFeatureMiddleware, OpenFeatureMiddlewareService};
+
+/// The axum extractor of [`Flags`].
+#[cfg(feature = "axum")]
+mod axum;
+
+/// A tower layer for servers built on tower, such as axum.
+#[cfg(feature = "tower")]
+mod tower;
+#[cfg(feature = "tower")]
+pub use self::tower::{OpenFeatureLayer, OpenFeatureService};
+
+// ============================================================
+//  HeaderContext
+// ============================================================
+
+/// Derives the evaluation context of a request from its headers.
+///
+/// By default, the targeting key is read from `X-User-Id`, the `locale` attribute from the
+/// preferred language of `Accept-Language`, and the `tenant` attribute from `X-Tenant-Id`.
+/// Missing headers and headers that are not valid strings are left out.
+#[derive(Clone, Debug)]
+pub struct HeaderContext {
+    targeting_key: Option<String>,
+    locale: Option<String>,
+    fields: Vec<(String, String)>,
+}
+
+impl Default for HeaderContext {
+    fn default() -> Self {
+        Self {
+            targeting_key: Some("x-user-id".to_string()),
+            locale: Some("accept-language".to_string()),
+            fields: vec![("x-tenant-id".to_string(), "tenant".to_string())],
+        }
+    }
+}
+
+impl HeaderContext {
+    /// Create a mapping reading no header.
+    pub fn empty() -> Self {
+        Self {
+            targeting_key: None,
+            locale: None,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Read the targeting key from header `name`.
+    #[must_use]
+    pub fn with_targeting_key(mut self, name: impl Into<String>) -> Self {
+        self.targeting_key = Some(name.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Read the `locale` attribute from the preferred language of header `name`, in the format
+    /// of `Accept-Language`.
+    #[must_use]
+    pub fn with_locale(mut self, name: impl Into<String>) -> Self {
+        self.locale = Some(name.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Read attribute `field` from header `name`.
+    #[must_use]
+    pub fn with_field(mut self, name: impl Into<String>, field: impl Into<String>) -> Self {
+        self.fields
+            .push((name.into().to_ascii_lowercase(), field.into()));
+        self
+    }
+
+    /// Return the evaluation context of a request, whose header values are returned by
+    /// `header` given their lowercase name.
+    pub fn context<'a>(&self, header: impl Fn(&str) -> Option<&'a str>) -> EvaluationContext {
+        let mut context = EvaluationContext::default();
+
+        if let Some(targeting_key) = self.targeting_key.as_deref().and_then(&header) {
+            context.targeting_key = Some(targeting_key.to_string());
+        }
+
+        let locale = self
+            .locale
+            .as_deref()
+            .and_then(&header)
+            .and_then(preferred_language);
+        if let Some(locale) = locale {
+            context.add_custom_field("locale", locale);
+        }
+
+        for (name, field) in &self.fields {
+            if let Some(value) = header(name) {
+                context.add_custom_field(field, value);
+            }
+        }
+
+        context
+    }
+}
+
+/// Return the language listed first in `accept_language`, unless any language is accepted.
+fn preferred_language(accept_language: &str) -> Option<&str> {
+    let language = accept_language.split(',').next()?;
+    let language = language.split(';').next()?.trim();
+
+    (!language.is_empty() && language != "*").then_some(language)
+}
+
+// ============================================================
+//  Flags
+// ============================================================
+
+/// The client of a request, with the evaluation context derived from the request.
+///
+/// Middleware add it to the extensions of the request, and run the request within a
+/// [`TransactionContext`](crate::TransactionContext) scope of the context, so that the
+/// evaluations of the handler include it.
+#[derive(Clone)]
+pub struct Flags {
+    client: Arc<Client>,
+    context: EvaluationContext,
+}
+
+impl Flags {
+    /// Create the flags of a request, evaluated by `client` for `context`.
+    pub fn new(client: Arc<Client>, context: EvaluationContext) -> Self {
+        Self { client, context }
+    }
+
+    /// Return the evaluation context derived from the request.
+    pub fn context(&self) -> &EvaluationContext {
+        &self.context
+    }
+}
+
+impl fmt::Debug for Flags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Flags")
+            .field("context", &self.context)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Deref for Flags {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.client
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn derive_context_from_headers() {
+        let headers: HashMap<_, _> = [
+            ("x-user-id", "alice"),
+            ("accept-language", "fr-CH, fr;q=0.9, en;q=0.8"),
+            ("x-tenant-id", "acme"),
+            ("x-plan", "pro"),
+        ]
+        .into_iter()
+        .collect();
+
+        let context = HeaderContext::default()
+            .with_field("X-Plan", "plan")
+            .context(|name| headers.get(name).copied());
+
+        assert_eq!(
+            context,
+            EvaluationContext::default()
+                .with_targeting_key("alice")
+                .with_custom_field("locale", "fr-CH")
+                .with_custom_field("tenant", "acme")
+                .with_custom_field("plan", "pro")
+        );
+
+        let context = HeaderContext::empty().context(|name| headers.get(name).copied());
+        assert_eq!(context, EvaluationContext::default());
+    }
+
+    #[test]
+    fn parse_preferred_language() {
+        assert_eq!(preferred_language("en-US"), Some("en-US"));
+        assert_eq!(preferred_language("de;q=0.7, en"), Some("de"));
+        assert_eq!(preferred_language("*"), None);
+        assert_eq!(preferred_language(""), None);
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::Request;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{Client, TransactionContext};
+
+use super::{Flags, HeaderContext};
+
+// ============================================================
+//  OpenFeatureLayer
+// ============================================================
+
+/// A tower layer deriving the evaluation context of every request from its headers, running the
+/// request within a [`TransactionContext`] scope of it, and adding [`Flags`] to its extensions.
+///
+/// With the `axum` feature, axum handlers take the flags as an extractor:
+///
+/// ```ignore
+/// use axum::{routing::get, Router};
+/// use open_feature::middleware::{Flags, OpenFeatureLayer};
+///
+/// let app = Router::new()
+///     .route("/checkout", get(checkout))
+///     .layer(OpenFeatureLayer::new(api.create_client()));
+///
+/// async fn checkout(flags: Flags) -> String {
+///     // The evaluation includes the targeting key, locale and tenant of the request.
+///     let enabled = flags.get_bool_value("checkout-v2", None, None).await;
+///     // ...
+/// }
+/// ```
+#[derive(Clone)]
+pub struct OpenFeatureLayer {
+    client: Arc<Client>,
+    headers: Arc<HeaderContext>,
+}
+
+impl OpenFeatureLayer {
+    /// Create a layer handing `client` to the requests, with the default [`HeaderContext`].
+    pub fn new(client: Client) -> Self {
+        Self {
+            client: Arc::new(client),
+            headers: Arc::default(),
+        }
+    }
+
+    /// Derive the evaluation context of the requests with `headers`.
+    #[must_use]
+    pub fn with_headers(mut self, headers: HeaderContext) -> Self {
+        self.headers = Arc::new(headers);
+        self
+    }
+}
+
+impl<S> Layer<S> for OpenFeatureLayer {
+    type Service = OpenFeatureService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        OpenFeatureService {
+            inner,
+            client: self.client.clone(),
+            headers: self.headers.clone(),
+        }
+    }
+}
+
+// ============================================================
+//  OpenFeatureService
+// ============================================================
+
+/// The service wrapped by an [`OpenFeatureLayer`].
+#[derive(Clone)]
+pub struct OpenFeatureService<S> {
+    inner: S,
+    client: Arc<Client>,
+    headers: Arc<HeaderContext>,
+}
+
+impl<S, B> Service<Request<B>> for OpenFeatureService<S>
+where
+    S: Service<Request<B>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let context = self.headers.context(|name| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        });
+
+        request
+            .extensions_mut()
+            .insert(Flags::new(self.client.clone(), context.clone()));
+
+        Box::pin(TransactionContext::scope(context, self.inner.call(request)))
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::*;
+    use crate::{
+        provider::{InMemoryFlag, InMemoryProvider},
+        OpenFeature,
+    };
+
+    /// Evaluates a flag with the client of the request.
+    #[derive(Clone)]
+    struct Handler;
+
+    impl Service<Request<()>> for Handler {
+        type Response = String;
+        type Error = Infallible;
+        type Future = Pin<Box<dyn Future<Output = Result<String, Infallible>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: Request<()>) -> Self::Future {
+            let flags = request.extensions().get::<Flags>().unwrap().clone();
+
+            Box::pin(async move {
+                Ok(flags
+                    .get_string_value("greeting", None, None)
+                    .await
+                    .unwrap())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn evaluate_with_request_context() {
+        let provider = InMemoryProvider::default().with_flag(
+            "greeting",
+            InMemoryFlag::new("hello")
+                .with_variant("hello", "Hello")
+                .with_variant("bonjour", "Bonjour")
+                .with_resolver(|context| {
+                    let locale = context.custom_fields.get("locale")?.as_str()?;
+                    locale.starts_with("fr").then(|| "bonjour".to_string())
+                }),
+        );
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider).await.unwrap();
+
+        let mut service = OpenFeatureLayer::new(api.create_client()).layer(Handler);
+
+        let request = Request::builder()
+            .header("Accept-Language", "fr-FR")
+            .body(())
+            .unwrap();
+        assert_eq!(service.call(request).await.unwrap(), "Bonjour");
+
+        let request = Request::builder().body(()).unwrap();
+        assert_eq!(service.call(request).await.unwrap(), "Hello");
+    }
+}
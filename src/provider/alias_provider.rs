@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::{
+    EvaluationContext, EvaluationResult, ProviderError, StructValue, TrackingEventDetails, Value,
+};
+
+use super::{
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+type DeprecationHandler = Box<dyn Fn(&str, &str) + Send + Sync>;
+
+// ============================================================
+//  AliasProvider
+// ============================================================
+
+/// A provider that delegates to `inner` after mapping old flag keys to new ones, so that flags
+/// can be renamed in the backend without breaking every call site at once.
+///
+/// ```no_run
+/// use open_feature::provider::{AliasProvider, NoOpProvider};
+///
+/// let provider = AliasProvider::new(NoOpProvider::default())
+///     .with_alias("new-checkout", "checkout-v2")
+///     .with_deprecation_handler(|old_key, new_key| {
+///         eprintln!("Flag {} is deprecated, use {} instead", old_key, new_key);
+///     });
+/// ```
+pub struct AliasProvider<P> {
+    inner: P,
+    aliases: HashMap<String, String>,
+    deprecation_handler: Option<DeprecationHandler>,
+}
+
+impl<P: FeatureProvider> AliasProvider<P> {
+    /// Create a provider delegating to `inner` without any alias.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            aliases: HashMap::new(),
+            deprecation_handler: None,
+        }
+    }
+
+    /// Resolve `old_key` as `new_key`.
+    #[must_use]
+    pub fn with_alias(mut self, old_key: impl Into<String>, new_key: impl Into<String>) -> Self {
+        self.add_alias(old_key, new_key);
+        self
+    }
+
+    /// Resolve `old_key` as `new_key`.
+    pub fn add_alias(&mut self, old_key: impl Into<String>, new_key: impl Into<String>) {
+        self.aliases.insert(old_key.into(), new_key.into());
+    }
+
+    /// Call `handler` with the old and the new key whenever an old key is resolved, for example
+    /// to log a deprecation warning.
+    #[must_use]
+    pub fn with_deprecation_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str, &str) + Send + Sync + 'static,
+    {
+        self.deprecation_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Return the key `flag_key` is resolved as.
+    pub fn resolve_key<'a>(&'a self, flag_key: &'a str) -> &'a str {
+        match self.aliases.get(flag_key) {
+            Some(new_key) => {
+                if let Some(handler) = &self.deprecation_handler {
+                    handler(flag_key, new_key);
+                }
+
+                new_key
+            }
+            None => flag_key,
+        }
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        T::resolve(&self.inner, self.resolve_key(flag_key), evaluation_context).await
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for AliasProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.inner.initialize(context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.inner
+            .track(event_name, evaluation_context, tracking_event_details)
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    /// Return the flags of `inner`, along with their old keys.
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let mut flags = self.inner.resolve_all(evaluation_context).await?;
+
+        for (old_key, new_key) in &self.aliases {
+            if let Some(details) = flags.get(new_key).cloned() {
+                flags.insert(old_key.clone(), details);
+            }
+        }
+
+        Ok(flags)
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{flags, OpenFeature};
+
+    #[tokio::test]
+    async fn resolve_alias() {
+        let deprecations = Arc::new(Mutex::new(Vec::new()));
+        let provider = AliasProvider::new(flags! { "checkout-v2" => bool: true })
+            .with_alias("new-checkout", "checkout-v2")
+            .with_deprecation_handler({
+                let deprecations = deprecations.clone();
+                move |old_key, new_key| {
+                    deprecations
+                        .lock()
+                        .unwrap()
+                        .push(format!("{} -> {}", old_key, new_key));
+                }
+            });
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider).await.unwrap();
+
+        let client = api.create_client();
+
+        assert!(client
+            .get_bool_value("new-checkout", None, None)
+            .await
+            .unwrap());
+        assert!(client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+        assert_eq!(
+            *deprecations.lock().unwrap(),
+            vec!["new-checkout -> checkout-v2".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_all_with_aliases() {
+        let provider = AliasProvider::new(flags! { "checkout-v2" => bool: true })
+            .with_alias("new-checkout", "checkout-v2")
+            .with_alias("old-search", "search-v2");
+
+        let flags = provider
+            .resolve_all(&EvaluationContext::default())
+            .await
+            .unwrap();
+
+        let mut keys: Vec<_> = flags.keys().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["checkout-v2", "new-checkout"]);
+    }
+}
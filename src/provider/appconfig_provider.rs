@@ -0,0 +1,728 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use aws_credential_types::{
+    provider::{ProvideCredentials, SharedCredentialsProvider},
+    Credentials,
+};
+use aws_sigv4::{
+    http_request::{sign, SignableBody, SignableRequest, SigningSettings},
+    sign::v4,
+};
+use serde_json::{json, Map, Value as JsonValue};
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationReason, EvaluationResult,
+    ProviderError, ProviderErrorKind, StructValue, Value,
+};
+
+use super::{
+    EventEmitter, FeatureProvider, Fetched, FlagType, FlagValue, PollingProvider, PollingSource,
+    ProviderMetadata, ResolutionDetails,
+};
+
+/// The name AppConfig requests are signed for.
+const SIGNING_NAME: &str = "appconfig";
+
+/// The time before their expiry after which credentials are refreshed.
+const CREDENTIALS_REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+// ============================================================
+//  AppConfigProvider
+// ============================================================
+
+/// A provider serving the flags of an AWS AppConfig configuration profile of type
+/// `AWS.AppConfig.FeatureFlags`, polled with the AppConfig Data API.
+///
+/// A configuration session is started once initialized, and every poll fetches the configuration
+/// with the token of the previous one, a new session being started when the token is rejected.
+/// Requests are signed with the credentials and in the region of the default AWS SDK
+/// configuration, read from the environment, the shared configuration files, or the instance or
+/// task role, unless set explicitly.
+///
+/// Flag `flag` resolves whether the flag is enabled as a bool, and its attributes as a struct,
+/// while `flag.attribute` resolves attribute `attribute` of the flag, of the type its constraints
+/// require. The flags of a deployed configuration are the same for every evaluation context,
+/// hence resolve for `STATIC`.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use open_feature::provider::AppConfigProvider;
+///
+/// let provider = AppConfigProvider::new("storefront", "production", "feature-flags")
+///     .with_region("eu-central-1")
+///     .with_polling_interval(Duration::from_secs(60));
+/// ```
+pub struct AppConfigProvider {
+    metadata: ProviderMetadata,
+    profile: ConfigurationProfile,
+    region: Option<String>,
+    credentials: Option<SharedCredentialsProvider>,
+    endpoint: Option<String>,
+    timeout: Duration,
+    polling_interval: Duration,
+    polling: PollingProvider<AppConfigSource>,
+}
+
+/// The configuration profile polled by an [`AppConfigProvider`].
+#[derive(Clone)]
+struct ConfigurationProfile {
+    application: String,
+    environment: String,
+    profile: String,
+}
+
+impl AppConfigProvider {
+    /// The interval the configuration is polled at by default.
+    pub const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_secs(30);
+
+    /// The time after which a request is abandoned by default.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Create a provider polling the feature flags of configuration profile `profile` deployed to
+    /// environment `environment` of application `application`, as names or IDs.
+    pub fn new(
+        application: impl Into<String>,
+        environment: impl Into<String>,
+        profile: impl Into<String>,
+    ) -> Self {
+        let profile = ConfigurationProfile {
+            application: application.into(),
+            environment: environment.into(),
+            profile: profile.into(),
+        };
+
+        Self {
+            metadata: ProviderMetadata::new("AWS AppConfig Provider"),
+            polling: PollingProvider::new(
+                "AWS AppConfig Provider",
+                AppConfigSource::new(
+                    profile.clone(),
+                    None,
+                    None,
+                    None,
+                    Self::DEFAULT_TIMEOUT,
+                    Self::DEFAULT_POLLING_INTERVAL,
+                ),
+                Self::DEFAULT_POLLING_INTERVAL,
+            ),
+            profile,
+            region: None,
+            credentials: None,
+            endpoint: None,
+            timeout: Self::DEFAULT_TIMEOUT,
+            polling_interval: Self::DEFAULT_POLLING_INTERVAL,
+        }
+    }
+
+    /// Poll the configuration in `region` instead of the region of the default AWS SDK
+    /// configuration.
+    #[must_use]
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self.rebuild()
+    }
+
+    /// Sign the requests with the credentials of `credentials` instead of those of the default
+    /// AWS SDK configuration.
+    #[must_use]
+    pub fn with_credentials(mut self, credentials: impl ProvideCredentials + 'static) -> Self {
+        self.credentials = Some(SharedCredentialsProvider::new(credentials));
+        self.rebuild()
+    }
+
+    /// Send the requests to `endpoint`, such as a VPC endpoint, instead of the regional endpoint
+    /// of the AppConfig Data API.
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into().trim_end_matches('/').to_string());
+        self.rebuild()
+    }
+
+    /// Abandon requests taking longer than `timeout`.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.rebuild()
+    }
+
+    /// Poll the configuration every `interval` instead of [`Self::DEFAULT_POLLING_INTERVAL`],
+    /// which AppConfig requires to be at least 15 seconds.
+    #[must_use]
+    pub fn with_polling_interval(mut self, interval: Duration) -> Self {
+        self.polling_interval = interval;
+        self.rebuild()
+    }
+
+    fn rebuild(mut self) -> Self {
+        self.polling = PollingProvider::new(
+            "AWS AppConfig Provider",
+            AppConfigSource::new(
+                self.profile.clone(),
+                self.region.clone(),
+                self.credentials.clone(),
+                self.endpoint.clone(),
+                self.timeout,
+                self.polling_interval,
+            ),
+            self.polling_interval,
+        );
+        self
+    }
+
+    fn resolve<T: FlagValue>(&self, flag_key: &str) -> EvaluationResult<ResolutionDetails<T>> {
+        let flags = self.polling.configuration().ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ProviderNotReady)
+                .message("The AppConfig configuration has not been fetched yet")
+                .build()
+        })?;
+
+        let value = match (T::FLAG_TYPE, resolve_flag(&flags, flag_key, T::FLAG_TYPE)?) {
+            #[allow(clippy::cast_precision_loss)]
+            (FlagType::Float, Value::Int(value)) => Value::Float(value as f64),
+            (_, value) => value,
+        };
+
+        Ok(ResolutionDetails {
+            value: T::from_value(value).ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::TypeMismatch)
+                    .message(format!("Expected a {:?} flag", T::FLAG_TYPE))
+                    .build()
+            })?,
+            variant: None,
+            reason: Some(EvaluationReason::Static),
+            flag_metadata: None,
+        })
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for AppConfigProvider {
+    async fn initialize(&mut self, _context: &EvaluationContext) -> Result<(), ProviderError> {
+        match self.polling.start().await {
+            Err(error) if !error.is_retryable() => Err(error),
+            // A transient failure is retried by the next poll.
+            _ => Ok(()),
+        }
+    }
+
+    async fn shutdown(&self) {
+        self.polling.stop();
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        Some(self.polling.event_emitter())
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key)
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key)
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key)
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key)
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key)
+    }
+}
+
+/// Resolve `flag_key` as `flag_type` among `flags`: a flag as whether it is enabled or as its
+/// attributes, or an attribute of a flag with a `flag.attribute` key.
+fn resolve_flag(
+    flags: &HashMap<String, Map<String, JsonValue>>,
+    flag_key: &str,
+    flag_type: FlagType,
+) -> EvaluationResult<Value> {
+    let not_found = |message: String| {
+        EvaluationError::builder()
+            .code(EvaluationErrorCode::FlagNotFound)
+            .message(message)
+            .build()
+    };
+
+    // Flag keys cannot contain dots, unlike attribute names.
+    let (key, attribute) = match flag_key.split_once('.') {
+        Some((key, attribute)) => (key, Some(attribute)),
+        None => (flag_key, None),
+    };
+
+    let flag = flags
+        .get(key)
+        .ok_or_else(|| not_found(format!("AppConfig flag \"{key}\" does not exist")))?;
+
+    let value = match (attribute, flag_type) {
+        (None, FlagType::Bool) => flag
+            .get("enabled")
+            .cloned()
+            .unwrap_or(JsonValue::Bool(false)),
+        (None, FlagType::Struct) => JsonValue::Object(flag.clone()),
+        (None, _) => {
+            return Err(EvaluationError::builder()
+                .code(EvaluationErrorCode::TypeMismatch)
+                .message(format!(
+                    "AppConfig flag \"{key}\" is a bool or a struct, use \"{key}.<attribute>\" \
+                     to resolve an attribute"
+                ))
+                .build())
+        }
+        // Disabled flags have no attributes.
+        (Some(attribute), _) => flag.get(attribute).cloned().ok_or_else(|| {
+            not_found(format!(
+                "AppConfig flag \"{key}\" has no attribute \"{attribute}\", or is disabled"
+            ))
+        })?,
+    };
+
+    Value::try_from(value)
+}
+
+// ============================================================
+//  AppConfigSource
+// ============================================================
+
+/// Polls a configuration profile with the session token API of AppConfig Data.
+struct AppConfigSource {
+    client: reqwest::Client,
+    profile: ConfigurationProfile,
+    region: Option<String>,
+    credentials: Option<SharedCredentialsProvider>,
+    endpoint: Option<String>,
+    poll_interval: Duration,
+    aws: OnceCell<AwsConfig>,
+    token: Mutex<Option<String>>,
+    cached_credentials: Mutex<Option<Credentials>>,
+}
+
+/// The region and credentials requests are signed with.
+struct AwsConfig {
+    region: String,
+    credentials: SharedCredentialsProvider,
+}
+
+impl AppConfigSource {
+    fn new(
+        profile: ConfigurationProfile,
+        region: Option<String>,
+        credentials: Option<SharedCredentialsProvider>,
+        endpoint: Option<String>,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .expect("The HTTP client can be built"),
+            profile,
+            region,
+            credentials,
+            endpoint,
+            poll_interval,
+            aws: OnceCell::new(),
+            token: Mutex::new(None),
+            cached_credentials: Mutex::new(None),
+        }
+    }
+
+    /// Return the region and credentials, loading the default AWS SDK configuration for those
+    /// that are not set.
+    async fn aws(&self) -> Result<&AwsConfig, ProviderError> {
+        self.aws
+            .get_or_try_init(|| async {
+                let sdk_config = match (&self.region, &self.credentials) {
+                    (Some(_), Some(_)) => None,
+                    _ => Some(aws_config::defaults(BehaviorVersion::latest()).load().await),
+                };
+
+                let region = self
+                    .region
+                    .clone()
+                    .or_else(|| Some(sdk_config.as_ref()?.region()?.to_string()))
+                    .ok_or_else(|| {
+                        ProviderError::new(
+                            ProviderErrorKind::Misconfigured,
+                            "No AWS region is configured",
+                        )
+                    })?;
+
+                let credentials = self
+                    .credentials
+                    .clone()
+                    .or_else(|| sdk_config.as_ref()?.credentials_provider())
+                    .ok_or_else(|| {
+                        ProviderError::new(
+                            ProviderErrorKind::Misconfigured,
+                            "No AWS credentials are configured",
+                        )
+                    })?;
+
+                Ok(AwsConfig {
+                    region,
+                    credentials,
+                })
+            })
+            .await
+    }
+
+    /// Return the credentials to sign with, cached until shortly before they expire.
+    async fn credentials(&self, aws: &AwsConfig) -> Result<Credentials, ProviderError> {
+        let mut cached = self.cached_credentials.lock().await;
+
+        let fresh = cached.as_ref().filter(|credentials| {
+            credentials.expiry().map_or(true, |expiry| {
+                SystemTime::now() + CREDENTIALS_REFRESH_MARGIN < expiry
+            })
+        });
+        if let Some(credentials) = fresh {
+            return Ok(credentials.clone());
+        }
+
+        let credentials = aws
+            .credentials
+            .provide_credentials()
+            .await
+            .map_err(|error| {
+                ProviderError::new(ProviderErrorKind::Unauthorized, error.to_string())
+                    .with_source(error)
+            })?;
+
+        *cached = Some(credentials.clone());
+        Ok(credentials)
+    }
+
+    /// Send a signed request, returning the response if successful.
+    async fn send(
+        &self,
+        method: &str,
+        path_and_query: &str,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, ProviderError> {
+        let aws = self.aws().await?;
+        let credentials = self.credentials(aws).await?;
+
+        let url = match &self.endpoint {
+            Some(endpoint) => format!("{endpoint}{path_and_query}"),
+            None => format!(
+                "https://appconfigdata.{}.amazonaws.com{}",
+                aws.region, path_and_query
+            ),
+        };
+
+        let headers = [("content-type", "application/json")];
+        let signing_error =
+            |error: String| ProviderError::new(ProviderErrorKind::Misconfigured, error);
+
+        let identity = credentials.into();
+        let signing_params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&aws.region)
+            .name(SIGNING_NAME)
+            .time(SystemTime::now())
+            .settings(SigningSettings::default())
+            .build()
+            .map_err(|error| signing_error(error.to_string()))?
+            .into();
+        let signable = SignableRequest::new(
+            method,
+            url.as_str(),
+            headers.into_iter(),
+            SignableBody::Bytes(&body),
+        )
+        .map_err(|error| signing_error(error.to_string()))?;
+        let (instructions, _) = sign(signable, &signing_params)
+            .map_err(|error| signing_error(error.to_string()))?
+            .into_parts();
+
+        let mut request = match method {
+            "POST" => self.client.post(&url).body(body),
+            _ => self.client.get(&url),
+        };
+        for (name, value) in headers.into_iter().chain(instructions.headers()) {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await.map_err(|error| {
+            let kind = if error.is_timeout() {
+                ProviderErrorKind::Timeout
+            } else {
+                ProviderErrorKind::Network
+            };
+
+            ProviderError::new(kind, error.to_string()).with_source(error)
+        })?;
+
+        let status = response.status().as_u16();
+        let kind = match status {
+            200 | 201 => return Ok(response),
+            // Expired or reused configuration tokens are rejected as bad requests.
+            400 => ProviderErrorKind::Other("BadRequest".to_string()),
+            401 | 403 => ProviderErrorKind::Unauthorized,
+            404 => ProviderErrorKind::Misconfigured,
+            429 => ProviderErrorKind::RateLimited,
+            500..=599 => ProviderErrorKind::Unavailable,
+            _ => ProviderErrorKind::InvalidResponse,
+        };
+
+        Err(ProviderError::new(
+            kind,
+            format!("The AppConfig Data API answered with {status}"),
+        ))
+    }
+
+    /// Start a configuration session, returning its initial configuration token.
+    async fn start_session(&self) -> Result<String, ProviderError> {
+        let body = json!({
+            "ApplicationIdentifier": self.profile.application,
+            "EnvironmentIdentifier": self.profile.environment,
+            "ConfigurationProfileIdentifier": self.profile.profile,
+            "RequiredMinimumPollIntervalInSeconds": self.poll_interval.as_secs().clamp(15, 86400),
+        });
+
+        let response = self
+            .send(
+                "POST",
+                "/configurationsessions",
+                body.to_string().into_bytes(),
+            )
+            .await?;
+        let body: JsonValue = response.json().await.map_err(|error| {
+            ProviderError::new(ProviderErrorKind::InvalidResponse, error.to_string())
+                .with_source(error)
+        })?;
+
+        body.get("InitialConfigurationToken")
+            .and_then(JsonValue::as_str)
+            .map(ToString::to_string)
+            .ok_or_else(|| {
+                ProviderError::new(
+                    ProviderErrorKind::InvalidResponse,
+                    "The configuration session has no token",
+                )
+            })
+    }
+
+    /// Fetch the latest configuration with `token`, returning the next token and the
+    /// configuration, empty if unchanged since the previous token.
+    async fn latest_configuration(&self, token: &str) -> Result<(String, Vec<u8>), ProviderError> {
+        let response = self
+            .send(
+                "GET",
+                &format!("/configuration?configuration_token={}", encode(token)),
+                Vec::new(),
+            )
+            .await?;
+
+        let next_token = response
+            .headers()
+            .get("next-poll-configuration-token")
+            .and_then(|token| token.to_str().ok())
+            .map(ToString::to_string)
+            .ok_or_else(|| {
+                ProviderError::new(
+                    ProviderErrorKind::InvalidResponse,
+                    "The configuration has no next token",
+                )
+            })?;
+
+        let payload = response.bytes().await.map_err(|error| {
+            ProviderError::new(ProviderErrorKind::Network, error.to_string()).with_source(error)
+        })?;
+
+        Ok((next_token, payload.to_vec()))
+    }
+}
+
+#[async_trait]
+impl PollingSource for AppConfigSource {
+    type Configuration = HashMap<String, Map<String, JsonValue>>;
+
+    async fn fetch(&self, _etag: Option<&str>) -> Result<Fetched, ProviderError> {
+        let mut token = self.token.lock().await;
+
+        let current = match token.take() {
+            Some(current) => current,
+            None => self.start_session().await?,
+        };
+
+        // A rejected token is dropped, so that the next poll starts a new session.
+        let (next_token, payload) = self.latest_configuration(&current).await?;
+        *token = Some(next_token);
+
+        if payload.is_empty() {
+            return Ok(Fetched::NotModified);
+        }
+
+        Ok(Fetched::Payload {
+            payload,
+            etag: None,
+        })
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Configuration, ProviderError> {
+        parse_flags(payload)
+    }
+
+    fn flags_changed(
+        &self,
+        previous: &Self::Configuration,
+        current: &Self::Configuration,
+    ) -> Option<Vec<String>> {
+        let mut flags_changed: Vec<_> = previous
+            .keys()
+            .chain(current.keys())
+            .filter(|key| previous.get(*key) != current.get(*key))
+            .cloned()
+            .collect();
+        flags_changed.sort();
+        flags_changed.dedup();
+
+        Some(flags_changed)
+    }
+}
+
+/// Parse the feature flags returned by AppConfig Data, mapping flag keys to whether they are
+/// enabled and their attributes.
+fn parse_flags(payload: &[u8]) -> Result<HashMap<String, Map<String, JsonValue>>, ProviderError> {
+    let flags: Map<String, JsonValue> = serde_json::from_slice(payload).map_err(|error| {
+        ProviderError::new(ProviderErrorKind::InvalidResponse, error.to_string()).with_source(error)
+    })?;
+
+    flags
+        .into_iter()
+        .map(|(key, flag)| match flag {
+            JsonValue::Object(flag) => Ok((key, flag)),
+            _ => Err(ProviderError::new(
+                ProviderErrorKind::InvalidResponse,
+                format!("AppConfig flag \"{key}\" is not an object"),
+            )),
+        })
+        .collect()
+}
+
+/// Percent-encode `text` for a query string.
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                char::from(byte).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_flags() -> HashMap<String, Map<String, JsonValue>> {
+        parse_flags(
+            json!({
+                "checkout-v2": { "enabled": true, "max-items": 20, "theme": "dark", "ratio": 0.5 },
+                "legacy-search": { "enabled": false }
+            })
+            .to_string()
+            .as_bytes(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn resolve_flags_and_attributes() {
+        let flags = create_flags();
+
+        assert_eq!(
+            resolve_flag(&flags, "checkout-v2", FlagType::Bool).unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            resolve_flag(&flags, "legacy-search", FlagType::Bool).unwrap(),
+            Value::Bool(false)
+        );
+        assert_eq!(
+            resolve_flag(&flags, "checkout-v2.max-items", FlagType::Int).unwrap(),
+            Value::Int(20)
+        );
+        assert_eq!(
+            resolve_flag(&flags, "checkout-v2.theme", FlagType::String).unwrap(),
+            Value::String("dark".to_string())
+        );
+
+        let Value::Struct(attributes) =
+            resolve_flag(&flags, "checkout-v2", FlagType::Struct).unwrap()
+        else {
+            panic!("Expected a struct");
+        };
+        assert_eq!(attributes.fields["max-items"], Value::Int(20));
+    }
+
+    #[test]
+    fn fail_missing_flags() {
+        let flags = create_flags();
+
+        let error = resolve_flag(&flags, "missing", FlagType::Bool).unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+
+        let error = resolve_flag(&flags, "legacy-search.limit", FlagType::Int).unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+
+        let error = resolve_flag(&flags, "checkout-v2", FlagType::String).unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::TypeMismatch);
+    }
+
+    #[test]
+    fn reject_freeform_configurations() {
+        let error = parse_flags(br#"{ "timeout": 30 }"#).unwrap_err();
+        assert_eq!(error.kind, ProviderErrorKind::InvalidResponse);
+    }
+
+    #[test]
+    fn encode_tokens() {
+        assert_eq!(encode("AYADeJ+/y=="), "AYADeJ%2B%2Fy%3D%3D");
+    }
+}
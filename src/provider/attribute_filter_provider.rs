@@ -0,0 +1,230 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+
+use crate::{
+    EvaluationContext, EvaluationResult, ProviderError, StructValue, TrackingEventDetails, Value,
+};
+
+use super::{EventEmitter, FeatureProvider, ProviderMetadata, ProviderStatus, ResolutionDetails};
+
+// ============================================================
+//  AttributeFilter
+// ============================================================
+
+/// The evaluation context attributes an [`AttributeFilterProvider`] passes on.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AttributeFilter {
+    /// Only the listed attributes.
+    Allow(HashSet<String>),
+
+    /// All the attributes but the listed ones.
+    Deny(HashSet<String>),
+}
+
+impl AttributeFilter {
+    /// Create a filter passing on only the attributes in `names`.
+    pub fn allow<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Allow(names.into_iter().map(Into::into).collect())
+    }
+
+    /// Create a filter passing on all the attributes but those in `names`.
+    pub fn deny<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Deny(names.into_iter().map(Into::into).collect())
+    }
+
+    /// Return `true` if attribute `name` is passed on.
+    pub fn is_allowed(&self, name: &str) -> bool {
+        match self {
+            Self::Allow(names) => names.contains(name),
+            Self::Deny(names) => !names.contains(name),
+        }
+    }
+}
+
+// ============================================================
+//  AttributeFilterProvider
+// ============================================================
+
+/// A provider that delegates to `inner` with only the evaluation context attributes permitted
+/// by an [`AttributeFilter`], so that internal attributes never reach a third-party service
+/// backing `inner`. The targeting key is always passed on.
+///
+/// ```no_run
+/// use open_feature::provider::{AttributeFilter, AttributeFilterProvider, NoOpProvider};
+///
+/// let provider = AttributeFilterProvider::new(
+///     NoOpProvider::default(),
+///     AttributeFilter::deny(["internal_id", "email"]),
+/// );
+/// ```
+pub struct AttributeFilterProvider<P> {
+    inner: P,
+    filter: AttributeFilter,
+}
+
+impl<P: FeatureProvider> AttributeFilterProvider<P> {
+    /// Create a provider delegating to `inner` with the attributes permitted by `filter`.
+    pub fn new(inner: P, filter: AttributeFilter) -> Self {
+        Self { inner, filter }
+    }
+
+    /// Return `evaluation_context` without the attributes not permitted by the filter.
+    pub fn filter(&self, evaluation_context: &EvaluationContext) -> EvaluationContext {
+        let mut filtered = evaluation_context.clone();
+        filtered
+            .custom_fields
+            .retain(|name, _| self.filter.is_allowed(name));
+        filtered
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for AttributeFilterProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        let context = self.filter(context);
+        self.inner.initialize(&context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.inner
+            .track(
+                event_name,
+                &self.filter(evaluation_context),
+                tracking_event_details,
+            )
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.inner
+            .resolve_bool_value(flag_key, &self.filter(evaluation_context))
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.inner
+            .resolve_int_value(flag_key, &self.filter(evaluation_context))
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.inner
+            .resolve_float_value(flag_key, &self.filter(evaluation_context))
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.inner
+            .resolve_string_value(flag_key, &self.filter(evaluation_context))
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.inner
+            .resolve_struct_value(flag_key, &self.filter(evaluation_context))
+            .await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.inner
+            .resolve_all(&self.filter(evaluation_context))
+            .await
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::NoOpProvider;
+
+    fn create_context() -> EvaluationContext {
+        EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("country", "FR")
+            .with_custom_field("internal_id", 42)
+    }
+
+    #[test]
+    fn allow_attributes() {
+        let provider = AttributeFilterProvider::new(
+            NoOpProvider::default(),
+            AttributeFilter::allow(["country"]),
+        );
+
+        let context = provider.filter(&create_context());
+
+        assert_eq!(context.targeting_key, Some("alice".to_string()));
+        assert!(context.custom_fields.contains_key("country"));
+        assert!(!context.custom_fields.contains_key("internal_id"));
+    }
+
+    #[test]
+    fn deny_attributes() {
+        let provider = AttributeFilterProvider::new(
+            NoOpProvider::default(),
+            AttributeFilter::deny(["internal_id"]),
+        );
+
+        let context = provider.filter(&create_context());
+
+        assert!(context.custom_fields.contains_key("country"));
+        assert!(!context.custom_fields.contains_key("internal_id"));
+    }
+}
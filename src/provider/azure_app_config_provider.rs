@@ -0,0 +1,988 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
+use serde_json::{Map, Value as JsonValue};
+use sha2::{Digest, Sha256};
+use time::{
+    format_description::well_known::{Rfc2822, Rfc3339},
+    OffsetDateTime,
+};
+use tokio::sync::Mutex;
+
+use crate::{
+    EvaluationContext, EvaluationContextFieldValue, EvaluationError, EvaluationErrorCode,
+    EvaluationReason, EvaluationResult, ProviderError, ProviderErrorKind, StructValue,
+};
+
+use super::{
+    EventEmitter, FeatureProvider, Fetched, PollingProvider, PollingSource, ProviderMetadata,
+    ResolutionDetails,
+};
+
+/// The prefix of the keys of feature flags.
+const FEATURE_FLAG_PREFIX: &str = ".appconfig.featureflag/";
+
+/// The version of the App Configuration REST API the provider speaks.
+const API_VERSION: &str = "1.0";
+
+/// The resource managed identity tokens are requested for.
+const TOKEN_RESOURCE: &str = "https://azconfig.io";
+
+/// The time before their expiry after which tokens are refreshed.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+// ============================================================
+//  AzureAppConfigProvider
+// ============================================================
+
+/// A provider evaluating the feature flags of an
+/// [Azure App Configuration](https://learn.microsoft.com/azure/azure-app-configuration/) store
+/// locally, as the Microsoft feature management libraries do.
+///
+/// The flags are polled from the store, authenticating with the HMAC signature of a connection
+/// string, or with a bearer token of the managed identity of the host, from the App Service or
+/// the instance metadata endpoint.
+///
+/// Enabled flags are evaluated with their `Microsoft.Percentage`, `Microsoft.Targeting` and
+/// `Microsoft.TimeWindow` filters, any or all of which must pass as their requirement type
+/// requires. Targeting uses the targeting key as user ID and the `groups` attribute, a list of
+/// group names, and rolls out consistently for a user. The percentage filter is random, unless
+/// the evaluation context has a targeting key to bucket consistently with.
+///
+/// ```no_run
+/// use open_feature::provider::AzureAppConfigProvider;
+///
+/// # fn example() -> Result<(), open_feature::ProviderError> {
+/// let provider = AzureAppConfigProvider::from_connection_string(
+///     "Endpoint=https://contoso.azconfig.io;Id=xxxx;Secret=xxxx",
+/// )?
+/// .with_label("production");
+///
+/// let provider = AzureAppConfigProvider::with_managed_identity("https://contoso.azconfig.io");
+/// # Ok(())
+/// # }
+/// ```
+pub struct AzureAppConfigProvider {
+    metadata: ProviderMetadata,
+    endpoint: String,
+    credential: Credential,
+    label: Option<String>,
+    timeout: Duration,
+    polling_interval: Duration,
+    polling: PollingProvider<AzureAppConfigSource>,
+}
+
+/// How the provider authenticates with the store.
+#[derive(Clone)]
+enum Credential {
+    AccessKey { id: String, secret: Vec<u8> },
+    ManagedIdentity { client_id: Option<String> },
+}
+
+impl AzureAppConfigProvider {
+    /// The interval the flags are polled at by default.
+    pub const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_secs(30);
+
+    /// The time after which a request is abandoned by default.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Create a provider polling the store of `connection_string`, of the form
+    /// `Endpoint=https://contoso.azconfig.io;Id=<id>;Secret=<secret>`.
+    pub fn from_connection_string(connection_string: &str) -> Result<Self, ProviderError> {
+        let mut endpoint = None;
+        let mut id = None;
+        let mut secret = None;
+
+        for part in connection_string.split(';').filter(|part| !part.is_empty()) {
+            match part.split_once('=') {
+                Some(("Endpoint", value)) => endpoint = Some(value),
+                Some(("Id", value)) => id = Some(value),
+                Some(("Secret", value)) => secret = Some(value),
+                _ => {}
+            }
+        }
+
+        let (Some(endpoint), Some(id), Some(secret)) = (endpoint, id, secret) else {
+            return Err(ProviderError::new(
+                ProviderErrorKind::Misconfigured,
+                "The connection string needs an Endpoint, an Id and a Secret",
+            ));
+        };
+
+        let secret = BASE64.decode(secret).map_err(|error| {
+            ProviderError::new(ProviderErrorKind::Misconfigured, error.to_string())
+                .with_source(error)
+        })?;
+
+        Ok(Self::new(
+            endpoint,
+            Credential::AccessKey {
+                id: id.to_string(),
+                secret,
+            },
+        ))
+    }
+
+    /// Create a provider polling the store at `endpoint`, authenticating with the
+    /// system-assigned managed identity of the host.
+    pub fn with_managed_identity(endpoint: impl Into<String>) -> Self {
+        Self::new(
+            &endpoint.into(),
+            Credential::ManagedIdentity { client_id: None },
+        )
+    }
+
+    fn new(endpoint: &str, credential: Credential) -> Self {
+        let endpoint = endpoint.trim_end_matches('/').to_string();
+
+        Self {
+            metadata: ProviderMetadata::new("Azure App Configuration Provider"),
+            polling: create_polling(
+                &endpoint,
+                &credential,
+                None,
+                Self::DEFAULT_TIMEOUT,
+                Self::DEFAULT_POLLING_INTERVAL,
+            ),
+            endpoint,
+            credential,
+            label: None,
+            timeout: Self::DEFAULT_TIMEOUT,
+            polling_interval: Self::DEFAULT_POLLING_INTERVAL,
+        }
+    }
+
+    /// Authenticate with the user-assigned managed identity of `client_id`, instead of the
+    /// system-assigned one. Has no effect with a connection string.
+    #[must_use]
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        if let Credential::ManagedIdentity { .. } = self.credential {
+            self.credential = Credential::ManagedIdentity {
+                client_id: Some(client_id.into()),
+            };
+        }
+        self.rebuild()
+    }
+
+    /// Only read the flags with label `label`, instead of those without label.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self.rebuild()
+    }
+
+    /// Abandon requests taking longer than `timeout`.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.rebuild()
+    }
+
+    /// Poll the flags every `interval` instead of [`Self::DEFAULT_POLLING_INTERVAL`].
+    #[must_use]
+    pub fn with_polling_interval(mut self, interval: Duration) -> Self {
+        self.polling_interval = interval;
+        self.rebuild()
+    }
+
+    fn rebuild(mut self) -> Self {
+        self.polling = create_polling(
+            &self.endpoint,
+            &self.credential,
+            self.label.as_deref(),
+            self.timeout,
+            self.polling_interval,
+        );
+        self
+    }
+}
+
+fn create_polling(
+    endpoint: &str,
+    credential: &Credential,
+    label: Option<&str>,
+    timeout: Duration,
+    interval: Duration,
+) -> PollingProvider<AzureAppConfigSource> {
+    let source = AzureAppConfigSource {
+        client: reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("The HTTP client can be built"),
+        endpoint: endpoint.to_string(),
+        credential: credential.clone(),
+        label: label.map(ToString::to_string),
+        token: Mutex::new(None),
+    };
+
+    PollingProvider::new("Azure App Configuration Provider", source, interval)
+}
+
+#[async_trait]
+impl FeatureProvider for AzureAppConfigProvider {
+    async fn initialize(&mut self, _context: &EvaluationContext) -> Result<(), ProviderError> {
+        match self.polling.start().await {
+            Err(error) if !error.is_retryable() => Err(error),
+            // A transient failure is retried by the next poll.
+            _ => Ok(()),
+        }
+    }
+
+    async fn shutdown(&self) {
+        self.polling.stop();
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        Some(self.polling.event_emitter())
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        let flags = self.polling.configuration().ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ProviderNotReady)
+                .message("The Azure App Configuration flags have not been fetched yet")
+                .build()
+        })?;
+
+        let flag = flags.get(flag_key).ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .message(format!("Feature flag \"{flag_key}\" does not exist"))
+                .build()
+        })?;
+
+        let (value, reason) = evaluate(flag_key, flag, evaluation_context);
+        Ok(ResolutionDetails {
+            value,
+            variant: None,
+            reason: Some(reason),
+            flag_metadata: None,
+        })
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        Err(not_a_bool(flag_key))
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        Err(not_a_bool(flag_key))
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        Err(not_a_bool(flag_key))
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        Err(not_a_bool(flag_key))
+    }
+}
+
+fn not_a_bool(flag_key: &str) -> EvaluationError {
+    EvaluationError::builder()
+        .code(EvaluationErrorCode::TypeMismatch)
+        .message(format!("Feature flag \"{flag_key}\" is a bool"))
+        .build()
+}
+
+// ============================================================
+//  AzureAppConfigSource
+// ============================================================
+
+/// Lists the feature flags of a store.
+struct AzureAppConfigSource {
+    client: reqwest::Client,
+    endpoint: String,
+    credential: Credential,
+    label: Option<String>,
+    token: Mutex<Option<(String, Instant)>>,
+}
+
+#[async_trait]
+impl PollingSource for AzureAppConfigSource {
+    type Configuration = HashMap<String, JsonValue>;
+
+    async fn fetch(&self, etag: Option<&str>) -> Result<Fetched, ProviderError> {
+        let label = self
+            .label
+            .as_deref()
+            .map_or_else(|| "%00".to_string(), encode);
+        let mut path = Some(format!(
+            "/kv?key={}*&label={}&api-version={}",
+            encode(FEATURE_FLAG_PREFIX),
+            label,
+            API_VERSION
+        ));
+
+        let mut items = Vec::new();
+        let mut first_etag = None;
+        let mut paged = false;
+
+        while let Some(current) = path.take() {
+            let response = self
+                .get(&current, etag.filter(|_| items.is_empty() && !paged))
+                .await?;
+
+            if response.status().as_u16() == 304 {
+                return Ok(Fetched::NotModified);
+            }
+
+            if first_etag.is_none() && !paged {
+                first_etag = response
+                    .headers()
+                    .get("etag")
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(ToString::to_string);
+            }
+
+            let body: JsonValue = response.json().await.map_err(|error| {
+                ProviderError::new(ProviderErrorKind::InvalidResponse, error.to_string())
+                    .with_source(error)
+            })?;
+
+            items.extend(
+                body.get("items")
+                    .and_then(JsonValue::as_array)
+                    .into_iter()
+                    .flatten()
+                    .cloned(),
+            );
+
+            path = body
+                .get("@nextLink")
+                .and_then(JsonValue::as_str)
+                .map(ToString::to_string);
+            paged |= path.is_some();
+        }
+
+        Ok(Fetched::Payload {
+            payload: JsonValue::Array(items).to_string().into_bytes(),
+            // Only a single page is identified by its ETag.
+            etag: first_etag.filter(|_| !paged),
+        })
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<Self::Configuration, ProviderError> {
+        parse_flags(payload)
+    }
+
+    fn flags_changed(
+        &self,
+        previous: &Self::Configuration,
+        current: &Self::Configuration,
+    ) -> Option<Vec<String>> {
+        let mut flags_changed: Vec<_> = previous
+            .keys()
+            .chain(current.keys())
+            .filter(|key| previous.get(*key) != current.get(*key))
+            .cloned()
+            .collect();
+        flags_changed.sort();
+        flags_changed.dedup();
+
+        Some(flags_changed)
+    }
+}
+
+impl AzureAppConfigSource {
+    /// Send an authenticated GET request for `path_and_query`, returning the response if
+    /// successful or not modified.
+    async fn get(
+        &self,
+        path_and_query: &str,
+        etag: Option<&str>,
+    ) -> Result<reqwest::Response, ProviderError> {
+        let mut request = self
+            .client
+            .get(format!("{}{}", self.endpoint, path_and_query));
+
+        match &self.credential {
+            Credential::AccessKey { id, secret } => {
+                let host = self.endpoint.split("://").nth(1).unwrap_or(&self.endpoint);
+                let date = http_date(OffsetDateTime::now_utc());
+
+                for (name, value) in sign(id, secret, "GET", path_and_query, host, &date, b"") {
+                    request = request.header(name, value);
+                }
+            }
+            Credential::ManagedIdentity { client_id } => {
+                let token = self.token(client_id.as_deref()).await?;
+                request = request.bearer_auth(token);
+            }
+        }
+
+        if let Some(etag) = etag {
+            request = request.header("If-None-Match", etag);
+        }
+
+        let response = request.send().await.map_err(network_error)?;
+
+        let status = response.status().as_u16();
+        let kind = match status {
+            200 | 304 => return Ok(response),
+            401 | 403 => ProviderErrorKind::Unauthorized,
+            429 => ProviderErrorKind::RateLimited,
+            500..=599 => ProviderErrorKind::Unavailable,
+            _ => ProviderErrorKind::InvalidResponse,
+        };
+
+        Err(ProviderError::new(
+            kind,
+            format!("Azure App Configuration answered with {status}"),
+        ))
+    }
+
+    /// Return a token of the managed identity of the host, cached until shortly before it
+    /// expires.
+    async fn token(&self, client_id: Option<&str>) -> Result<String, ProviderError> {
+        let mut cached = self.token.lock().await;
+
+        if let Some((token, expiry)) = cached.as_ref() {
+            if Instant::now() + TOKEN_REFRESH_MARGIN < *expiry {
+                return Ok(token.clone());
+            }
+        }
+
+        // App Service and Functions expose their own endpoint, other hosts the instance metadata
+        // service.
+        let mut request = match (
+            std::env::var("IDENTITY_ENDPOINT"),
+            std::env::var("IDENTITY_HEADER"),
+        ) {
+            (Ok(endpoint), Ok(header)) => self
+                .client
+                .get(endpoint)
+                .query(&[("api-version", "2019-08-01"), ("resource", TOKEN_RESOURCE)])
+                .header("X-IDENTITY-HEADER", header),
+            _ => self
+                .client
+                .get("http://169.254.169.254/metadata/identity/oauth2/token")
+                .query(&[("api-version", "2018-02-01"), ("resource", TOKEN_RESOURCE)])
+                .header("Metadata", "true"),
+        };
+        if let Some(client_id) = client_id {
+            request = request.query(&[("client_id", client_id)]);
+        }
+
+        let response = request.send().await.map_err(network_error)?;
+        if !response.status().is_success() {
+            return Err(ProviderError::new(
+                ProviderErrorKind::Unauthorized,
+                format!(
+                    "The managed identity endpoint answered with {}",
+                    response.status().as_u16()
+                ),
+            ));
+        }
+
+        let body: JsonValue = response.json().await.map_err(|error| {
+            ProviderError::new(ProviderErrorKind::InvalidResponse, error.to_string())
+                .with_source(error)
+        })?;
+
+        let token = body
+            .get("access_token")
+            .and_then(JsonValue::as_str)
+            .ok_or_else(|| {
+                ProviderError::new(
+                    ProviderErrorKind::InvalidResponse,
+                    "The managed identity endpoint sent no token",
+                )
+            })?
+            .to_string();
+        // Both endpoints send the lifetime in seconds as a string.
+        let expires_in = body
+            .get("expires_in")
+            .and_then(|expires_in| match expires_in {
+                JsonValue::String(expires_in) => expires_in.parse().ok(),
+                expires_in => expires_in.as_u64(),
+            })
+            .unwrap_or(0);
+
+        *cached = Some((
+            token.clone(),
+            Instant::now() + Duration::from_secs(expires_in),
+        ));
+        Ok(token)
+    }
+}
+
+fn network_error(error: reqwest::Error) -> ProviderError {
+    let kind = if error.is_timeout() {
+        ProviderErrorKind::Timeout
+    } else {
+        ProviderErrorKind::Network
+    };
+
+    ProviderError::new(kind, error.to_string()).with_source(error)
+}
+
+/// Return the headers authenticating a request with the HMAC-SHA256 signature of access key
+/// `id`.
+fn sign(
+    id: &str,
+    secret: &[u8],
+    method: &str,
+    path_and_query: &str,
+    host: &str,
+    date: &str,
+    body: &[u8],
+) -> Vec<(&'static str, String)> {
+    let content_hash = BASE64.encode(Sha256::digest(body));
+    let string_to_sign = format!("{method}\n{path_and_query}\n{date};{host};{content_hash}");
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC takes keys of any size");
+    mac.update(string_to_sign.as_bytes());
+    let signature = BASE64.encode(mac.finalize().into_bytes());
+
+    vec![
+        ("x-ms-date", date.to_string()),
+        ("x-ms-content-sha256", content_hash),
+        (
+            "Authorization",
+            format!(
+                "HMAC-SHA256 Credential={id}&SignedHeaders=x-ms-date;host;x-ms-content-sha256&Signature={signature}"
+            ),
+        ),
+    ]
+}
+
+/// Format `date` as an HTTP date, such as `Wed, 01 May 2019 13:59:59 GMT`.
+fn http_date(date: OffsetDateTime) -> String {
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        &date.weekday().to_string()[..3],
+        date.day(),
+        &date.month().to_string()[..3],
+        date.year(),
+        date.hour(),
+        date.minute(),
+        date.second()
+    )
+}
+
+/// Percent-encode `text` for a query string.
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                char::from(byte).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Parse the listed key-values into the feature flags they hold, keyed by flag ID.
+fn parse_flags(payload: &[u8]) -> Result<HashMap<String, JsonValue>, ProviderError> {
+    let invalid = |message: String| ProviderError::new(ProviderErrorKind::InvalidResponse, message);
+
+    let items: Vec<JsonValue> =
+        serde_json::from_slice(payload).map_err(|error| invalid(error.to_string()))?;
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let key = item.get("key")?.as_str()?;
+            let id = key.strip_prefix(FEATURE_FLAG_PREFIX)?;
+            Some((id, item.get("value").and_then(JsonValue::as_str)))
+        })
+        .map(|(id, value)| {
+            let flag = value
+                .and_then(|value| serde_json::from_str(value).ok())
+                .filter(JsonValue::is_object)
+                .ok_or_else(|| invalid(format!("Feature flag \"{id}\" is not valid JSON")))?;
+            Ok((id.to_string(), flag))
+        })
+        .collect()
+}
+
+// ============================================================
+//  Evaluation
+// ============================================================
+
+/// Evaluate `flag` for `context`, as the Microsoft feature management libraries do.
+fn evaluate(
+    flag_key: &str,
+    flag: &JsonValue,
+    context: &EvaluationContext,
+) -> (bool, EvaluationReason) {
+    if !flag
+        .get("enabled")
+        .and_then(JsonValue::as_bool)
+        .unwrap_or(false)
+    {
+        return (false, EvaluationReason::Disabled);
+    }
+
+    let conditions = flag.get("conditions");
+    let filters = conditions
+        .and_then(|conditions| conditions.get("client_filters"))
+        .and_then(JsonValue::as_array)
+        .map_or(&[][..], Vec::as_slice);
+    if filters.is_empty() {
+        return (true, EvaluationReason::Static);
+    }
+
+    let require_all = conditions
+        .and_then(|conditions| conditions.get("requirement_type"))
+        .and_then(JsonValue::as_str)
+        .map_or(false, |requirement_type| {
+            requirement_type.eq_ignore_ascii_case("All")
+        });
+
+    let mut passed = filters
+        .iter()
+        .map(|filter| evaluate_filter(flag_key, filter, context));
+    let enabled = if require_all {
+        passed.all(|passed| passed)
+    } else {
+        passed.any(|passed| passed)
+    };
+
+    (enabled, EvaluationReason::TargetingMatch)
+}
+
+/// Return whether `filter` passes for `context`. Unknown filters never pass.
+fn evaluate_filter(flag_key: &str, filter: &JsonValue, context: &EvaluationContext) -> bool {
+    let name = filter
+        .get("name")
+        .and_then(JsonValue::as_str)
+        .unwrap_or_default();
+    let parameters = filter.get("parameters").unwrap_or(&JsonValue::Null);
+
+    match name {
+        "Microsoft.Percentage" | "Percentage" => {
+            let percentage = parameter(parameters, "Value")
+                .and_then(JsonValue::as_f64)
+                .unwrap_or(0.0);
+
+            match &context.targeting_key {
+                Some(targeting_key) => {
+                    is_targeted(&format!("{targeting_key}\n{flag_key}"), percentage)
+                }
+                None => rand::random::<f64>() * 100.0 < percentage,
+            }
+        }
+        "Microsoft.Targeting" | "Targeting" => parameter(parameters, "Audience")
+            .map_or(false, |audience| {
+                is_in_audience(flag_key, audience, context)
+            }),
+        "Microsoft.TimeWindow" | "TimeWindow" => {
+            let now = OffsetDateTime::now_utc();
+            let bound = |name| {
+                parameter(parameters, name)
+                    .and_then(JsonValue::as_str)
+                    .and_then(parse_date)
+            };
+
+            bound("Start").map_or(true, |start| start <= now)
+                && bound("End").map_or(true, |end| now < end)
+        }
+        _ => false,
+    }
+}
+
+/// Return whether the user of `context` is in `audience`: not excluded, and either listed, or
+/// rolled out to with one of their groups or by default.
+fn is_in_audience(flag_key: &str, audience: &JsonValue, context: &EvaluationContext) -> bool {
+    let user_id = context.targeting_key.as_deref().unwrap_or_default();
+    let groups: Vec<&str> = match context.custom_fields.get("groups") {
+        Some(EvaluationContextFieldValue::List(groups)) => {
+            groups.iter().filter_map(|group| group.as_str()).collect()
+        }
+        Some(EvaluationContextFieldValue::String(group)) => vec![group.as_str()],
+        _ => Vec::new(),
+    };
+
+    let names = |value: Option<&JsonValue>| -> Vec<String> {
+        value
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|name| name.as_str().map(ToString::to_string))
+            .collect()
+    };
+
+    if let Some(exclusion) = parameter(audience, "Exclusion") {
+        let excluded_groups = names(parameter(exclusion, "Groups"));
+        if names(parameter(exclusion, "Users"))
+            .iter()
+            .any(|user| user == user_id)
+            || groups
+                .iter()
+                .any(|group| excluded_groups.iter().any(|excluded| excluded == group))
+        {
+            return false;
+        }
+    }
+
+    if !user_id.is_empty()
+        && names(parameter(audience, "Users"))
+            .iter()
+            .any(|user| user == user_id)
+    {
+        return true;
+    }
+
+    let rolled_out_groups = parameter(audience, "Groups")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten();
+    for group in rolled_out_groups {
+        let Some(name) = parameter(group, "Name").and_then(JsonValue::as_str) else {
+            continue;
+        };
+        if !groups.contains(&name) {
+            continue;
+        }
+
+        let percentage = parameter(group, "RolloutPercentage")
+            .and_then(JsonValue::as_f64)
+            .unwrap_or(0.0);
+        if is_targeted(&format!("{user_id}\n{flag_key}\n{name}"), percentage) {
+            return true;
+        }
+    }
+
+    let percentage = parameter(audience, "DefaultRolloutPercentage")
+        .and_then(JsonValue::as_f64)
+        .unwrap_or(0.0);
+    is_targeted(&format!("{user_id}\n{flag_key}"), percentage)
+}
+
+/// Return whether `context_id` falls within `percentage` of the audience: the first 4 bytes of
+/// its SHA-256 hash, as a little-endian integer, make up its share of `u32::MAX`.
+fn is_targeted(context_id: &str, percentage: f64) -> bool {
+    if percentage >= 100.0 {
+        return true;
+    }
+
+    let hash = Sha256::digest(context_id.as_bytes());
+    let marker = u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]);
+
+    f64::from(marker) / f64::from(u32::MAX) * 100.0 < percentage
+}
+
+/// Return parameter `name` of `parameters`, whose names are case-insensitive.
+fn parameter<'a>(parameters: &'a JsonValue, name: &str) -> Option<&'a JsonValue> {
+    parameters
+        .as_object()
+        .and_then(|parameters: &Map<String, JsonValue>| {
+            parameters
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value)
+        })
+}
+
+/// Parse a time window bound, as an HTTP date or in RFC 3339.
+fn parse_date(date: &str) -> Option<OffsetDateTime> {
+    let date = date.trim();
+    let rfc2822 = date
+        .strip_suffix(" GMT")
+        .map_or_else(|| date.to_string(), |date| format!("{date} +0000"));
+
+    OffsetDateTime::parse(&rfc2822, &Rfc2822)
+        .or_else(|_| OffsetDateTime::parse(date, &Rfc3339))
+        .ok()
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn create_flags() -> HashMap<String, JsonValue> {
+        let flag = |id: &str, value: JsonValue| {
+            json!({
+                "key": format!("{FEATURE_FLAG_PREFIX}{id}"),
+                "content_type": "application/vnd.microsoft.appconfig.ff+json;charset=utf-8",
+                "value": value.to_string()
+            })
+        };
+
+        let items = json!([
+            flag("checkout-v2", json!({ "id": "checkout-v2", "enabled": true })),
+            flag("legacy-search", json!({ "id": "legacy-search", "enabled": false })),
+            flag("beta", json!({
+                "id": "beta",
+                "enabled": true,
+                "conditions": {
+                    "client_filters": [{
+                        "name": "Microsoft.Targeting",
+                        "parameters": {
+                            "Audience": {
+                                "Users": ["alice"],
+                                "Groups": [{ "Name": "staff", "RolloutPercentage": 100 }],
+                                "DefaultRolloutPercentage": 0,
+                                "Exclusion": { "Users": ["mallory"], "Groups": ["contractors"] }
+                            }
+                        }
+                    }]
+                }
+            })),
+            { "key": "timeout", "value": "30" }
+        ]);
+
+        parse_flags(items.to_string().as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn evaluate_flags() {
+        let flags = create_flags();
+        assert_eq!(flags.len(), 3);
+
+        let context = EvaluationContext::default();
+        assert_eq!(
+            evaluate("checkout-v2", &flags["checkout-v2"], &context),
+            (true, EvaluationReason::Static)
+        );
+        assert_eq!(
+            evaluate("legacy-search", &flags["legacy-search"], &context),
+            (false, EvaluationReason::Disabled)
+        );
+    }
+
+    #[test]
+    fn evaluate_targeting_filter() {
+        let flags = create_flags();
+        let enabled = |context: EvaluationContext| evaluate("beta", &flags["beta"], &context).0;
+
+        assert!(enabled(
+            EvaluationContext::default().with_targeting_key("alice")
+        ));
+        assert!(enabled(
+            EvaluationContext::default()
+                .with_targeting_key("bob")
+                .with_custom_field("groups", vec!["staff".to_string()])
+        ));
+        assert!(!enabled(
+            EvaluationContext::default().with_targeting_key("bob")
+        ));
+        assert!(!enabled(
+            EvaluationContext::default()
+                .with_targeting_key("mallory")
+                .with_custom_field("groups", vec!["staff".to_string()])
+        ));
+        assert!(!enabled(
+            EvaluationContext::default()
+                .with_targeting_key("alice")
+                .with_custom_field("groups", vec!["contractors".to_string()])
+        ));
+    }
+
+    #[test]
+    fn roll_out_consistently() {
+        let targeted = (0..1000)
+            .filter(|index| is_targeted(&format!("user-{index}\nbeta"), 30.0))
+            .count();
+        assert!((250..350).contains(&targeted), "{targeted}");
+
+        assert_eq!(
+            is_targeted("alice\nbeta", 30.0),
+            is_targeted("alice\nbeta", 30.0)
+        );
+        assert!(is_targeted("alice\nbeta", 100.0));
+        assert!(!is_targeted("alice\nbeta", 0.0));
+    }
+
+    #[test]
+    fn evaluate_time_window_filter() {
+        let filter = |start: &str, end: &str| {
+            json!({
+                "name": "Microsoft.TimeWindow",
+                "parameters": { "Start": start, "End": end }
+            })
+        };
+        let context = EvaluationContext::default();
+
+        assert!(evaluate_filter(
+            "sale",
+            &filter("Wed, 01 May 2019 13:59:59 GMT", "2999-01-01T00:00:00Z"),
+            &context
+        ));
+        assert!(!evaluate_filter(
+            "sale",
+            &filter(
+                "Wed, 01 May 2019 13:59:59 GMT",
+                "Thu, 02 May 2019 13:59:59 GMT"
+            ),
+            &context
+        ));
+    }
+
+    #[test]
+    fn sign_requests() {
+        let date = http_date(OffsetDateTime::from_unix_timestamp(1_556_719_199).unwrap());
+        assert_eq!(date, "Wed, 01 May 2019 13:59:59 GMT");
+
+        let headers = sign(
+            "id",
+            b"secret",
+            "GET",
+            "/kv?api-version=1.0",
+            "contoso.azconfig.io",
+            &date,
+            b"",
+        );
+
+        assert_eq!(
+            headers[1],
+            (
+                "x-ms-content-sha256",
+                "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=".to_string()
+            )
+        );
+        assert!(headers[2].1.starts_with(
+            "HMAC-SHA256 Credential=id&SignedHeaders=x-ms-date;host;x-ms-content-sha256&Signature="
+        ));
+    }
+
+    #[test]
+    fn parse_connection_strings() {
+        let provider = AzureAppConfigProvider::from_connection_string(
+            "Endpoint=https://contoso.azconfig.io/;Id=abc;Secret=c2VjcmV0",
+        )
+        .unwrap();
+        assert_eq!(provider.endpoint, "https://contoso.azconfig.io");
+
+        let error = AzureAppConfigProvider::from_connection_string("Endpoint=https://contoso")
+            .err()
+            .unwrap();
+        assert_eq!(error.kind, ProviderErrorKind::Misconfigured);
+    }
+}
@@ -0,0 +1,128 @@
+// ============================================================
+//  Bucketing
+// ============================================================
+
+/// Return the ratio, within `[0, 1]`, `value` is consistently bucketed at, as flagd does: the
+/// absolute value of its murmur3 hash as a signed 32 bits integer, divided by `i32::MAX`.
+///
+/// The same value is bucketed at the same ratio across evaluations, processes and deployments.
+pub fn bucket_ratio(value: &str) -> f64 {
+    #[allow(clippy::cast_possible_wrap)]
+    let hash = murmur3_32(value.as_bytes(), 0) as i32;
+
+    f64::from(hash).abs() / f64::from(i32::MAX)
+}
+
+/// Return the variant of `distribution` `bucketing_value` falls into, each variant being served
+/// to a share of the values proportional to its weight, or `None` if all weights are 0.
+///
+/// Like the `fractional` targeting operator, flags usually bucket subjects by their flag key
+/// followed by their targeting key, so that a subject is served the same variant of a flag on
+/// every evaluation, while being bucketed independently for different flags.
+///
+/// ```
+/// use open_feature::provider::fractional_variant;
+///
+/// let distribution = [("new-checkout", 20), ("checkout", 80)];
+/// let variant = fractional_variant("checkout-flowuser-42", &distribution);
+///
+/// assert!(variant.is_some());
+/// assert_eq!(variant, fractional_variant("checkout-flowuser-42", &distribution));
+/// ```
+pub fn fractional_variant<'a, V>(
+    bucketing_value: &str,
+    distribution: &'a [(V, u32)],
+) -> Option<&'a V> {
+    let total_weight: u64 = distribution
+        .iter()
+        .map(|(_, weight)| u64::from(*weight))
+        .sum();
+    #[allow(clippy::cast_precision_loss)]
+    let bucket = bucket_ratio(bucketing_value) * total_weight as f64;
+    let mut range_end = 0;
+
+    distribution.iter().find_map(|(variant, weight)| {
+        range_end += u64::from(*weight);
+
+        #[allow(clippy::cast_precision_loss)]
+        (bucket < range_end as f64).then_some(variant)
+    })
+}
+
+/// Return the 32 bits murmur3 hash of `data` (the x86 variant).
+fn murmur3_32(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let mix = |k: u32| k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+    let mut hash = seed;
+    let chunks = data.chunks_exact(4);
+    let tail = chunks.remainder();
+
+    for chunk in chunks {
+        hash ^= mix(u32::from_le_bytes(
+            chunk.try_into().expect("A chunk of 4 bytes"),
+        ));
+        hash = hash
+            .rotate_left(13)
+            .wrapping_mul(5)
+            .wrapping_add(0xe654_6b64);
+    }
+
+    if !tail.is_empty() {
+        let k = tail
+            .iter()
+            .rev()
+            .fold(0, |k, byte| (k << 8) | u32::from(*byte));
+        hash ^= mix(k);
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        hash ^= data.len() as u32;
+    }
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^ (hash >> 16)
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash() {
+        assert_eq!(murmur3_32(b"", 0), 0);
+        assert_eq!(murmur3_32(b"", 1), 0x514e_28b7);
+        assert_eq!(murmur3_32(b"hello", 0), 0x248b_fa47);
+        assert_eq!(murmur3_32(b"Hello, world!", 0), 0xc036_3e43);
+        assert_eq!(
+            murmur3_32(b"The quick brown fox jumps over the lazy dog", 0),
+            0x2e4f_f723
+        );
+    }
+
+    #[test]
+    fn distribute_fractions() {
+        let distribution = [("a", 20), ("b", 80), ("never", 0)];
+        let mut counts = std::collections::HashMap::new();
+
+        for index in 0..10_000 {
+            let variant = fractional_variant(&format!("flaguser-{}", index), &distribution);
+            *counts.entry(*variant.unwrap()).or_insert(0) += 1;
+        }
+
+        assert!((1800..2200).contains(&counts["a"]), "{:?}", counts);
+        assert!(!counts.contains_key("never"));
+
+        assert_eq!(fractional_variant("flaguser-1", &[("a", 0)]), None);
+        assert_eq!(fractional_variant::<&str>("flaguser-1", &[]), None);
+    }
+}
@@ -0,0 +1,385 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationResult, ProviderError,
+    ProviderErrorKind, StructValue, TrackingEventDetails, Value,
+};
+
+use super::{
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+// ============================================================
+//  CircuitState
+// ============================================================
+
+/// The state of a [`CircuitBreakerProvider`].
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum CircuitState {
+    /// Resolutions go to the inner provider.
+    Closed,
+
+    /// Resolutions fail right away, until the cooldown elapses.
+    Open,
+
+    /// A single resolution probes the inner provider, closing the circuit if it succeeds and
+    /// opening it again otherwise. Other resolutions fail right away meanwhile.
+    HalfOpen,
+}
+
+// ============================================================
+//  CircuitBreakerProvider
+// ============================================================
+
+/// A provider that delegates to `inner` until it fails too many times in a row, then fails
+/// right away for a cooldown instead of waiting for a degraded backend, so that callers fall back
+/// to their default values without delay.
+///
+/// Only provider faults, carried by [`EvaluationErrorCode::Provider`], count as failures: errors
+/// about the flag or the evaluation context show that the backend is responsive. Resolutions
+/// rejected by the open circuit fail with a non-retryable [`ProviderErrorKind::Unavailable`]
+/// error.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use open_feature::provider::{CircuitBreakerProvider, NoOpProvider};
+///
+/// let provider = CircuitBreakerProvider::new(NoOpProvider::default())
+///     .with_failure_threshold(3)
+///     .with_cooldown(Duration::from_secs(10));
+/// ```
+pub struct CircuitBreakerProvider<P> {
+    inner: P,
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+enum BreakerState {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// Probing since `since`. A probe taking longer than the cooldown, such as one cancelled
+    /// midway, is superseded by the next resolution.
+    HalfOpen {
+        since: Instant,
+    },
+}
+
+impl<P: FeatureProvider> CircuitBreakerProvider<P> {
+    /// The number of consecutive failures opening the circuit by default.
+    pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+    /// The time the circuit stays open by default.
+    pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+    /// Create a closed circuit breaker delegating to `inner`.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            failure_threshold: Self::DEFAULT_FAILURE_THRESHOLD,
+            cooldown: Self::DEFAULT_COOLDOWN,
+            state: Mutex::new(BreakerState::Closed { failures: 0 }),
+        }
+    }
+
+    /// Set the number of consecutive failures opening the circuit.
+    #[must_use]
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+
+    /// Set the time the circuit stays open before being probed.
+    #[must_use]
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Return the current state of the circuit. An open circuit whose cooldown elapsed is
+    /// reported half-open, as the next resolution probes it.
+    pub fn state(&self) -> CircuitState {
+        match *self.state.lock().unwrap() {
+            BreakerState::Closed { .. } => CircuitState::Closed,
+            BreakerState::Open { until } if until > Instant::now() => CircuitState::Open,
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Return `true` if a resolution may go to the inner provider, turning an open circuit
+    /// whose cooldown elapsed half-open.
+    fn acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        let probe = match *state {
+            BreakerState::Closed { .. } => return true,
+            BreakerState::Open { until } => until <= now,
+            BreakerState::HalfOpen { since } => since + self.cooldown <= now,
+        };
+
+        if probe {
+            *state = BreakerState::HalfOpen { since: now };
+        }
+
+        probe
+    }
+
+    fn record(&self, failed: bool) {
+        let mut state = self.state.lock().unwrap();
+
+        *state = match (&*state, failed) {
+            (_, false) => BreakerState::Closed { failures: 0 },
+            (BreakerState::Closed { failures }, true) if failures + 1 < self.failure_threshold => {
+                BreakerState::Closed {
+                    failures: failures + 1,
+                }
+            }
+            (_, true) => BreakerState::Open {
+                until: Instant::now() + self.cooldown,
+            },
+        };
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        if !self.acquire() {
+            return Err(ProviderError::new(
+                ProviderErrorKind::Unavailable,
+                format!(
+                    "Circuit breaker of provider \"{}\" is open",
+                    self.inner.metadata().name
+                ),
+            )
+            .with_retryable(false)
+            .into());
+        }
+
+        let result = T::resolve(&self.inner, flag_key, evaluation_context).await;
+
+        self.record(matches!(
+            result,
+            Err(EvaluationError {
+                code: EvaluationErrorCode::Provider(_),
+                ..
+            })
+        ));
+
+        result
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for CircuitBreakerProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.inner.initialize(context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.inner
+            .track(event_name, evaluation_context, tracking_event_details)
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.inner.resolve_all(evaluation_context).await
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::{
+        provider::{MockFeatureProvider, ProviderStatus},
+        OpenFeature,
+    };
+
+    #[tokio::test]
+    async fn trip_and_recover() {
+        let failing = Arc::new(AtomicBool::new(true));
+        let calls = Arc::new(AtomicU32::new(0));
+
+        let mut inner = MockFeatureProvider::new();
+        inner.expect_initialize().returning(|_| Ok(()));
+        inner.expect_status().returning(|| ProviderStatus::Ready);
+        inner.expect_shutdown().returning(|| ());
+        inner
+            .expect_metadata()
+            .return_const(ProviderMetadata::new("Remote"));
+        inner.expect_event_emitter().returning(|| None);
+
+        let (is_failing, counted) = (failing.clone(), calls.clone());
+        inner.expect_resolve_bool_value().returning(move |_, _| {
+            counted.fetch_add(1, Ordering::SeqCst);
+
+            if is_failing.load(Ordering::SeqCst) {
+                Err(ProviderError::new(ProviderErrorKind::Unavailable, "503").into())
+            } else {
+                Ok(ResolutionDetails::new(true))
+            }
+        });
+
+        let provider = CircuitBreakerProvider::new(inner)
+            .with_failure_threshold(2)
+            .with_cooldown(Duration::from_millis(20));
+
+        let context = EvaluationContext::default();
+
+        for _ in 0..2 {
+            assert!(provider
+                .resolve_bool_value("checkout-v2", &context)
+                .await
+                .is_err());
+        }
+        assert_eq!(provider.state(), CircuitState::Open);
+
+        // Rejected without calling the inner provider.
+        let error = provider
+            .resolve_bool_value("checkout-v2", &context)
+            .await
+            .unwrap_err();
+        assert!(!error.is_retryable());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // The probe fails, opening the circuit again.
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(provider.state(), CircuitState::HalfOpen);
+        assert!(provider
+            .resolve_bool_value("checkout-v2", &context)
+            .await
+            .is_err());
+        assert_eq!(provider.state(), CircuitState::Open);
+
+        // The probe succeeds, closing the circuit.
+        failing.store(false, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(
+            provider
+                .resolve_bool_value("checkout-v2", &context)
+                .await
+                .unwrap()
+                .value
+        );
+        assert_eq!(provider.state(), CircuitState::Closed);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn fail_fast_while_open() {
+        let mut inner = MockFeatureProvider::new();
+        inner.expect_initialize().returning(|_| Ok(()));
+        inner.expect_status().returning(|| ProviderStatus::Ready);
+        inner.expect_shutdown().returning(|| ());
+        inner
+            .expect_metadata()
+            .return_const(ProviderMetadata::new("Remote"));
+        inner.expect_event_emitter().returning(|| None);
+        inner.expect_resolve_int_value().times(1).returning(|_, _| {
+            Err(ProviderError::new(ProviderErrorKind::Timeout, "Timed out").into())
+        });
+
+        let mut api = OpenFeature::default();
+        api.set_provider(CircuitBreakerProvider::new(inner).with_failure_threshold(1))
+            .await
+            .unwrap();
+
+        let client = api.create_client();
+
+        for _ in 0..3 {
+            assert_eq!(
+                client
+                    .get_int_value("max-retries", None, None)
+                    .await
+                    .unwrap_or(3),
+                3
+            );
+        }
+    }
+}
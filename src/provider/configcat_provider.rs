@@ -0,0 +1,1218 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use async_trait::async_trait;
+use serde_json::{Map, Value as JsonValue};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationReason, EvaluationResult,
+    ProviderError, ProviderErrorKind, StructValue, Value,
+};
+
+use super::{
+    json_logic::{to_string, SemVer},
+    EventEmitter, FeatureProvider, Fetched, FlagType, FlagValue, PollingProvider, PollingSource,
+    ProviderMetadata, ResolutionDetails,
+};
+
+/// The number of redirects to another CDN followed by a single fetch.
+const MAX_REDIRECTS: usize = 2;
+
+// ============================================================
+//  ConfigCatProvider
+// ============================================================
+
+/// A provider evaluating [ConfigCat](https://configcat.com) flags locally, from the config polled
+/// from the ConfigCat CDN with an SDK key.
+///
+/// Targeting rules, percentage options, segments and prerequisite flags are evaluated as the
+/// ConfigCat SDKs do, against the user object mapped from the evaluation context: the targeting
+/// key is the `Identifier`, the `email` and `country` attributes are the `Email` and `Country`,
+/// and the other attributes keep their name. Date-times compare as Unix timestamps.
+///
+/// The variant is the variation ID of the served value. A `PROVIDER_CONFIGURATION_CHANGED` event
+/// is emitted with the changed flags whenever the config changes.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use open_feature::provider::ConfigCatProvider;
+///
+/// let provider = ConfigCatProvider::new("configcat-sdk-1/#YOUR-SDK-KEY#")
+///     .with_base_url(ConfigCatProvider::EU_BASE_URL)
+///     .with_polling_interval(Duration::from_secs(30));
+/// ```
+pub struct ConfigCatProvider {
+    metadata: ProviderMetadata,
+    sdk_key: String,
+    base_url: Option<String>,
+    timeout: Duration,
+    polling_interval: Duration,
+    polling: PollingProvider<ConfigCatSource>,
+}
+
+impl ConfigCatProvider {
+    /// The global CDN of ConfigCat, redirecting to the CDN of the data governance of the config.
+    pub const GLOBAL_BASE_URL: &'static str = "https://cdn-global.configcat.com";
+
+    /// The CDN of ConfigCat for configs restricted to the EU.
+    pub const EU_BASE_URL: &'static str = "https://cdn-eu.configcat.com";
+
+    /// The interval the config is polled at by default.
+    pub const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_secs(60);
+
+    /// The time after which a request is abandoned by default.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Create a provider polling the config of `sdk_key` once initialized.
+    pub fn new(sdk_key: impl Into<String>) -> Self {
+        let sdk_key = sdk_key.into();
+
+        Self {
+            metadata: ProviderMetadata::new("ConfigCat Provider"),
+            polling: create_polling(
+                &sdk_key,
+                None,
+                Self::DEFAULT_TIMEOUT,
+                Self::DEFAULT_POLLING_INTERVAL,
+            ),
+            sdk_key,
+            base_url: None,
+            timeout: Self::DEFAULT_TIMEOUT,
+            polling_interval: Self::DEFAULT_POLLING_INTERVAL,
+        }
+    }
+
+    /// Fetch the config from `base_url`, such as [`Self::EU_BASE_URL`] or a ConfigCat proxy,
+    /// instead of [`Self::GLOBAL_BASE_URL`].
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into().trim_end_matches('/').to_string());
+        self.rebuild()
+    }
+
+    /// Abandon requests taking longer than `timeout`.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.rebuild()
+    }
+
+    /// Poll the config every `interval` instead of [`Self::DEFAULT_POLLING_INTERVAL`].
+    #[must_use]
+    pub fn with_polling_interval(mut self, interval: Duration) -> Self {
+        self.polling_interval = interval;
+        self.rebuild()
+    }
+
+    fn rebuild(mut self) -> Self {
+        self.polling = create_polling(
+            &self.sdk_key,
+            self.base_url.clone(),
+            self.timeout,
+            self.polling_interval,
+        );
+        self
+    }
+
+    fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let config = self.polling.configuration().ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ProviderNotReady)
+                .message("The ConfigCat config has not been fetched yet")
+                .build()
+        })?;
+
+        let details = config.evaluate(flag_key, &User::from(evaluation_context))?;
+
+        let value = match (T::FLAG_TYPE, details.value) {
+            #[allow(clippy::cast_precision_loss)]
+            (FlagType::Float, Value::Int(value)) => Value::Float(value as f64),
+            (_, value) => value,
+        };
+
+        Ok(ResolutionDetails {
+            value: T::from_value(value).ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::TypeMismatch)
+                    .message(format!("Expected a {:?} flag", T::FLAG_TYPE))
+                    .build()
+            })?,
+            variant: details.variant,
+            reason: details.reason,
+            flag_metadata: details.flag_metadata,
+        })
+    }
+}
+
+fn create_polling(
+    sdk_key: &str,
+    base_url: Option<String>,
+    timeout: Duration,
+    interval: Duration,
+) -> PollingProvider<ConfigCatSource> {
+    let source = ConfigCatSource {
+        client: reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("The HTTP client can be built"),
+        sdk_key: sdk_key.to_string(),
+        custom_base_url: base_url.is_some(),
+        base_url: Mutex::new(
+            base_url.unwrap_or_else(|| ConfigCatProvider::GLOBAL_BASE_URL.to_string()),
+        ),
+    };
+
+    PollingProvider::new("ConfigCat Provider", source, interval)
+}
+
+#[async_trait]
+impl FeatureProvider for ConfigCatProvider {
+    async fn initialize(&mut self, _context: &EvaluationContext) -> Result<(), ProviderError> {
+        match self.polling.start().await {
+            Err(error) if !error.is_retryable() => Err(error),
+            // A transient failure is retried by the next poll.
+            _ => Ok(()),
+        }
+    }
+
+    async fn shutdown(&self) {
+        self.polling.stop();
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        Some(self.polling.event_emitter())
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        Err(EvaluationError::builder()
+            .code(EvaluationErrorCode::TypeMismatch)
+            .message(format!("ConfigCat flag \"{flag_key}\" cannot be a struct"))
+            .build())
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let config = self.polling.configuration().ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ProviderNotReady)
+                .message("The ConfigCat config has not been fetched yet")
+                .build()
+        })?;
+
+        let user = User::from(evaluation_context);
+
+        Ok(config
+            .settings
+            .keys()
+            .filter_map(|key| Some((key.clone(), config.evaluate(key, &user).ok()?)))
+            .collect())
+    }
+}
+
+// ============================================================
+//  ConfigCatSource
+// ============================================================
+
+/// Fetches the config from the ConfigCat CDN, following its redirects to the CDN of the data
+/// governance of the config.
+struct ConfigCatSource {
+    client: reqwest::Client,
+    sdk_key: String,
+    custom_base_url: bool,
+    base_url: Mutex<String>,
+}
+
+#[async_trait]
+impl PollingSource for ConfigCatSource {
+    type Configuration = ConfigCatConfig;
+
+    async fn fetch(&self, etag: Option<&str>) -> Result<Fetched, ProviderError> {
+        let mut fetched = Fetched::NotModified;
+
+        for _ in 0..=MAX_REDIRECTS {
+            let base_url = self.base_url.lock().unwrap().clone();
+            fetched = self.fetch_from(&base_url, etag).await?;
+
+            let Fetched::Payload { payload, .. } = &fetched else {
+                break;
+            };
+
+            // The preferences tell whether the config is served by another CDN.
+            let preferences = serde_json::from_slice::<JsonValue>(payload)
+                .ok()
+                .and_then(|config| config.get("p").cloned());
+            let redirect_url = preferences
+                .as_ref()
+                .and_then(|preferences| preferences.get("u"))
+                .and_then(JsonValue::as_str)
+                .map(|url| url.trim_end_matches('/'));
+            let redirect = preferences
+                .as_ref()
+                .and_then(|preferences| preferences.get("r"))
+                .and_then(JsonValue::as_u64);
+
+            match (redirect_url, redirect) {
+                (Some(url), Some(1)) if !self.custom_base_url && url != base_url => {
+                    *self.base_url.lock().unwrap() = url.to_string();
+                }
+                (Some(url), Some(2)) if url != base_url => {
+                    *self.base_url.lock().unwrap() = url.to_string();
+                }
+                _ => break,
+            }
+        }
+
+        Ok(fetched)
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<ConfigCatConfig, ProviderError> {
+        let config: JsonValue = serde_json::from_slice(payload).map_err(|error| {
+            ProviderError::new(ProviderErrorKind::InvalidResponse, error.to_string())
+                .with_source(error)
+        })?;
+
+        ConfigCatConfig::parse(&config)
+            .map_err(|message| ProviderError::new(ProviderErrorKind::InvalidResponse, message))
+    }
+
+    fn flags_changed(
+        &self,
+        previous: &ConfigCatConfig,
+        current: &ConfigCatConfig,
+    ) -> Option<Vec<String>> {
+        // Segments and the salt apply to any flag.
+        if previous.segments != current.segments || previous.salt != current.salt {
+            return None;
+        }
+
+        let mut flags_changed: Vec<_> = previous
+            .settings
+            .keys()
+            .chain(current.settings.keys())
+            .filter(|key| previous.settings.get(*key) != current.settings.get(*key))
+            .cloned()
+            .collect();
+        flags_changed.sort();
+        flags_changed.dedup();
+
+        Some(flags_changed)
+    }
+}
+
+impl ConfigCatSource {
+    async fn fetch_from(
+        &self,
+        base_url: &str,
+        etag: Option<&str>,
+    ) -> Result<Fetched, ProviderError> {
+        let url = format!(
+            "{}/configuration-files/{}/config_v6.json",
+            base_url, self.sdk_key
+        );
+
+        let mut request = self.client.get(url);
+        if let Some(etag) = etag {
+            request = request.header("If-None-Match", etag);
+        }
+
+        let response = request.send().await.map_err(|error| {
+            let kind = if error.is_timeout() {
+                ProviderErrorKind::Timeout
+            } else {
+                ProviderErrorKind::Network
+            };
+
+            ProviderError::new(kind, error.to_string()).with_source(error)
+        })?;
+
+        let status = response.status().as_u16();
+        let kind = match status {
+            200 => {
+                let etag = response
+                    .headers()
+                    .get("etag")
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(ToString::to_string);
+                let payload = response.bytes().await.map_err(|error| {
+                    ProviderError::new(ProviderErrorKind::Network, error.to_string())
+                        .with_source(error)
+                })?;
+
+                return Ok(Fetched::Payload {
+                    payload: payload.to_vec(),
+                    etag,
+                });
+            }
+            304 => return Ok(Fetched::NotModified),
+            // The CDN answers 404 for unknown SDK keys.
+            401 | 403 | 404 => ProviderErrorKind::Unauthorized,
+            429 => ProviderErrorKind::RateLimited,
+            500..=599 => ProviderErrorKind::Unavailable,
+            _ => ProviderErrorKind::InvalidResponse,
+        };
+
+        Err(ProviderError::new(
+            kind,
+            format!("The ConfigCat CDN answered with {status}"),
+        ))
+    }
+}
+
+// ============================================================
+//  Config
+// ============================================================
+
+/// A parsed ConfigCat config, in the `config_v6.json` format.
+struct ConfigCatConfig {
+    settings: HashMap<String, Setting>,
+    segments: Vec<Segment>,
+    salt: String,
+}
+
+#[derive(PartialEq, Debug)]
+struct Setting {
+    value: ServedValue,
+    percentage_attribute: Option<String>,
+    rules: Vec<TargetingRule>,
+    percentage_options: Vec<PercentageOption>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct ServedValue {
+    value: Value,
+    variation_id: Option<String>,
+}
+
+#[derive(PartialEq, Debug)]
+struct TargetingRule {
+    conditions: Vec<Condition>,
+    outcome: RuleOutcome,
+}
+
+#[derive(PartialEq, Debug)]
+enum RuleOutcome {
+    Value(ServedValue),
+    Percentage(Vec<PercentageOption>),
+}
+
+#[derive(PartialEq, Debug)]
+struct PercentageOption {
+    percentage: u64,
+    value: ServedValue,
+}
+
+#[derive(PartialEq, Debug)]
+enum Condition {
+    User(UserCondition),
+    Segment {
+        index: usize,
+        is_in: bool,
+    },
+    Prerequisite {
+        flag_key: String,
+        equals: bool,
+        value: Value,
+    },
+}
+
+#[derive(PartialEq, Debug)]
+struct UserCondition {
+    attribute: String,
+    comparator: u64,
+    text: Option<String>,
+    number: Option<f64>,
+    list: Vec<String>,
+}
+
+#[derive(PartialEq, Debug)]
+struct Segment {
+    name: String,
+    conditions: Vec<UserCondition>,
+}
+
+impl ConfigCatConfig {
+    fn parse(config: &JsonValue) -> Result<Self, String> {
+        let settings = match config.get("f") {
+            Some(JsonValue::Object(settings)) => settings
+                .iter()
+                .map(|(key, setting)| Ok((key.clone(), parse_setting(setting)?)))
+                .collect::<Result<_, String>>()?,
+            None => HashMap::new(),
+            Some(_) => return Err("The settings are not an object".to_string()),
+        };
+
+        let segments = as_array(config.get("s"))
+            .iter()
+            .map(|segment| {
+                Ok(Segment {
+                    name: segment
+                        .get("n")
+                        .and_then(JsonValue::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    conditions: as_array(segment.get("r"))
+                        .iter()
+                        .map(parse_user_condition)
+                        .collect::<Result<_, String>>()?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        let salt = config
+            .get("p")
+            .and_then(|preferences| preferences.get("s"))
+            .and_then(JsonValue::as_str)
+            .unwrap_or_default()
+            .to_string();
+
+        Ok(Self {
+            settings,
+            segments,
+            salt,
+        })
+    }
+
+    /// Evaluate the setting `key` for `user`.
+    fn evaluate(&self, key: &str, user: &User) -> EvaluationResult<ResolutionDetails<Value>> {
+        Evaluation {
+            config: self,
+            user,
+            visited: Vec::new(),
+        }
+        .evaluate(key)
+    }
+}
+
+fn parse_setting(setting: &JsonValue) -> Result<Setting, String> {
+    Ok(Setting {
+        value: parse_served_value(setting)?,
+        percentage_attribute: setting
+            .get("a")
+            .and_then(JsonValue::as_str)
+            .map(ToString::to_string),
+        rules: as_array(setting.get("r"))
+            .iter()
+            .map(|rule| {
+                let conditions = as_array(rule.get("c"))
+                    .iter()
+                    .map(parse_condition)
+                    .collect::<Result<_, String>>()?;
+
+                let outcome = match (rule.get("s"), rule.get("p")) {
+                    (Some(served), _) => RuleOutcome::Value(parse_served_value(served)?),
+                    (None, Some(options)) => {
+                        RuleOutcome::Percentage(parse_percentage_options(Some(options))?)
+                    }
+                    (None, None) => return Err("A targeting rule serves nothing".to_string()),
+                };
+
+                Ok(TargetingRule {
+                    conditions,
+                    outcome,
+                })
+            })
+            .collect::<Result<_, String>>()?,
+        percentage_options: parse_percentage_options(setting.get("p"))?,
+    })
+}
+
+fn parse_percentage_options(options: Option<&JsonValue>) -> Result<Vec<PercentageOption>, String> {
+    as_array(options)
+        .iter()
+        .map(|option| {
+            Ok(PercentageOption {
+                percentage: option.get("p").and_then(JsonValue::as_u64).unwrap_or(0),
+                value: parse_served_value(option)?,
+            })
+        })
+        .collect()
+}
+
+/// Parse the value at `v` of `served`, and its variation ID at `i`.
+fn parse_served_value(served: &JsonValue) -> Result<ServedValue, String> {
+    let value = served
+        .get("v")
+        .ok_or_else(|| "A setting value is missing".to_string())?;
+
+    let value = if let Some(value) = value.get("b").and_then(JsonValue::as_bool) {
+        Value::Bool(value)
+    } else if let Some(value) = value.get("s").and_then(JsonValue::as_str) {
+        Value::String(value.to_string())
+    } else if let Some(value) = value.get("i").and_then(JsonValue::as_i64) {
+        Value::Int(value)
+    } else if let Some(value) = value.get("d").and_then(JsonValue::as_f64) {
+        Value::Float(value)
+    } else {
+        return Err(format!("Invalid setting value {value}"));
+    };
+
+    Ok(ServedValue {
+        value,
+        variation_id: served
+            .get("i")
+            .and_then(JsonValue::as_str)
+            .map(ToString::to_string),
+    })
+}
+
+fn parse_condition(condition: &JsonValue) -> Result<Condition, String> {
+    if let Some(user_condition) = condition.get("u") {
+        return Ok(Condition::User(parse_user_condition(user_condition)?));
+    }
+
+    if let Some(segment_condition) = condition.get("s") {
+        return Ok(Condition::Segment {
+            index: segment_condition
+                .get("s")
+                .and_then(JsonValue::as_u64)
+                .and_then(|index| usize::try_from(index).ok())
+                .ok_or_else(|| "A segment condition has no segment".to_string())?,
+            is_in: segment_condition.get("c").and_then(JsonValue::as_u64) == Some(0),
+        });
+    }
+
+    if let Some(prerequisite) = condition.get("p") {
+        return Ok(Condition::Prerequisite {
+            flag_key: prerequisite
+                .get("f")
+                .and_then(JsonValue::as_str)
+                .ok_or_else(|| "A prerequisite flag condition has no flag".to_string())?
+                .to_string(),
+            equals: prerequisite.get("c").and_then(JsonValue::as_u64) == Some(0),
+            value: parse_served_value(prerequisite)?.value,
+        });
+    }
+
+    Err(format!("Unknown condition {condition}"))
+}
+
+fn parse_user_condition(condition: &JsonValue) -> Result<UserCondition, String> {
+    Ok(UserCondition {
+        attribute: condition
+            .get("a")
+            .and_then(JsonValue::as_str)
+            .ok_or_else(|| "A user condition has no attribute".to_string())?
+            .to_string(),
+        comparator: condition
+            .get("c")
+            .and_then(JsonValue::as_u64)
+            .ok_or_else(|| "A user condition has no comparator".to_string())?,
+        text: condition
+            .get("s")
+            .and_then(JsonValue::as_str)
+            .map(ToString::to_string),
+        number: condition.get("d").and_then(JsonValue::as_f64),
+        list: as_array(condition.get("l"))
+            .iter()
+            .filter_map(JsonValue::as_str)
+            .map(ToString::to_string)
+            .collect(),
+    })
+}
+
+fn as_array(value: Option<&JsonValue>) -> &[JsonValue] {
+    value
+        .and_then(JsonValue::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+// ============================================================
+//  User
+// ============================================================
+
+/// The ConfigCat user object of an evaluation context.
+struct User {
+    attributes: Map<String, JsonValue>,
+}
+
+impl From<&EvaluationContext> for User {
+    fn from(context: &EvaluationContext) -> Self {
+        let JsonValue::Object(context) = JsonValue::from(context) else {
+            unreachable!("Evaluation contexts convert to JSON objects");
+        };
+
+        let attributes = context
+            .into_iter()
+            .map(|(name, value)| {
+                let name = match name.as_str() {
+                    "targetingKey" => "Identifier".to_string(),
+                    "email" => "Email".to_string(),
+                    "country" => "Country".to_string(),
+                    _ => name,
+                };
+                (name, value)
+            })
+            .collect();
+
+        Self { attributes }
+    }
+}
+
+impl User {
+    fn text(&self, attribute: &str) -> Option<String> {
+        self.attributes
+            .get(attribute)
+            .filter(|value| !value.is_null())
+            .map(to_string)
+    }
+
+    fn number(&self, attribute: &str) -> Option<f64> {
+        match self.attributes.get(attribute)? {
+            JsonValue::Number(value) => value.as_f64(),
+            JsonValue::String(value) => value.trim().replace(',', ".").parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Return the list of strings at `attribute`, given as an array or as a JSON array string.
+    fn list(&self, attribute: &str) -> Option<Vec<String>> {
+        let parsed;
+        let items = match self.attributes.get(attribute)? {
+            JsonValue::Array(items) => items,
+            JsonValue::String(text) => {
+                parsed = serde_json::from_str::<Vec<JsonValue>>(text).ok()?;
+                &parsed
+            }
+            _ => return None,
+        };
+
+        items
+            .iter()
+            .map(|item| item.as_str().map(ToString::to_string))
+            .collect()
+    }
+}
+
+// ============================================================
+//  Evaluation
+// ============================================================
+
+/// The evaluation of a setting, and of the prerequisite flags it depends on.
+struct Evaluation<'a> {
+    config: &'a ConfigCatConfig,
+    user: &'a User,
+    visited: Vec<String>,
+}
+
+impl Evaluation<'_> {
+    fn evaluate(&mut self, key: &str) -> EvaluationResult<ResolutionDetails<Value>> {
+        let setting = self.config.settings.get(key).ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .message(format!("ConfigCat flag \"{key}\" does not exist"))
+                .build()
+        })?;
+
+        if self.visited.iter().any(|visited| visited == key) {
+            return Err(EvaluationError::builder()
+                .code(EvaluationErrorCode::ParseError)
+                .message(format!(
+                    "Circular prerequisite flags: {} -> {}",
+                    self.visited.join(" -> "),
+                    key
+                ))
+                .build());
+        }
+        self.visited.push(key.to_string());
+
+        let mut result = None;
+
+        for rule in &setting.rules {
+            if !self.matches_all(key, &rule.conditions)? {
+                continue;
+            }
+
+            match &rule.outcome {
+                RuleOutcome::Value(served) => {
+                    result = Some((served, EvaluationReason::TargetingMatch));
+                }
+                RuleOutcome::Percentage(options) => {
+                    // Rules whose percentage attribute is missing are skipped.
+                    match self.percentage_option(key, setting, options) {
+                        Some(served) => result = Some((served, EvaluationReason::Split)),
+                        None => continue,
+                    }
+                }
+            }
+            break;
+        }
+
+        let (served, reason) = result
+            .or_else(|| {
+                self.percentage_option(key, setting, &setting.percentage_options)
+                    .map(|served| (served, EvaluationReason::Split))
+            })
+            .unwrap_or((&setting.value, EvaluationReason::Default));
+
+        self.visited.pop();
+
+        Ok(ResolutionDetails {
+            value: served.value.clone(),
+            variant: served.variation_id.clone(),
+            reason: Some(reason),
+            flag_metadata: None,
+        })
+    }
+
+    fn matches_all(&mut self, key: &str, conditions: &[Condition]) -> EvaluationResult<bool> {
+        for condition in conditions {
+            let matches = match condition {
+                Condition::User(condition) => self.matches(key, condition),
+                Condition::Segment { index, is_in } => {
+                    let segment = self.config.segments.get(*index).ok_or_else(|| {
+                        EvaluationError::builder()
+                            .code(EvaluationErrorCode::ParseError)
+                            .message(format!("Segment {index} does not exist"))
+                            .build()
+                    })?;
+
+                    // A segment whose attributes are missing matches neither way.
+                    let in_segment = segment
+                        .conditions
+                        .iter()
+                        .all(|condition| self.matches(key, condition));
+                    let known = segment
+                        .conditions
+                        .iter()
+                        .all(|condition| self.user.attributes.contains_key(&condition.attribute));
+
+                    known && in_segment == *is_in
+                }
+                Condition::Prerequisite {
+                    flag_key,
+                    equals,
+                    value,
+                } => {
+                    let details = self.evaluate(flag_key)?;
+                    (details.value == *value) == *equals
+                }
+            };
+
+            if !matches {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Return whether the user satisfies `condition` when evaluating setting `key`. Conditions
+    /// on missing attributes are never satisfied.
+    fn matches(&self, key: &str, condition: &UserCondition) -> bool {
+        let attribute = condition.attribute.as_str();
+        let list = &condition.list;
+        let hash = |text: &str| {
+            format!(
+                "{:x}",
+                Sha256::digest(format!("{}{}{}", text, self.config.salt, key))
+            )
+        };
+
+        match condition.comparator {
+            // Is one of, is not one of, in cleartext and hashed.
+            0 | 1 | 16 | 17 => self.user.text(attribute).map_or(false, |text| {
+                let text = if condition.comparator >= 16 {
+                    hash(&text)
+                } else {
+                    text
+                };
+                list.contains(&text) == (condition.comparator % 2 == 0)
+            }),
+            // Contains any of, does not contain any of.
+            2 | 3 => self.user.text(attribute).map_or(false, |text| {
+                list.iter().any(|item| text.contains(item.as_str())) == (condition.comparator == 2)
+            }),
+            // Semantic version is one of, is not one of, ignoring empty items.
+            4 | 5 => self.semver(attribute).map_or(false, |version| {
+                list.iter()
+                    .filter(|item| !item.trim().is_empty())
+                    .map(|item| SemVer::parse(item))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_or(false, |items| {
+                        items.contains(&version) == (condition.comparator == 4)
+                    })
+            }),
+            // Semantic version <, <=, >, >=.
+            6..=9 => {
+                let target = condition.text.as_deref().map(SemVer::parse);
+                match (self.semver(attribute), target) {
+                    (Some(version), Some(Ok(target))) => match condition.comparator {
+                        6 => version < target,
+                        7 => version <= target,
+                        8 => version > target,
+                        _ => version >= target,
+                    },
+                    _ => false,
+                }
+            }
+            // Number =, !=, <, <=, >, >=, and date before, after as Unix timestamps.
+            #[allow(clippy::float_cmp)]
+            10..=15 | 18 | 19 => match (self.user.number(attribute), condition.number) {
+                (Some(number), Some(target)) => match condition.comparator {
+                    10 => number == target,
+                    11 => number != target,
+                    12 | 18 => number < target,
+                    13 => number <= target,
+                    14 | 19 => number > target,
+                    _ => number >= target,
+                },
+                _ => false,
+            },
+            // Equals, does not equal, in cleartext and hashed.
+            20 | 21 | 28 | 29 => match (self.user.text(attribute), condition.text.as_ref()) {
+                (Some(text), Some(target)) => {
+                    let text = if condition.comparator < 28 {
+                        hash(&text)
+                    } else {
+                        text
+                    };
+                    (text == *target) == (condition.comparator % 2 == 0)
+                }
+                _ => false,
+            },
+            // Starts with any of, ends with any of, and their negations, hashed as
+            // `length_hash` items.
+            22..=25 => self.user.text(attribute).map_or(false, |text| {
+                let starts = condition.comparator <= 23;
+                let any = list.iter().any(|item| {
+                    let Some((length, target)) = item.split_once('_') else {
+                        return false;
+                    };
+                    let Ok(length) = length.trim().parse::<usize>() else {
+                        return false;
+                    };
+                    let bytes = text.as_bytes();
+                    if length > bytes.len() {
+                        return false;
+                    }
+                    let part = if starts {
+                        &bytes[..length]
+                    } else {
+                        &bytes[bytes.len() - length..]
+                    };
+                    std::str::from_utf8(part).map_or(false, |part| hash(part) == target)
+                });
+                any == (condition.comparator % 2 == 0)
+            }),
+            // Starts with any of, ends with any of, and their negations, in cleartext.
+            30..=33 => self.user.text(attribute).map_or(false, |text| {
+                let any = list.iter().any(|item| {
+                    if condition.comparator <= 31 {
+                        text.starts_with(item.as_str())
+                    } else {
+                        text.ends_with(item.as_str())
+                    }
+                });
+                any == (condition.comparator % 2 == 0)
+            }),
+            // Array contains any of, does not contain any of, hashed and in cleartext.
+            26 | 27 | 34 | 35 => self.user.list(attribute).map_or(false, |items| {
+                let any = items.iter().any(|item| {
+                    let item = if condition.comparator <= 27 {
+                        hash(item)
+                    } else {
+                        item.clone()
+                    };
+                    list.contains(&item)
+                });
+                any == (condition.comparator % 2 == 0)
+            }),
+            _ => false,
+        }
+    }
+
+    fn semver(&self, attribute: &str) -> Option<SemVer> {
+        SemVer::parse(&self.user.text(attribute)?).ok()
+    }
+
+    /// Return the percentage option of the user among `options` of `setting`, unless the
+    /// percentage attribute of the user is missing.
+    fn percentage_option<'s>(
+        &self,
+        key: &str,
+        setting: &Setting,
+        options: &'s [PercentageOption],
+    ) -> Option<&'s ServedValue> {
+        if options.is_empty() {
+            return None;
+        }
+
+        let attribute = setting
+            .percentage_attribute
+            .as_deref()
+            .unwrap_or("Identifier");
+        let value = self.user.text(attribute)?;
+
+        let hash = format!("{:x}", Sha1::digest(format!("{key}{value}")));
+        let bucket = u64::from_str_radix(&hash[..7], 16).ok()? % 100;
+
+        let mut threshold = 0;
+        for option in options {
+            threshold += option.percentage;
+            if bucket < threshold {
+                return Some(&option.value);
+            }
+        }
+
+        options.last().map(|option| &option.value)
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn create_config() -> ConfigCatConfig {
+        ConfigCatConfig::parse(&json!({
+            "p": { "u": "https://cdn-global.configcat.com", "r": 0, "s": "salt" },
+            "s": [
+                { "n": "Beta users", "r": [{ "a": "Email", "c": 32, "l": ["@example.com"] }] }
+            ],
+            "f": {
+                "checkout-v2": {
+                    "t": 0,
+                    "v": { "b": false },
+                    "i": "off",
+                    "r": [
+                        {
+                            "c": [{ "u": { "a": "Country", "c": 0, "l": ["CH", "LI"] } }],
+                            "s": { "v": { "b": true }, "i": "swiss" }
+                        },
+                        {
+                            "c": [{ "s": { "s": 0, "c": 0 } }],
+                            "s": { "v": { "b": true }, "i": "beta" }
+                        }
+                    ]
+                },
+                "discount": {
+                    "t": 2,
+                    "v": { "i": 0 },
+                    "r": [
+                        {
+                            "c": [
+                                { "p": { "f": "checkout-v2", "c": 0, "v": { "b": true } } },
+                                { "u": { "a": "orders", "c": 14, "d": 10 } }
+                            ],
+                            "s": { "v": { "i": 15 }, "i": "loyal" }
+                        }
+                    ]
+                },
+                "banner": {
+                    "t": 1,
+                    "v": { "s": "none" },
+                    "p": [
+                        { "p": 50, "v": { "s": "red" }, "i": "red" },
+                        { "p": 50, "v": { "s": "blue" }, "i": "blue" }
+                    ]
+                },
+                "loop": {
+                    "t": 0,
+                    "v": { "b": false },
+                    "r": [
+                        {
+                            "c": [{ "p": { "f": "loop", "c": 0, "v": { "b": true } } }],
+                            "s": { "v": { "b": true } }
+                        }
+                    ]
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn evaluate(key: &str, context: &EvaluationContext) -> ResolutionDetails<Value> {
+        create_config().evaluate(key, &User::from(context)).unwrap()
+    }
+
+    #[test]
+    fn evaluate_targeting_rules() {
+        let context = EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("country", "CH");
+        let details = evaluate("checkout-v2", &context);
+        assert_eq!(details.value, Value::Bool(true));
+        assert_eq!(details.variant.as_deref(), Some("swiss"));
+        assert_eq!(details.reason, Some(EvaluationReason::TargetingMatch));
+
+        let context = EvaluationContext::default().with_custom_field("email", "bob@example.com");
+        assert_eq!(
+            evaluate("checkout-v2", &context).variant.as_deref(),
+            Some("beta")
+        );
+
+        let context = EvaluationContext::default().with_custom_field("email", "bob@acme.com");
+        let details = evaluate("checkout-v2", &context);
+        assert_eq!(details.value, Value::Bool(false));
+        assert_eq!(details.reason, Some(EvaluationReason::Default));
+    }
+
+    #[test]
+    fn evaluate_prerequisite_flags() {
+        let context = EvaluationContext::default()
+            .with_custom_field("country", "CH")
+            .with_custom_field("orders", 12);
+        assert_eq!(evaluate("discount", &context).value, Value::Int(15));
+
+        let context = EvaluationContext::default()
+            .with_custom_field("country", "FR")
+            .with_custom_field("orders", 12);
+        assert_eq!(evaluate("discount", &context).value, Value::Int(0));
+
+        let error = create_config()
+            .evaluate("loop", &User::from(&EvaluationContext::default()))
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::ParseError);
+    }
+
+    #[test]
+    fn evaluate_percentage_options() {
+        let mut counts = HashMap::new();
+        for index in 0..1000 {
+            let context = EvaluationContext::default().with_targeting_key(format!("user-{index}"));
+            let details = evaluate("banner", &context);
+            assert_eq!(details.reason, Some(EvaluationReason::Split));
+            *counts.entry(details.variant.unwrap()).or_insert(0) += 1;
+        }
+        assert!((400..600).contains(&counts["red"]), "{counts:?}");
+
+        // The same user always gets the same option.
+        let context = EvaluationContext::default().with_targeting_key("user-1");
+        assert_eq!(
+            evaluate("banner", &context).variant,
+            evaluate("banner", &context).variant
+        );
+
+        let details = evaluate("banner", &EvaluationContext::default());
+        assert_eq!(details.value, Value::String("none".to_string()));
+        assert_eq!(details.reason, Some(EvaluationReason::Default));
+    }
+
+    #[test]
+    fn compare_user_attributes() {
+        let config = create_config();
+        let context = EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("version", "1.4.2")
+            .with_custom_field("plan", "pro");
+        let user = User::from(&context);
+        let evaluation = Evaluation {
+            config: &config,
+            user: &user,
+            visited: Vec::new(),
+        };
+        let hashed = format!("{:x}", Sha256::digest("alicesaltkey"));
+
+        let cases = [
+            (json!({ "a": "plan", "c": 0, "l": ["free", "pro"] }), true),
+            (json!({ "a": "plan", "c": 1, "l": ["free", "pro"] }), false),
+            (json!({ "a": "plan", "c": 2, "l": ["ro"] }), true),
+            (json!({ "a": "version", "c": 4, "l": ["1.4.2", ""] }), true),
+            (json!({ "a": "version", "c": 6, "s": "1.10.0" }), true),
+            (json!({ "a": "version", "c": 9, "s": "2.0.0-beta" }), false),
+            (json!({ "a": "Identifier", "c": 16, "l": [hashed] }), true),
+            (json!({ "a": "Identifier", "c": 20, "s": hashed }), true),
+            (json!({ "a": "plan", "c": 28, "s": "pro" }), true),
+            (json!({ "a": "plan", "c": 31, "l": ["p"] }), false),
+            (json!({ "a": "missing", "c": 29, "s": "pro" }), false),
+            (json!({ "a": "plan", "c": 99, "s": "pro" }), false),
+        ];
+
+        for (condition, expected) in cases {
+            let parsed = parse_user_condition(&condition).unwrap();
+            assert_eq!(evaluation.matches("key", &parsed), expected, "{condition}");
+        }
+    }
+
+    #[test]
+    fn map_user_object() {
+        let context = EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("email", "alice@example.com")
+            .with_custom_field("tags", vec!["a".to_string()]);
+        let user = User::from(&context);
+
+        assert_eq!(user.text("Identifier").as_deref(), Some("alice"));
+        assert_eq!(user.text("Email").as_deref(), Some("alice@example.com"));
+        assert_eq!(user.list("tags"), Some(vec!["a".to_string()]));
+        assert_eq!(user.text("Country"), None);
+    }
+
+    #[test]
+    fn detect_changed_flags() {
+        let source = ConfigCatSource {
+            client: reqwest::Client::new(),
+            sdk_key: "key".to_string(),
+            custom_base_url: false,
+            base_url: Mutex::new(ConfigCatProvider::GLOBAL_BASE_URL.to_string()),
+        };
+
+        let previous = create_config();
+        let mut current = create_config();
+        current.settings.remove("loop");
+        current
+            .settings
+            .get_mut("banner")
+            .unwrap()
+            .percentage_options
+            .pop();
+
+        assert_eq!(
+            source.flags_changed(&previous, &current),
+            Some(vec!["banner".to_string(), "loop".to_string()])
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    EvaluationContext, EvaluationContextFieldValue, EvaluationError, EvaluationErrorCode,
+    EvaluationResult, ProviderError, StructValue, TrackingEventDetails, Value,
+};
+
+use super::{
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+type ViolationHandler = Box<dyn Fn(&ContextLimitViolation) + Send + Sync>;
+
+// ============================================================
+//  ContextLimits
+// ============================================================
+
+/// What a [`ContextLimitProvider`] does with an evaluation context exceeding its limits.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum ContextLimitPolicy {
+    /// Fail the resolution with [`EvaluationErrorCode::InvalidContext`].
+    #[default]
+    Reject,
+
+    /// Keep the attributes in the order of their names until a limit is reached, and drop the
+    /// others.
+    Truncate,
+
+    /// Drop the largest attributes until the limits are met.
+    DropLargest,
+}
+
+/// The limits enforced by a [`ContextLimitProvider`].
+#[derive(Clone, TypedBuilder, Debug)]
+pub struct ContextLimits {
+    /// The maximum number of custom fields.
+    #[builder(default, setter(strip_option))]
+    pub max_attributes: Option<usize>,
+
+    /// The maximum serialized size in bytes, estimated from the targeting key and the names and
+    /// values of custom fields. Struct fields are opaque, and only their name is counted.
+    #[builder(default, setter(strip_option))]
+    pub max_size: Option<usize>,
+
+    /// What to do with an evaluation context exceeding the limits.
+    #[builder(default)]
+    pub policy: ContextLimitPolicy,
+}
+
+/// An evaluation context found exceeding the limits of a [`ContextLimitProvider`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ContextLimitViolation {
+    /// The number of custom fields of the evaluation context.
+    pub attribute_count: usize,
+
+    /// The estimated serialized size of the evaluation context.
+    pub size: usize,
+
+    /// The attributes dropped to meet the limits, empty if the evaluation context was rejected.
+    pub dropped_attributes: Vec<String>,
+}
+
+// ============================================================
+//  ContextLimitProvider
+// ============================================================
+
+/// A provider that delegates to `inner` once the evaluation context meets configured limits,
+/// protecting remote providers and logs from oversized evaluation contexts.
+///
+/// ```no_run
+/// use open_feature::provider::{
+///     ContextLimitPolicy, ContextLimitProvider, ContextLimits, NoOpProvider,
+/// };
+///
+/// let provider = ContextLimitProvider::new(
+///     NoOpProvider::default(),
+///     ContextLimits::builder()
+///         .max_attributes(50)
+///         .max_size(16 * 1024)
+///         .policy(ContextLimitPolicy::DropLargest)
+///         .build(),
+/// )
+/// .with_violation_handler(|violation| eprintln!("Oversized context: {:?}", violation));
+/// ```
+pub struct ContextLimitProvider<P> {
+    inner: P,
+    limits: ContextLimits,
+    violation_handler: Option<ViolationHandler>,
+}
+
+impl<P: FeatureProvider> ContextLimitProvider<P> {
+    /// Create a provider delegating to `inner` with evaluation contexts meeting `limits`.
+    pub fn new(inner: P, limits: ContextLimits) -> Self {
+        Self {
+            inner,
+            limits,
+            violation_handler: None,
+        }
+    }
+
+    /// Call `handler` with every evaluation context exceeding the limits, for example to log a
+    /// warning.
+    #[must_use]
+    pub fn with_violation_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&ContextLimitViolation) + Send + Sync + 'static,
+    {
+        self.violation_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Return `evaluation_context` once made to meet the limits according to the policy, or an
+    /// error if it is rejected.
+    pub fn limit(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<EvaluationContext> {
+        let attribute_count = evaluation_context.custom_fields.len();
+        let size = context_size(evaluation_context);
+
+        if self.is_within_limits(attribute_count, size) {
+            return Ok(evaluation_context.clone());
+        }
+
+        let mut attributes: Vec<_> = evaluation_context
+            .custom_fields
+            .iter()
+            .map(|(name, value)| (name, attribute_size(name, value)))
+            .collect();
+
+        match self.limits.policy {
+            ContextLimitPolicy::Reject => {
+                self.report(attribute_count, size, Vec::new());
+
+                return Err(EvaluationError::builder()
+                    .code(EvaluationErrorCode::InvalidContext)
+                    .message(format!(
+                        "Evaluation context exceeds limits with {} attributes and {} bytes",
+                        attribute_count, size
+                    ))
+                    .build());
+            }
+            // The attributes dropped first go last.
+            ContextLimitPolicy::Truncate => attributes.sort_by(|a, b| a.0.cmp(b.0)),
+            ContextLimitPolicy::DropLargest => {
+                attributes.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+            }
+        }
+
+        let mut context = evaluation_context.clone();
+        let mut remaining_size = size;
+        let mut dropped_attributes = Vec::new();
+
+        while !self.is_within_limits(context.custom_fields.len(), remaining_size) {
+            let Some((name, attribute_size)) = attributes.pop() else {
+                break;
+            };
+
+            context.custom_fields.remove(name);
+            remaining_size -= attribute_size;
+            dropped_attributes.push(name.clone());
+        }
+
+        self.report(attribute_count, size, dropped_attributes);
+
+        Ok(context)
+    }
+
+    fn is_within_limits(&self, attribute_count: usize, size: usize) -> bool {
+        self.limits
+            .max_attributes
+            .map_or(true, |max_attributes| attribute_count <= max_attributes)
+            && self
+                .limits
+                .max_size
+                .map_or(true, |max_size| size <= max_size)
+    }
+
+    fn report(&self, attribute_count: usize, size: usize, dropped_attributes: Vec<String>) {
+        if let Some(handler) = &self.violation_handler {
+            handler(&ContextLimitViolation {
+                attribute_count,
+                size,
+                dropped_attributes,
+            });
+        }
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let context = self.limit(evaluation_context)?;
+
+        T::resolve(&self.inner, flag_key, &context).await
+    }
+}
+
+fn context_size(evaluation_context: &EvaluationContext) -> usize {
+    evaluation_context
+        .targeting_key
+        .as_ref()
+        .map_or(0, String::len)
+        + evaluation_context
+            .custom_fields
+            .iter()
+            .map(|(name, value)| attribute_size(name, value))
+            .sum::<usize>()
+}
+
+fn attribute_size(name: &str, value: &EvaluationContextFieldValue) -> usize {
+    name.len() + value_size(value)
+}
+
+fn value_size(value: &EvaluationContextFieldValue) -> usize {
+    match value {
+        EvaluationContextFieldValue::Bool(value) => value.to_string().len(),
+        EvaluationContextFieldValue::Int(value) => value.to_string().len(),
+        EvaluationContextFieldValue::Float(value) => value.to_string().len(),
+        EvaluationContextFieldValue::String(value) => value.len(),
+        EvaluationContextFieldValue::DateTime(value) => value.to_string().len(),
+        EvaluationContextFieldValue::List(values) => values.iter().map(value_size).sum(),
+        EvaluationContextFieldValue::Struct(_) => 0,
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for ContextLimitProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.inner.initialize(context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    /// Drop events whose evaluation context is rejected.
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        if let Ok(context) = self.limit(evaluation_context) {
+            self.inner
+                .track(event_name, &context, tracking_event_details)
+                .await;
+        }
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.inner
+            .resolve_all(&self.limit(evaluation_context)?)
+            .await
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::provider::NoOpProvider;
+
+    fn create_context() -> EvaluationContext {
+        EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("a", "x".repeat(100))
+            .with_custom_field("b", true)
+            .with_custom_field("c", 42)
+    }
+
+    fn create_provider(limits: ContextLimits) -> ContextLimitProvider<NoOpProvider> {
+        ContextLimitProvider::new(NoOpProvider::default(), limits)
+    }
+
+    #[test]
+    fn within_limits() {
+        let provider = create_provider(
+            ContextLimits::builder()
+                .max_attributes(3)
+                .max_size(1000)
+                .build(),
+        );
+
+        assert_eq!(provider.limit(&create_context()).unwrap(), create_context());
+    }
+
+    #[tokio::test]
+    async fn reject() {
+        let violations = Arc::new(Mutex::new(Vec::new()));
+        let provider = create_provider(ContextLimits::builder().max_attributes(2).build())
+            .with_violation_handler({
+                let violations = violations.clone();
+                move |violation| violations.lock().unwrap().push(violation.clone())
+            });
+
+        let error = provider
+            .resolve_bool_value("key", &create_context())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.code, EvaluationErrorCode::InvalidContext);
+        assert_eq!(
+            *violations.lock().unwrap(),
+            vec![ContextLimitViolation {
+                attribute_count: 3,
+                size: 5 + 101 + 5 + 3,
+                dropped_attributes: Vec::new(),
+            }]
+        );
+    }
+
+    #[test]
+    fn truncate() {
+        let provider = create_provider(
+            ContextLimits::builder()
+                .max_attributes(2)
+                .policy(ContextLimitPolicy::Truncate)
+                .build(),
+        );
+
+        let context = provider.limit(&create_context()).unwrap();
+
+        let mut names: Vec<_> = context.custom_fields.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn drop_largest() {
+        let provider = create_provider(
+            ContextLimits::builder()
+                .max_size(50)
+                .policy(ContextLimitPolicy::DropLargest)
+                .build(),
+        );
+
+        let context = provider.limit(&create_context()).unwrap();
+
+        let mut names: Vec<_> = context.custom_fields.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["b", "c"]);
+    }
+}
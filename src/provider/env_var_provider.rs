@@ -0,0 +1,241 @@
+use async_trait::async_trait;
+
+use crate::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationReason, EvaluationResult,
+    StructValue, Value,
+};
+
+use super::{FeatureProvider, FlagType, FlagValue, ProviderMetadata, ResolutionDetails};
+
+// ============================================================
+//  EnvVarProvider
+// ============================================================
+
+/// A provider resolving flags from environment variables, for twelve-factor deployments without
+/// a flag service.
+///
+/// The variable of a flag is named after its key, prefixed, upper-cased and with characters
+/// other than letters and digits replaced by underscores: with prefix `FLAG_`, flag
+/// `new-checkout` is read from `FLAG_NEW_CHECKOUT`.
+///
+/// Values are coerced to the type of the evaluated flag: bools are `true` or `false` in any
+/// case, ints and floats are parsed as such, and strings are taken as is. Struct flags are
+/// parsed as JSON objects with the `serde_json` feature, and are not supported otherwise.
+/// Malformed values fail with [`EvaluationErrorCode::ParseError`].
+///
+/// ```
+/// use open_feature::provider::EnvVarProvider;
+///
+/// let provider = EnvVarProvider::new().with_prefix("FLAG_");
+///
+/// assert_eq!(provider.var_name("new-checkout"), "FLAG_NEW_CHECKOUT");
+/// ```
+#[derive(Clone, Debug)]
+pub struct EnvVarProvider {
+    metadata: ProviderMetadata,
+    prefix: String,
+}
+
+impl Default for EnvVarProvider {
+    fn default() -> Self {
+        Self {
+            metadata: ProviderMetadata::new("Environment Variable Provider"),
+            prefix: String::new(),
+        }
+    }
+}
+
+impl EnvVarProvider {
+    /// Create a provider reading variables without prefix.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefix the names of the variables with `prefix`, such as `FLAG_`.
+    #[must_use]
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Return the name of the variable of `flag_key`.
+    pub fn var_name(&self, flag_key: &str) -> String {
+        let name: String = flag_key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        format!("{}{}", self.prefix, name)
+    }
+
+    fn resolve<T: FlagValue>(&self, flag_key: &str) -> EvaluationResult<ResolutionDetails<T>> {
+        let var_name = self.var_name(flag_key);
+
+        let value = std::env::var(&var_name).map_err(|_| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .message(format!("Variable {} is not set", var_name))
+                .build()
+        })?;
+
+        let value = parse_value(T::FLAG_TYPE, value.trim())
+            .and_then(T::from_value)
+            .ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::ParseError)
+                    .message(format!(
+                        "Variable {} is not a valid {:?} value",
+                        var_name,
+                        T::FLAG_TYPE
+                    ))
+                    .build()
+            })?;
+
+        Ok(ResolutionDetails {
+            value,
+            variant: None,
+            reason: Some(EvaluationReason::Static),
+            flag_metadata: None,
+        })
+    }
+}
+
+/// Parse `value` as a value of `flag_type`.
+fn parse_value(flag_type: FlagType, value: &str) -> Option<Value> {
+    match flag_type {
+        FlagType::Bool => match value.to_ascii_lowercase().as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        FlagType::Int => value.parse().ok().map(Value::Int),
+        FlagType::Float => value.parse().ok().map(Value::Float),
+        FlagType::String => Some(Value::String(value.to_string())),
+        FlagType::Struct => parse_struct(value),
+    }
+}
+
+#[cfg(feature = "serde_json")]
+fn parse_struct(value: &str) -> Option<Value> {
+    let json: serde_json::Value = serde_json::from_str(value).ok()?;
+
+    Value::try_from(json).ok().filter(Value::is_struct)
+}
+
+#[cfg(not(feature = "serde_json"))]
+fn parse_struct(_value: &str) -> Option<Value> {
+    None
+}
+
+#[async_trait]
+impl FeatureProvider for EnvVarProvider {
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key)
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key)
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key)
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key)
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key)
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpenFeature;
+
+    #[tokio::test]
+    async fn resolve_variables() {
+        std::env::set_var("ENV_VAR_TEST_NEW_CHECKOUT", "TRUE");
+        std::env::set_var("ENV_VAR_TEST_MAX_RETRIES", " 3 ");
+        std::env::set_var("ENV_VAR_TEST_RATIO", "0.5");
+
+        let mut api = OpenFeature::default();
+        api.set_provider(EnvVarProvider::new().with_prefix("ENV_VAR_TEST_"))
+            .await
+            .unwrap();
+
+        let client = api.create_client();
+
+        assert!(client
+            .get_bool_value("new-checkout", None, None)
+            .await
+            .unwrap());
+        assert_eq!(
+            client
+                .get_int_value("max.retries", None, None)
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            client.get_string_value("ratio", None, None).await.unwrap(),
+            "0.5"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_errors() {
+        std::env::set_var("ENV_VAR_ERROR_TEST_TIER", "gold");
+
+        let provider = EnvVarProvider::new().with_prefix("ENV_VAR_ERROR_TEST_");
+        let context = EvaluationContext::default();
+
+        let error = provider
+            .resolve_int_value("tier", &context)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::ParseError);
+
+        let error = provider
+            .resolve_bool_value("missing", &context)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+use tokio::sync::broadcast;
+use typed_builder::TypedBuilder;
+
+use crate::{EvaluationErrorCode, FlagMetadata};
+
+/// The number of events buffered for each listener before the oldest are dropped.
+const EVENT_CAPACITY: usize = 64;
+
+// ============================================================
+//  ProviderEventType
+// ============================================================
+
+/// The type of a [`ProviderEvent`].
+///
+/// See the [spec](https://openfeature.dev/specification/sections/events).
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ProviderEventType {
+    /// The provider is ready to perform flag evaluations.
+    Ready,
+
+    /// The provider signaled an error.
+    Error,
+
+    /// The flag configuration of the provider has changed.
+    ConfigurationChanged,
+
+    /// The provider is serving cached values that might be outdated.
+    Stale,
+
+    /// The provider is reconciling its state with a new evaluation context.
+    Reconciling,
+
+    /// The provider has reconciled its state with a new evaluation context.
+    ContextChanged,
+}
+
+impl fmt::Display for ProviderEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ready => "PROVIDER_READY",
+            Self::Error => "PROVIDER_ERROR",
+            Self::ConfigurationChanged => "PROVIDER_CONFIGURATION_CHANGED",
+            Self::Stale => "PROVIDER_STALE",
+            Self::Reconciling => "PROVIDER_RECONCILING",
+            Self::ContextChanged => "PROVIDER_CONTEXT_CHANGED",
+        })
+    }
+}
+
+// ============================================================
+//  ProviderEvent
+// ============================================================
+
+/// An event emitted by a provider, or by the SDK on behalf of a provider.
+#[derive(Clone, PartialEq, TypedBuilder, Debug)]
+pub struct ProviderEvent {
+    /// The type of the event.
+    pub event_type: ProviderEventType,
+
+    /// The name of the provider, as in its metadata.
+    #[builder(setter(into))]
+    pub provider_name: String,
+
+    /// The keys of the flags whose configuration changed. `None` means any flag might have
+    /// changed.
+    #[builder(default, setter(strip_option))]
+    pub flags_changed: Option<Vec<String>>,
+
+    /// A message describing the event.
+    #[builder(default, setter(strip_option, into))]
+    pub message: Option<String>,
+
+    /// The error code of an error event.
+    #[builder(default, setter(strip_option))]
+    pub error_code: Option<EvaluationErrorCode>,
+
+    /// Arbitrary data associated with the event.
+    #[builder(default)]
+    pub event_metadata: FlagMetadata,
+}
+
+impl ProviderEvent {
+    /// Return `true` if the value of `flag_key` might be different after this event.
+    pub fn affects(&self, flag_key: &str) -> bool {
+        match self.event_type {
+            ProviderEventType::Ready | ProviderEventType::ContextChanged => true,
+            ProviderEventType::ConfigurationChanged => match &self.flags_changed {
+                Some(flags_changed) => flags_changed.iter().any(|key| key == flag_key),
+                None => true,
+            },
+            ProviderEventType::Error
+            | ProviderEventType::Stale
+            | ProviderEventType::Reconciling => false,
+        }
+    }
+}
+
+// ============================================================
+//  EventEmitter
+// ============================================================
+
+/// The channel through which a provider emits its events.
+///
+/// A provider emitting events keeps an instance and returns a clone of it from
+/// [`FeatureProvider::event_emitter`](super::FeatureProvider::event_emitter). All the clones
+/// share the same channel.
+#[derive(Clone, Debug)]
+pub struct EventEmitter {
+    sender: broadcast::Sender<ProviderEvent>,
+}
+
+impl Default for EventEmitter {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(EVENT_CAPACITY).0,
+        }
+    }
+}
+
+impl EventEmitter {
+    /// Send `event` to all the current listeners. Nothing happens if there is none.
+    pub fn emit(&self, event: ProviderEvent) {
+        // An error only means nobody is listening.
+        let _ = self.sender.send(event);
+    }
+
+    /// Return a receiver of the events emitted from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ProviderEvent> {
+        self.sender.subscribe()
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_event(event_type: ProviderEventType) -> ProviderEvent {
+        ProviderEvent::builder()
+            .event_type(event_type)
+            .provider_name("Test Provider")
+            .build()
+    }
+
+    #[test]
+    fn affected_flags() {
+        let mut event = create_event(ProviderEventType::ConfigurationChanged);
+        assert!(event.affects("checkout-v2"));
+
+        event.flags_changed = Some(vec!["tier".to_string()]);
+        assert!(event.affects("tier"));
+        assert!(!event.affects("checkout-v2"));
+
+        assert!(create_event(ProviderEventType::Ready).affects("checkout-v2"));
+        assert!(!create_event(ProviderEventType::Stale).affects("checkout-v2"));
+    }
+
+    #[tokio::test]
+    async fn emit_to_subscribers() {
+        let emitter = EventEmitter::default();
+
+        // No listener yet.
+        emitter.emit(create_event(ProviderEventType::Stale));
+
+        let mut receiver = emitter.clone().subscribe();
+        emitter.emit(create_event(ProviderEventType::Ready));
+
+        assert_eq!(
+            receiver.recv().await.unwrap().event_type,
+            ProviderEventType::Ready
+        );
+    }
+}
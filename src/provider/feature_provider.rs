@@ -1,8 +1,13 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 
-use crate::{EvaluationContext, EvaluationResult, StructValue};
+use crate::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationResult, ProviderError,
+    StructValue, TrackingEventDetails, Value,
+};
 
-use super::ResolutionDetails;
+use super::{EventEmitter, FlagType, ResolutionDetails};
 
 // ============================================================
 //  FeatureProvider
@@ -34,13 +39,30 @@ pub trait FeatureProvider: Send + Sync + 'static {
     /// abnormally.
     /// * The provider SHOULD indicate an error if flag resolution is attempted before the provider
     /// is ready.
+    ///
+    /// An initialization failure is returned, in which case the SDK does not register the
+    /// provider and reports the error to whoever set it.
     #[allow(unused_variables)]
-    async fn initialize(&mut self, context: &EvaluationContext) {}
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        Ok(())
+    }
+
+    /// The provider MAY define a shutdown function to gracefully release its resources, such as
+    /// connections or background tasks.
+    ///
+    /// The SDK calls it once the provider is replaced or the API is shut down. Evaluations
+    /// already holding the provider may still complete afterwards.
+    async fn shutdown(&self) {}
 
     /// The provider MAY define a status field/accessor which indicates the readiness of the
-    /// provider, with possible values NOT_READY, READY, or ERROR.
+    /// provider, with possible values NOT_READY, READY, ERROR, STALE or FATAL.
     ///
-    /// Providers without this field can be assumed to be ready immediately.
+    /// The SDK tracks the status of registered providers itself: `READY` once initialized, then
+    /// `ERROR`, `FATAL`, `STALE` or `READY` again as the provider emits the matching events, and
+    /// `NOT_READY` once shut down. The status reported here is only used while the tracked one
+    /// is `READY`, so providers emitting events don't need to define it. The SDK fails the
+    /// evaluations with `PROVIDER_NOT_READY` or `PROVIDER_FATAL` on its own when the provider is
+    /// not ready or fatal, without calling it.
     fn status(&self) -> ProviderStatus {
         ProviderStatus::Ready
     }
@@ -49,6 +71,31 @@ pub trait FeatureProvider: Send + Sync + 'static {
     /// or accessor of type string, which identifies the provider implementation.
     fn metadata(&self) -> &ProviderMetadata;
 
+    /// The provider MAY emit events, such as when its flag configuration changes, through the
+    /// returned [`EventEmitter`].
+    ///
+    /// The SDK subscribes to it once the provider is initialized, to track its status. A
+    /// `PROVIDER_ERROR` event with the `PROVIDER_FATAL` error code makes the provider fatal.
+    /// `PROVIDER_READY` is emitted by the SDK itself once the provider is initialized.
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        None
+    }
+
+    /// The provider MAY define a track function, recording that the subject of
+    /// `evaluation_context` performed the action `event_name`, such as a conversion, so that it
+    /// can be associated with flag evaluations for experimentation analysis.
+    ///
+    /// The SDK does not call it when the provider is not ready or fatal. Providers not supporting
+    /// tracking ignore the events, which they do by default.
+    #[allow(unused_variables)]
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+    }
+
     /// Resolve given `flag_key` as a bool value.
     async fn resolve_bool_value(
         &self,
@@ -83,6 +130,49 @@ pub trait FeatureProvider: Send + Sync + 'static {
         flag_key: &str,
         evaluation_context: &EvaluationContext,
     ) -> EvaluationResult<ResolutionDetails<StructValue>>;
+
+    /// Resolve the given flags as their type for the same `evaluation_context`, keyed by flag
+    /// key, as needed by [`Client::evaluate_all`](crate::Client::evaluate_all). The client fails
+    /// the values of another type with `TYPE_MISMATCH`.
+    ///
+    /// Providers backed by a remote service SHOULD resolve them in a single pass, such as with a
+    /// single request. By default, the flags are resolved one after the other.
+    async fn resolve_bulk(
+        &self,
+        flags: &[(String, FlagType)],
+        evaluation_context: &EvaluationContext,
+    ) -> HashMap<String, EvaluationResult<ResolutionDetails<Value>>> {
+        let mut results = HashMap::new();
+
+        for (flag_key, flag_type) in flags {
+            let result = flag_type.resolve(self, flag_key, evaluation_context).await;
+            results.insert(flag_key.clone(), result);
+        }
+
+        results
+    }
+
+    /// Resolve all the flags at once for given `evaluation_context`, keyed by flag key, as
+    /// needed by [`StaticContextClient`](crate::StaticContextClient). Flags failing to resolve
+    /// are left out.
+    ///
+    /// Providers not supporting it return an error, which they do by default.
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let _ = evaluation_context;
+
+        Err(EvaluationError::builder()
+            .code(EvaluationErrorCode::General(
+                "Resolving all the flags is not supported".to_string(),
+            ))
+            .message(format!(
+                "Provider \"{}\" cannot resolve all the flags at once",
+                self.metadata().name
+            ))
+            .build())
+    }
 }
 
 // ============================================================
@@ -108,7 +198,7 @@ impl ProviderMetadata {
 // ============================================================
 
 /// The status of a feature provider.
-#[derive(Default, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
 pub enum ProviderStatus {
     /// The provider has not been initialized.
     #[default]
@@ -123,4 +213,8 @@ pub enum ProviderStatus {
     /// The provider's cached state is no longer valid and may not be up-to-date with the source of
     /// truth.
     STALE,
+
+    /// The provider is in an irrecoverable error state. Evaluations fail with
+    /// `PROVIDER_FATAL` until the provider is replaced.
+    Fatal,
 }
@@ -0,0 +1,485 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use tokio::task::JoinHandle;
+
+use crate::{
+    Clock, EvaluationContext, EvaluationResult, ProviderError, ProviderErrorKind, SdkError,
+    StructValue, Value,
+};
+
+use super::{
+    flagd, EventEmitter, FeatureProvider, InMemoryFlag, InMemoryProvider, ProviderEvent,
+    ProviderEventType, ProviderMetadata, ResolutionDetails,
+};
+
+// ============================================================
+//  FileProvider
+// ============================================================
+
+/// A provider serving flags defined in a JSON file, or a YAML one with the `yaml` feature,
+/// such as a Kubernetes ConfigMap mount.
+///
+/// Flags are defined in the [`FlagdConfiguration`](super::FlagdConfiguration) format, keyed by
+/// flag key under `flags`, and evaluated in-process:
+///
+/// ```json
+/// {
+///   "flags": {
+///     "new-checkout": {
+///       "state": "ENABLED",
+///       "variants": { "on": true, "off": false },
+///       "defaultVariant": "off",
+///       "metadata": { "team": "payments" }
+///     }
+///   }
+/// }
+/// ```
+///
+/// Disabled flags are not served. Changes are picked up by polling rather than filesystem
+/// notifications, so that they are also seen through mounts not supporting them: once
+/// initialized, the file is read every [`Self::DEFAULT_POLL_INTERVAL`] by default, or
+/// [`Self::with_poll_interval`], and the flags are swapped at once when its content changes,
+/// emitting a
+/// `PROVIDER_CONFIGURATION_CHANGED` event listing the changed flags. A file failing to load
+/// keeps the current flags and emits a `PROVIDER_ERROR` event, putting the provider in the
+/// `ERROR` status until it loads again, which emits a `PROVIDER_READY` event. A file failing to
+/// load on initialization fails it.
+///
+/// ```no_run
+/// use open_feature::{provider::FileProvider, OpenFeature};
+///
+/// # async fn example() -> Result<(), open_feature::SdkError> {
+/// let provider = FileProvider::new("/etc/flags/flags.json");
+///
+/// OpenFeature::singleton_mut()
+///     .await
+///     .set_provider(provider)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FileProvider {
+    file: FlagFile,
+    poll_interval: Option<Duration>,
+    watcher: Option<Watcher>,
+}
+
+impl FileProvider {
+    /// The interval the file is read at by default.
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Create a provider serving the flags of the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            file: FlagFile {
+                path: path.into(),
+                flags: InMemoryProvider::default()
+                    .with_metadata(ProviderMetadata::new("File Provider")),
+                failing: Arc::default(),
+                content: Arc::default(),
+            },
+            poll_interval: Some(Self::DEFAULT_POLL_INTERVAL),
+            watcher: None,
+        }
+    }
+
+    /// Read the file for changes every `poll_interval`, or never if `None`.
+    #[must_use]
+    pub fn with_poll_interval(mut self, poll_interval: Option<Duration>) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Check the activation windows of the flags against `clock` rather than the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.file.flags = self.file.flags.with_clock(clock);
+        self
+    }
+
+    /// Load the file now, replacing the current flags.
+    pub async fn load(&self) -> Result<(), SdkError> {
+        self.file.load().await
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for FileProvider {
+    async fn initialize(&mut self, _context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.file.load().await.map_err(|error| {
+            ProviderError::new(ProviderErrorKind::Misconfigured, error.to_string())
+                .with_source(error)
+        })?;
+
+        let Some(poll_interval) = self.poll_interval else {
+            return Ok(());
+        };
+
+        let file = self.file.clone();
+
+        self.watcher = Some(Watcher(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(poll_interval);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                file.reload().await;
+            }
+        })));
+
+        Ok(())
+    }
+
+    async fn shutdown(&self) {
+        if let Some(watcher) = &self.watcher {
+            watcher.0.abort();
+        }
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.file.flags.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.file.flags.event_emitter()
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.file
+            .flags
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.file
+            .flags
+            .resolve_int_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.file
+            .flags
+            .resolve_float_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.file
+            .flags
+            .resolve_string_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.file
+            .flags
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.file.flags.resolve_all(evaluation_context).await
+    }
+}
+
+/// The task watching the file, aborted when dropped along with the provider.
+struct Watcher(JoinHandle<()>);
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+// ============================================================
+//  FlagFile
+// ============================================================
+
+/// The flags of a file, shared with the task watching it.
+#[derive(Clone)]
+struct FlagFile {
+    path: PathBuf,
+    flags: InMemoryProvider,
+    failing: Arc<AtomicBool>,
+    /// The content last read, so that rewrites are detected whatever the resolution of
+    /// modification times.
+    content: Arc<Mutex<Option<String>>>,
+}
+
+impl FlagFile {
+    /// Load the file, emitting `PROVIDER_ERROR` on failure and `PROVIDER_READY` on recovery.
+    async fn load(&self) -> Result<(), SdkError> {
+        let text = self.read_text().await;
+        self.apply(text)
+    }
+
+    /// Load the file if its content changed since it was last read, or if it can no longer be
+    /// read.
+    async fn reload(&self) {
+        let text = self.read_text().await;
+
+        let unchanged = match &text {
+            Ok(text) => self.content.lock().unwrap().as_ref() == Some(text),
+            Err(_) => self.failing.load(Ordering::Relaxed),
+        };
+
+        if !unchanged {
+            let _ = self.apply(text);
+        }
+    }
+
+    /// Parse `text` and replace the flags with it, emitting events.
+    fn apply(&self, text: Result<String, SdkError>) -> Result<(), SdkError> {
+        let result = text.and_then(|text| {
+            let flags = parse_file(&self.path, &text).map_err(|message| SdkError::Configuration {
+                message: format!("{}: {}", self.path.display(), message),
+            });
+
+            *self.content.lock().unwrap() = Some(text);
+            flags
+        });
+
+        let event = match &result {
+            Ok(flags) => {
+                self.flags.set_flags(flags.clone());
+
+                if !self.failing.swap(false, Ordering::Relaxed) {
+                    return Ok(());
+                }
+
+                ProviderEvent::builder()
+                    .event_type(ProviderEventType::Ready)
+                    .provider_name(self.flags.metadata().name.clone())
+                    .build()
+            }
+            Err(error) => {
+                self.failing.store(true, Ordering::Relaxed);
+
+                ProviderEvent::builder()
+                    .event_type(ProviderEventType::Error)
+                    .provider_name(self.flags.metadata().name.clone())
+                    .message(error.to_string())
+                    .build()
+            }
+        };
+
+        if let Some(events) = self.flags.event_emitter() {
+            events.emit(event);
+        }
+
+        result.map(|_| ())
+    }
+
+    async fn read_text(&self) -> Result<String, SdkError> {
+        tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|source: io::Error| SdkError::Io {
+                path: self.path.display().to_string(),
+                source,
+            })
+    }
+}
+
+// ============================================================
+//  Flag definitions
+// ============================================================
+
+/// Parse `text` as JSON, or as YAML if `path` has a YAML extension.
+pub(super) fn parse_file(path: &Path, text: &str) -> Result<HashMap<String, InMemoryFlag>, String> {
+    let is_yaml = matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("yaml" | "yml")
+    );
+
+    let definitions = if is_yaml {
+        parse_yaml(text)?
+    } else {
+        serde_json::from_str(text).map_err(|error| error.to_string())?
+    };
+
+    flagd::parse_definitions(&definitions)
+}
+
+#[cfg(feature = "yaml")]
+fn parse_yaml(text: &str) -> Result<JsonValue, String> {
+    serde_yaml::from_str(text).map_err(|error| error.to_string())
+}
+
+#[cfg(not(feature = "yaml"))]
+fn parse_yaml(_text: &str) -> Result<JsonValue, String> {
+    Err("YAML flag files require the `yaml` feature".to_string())
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reload() {
+        let path = std::env::temp_dir().join(format!(
+            "open-feature-file-provider-{}.json",
+            std::process::id()
+        ));
+        let write = |default_variant: &str| {
+            let definitions = json!({
+                "flags": {
+                    "new-checkout": {
+                        "variants": { "on": true, "off": false },
+                        "defaultVariant": default_variant
+                    }
+                }
+            });
+            std::fs::write(&path, definitions.to_string()).unwrap();
+        };
+        let resolve = |provider: &FileProvider| {
+            let provider = provider.file.flags.clone();
+            async move {
+                provider
+                    .resolve_bool_value("new-checkout", &EvaluationContext::default())
+                    .await
+                    .unwrap()
+                    .value
+            }
+        };
+
+        let provider = FileProvider::new(&path).with_poll_interval(None);
+        let mut events = provider.event_emitter().unwrap().subscribe();
+
+        write("off");
+        provider.load().await.unwrap();
+        assert!(!resolve(&provider).await);
+
+        write("on");
+        provider.load().await.unwrap();
+        assert!(resolve(&provider).await);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(provider.load().await, Err(SdkError::Io { .. })));
+        assert!(resolve(&provider).await);
+
+        write("on");
+        provider.load().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let event_types: Vec<_> = std::iter::from_fn(|| events.try_recv().ok())
+            .map(|event| event.event_type)
+            .collect();
+        assert_eq!(
+            event_types,
+            vec![
+                ProviderEventType::ConfigurationChanged,
+                ProviderEventType::ConfigurationChanged,
+                ProviderEventType::Error,
+                ProviderEventType::Ready
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reload_rewrite_of_same_length() {
+        let path = std::env::temp_dir().join(format!(
+            "open-feature-file-provider-rewrite-{}.json",
+            std::process::id()
+        ));
+        let write = |default_variant: &str| {
+            let definitions = json!({
+                "flags": {
+                    "new-checkout": {
+                        "variants": { "on": true, "no": false },
+                        "defaultVariant": default_variant
+                    }
+                }
+            });
+            std::fs::write(&path, definitions.to_string()).unwrap();
+        };
+
+        let provider = FileProvider::new(&path).with_poll_interval(None);
+        let mut events = provider.event_emitter().unwrap().subscribe();
+
+        write("no");
+        provider.load().await.unwrap();
+
+        // Rewritten within the same second, with content of the same length.
+        write("on");
+        provider.file.reload().await;
+        provider.file.reload().await;
+        std::fs::remove_file(&path).unwrap();
+
+        let value = provider
+            .file
+            .flags
+            .resolve_bool_value("new-checkout", &EvaluationContext::default())
+            .await
+            .unwrap()
+            .value;
+        assert!(value);
+
+        let changes = std::iter::from_fn(|| events.try_recv().ok()).count();
+        assert_eq!(changes, 2);
+    }
+
+    #[tokio::test]
+    async fn stop_watching_on_drop() {
+        let path = std::env::temp_dir().join(format!(
+            "open-feature-file-provider-drop-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, r#"{ "flags": {} }"#).unwrap();
+
+        let mut provider = FileProvider::new(&path);
+        provider
+            .initialize(&EvaluationContext::default())
+            .await
+            .unwrap();
+        let failing = provider.file.failing.clone();
+        assert_eq!(Arc::strong_count(&failing), 3);
+
+        drop(provider);
+        tokio::task::yield_now().await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(Arc::strong_count(&failing), 1);
+    }
+}
@@ -0,0 +1,247 @@
+use async_trait::async_trait;
+
+use crate::{EvaluationContext, EvaluationResult, StructValue, Value};
+
+use super::{FeatureProvider, ResolutionDetails};
+
+// ============================================================
+//  FlagType
+// ============================================================
+
+/// The type of a flag value, as seen by the provider.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum FlagType {
+    /// A bool flag.
+    Bool,
+
+    /// An int (i64) flag.
+    Int,
+
+    /// A float (f64) flag.
+    Float,
+
+    /// A string flag.
+    String,
+
+    /// A struct flag.
+    Struct,
+}
+
+impl FlagType {
+    /// Resolve given `flag_key` as this type with `provider`, returning the value as a
+    /// [`Value`].
+    pub async fn resolve<P: FeatureProvider + ?Sized>(
+        self,
+        provider: &P,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Value>> {
+        Ok(match self {
+            Self::Bool => value_details(
+                provider
+                    .resolve_bool_value(flag_key, evaluation_context)
+                    .await?,
+            ),
+            Self::Int => value_details(
+                provider
+                    .resolve_int_value(flag_key, evaluation_context)
+                    .await?,
+            ),
+            Self::Float => value_details(
+                provider
+                    .resolve_float_value(flag_key, evaluation_context)
+                    .await?,
+            ),
+            Self::String => value_details(
+                provider
+                    .resolve_string_value(flag_key, evaluation_context)
+                    .await?,
+            ),
+            Self::Struct => value_details(
+                provider
+                    .resolve_struct_value(flag_key, evaluation_context)
+                    .await?,
+            ),
+        })
+    }
+
+    /// Return `value` if it holds this type, widening ints to floats, or `None` otherwise.
+    pub fn cast(self, value: Value) -> Option<Value> {
+        match (self, value) {
+            (Self::Bool, value @ Value::Bool(_))
+            | (Self::Int, value @ Value::Int(_))
+            | (Self::Float, value @ Value::Float(_))
+            | (Self::String, value @ Value::String(_))
+            | (Self::Struct, value @ Value::Struct(_)) => Some(value),
+            #[allow(clippy::cast_precision_loss)]
+            (Self::Float, Value::Int(value)) => Some(Value::Float(value as f64)),
+            _ => None,
+        }
+    }
+}
+
+fn value_details<T: FlagValue>(details: ResolutionDetails<T>) -> ResolutionDetails<Value> {
+    ResolutionDetails {
+        value: details.value.to_value(),
+        variant: details.variant,
+        reason: details.reason,
+        flag_metadata: details.flag_metadata,
+    }
+}
+
+// ============================================================
+//  FlagValue
+// ============================================================
+
+/// The types of flag values a [`FeatureProvider`] is able to resolve.
+///
+/// It allows code to be generic over the flag type, dispatching to the corresponding
+/// `resolve_*_value` function of the provider.
+#[async_trait]
+pub trait FlagValue: Clone + Send + Sync + 'static {
+    /// The type of flag resolved as `Self`.
+    const FLAG_TYPE: FlagType;
+
+    /// Resolve given `flag_key` as `Self` with `provider`.
+    async fn resolve(
+        provider: &dyn FeatureProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>>;
+
+    /// Convert `self` into a [`Value`].
+    fn to_value(&self) -> Value;
+
+    /// Convert `value` into `Self`, returning `None` if it holds another type.
+    fn from_value(value: Value) -> Option<Self>;
+}
+
+#[async_trait]
+impl FlagValue for bool {
+    const FLAG_TYPE: FlagType = FlagType::Bool;
+
+    async fn resolve(
+        provider: &dyn FeatureProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>> {
+        provider
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl FlagValue for i64 {
+    const FLAG_TYPE: FlagType = FlagType::Int;
+
+    async fn resolve(
+        provider: &dyn FeatureProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>> {
+        provider
+            .resolve_int_value(flag_key, evaluation_context)
+            .await
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Int(*self)
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl FlagValue for f64 {
+    const FLAG_TYPE: FlagType = FlagType::Float;
+
+    async fn resolve(
+        provider: &dyn FeatureProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>> {
+        provider
+            .resolve_float_value(flag_key, evaluation_context)
+            .await
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Float(*self)
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl FlagValue for String {
+    const FLAG_TYPE: FlagType = FlagType::String;
+
+    async fn resolve(
+        provider: &dyn FeatureProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>> {
+        provider
+            .resolve_string_value(flag_key, evaluation_context)
+            .await
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl FlagValue for StructValue {
+    const FLAG_TYPE: FlagType = FlagType::Struct;
+
+    async fn resolve(
+        provider: &dyn FeatureProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Self>> {
+        provider
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Struct(self.clone())
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Struct(value) => Some(value),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,585 @@
+use std::collections::HashMap;
+
+use serde_json::{Map, Value as JsonValue};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime, Time, UtcOffset, Weekday};
+
+use crate::{FlagMetadata, SdkError, Value};
+
+use super::{ActivationWindow, InMemoryFlag, InMemoryProvider, Recurrence, Targeting};
+
+// ============================================================
+//  FlagdConfiguration
+// ============================================================
+
+/// A flag configuration in the [flagd](https://flagd.dev/reference/flag-definitions/) format,
+/// evaluated in-process once loaded into an [`InMemoryProvider`], without a flagd sidecar:
+///
+/// ```json
+/// {
+///   "flags": {
+///     "new-checkout": {
+///       "state": "ENABLED",
+///       "variants": { "on": true, "off": false },
+///       "defaultVariant": "off",
+///       "targeting": { "if": [{ "$ref": "employees" }, "on", null] },
+///       "metadata": { "team": "payments" }
+///     },
+///     "express-payment": {
+///       "variants": { "on": true, "off": false },
+///       "defaultVariant": "on",
+///       "prerequisites": [{ "flagKey": "new-checkout", "variant": "on" }]
+///     },
+///     "black-friday": {
+///       "variants": { "on": true, "off": false },
+///       "defaultVariant": "on",
+///       "activationWindows": [{
+///         "start": "2026-11-27T00:00:00Z",
+///         "end": "2026-11-28T00:00:00Z",
+///         "recurrence": { "from": "08:00", "until": "20:00", "offset": "-05:00" }
+///       }]
+///     }
+///   },
+///   "$evaluators": {
+///     "employees": { "in": ["@example.com", { "var": "email" }] }
+///   }
+/// }
+/// ```
+///
+/// Disabled flags are left out. Targeting rules may refer to the shared rules of `$evaluators`
+/// with `{"$ref": name}`, and are applied as a [`Targeting`]. As an extension of the format, a
+/// flag may list `prerequisites`, as described by [`InMemoryFlag::with_prerequisite`], and
+/// `activationWindows`, as described by [`InMemoryFlag::with_activation_window`]: RFC 3339
+/// `start` and `end` times, and a `recurrence` from and until `HH:MM` times of the day, on some
+/// `weekdays` such as `"monday"`, at a UTC `offset`, all optional.
+///
+/// ```
+/// use open_feature::provider::{FlagdConfiguration, InMemoryProvider};
+///
+/// let configuration = FlagdConfiguration::parse(
+///     r#"{ "flags": { "tier": { "variants": { "gold": 1 }, "defaultVariant": "gold" } } }"#,
+/// )
+/// .unwrap();
+/// let provider = InMemoryProvider::from(configuration);
+/// ```
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct FlagdConfiguration {
+    /// The enabled flags, keyed by flag key.
+    pub flags: HashMap<String, InMemoryFlag>,
+}
+
+impl FlagdConfiguration {
+    /// Parse the JSON `text` of a flag configuration.
+    pub fn parse(text: &str) -> Result<Self, SdkError> {
+        let definitions: JsonValue =
+            serde_json::from_str(text).map_err(|error| SdkError::Configuration {
+                message: error.to_string(),
+            })?;
+
+        Self::from_json(&definitions)
+    }
+
+    /// Build a flag configuration out of its JSON `definitions`.
+    pub fn from_json(definitions: &JsonValue) -> Result<Self, SdkError> {
+        parse_definitions(definitions)
+            .map(|flags| Self { flags })
+            .map_err(|message| SdkError::Configuration { message })
+    }
+}
+
+impl From<FlagdConfiguration> for InMemoryProvider {
+    fn from(configuration: FlagdConfiguration) -> Self {
+        let provider = InMemoryProvider::default();
+        provider.set_flags(configuration.flags);
+        provider
+    }
+}
+
+// ============================================================
+//  Flag definitions
+// ============================================================
+
+/// Parse the flags of `definitions`, leaving out the disabled ones.
+pub(super) fn parse_definitions(
+    definitions: &JsonValue,
+) -> Result<HashMap<String, InMemoryFlag>, String> {
+    let flags = definitions
+        .get("flags")
+        .and_then(JsonValue::as_object)
+        .ok_or("Flags are expected under a \"flags\" object")?;
+
+    let empty = Map::new();
+    let evaluators = match definitions.get("$evaluators") {
+        None => &empty,
+        Some(evaluators) => evaluators
+            .as_object()
+            .ok_or("Evaluators are expected as a \"$evaluators\" object")?,
+    };
+
+    let mut result = HashMap::new();
+
+    for (flag_key, definition) in flags {
+        let flag = parse_flag(definition, evaluators)
+            .map_err(|message| format!("{}: {}", flag_key, message))?;
+
+        if let Some(flag) = flag {
+            result.insert(flag_key.clone(), flag);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Parse the flag of `definition`, or return `None` if it is disabled.
+fn parse_flag(
+    definition: &JsonValue,
+    evaluators: &Map<String, JsonValue>,
+) -> Result<Option<InMemoryFlag>, String> {
+    match definition.get("state").and_then(JsonValue::as_str) {
+        None | Some("ENABLED") => {}
+        Some("DISABLED") => return Ok(None),
+        Some(state) => return Err(format!("Unknown state \"{}\"", state)),
+    }
+
+    let default_variant = definition
+        .get("defaultVariant")
+        .and_then(JsonValue::as_str)
+        .ok_or("A \"defaultVariant\" string is expected")?;
+
+    let variants = definition
+        .get("variants")
+        .and_then(JsonValue::as_object)
+        .ok_or("A \"variants\" object is expected")?;
+
+    if !variants.contains_key(default_variant) {
+        return Err(format!("Variant \"{}\" is not defined", default_variant));
+    }
+
+    let mut flag = InMemoryFlag::new(default_variant);
+
+    for (variant, value) in variants {
+        let value = Value::try_from(value)
+            .map_err(|_| format!("Variant \"{}\" has an unsupported value", variant))?;
+
+        flag = flag.with_variant(variant.clone(), value);
+    }
+
+    match definition.get("targeting") {
+        None | Some(JsonValue::Null) => {}
+        Some(JsonValue::Object(rule)) if rule.is_empty() => {}
+        Some(rule) => {
+            flag = flag.with_targeting(Targeting::new(resolve_refs(rule, evaluators)?));
+        }
+    }
+
+    if let Some(prerequisites) = definition.get("prerequisites") {
+        let prerequisites = prerequisites
+            .as_array()
+            .ok_or("Prerequisites are expected as a \"prerequisites\" array")?;
+
+        for prerequisite in prerequisites {
+            let field = |name: &str| prerequisite.get(name).and_then(JsonValue::as_str);
+
+            match (field("flagKey"), field("variant")) {
+                (Some(flag_key), Some(variant)) => {
+                    flag = flag.with_prerequisite(flag_key, variant);
+                }
+                _ => {
+                    return Err(
+                        "A prerequisite is expected as a {\"flagKey\", \"variant\"} object"
+                            .to_string(),
+                    )
+                }
+            }
+        }
+    }
+
+    if let Some(windows) = definition.get("activationWindows") {
+        let windows = windows
+            .as_array()
+            .ok_or("Activation windows are expected as an \"activationWindows\" array")?;
+
+        for window in windows {
+            flag = flag.with_activation_window(parse_activation_window(window)?);
+        }
+    }
+
+    if let Some(metadata) = definition.get("metadata").and_then(JsonValue::as_object) {
+        let mut flag_metadata = FlagMetadata::default();
+
+        for (key, value) in metadata {
+            match value {
+                JsonValue::Bool(value) => flag_metadata.add_value(key, *value),
+                JsonValue::Number(value) => match (value.as_i64(), value.as_f64()) {
+                    (Some(value), _) => flag_metadata.add_value(key, value),
+                    (None, Some(value)) => flag_metadata.add_value(key, value),
+                    (None, None) => {}
+                },
+                JsonValue::String(value) => flag_metadata.add_value(key, value.as_str()),
+                _ => {
+                    return Err(format!(
+                        "Metadata \"{}\" is not a bool, number or string",
+                        key
+                    ))
+                }
+            }
+        }
+
+        flag = flag.with_flag_metadata(flag_metadata);
+    }
+
+    Ok(Some(flag))
+}
+
+/// Parse an activation window.
+fn parse_activation_window(definition: &JsonValue) -> Result<ActivationWindow, String> {
+    let definition = definition
+        .as_object()
+        .ok_or("An activation window is expected as an object")?;
+
+    let date_time = |name: &str| -> Result<Option<OffsetDateTime>, String> {
+        definition
+            .get(name)
+            .map(|value| {
+                value
+                    .as_str()
+                    .and_then(|value| OffsetDateTime::parse(value, &Rfc3339).ok())
+                    .ok_or_else(|| format!("An RFC 3339 \"{}\" time is expected", name))
+            })
+            .transpose()
+    };
+
+    Ok(ActivationWindow {
+        start: date_time("start")?,
+        end: date_time("end")?,
+        recurrence: definition
+            .get("recurrence")
+            .map(parse_recurrence)
+            .transpose()?,
+    })
+}
+
+/// Parse the recurrence of an activation window.
+fn parse_recurrence(definition: &JsonValue) -> Result<Recurrence, String> {
+    let field = |name: &str| definition.get(name).and_then(JsonValue::as_str);
+
+    let time_of_day = |name: &str| {
+        field(name)
+            .and_then(|value| {
+                let (hour, minute) = value.split_once(':')?;
+                Time::from_hms(hour.parse().ok()?, minute.parse().ok()?, 0).ok()
+            })
+            .ok_or_else(|| format!("A recurrence \"{}\" time is expected as HH:MM", name))
+    };
+
+    let mut recurrence = Recurrence::daily(time_of_day("from")?, time_of_day("until")?);
+
+    if let Some(weekdays) = definition.get("weekdays") {
+        recurrence = recurrence.on(weekdays
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|weekday| weekday.as_str().and_then(parse_weekday))
+            .collect::<Option<Vec<_>>>()
+            .ok_or("Recurrence weekdays are expected as an array of day names")?);
+    }
+
+    if let Some(offset) = definition.get("offset") {
+        recurrence = recurrence.with_offset(
+            offset
+                .as_str()
+                .and_then(parse_offset)
+                .ok_or("A recurrence \"offset\" is expected as +HH:MM")?,
+        );
+    }
+
+    Ok(recurrence)
+}
+
+fn parse_offset(offset: &str) -> Option<UtcOffset> {
+    let (sign, offset) = match offset.split_at(1) {
+        ("+", offset) => (1, offset),
+        ("-", offset) => (-1, offset),
+        _ => return None,
+    };
+    let (hours, minutes) = offset.split_once(':')?;
+
+    UtcOffset::from_hms(
+        sign * hours.parse::<i8>().ok()?,
+        sign * minutes.parse::<i8>().ok()?,
+        0,
+    )
+    .ok()
+}
+
+fn parse_weekday(name: &str) -> Option<Weekday> {
+    match name.to_ascii_lowercase().as_str() {
+        "monday" => Some(Weekday::Monday),
+        "tuesday" => Some(Weekday::Tuesday),
+        "wednesday" => Some(Weekday::Wednesday),
+        "thursday" => Some(Weekday::Thursday),
+        "friday" => Some(Weekday::Friday),
+        "saturday" => Some(Weekday::Saturday),
+        "sunday" => Some(Weekday::Sunday),
+        _ => None,
+    }
+}
+
+/// Replace the `{"$ref": name}` objects of `rule` with the evaluator `name`.
+fn resolve_refs(
+    rule: &JsonValue,
+    evaluators: &Map<String, JsonValue>,
+) -> Result<JsonValue, String> {
+    resolve_refs_within(rule, evaluators, &mut Vec::new())
+}
+
+fn resolve_refs_within<'a>(
+    rule: &'a JsonValue,
+    evaluators: &'a Map<String, JsonValue>,
+    resolving: &mut Vec<&'a str>,
+) -> Result<JsonValue, String> {
+    match rule {
+        JsonValue::Object(fields) => {
+            if let (1, Some(JsonValue::String(name))) = (fields.len(), fields.get("$ref")) {
+                let evaluator = evaluators
+                    .get(name)
+                    .ok_or_else(|| format!("Evaluator \"{}\" is not defined", name))?;
+
+                if resolving.contains(&name.as_str()) {
+                    return Err(format!("Evaluator \"{}\" refers to itself", name));
+                }
+
+                resolving.push(name);
+                let evaluator = resolve_refs_within(evaluator, evaluators, resolving)?;
+                resolving.pop();
+
+                return Ok(evaluator);
+            }
+
+            fields
+                .iter()
+                .map(|(key, value)| {
+                    Ok((
+                        key.clone(),
+                        resolve_refs_within(value, evaluators, resolving)?,
+                    ))
+                })
+                .collect::<Result<_, String>>()
+                .map(JsonValue::Object)
+        }
+        JsonValue::Array(items) => items
+            .iter()
+            .map(|item| resolve_refs_within(item, evaluators, resolving))
+            .collect::<Result<_, _>>()
+            .map(JsonValue::Array),
+        rule => Ok(rule.clone()),
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{provider::FeatureProvider, EvaluationContext, EvaluationReason};
+
+    #[test]
+    fn parse_flags() {
+        let flags = parse_definitions(&json!({
+            "flags": {
+                "new-checkout": {
+                    "state": "ENABLED",
+                    "variants": { "on": true, "off": false },
+                    "defaultVariant": "on",
+                    "metadata": { "team": "payments" }
+                },
+                "legacy": {
+                    "state": "DISABLED",
+                    "variants": { "on": true },
+                    "defaultVariant": "on"
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(flags.len(), 1);
+        assert_eq!(
+            flags["new-checkout"],
+            InMemoryFlag::new("on")
+                .with_variant("on", true)
+                .with_variant("off", false)
+                .with_flag_metadata(FlagMetadata::default().with_value("team", "payments"))
+        );
+
+        let error = parse_definitions(&json!({
+            "flags": { "tier": { "variants": { "gold": 1 }, "defaultVariant": "silver" } }
+        }))
+        .unwrap_err();
+        assert_eq!(error, "tier: Variant \"silver\" is not defined");
+    }
+
+    #[test]
+    fn resolve_evaluators() {
+        let definitions = |targeting: JsonValue| {
+            json!({
+                "flags": {
+                    "new-checkout": {
+                        "variants": { "on": true, "off": false },
+                        "defaultVariant": "off",
+                        "targeting": targeting
+                    }
+                },
+                "$evaluators": {
+                    "employees": { "in": ["@example.com", { "var": "email" }] },
+                    "loop": { "$ref": "loop" }
+                }
+            })
+        };
+
+        let flags = parse_definitions(&definitions(
+            json!({ "if": [{ "$ref": "employees" }, "on"] }),
+        ))
+        .unwrap();
+        assert_eq!(
+            flags["new-checkout"],
+            InMemoryFlag::new("off")
+                .with_variant("on", true)
+                .with_variant("off", false)
+                .with_targeting(Targeting::new(json!({
+                    "if": [{ "in": ["@example.com", { "var": "email" }] }, "on"]
+                })))
+        );
+
+        assert_eq!(
+            parse_definitions(&definitions(json!({ "$ref": "missing" }))).unwrap_err(),
+            "new-checkout: Evaluator \"missing\" is not defined"
+        );
+        assert_eq!(
+            parse_definitions(&definitions(json!({ "$ref": "loop" }))).unwrap_err(),
+            "new-checkout: Evaluator \"loop\" refers to itself"
+        );
+    }
+
+    #[test]
+    fn parse_prerequisites() {
+        let flags = parse_definitions(&json!({
+            "flags": {
+                "express-payment": {
+                    "variants": { "on": true },
+                    "defaultVariant": "on",
+                    "prerequisites": [{ "flagKey": "new-checkout", "variant": "on" }]
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            flags["express-payment"],
+            InMemoryFlag::new("on")
+                .with_variant("on", true)
+                .with_prerequisite("new-checkout", "on")
+        );
+
+        let error = parse_definitions(&json!({
+            "flags": {
+                "express-payment": {
+                    "variants": { "on": true },
+                    "defaultVariant": "on",
+                    "prerequisites": [{ "flagKey": "new-checkout" }]
+                }
+            }
+        }))
+        .unwrap_err();
+        assert_eq!(
+            error,
+            "express-payment: A prerequisite is expected as a {\"flagKey\", \"variant\"} object"
+        );
+    }
+
+    #[test]
+    fn parse_activation_windows() {
+        let flags = parse_definitions(&json!({
+            "flags": {
+                "black-friday": {
+                    "variants": { "on": true },
+                    "defaultVariant": "on",
+                    "activationWindows": [{
+                        "start": "2026-11-27T00:00:00Z",
+                        "recurrence": {
+                            "from": "08:00",
+                            "until": "20:00",
+                            "weekdays": ["friday", "Saturday"],
+                            "offset": "-05:00"
+                        }
+                    }]
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            flags["black-friday"],
+            InMemoryFlag::new("on")
+                .with_variant("on", true)
+                .with_activation_window(
+                    ActivationWindow::starting_at(
+                        OffsetDateTime::parse("2026-11-27T00:00:00Z", &Rfc3339).unwrap()
+                    )
+                    .with_recurrence(
+                        Recurrence::daily(
+                            Time::from_hms(8, 0, 0).unwrap(),
+                            Time::from_hms(20, 0, 0).unwrap()
+                        )
+                        .on([Weekday::Friday, Weekday::Saturday])
+                        .with_offset(UtcOffset::from_hms(-5, 0, 0).unwrap())
+                    )
+                )
+        );
+
+        let error = parse_definitions(&json!({
+            "flags": {
+                "black-friday": {
+                    "variants": { "on": true },
+                    "defaultVariant": "on",
+                    "activationWindows": [{ "end": "tomorrow" }]
+                }
+            }
+        }))
+        .unwrap_err();
+        assert_eq!(error, "black-friday: An RFC 3339 \"end\" time is expected");
+    }
+
+    #[tokio::test]
+    async fn evaluate_in_process() {
+        let provider = InMemoryProvider::from(
+            FlagdConfiguration::from_json(&json!({
+                "flags": {
+                    "new-checkout": {
+                        "variants": { "on": true, "off": false },
+                        "defaultVariant": "off",
+                        "targeting": { "if": [{ "in": ["@example.com", { "var": "email" }] }, "on", null] }
+                    }
+                }
+            }))
+            .unwrap(),
+        );
+
+        let details = provider
+            .resolve_bool_value(
+                "new-checkout",
+                &EvaluationContext::default().with_custom_field("email", "ada@example.com"),
+            )
+            .await
+            .unwrap();
+        assert!(details.value);
+        assert_eq!(details.reason, Some(EvaluationReason::TargetingMatch));
+
+        let details = provider
+            .resolve_bool_value("new-checkout", &EvaluationContext::default())
+            .await
+            .unwrap();
+        assert!(!details.value);
+        assert_eq!(details.reason, Some(EvaluationReason::Default));
+    }
+}
@@ -0,0 +1,918 @@
+use std::{cmp::Ordering, collections::HashMap, time::Duration};
+
+use async_trait::async_trait;
+use serde_json::{Map, Value as JsonValue};
+
+use crate::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationReason, EvaluationResult,
+    FlagMetadata, ProviderError, ProviderErrorKind, StructValue, Value,
+};
+
+use super::{
+    json_logic::{to_number, to_string, SemVer},
+    EventEmitter, FeatureProvider, Fetched, FlagType, FlagValue, PollingProvider, PollingSource,
+    ProviderMetadata, ResolutionDetails,
+};
+
+// ============================================================
+//  GrowthBookProvider
+// ============================================================
+
+/// A provider evaluating [GrowthBook](https://www.growthbook.io) features locally, from the
+/// feature definitions polled from the GrowthBook API with a client key.
+///
+/// Force rules and experiments are evaluated as the GrowthBook SDKs do, against the attributes of
+/// the evaluation context, the targeting key being the `id` attribute. Users are assigned to
+/// experiment variations by hashing their hash attribute with FNV-1a, so that they consistently
+/// see the same variation.
+///
+/// Values forced by a rule resolve for `TARGETING_MATCH`, and experiment assignments for `SPLIT`,
+/// with the variation key as variant, and the `experimentKey` and `variationId` flag metadata for
+/// downstream analytics.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use open_feature::provider::GrowthBookProvider;
+///
+/// let provider = GrowthBookProvider::new("sdk-abc123")
+///     .with_api_host("https://growthbook-proxy.example.com")
+///     .with_polling_interval(Duration::from_secs(30));
+/// ```
+pub struct GrowthBookProvider {
+    metadata: ProviderMetadata,
+    client_key: String,
+    api_host: String,
+    timeout: Duration,
+    polling_interval: Duration,
+    polling: PollingProvider<GrowthBookSource>,
+}
+
+impl GrowthBookProvider {
+    /// The API host of GrowthBook Cloud.
+    pub const DEFAULT_API_HOST: &'static str = "https://cdn.growthbook.io";
+
+    /// The interval the features are polled at by default.
+    pub const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_secs(60);
+
+    /// The time after which a request is abandoned by default.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Create a provider polling the features of `client_key` once initialized.
+    pub fn new(client_key: impl Into<String>) -> Self {
+        let client_key = client_key.into();
+
+        Self {
+            metadata: ProviderMetadata::new("GrowthBook Provider"),
+            polling: create_polling(
+                &client_key,
+                Self::DEFAULT_API_HOST,
+                Self::DEFAULT_TIMEOUT,
+                Self::DEFAULT_POLLING_INTERVAL,
+            ),
+            client_key,
+            api_host: Self::DEFAULT_API_HOST.to_string(),
+            timeout: Self::DEFAULT_TIMEOUT,
+            polling_interval: Self::DEFAULT_POLLING_INTERVAL,
+        }
+    }
+
+    /// Fetch the features from `api_host`, such as a self-hosted GrowthBook or a GrowthBook
+    /// proxy, instead of [`Self::DEFAULT_API_HOST`].
+    #[must_use]
+    pub fn with_api_host(mut self, api_host: impl Into<String>) -> Self {
+        self.api_host = api_host.into().trim_end_matches('/').to_string();
+        self.rebuild()
+    }
+
+    /// Abandon requests taking longer than `timeout`.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.rebuild()
+    }
+
+    /// Poll the features every `interval` instead of [`Self::DEFAULT_POLLING_INTERVAL`].
+    #[must_use]
+    pub fn with_polling_interval(mut self, interval: Duration) -> Self {
+        self.polling_interval = interval;
+        self.rebuild()
+    }
+
+    fn rebuild(mut self) -> Self {
+        self.polling = create_polling(
+            &self.client_key,
+            &self.api_host,
+            self.timeout,
+            self.polling_interval,
+        );
+        self
+    }
+
+    fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let features = self.polling.configuration().ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ProviderNotReady)
+                .message("The GrowthBook features have not been fetched yet")
+                .build()
+        })?;
+
+        let details = features.evaluate(flag_key, &attributes(evaluation_context))?;
+
+        let value = match (T::FLAG_TYPE, details.value) {
+            #[allow(clippy::cast_precision_loss)]
+            (FlagType::Float, Value::Int(value)) => Value::Float(value as f64),
+            (_, value) => value,
+        };
+
+        Ok(ResolutionDetails {
+            value: T::from_value(value).ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::TypeMismatch)
+                    .message(format!("Expected a {:?} flag", T::FLAG_TYPE))
+                    .build()
+            })?,
+            variant: details.variant,
+            reason: details.reason,
+            flag_metadata: details.flag_metadata,
+        })
+    }
+}
+
+fn create_polling(
+    client_key: &str,
+    api_host: &str,
+    timeout: Duration,
+    interval: Duration,
+) -> PollingProvider<GrowthBookSource> {
+    let source = GrowthBookSource {
+        client: reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("The HTTP client can be built"),
+        url: format!("{api_host}/api/features/{client_key}"),
+    };
+
+    PollingProvider::new("GrowthBook Provider", source, interval)
+}
+
+#[async_trait]
+impl FeatureProvider for GrowthBookProvider {
+    async fn initialize(&mut self, _context: &EvaluationContext) -> Result<(), ProviderError> {
+        match self.polling.start().await {
+            Err(error) if !error.is_retryable() => Err(error),
+            // A transient failure is retried by the next poll.
+            _ => Ok(()),
+        }
+    }
+
+    async fn shutdown(&self) {
+        self.polling.stop();
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        Some(self.polling.event_emitter())
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let features = self.polling.configuration().ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ProviderNotReady)
+                .message("The GrowthBook features have not been fetched yet")
+                .build()
+        })?;
+
+        let attributes = attributes(evaluation_context);
+
+        Ok(features
+            .features
+            .keys()
+            .filter_map(|key| Some((key.clone(), features.evaluate(key, &attributes).ok()?)))
+            .collect())
+    }
+}
+
+/// Return the GrowthBook attributes of `context`, the targeting key being the `id`.
+fn attributes(context: &EvaluationContext) -> JsonValue {
+    let JsonValue::Object(mut attributes) = JsonValue::from(context) else {
+        unreachable!("Evaluation contexts convert to JSON objects");
+    };
+
+    if let Some(targeting_key) = attributes.remove("targetingKey") {
+        attributes.insert("id".to_string(), targeting_key);
+    }
+
+    JsonValue::Object(attributes)
+}
+
+// ============================================================
+//  GrowthBookSource
+// ============================================================
+
+/// Fetches the feature definitions from the GrowthBook API.
+struct GrowthBookSource {
+    client: reqwest::Client,
+    url: String,
+}
+
+#[async_trait]
+impl PollingSource for GrowthBookSource {
+    type Configuration = GrowthBookFeatures;
+
+    async fn fetch(&self, etag: Option<&str>) -> Result<Fetched, ProviderError> {
+        let mut request = self.client.get(&self.url);
+        if let Some(etag) = etag {
+            request = request.header("If-None-Match", etag);
+        }
+
+        let response = request.send().await.map_err(|error| {
+            let kind = if error.is_timeout() {
+                ProviderErrorKind::Timeout
+            } else {
+                ProviderErrorKind::Network
+            };
+
+            ProviderError::new(kind, error.to_string()).with_source(error)
+        })?;
+
+        let status = response.status().as_u16();
+        let kind = match status {
+            200 => {
+                let etag = response
+                    .headers()
+                    .get("etag")
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(ToString::to_string);
+                let payload = response.bytes().await.map_err(|error| {
+                    ProviderError::new(ProviderErrorKind::Network, error.to_string())
+                        .with_source(error)
+                })?;
+
+                return Ok(Fetched::Payload {
+                    payload: payload.to_vec(),
+                    etag,
+                });
+            }
+            304 => return Ok(Fetched::NotModified),
+            // The API answers 400 for unknown client keys.
+            400 | 401 | 403 | 404 => ProviderErrorKind::Unauthorized,
+            429 => ProviderErrorKind::RateLimited,
+            500..=599 => ProviderErrorKind::Unavailable,
+            _ => ProviderErrorKind::InvalidResponse,
+        };
+
+        Err(ProviderError::new(
+            kind,
+            format!("The GrowthBook API answered with {status}"),
+        ))
+    }
+
+    fn parse(&self, payload: &[u8]) -> Result<GrowthBookFeatures, ProviderError> {
+        let body: JsonValue = serde_json::from_slice(payload).map_err(|error| {
+            ProviderError::new(ProviderErrorKind::InvalidResponse, error.to_string())
+                .with_source(error)
+        })?;
+
+        GrowthBookFeatures::parse(&body)
+    }
+
+    fn flags_changed(
+        &self,
+        previous: &GrowthBookFeatures,
+        current: &GrowthBookFeatures,
+    ) -> Option<Vec<String>> {
+        let mut flags_changed: Vec<_> = previous
+            .features
+            .keys()
+            .chain(current.features.keys())
+            .filter(|key| previous.features.get(*key) != current.features.get(*key))
+            .cloned()
+            .collect();
+        flags_changed.sort();
+        flags_changed.dedup();
+
+        Some(flags_changed)
+    }
+}
+
+// ============================================================
+//  GrowthBookFeatures
+// ============================================================
+
+/// The feature definitions of a GrowthBook client key.
+struct GrowthBookFeatures {
+    features: Map<String, JsonValue>,
+}
+
+impl GrowthBookFeatures {
+    fn parse(body: &JsonValue) -> Result<Self, ProviderError> {
+        if body.get("encryptedFeatures").is_some() {
+            return Err(ProviderError::new(
+                ProviderErrorKind::Misconfigured,
+                "Encrypted GrowthBook features are not supported",
+            ));
+        }
+
+        let features = body
+            .get("features")
+            .and_then(JsonValue::as_object)
+            .ok_or_else(|| {
+                ProviderError::new(
+                    ProviderErrorKind::InvalidResponse,
+                    "The GrowthBook response has no features",
+                )
+            })?;
+
+        Ok(Self {
+            features: features.clone(),
+        })
+    }
+
+    /// Evaluate feature `key` for `attributes`, applying the first matching rule.
+    fn evaluate(
+        &self,
+        key: &str,
+        attributes: &JsonValue,
+    ) -> EvaluationResult<ResolutionDetails<Value>> {
+        let feature = self.features.get(key).ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .message(format!("GrowthBook feature \"{key}\" does not exist"))
+                .build()
+        })?;
+
+        let rules = feature.get("rules").and_then(JsonValue::as_array);
+
+        for rule in rules.into_iter().flatten() {
+            let condition_met = rule
+                .get("condition")
+                .map_or(true, |condition| evaluate_condition(condition, attributes));
+            if !condition_met {
+                continue;
+            }
+
+            if let Some(value) = rule.get("force") {
+                if !is_included(key, rule, attributes) {
+                    continue;
+                }
+
+                return Ok(ResolutionDetails {
+                    value: Value::try_from(value)?,
+                    variant: None,
+                    reason: Some(EvaluationReason::TargetingMatch),
+                    flag_metadata: None,
+                });
+            }
+
+            if let Some(details) = run_experiment(key, rule, attributes)? {
+                return Ok(details);
+            }
+        }
+
+        let value = feature.get("defaultValue").unwrap_or(&JsonValue::Null);
+
+        Ok(ResolutionDetails {
+            value: Value::try_from(value)?,
+            variant: None,
+            reason: Some(EvaluationReason::Default),
+            flag_metadata: None,
+        })
+    }
+}
+
+// ============================================================
+//  Experiments
+// ============================================================
+
+/// Return whether the user is within the `range` or `coverage` of force `rule`, which they are
+/// if the rule has none.
+fn is_included(key: &str, rule: &JsonValue, attributes: &JsonValue) -> bool {
+    let range = rule.get("range").and_then(parse_range);
+    let coverage = rule.get("coverage").and_then(JsonValue::as_f64);
+    if range.is_none() && coverage.is_none() {
+        return true;
+    }
+
+    let Some(hash_value) = hash_value(rule, attributes) else {
+        return false;
+    };
+    let seed = rule.get("seed").and_then(JsonValue::as_str).unwrap_or(key);
+    let Some(n) = hash(seed, &hash_value, hash_version(rule)) else {
+        return false;
+    };
+
+    match range {
+        Some(range) => in_range(n, range),
+        None => n <= coverage.unwrap_or(1.0),
+    }
+}
+
+/// Assign the user to a variation of experiment `rule`, unless they are not part of it.
+fn run_experiment(
+    key: &str,
+    rule: &JsonValue,
+    attributes: &JsonValue,
+) -> EvaluationResult<Option<ResolutionDetails<Value>>> {
+    let Some(variations) = rule.get("variations").and_then(JsonValue::as_array) else {
+        return Ok(None);
+    };
+    if variations.len() < 2 {
+        return Ok(None);
+    }
+
+    let Some(hash_value) = hash_value(rule, attributes) else {
+        return Ok(None);
+    };
+
+    if let Some(namespace) = rule.get("namespace").and_then(JsonValue::as_array) {
+        let in_namespace = match namespace.as_slice() {
+            [id, start, end] => hash(&format!("__{}", to_string(id)), &hash_value, 1)
+                .zip(start.as_f64().zip(end.as_f64()))
+                .map_or(false, |(n, range)| in_range(n, range)),
+            _ => false,
+        };
+        if !in_namespace {
+            return Ok(None);
+        }
+    }
+
+    let experiment_key = rule.get("key").and_then(JsonValue::as_str).unwrap_or(key);
+    let seed = rule
+        .get("seed")
+        .and_then(JsonValue::as_str)
+        .unwrap_or(experiment_key);
+    let Some(n) = hash(seed, &hash_value, hash_version(rule)) else {
+        return Ok(None);
+    };
+
+    let ranges = match rule.get("ranges").and_then(JsonValue::as_array) {
+        Some(ranges) => ranges.iter().filter_map(parse_range).collect(),
+        None => bucket_ranges(
+            variations.len(),
+            rule.get("coverage")
+                .and_then(JsonValue::as_f64)
+                .unwrap_or(1.0),
+            rule.get("weights").and_then(JsonValue::as_array),
+        ),
+    };
+
+    let Some(variation_id) = ranges.iter().position(|range| in_range(n, *range)) else {
+        return Ok(None);
+    };
+
+    let variation_key = rule
+        .get("meta")
+        .and_then(|meta| meta.get(variation_id))
+        .and_then(|meta| meta.get("key"))
+        .and_then(JsonValue::as_str)
+        .map_or_else(|| variation_id.to_string(), ToString::to_string);
+
+    Ok(Some(ResolutionDetails {
+        value: Value::try_from(&variations[variation_id])?,
+        variant: Some(variation_key),
+        reason: Some(EvaluationReason::Split),
+        flag_metadata: Some(
+            FlagMetadata::default()
+                .with_value("experimentKey", experiment_key)
+                .with_value(
+                    "variationId",
+                    i64::try_from(variation_id).unwrap_or(i64::MAX),
+                ),
+        ),
+    }))
+}
+
+/// Return the value of the hash attribute of `rule`, `id` by default, unless missing or empty.
+fn hash_value(rule: &JsonValue, attributes: &JsonValue) -> Option<String> {
+    let hash_attribute = rule
+        .get("hashAttribute")
+        .and_then(JsonValue::as_str)
+        .unwrap_or("id");
+
+    let value = to_string(attributes.get(hash_attribute)?);
+    (!value.is_empty()).then_some(value)
+}
+
+fn hash_version(rule: &JsonValue) -> u64 {
+    rule.get("hashVersion")
+        .and_then(JsonValue::as_u64)
+        .unwrap_or(1)
+}
+
+/// Hash `value` with `seed` into `[0, 1)` as GrowthBook does, with hashing algorithm `version`.
+fn hash(seed: &str, value: &str, version: u64) -> Option<f64> {
+    match version {
+        1 => Some(f64::from(fnv1a32(&format!("{value}{seed}")) % 1000) / 1000.0),
+        2 => {
+            let n = fnv1a32(&fnv1a32(&format!("{seed}{value}")).to_string());
+            Some(f64::from(n % 10000) / 10000.0)
+        }
+        _ => None,
+    }
+}
+
+/// The 32-bit FNV-1a hash of `text`.
+fn fnv1a32(text: &str) -> u32 {
+    text.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Return the range of each variation: consecutive shares of `[0, 1)` proportional to their
+/// weights, equal by default, shrunk to `coverage`.
+fn bucket_ranges(
+    variations: usize,
+    coverage: f64,
+    weights: Option<&Vec<JsonValue>>,
+) -> Vec<(f64, f64)> {
+    let coverage = coverage.clamp(0.0, 1.0);
+
+    #[allow(clippy::cast_precision_loss)]
+    let equal = vec![1.0 / variations as f64; variations];
+    let weights = weights
+        .map(|weights| {
+            weights
+                .iter()
+                .filter_map(JsonValue::as_f64)
+                .collect::<Vec<_>>()
+        })
+        .filter(|weights| {
+            weights.len() == variations && (weights.iter().sum::<f64>() - 1.0).abs() < 0.01
+        })
+        .unwrap_or(equal);
+
+    let mut cumulative = 0.0;
+    weights
+        .into_iter()
+        .map(|weight| {
+            let start = cumulative;
+            cumulative += weight;
+            (start, start + coverage * weight)
+        })
+        .collect()
+}
+
+fn parse_range(range: &JsonValue) -> Option<(f64, f64)> {
+    match range.as_array()?.as_slice() {
+        [start, end] => Some((start.as_f64()?, end.as_f64()?)),
+        _ => None,
+    }
+}
+
+fn in_range(n: f64, (start, end): (f64, f64)) -> bool {
+    n >= start && n < end
+}
+
+// ============================================================
+//  Conditions
+// ============================================================
+
+/// Return whether `attributes` satisfy `condition`, a MongoDB-like query as used by GrowthBook
+/// targeting conditions. Attributes are looked up with dotted paths.
+fn evaluate_condition(condition: &JsonValue, attributes: &JsonValue) -> bool {
+    let Some(condition) = condition.as_object() else {
+        return false;
+    };
+
+    condition.iter().all(|(key, value)| match key.as_str() {
+        "$or" => value.as_array().map_or(false, |conditions| {
+            conditions.is_empty()
+                || conditions
+                    .iter()
+                    .any(|condition| evaluate_condition(condition, attributes))
+        }),
+        "$nor" => value.as_array().map_or(false, |conditions| {
+            !conditions
+                .iter()
+                .any(|condition| evaluate_condition(condition, attributes))
+        }),
+        "$and" => value.as_array().map_or(false, |conditions| {
+            conditions
+                .iter()
+                .all(|condition| evaluate_condition(condition, attributes))
+        }),
+        "$not" => !evaluate_condition(value, attributes),
+        path => evaluate_value(value, lookup(attributes, path)),
+    })
+}
+
+fn lookup<'a>(attributes: &'a JsonValue, path: &str) -> Option<&'a JsonValue> {
+    path.split('.')
+        .try_fold(attributes, |value, key| value.get(key))
+}
+
+/// Return whether `actual` satisfies `expected`, an object of operators or a literal.
+fn evaluate_value(expected: &JsonValue, actual: Option<&JsonValue>) -> bool {
+    match expected.as_object() {
+        Some(operators) if operators.keys().all(|key| key.starts_with('$')) => operators
+            .iter()
+            .all(|(operator, expected)| evaluate_operator(operator, expected, actual)),
+        _ => actual.unwrap_or(&JsonValue::Null) == expected,
+    }
+}
+
+fn evaluate_operator(operator: &str, expected: &JsonValue, actual: Option<&JsonValue>) -> bool {
+    let value = actual.unwrap_or(&JsonValue::Null);
+
+    match operator {
+        "$eq" => value == expected,
+        "$ne" => value != expected,
+        "$lt" => compare(value, expected) == Some(Ordering::Less),
+        "$lte" => compare(value, expected).map_or(false, Ordering::is_le),
+        "$gt" => compare(value, expected) == Some(Ordering::Greater),
+        "$gte" => compare(value, expected).map_or(false, Ordering::is_ge),
+        "$veq" | "$vne" | "$vlt" | "$vlte" | "$vgt" | "$vgte" => {
+            let versions = SemVer::parse(&to_string(value))
+                .ok()
+                .zip(SemVer::parse(&to_string(expected)).ok());
+            versions.map_or(false, |(version, expected)| {
+                let ordering = version.cmp(&expected);
+                match operator {
+                    "$veq" => ordering.is_eq(),
+                    "$vne" => ordering.is_ne(),
+                    "$vlt" => ordering.is_lt(),
+                    "$vlte" => ordering.is_le(),
+                    "$vgt" => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                }
+            })
+        }
+        "$in" | "$nin" => {
+            let is_in = expected.as_array().map_or(false, |items| match value {
+                JsonValue::Array(values) => values.iter().any(|value| items.contains(value)),
+                value => items.contains(value),
+            });
+            is_in == (operator == "$in")
+        }
+        "$all" => match (value.as_array(), expected.as_array()) {
+            (Some(values), Some(items)) => items.iter().all(|item| values.contains(item)),
+            _ => false,
+        },
+        "$elemMatch" => value.as_array().map_or(false, |values| {
+            values.iter().any(|value| {
+                if expected.as_object().map_or(false, |object| {
+                    object.keys().all(|key| key.starts_with('$'))
+                }) {
+                    evaluate_value(expected, Some(value))
+                } else {
+                    evaluate_condition(expected, value)
+                }
+            })
+        }),
+        "$size" => value.as_array().map_or(false, |values| {
+            evaluate_value(expected, Some(&JsonValue::from(values.len())))
+        }),
+        "$exists" => {
+            let exists = actual.map_or(false, |value| !value.is_null());
+            exists == expected.as_bool().unwrap_or(false)
+        }
+        "$type" => {
+            let actual_type = match value {
+                JsonValue::Null => "null",
+                JsonValue::Bool(_) => "boolean",
+                JsonValue::Number(_) => "number",
+                JsonValue::String(_) => "string",
+                JsonValue::Array(_) => "array",
+                JsonValue::Object(_) => "object",
+            };
+            expected.as_str() == Some(actual_type)
+        }
+        "$not" => !evaluate_value(expected, actual),
+        // Unknown operators, such as `$regex`, never match.
+        _ => false,
+    }
+}
+
+/// Compare numbers as numbers, and anything else as strings.
+fn compare(left: &JsonValue, right: &JsonValue) -> Option<Ordering> {
+    match (left, right) {
+        (JsonValue::Null, _) | (_, JsonValue::Null) => None,
+        (JsonValue::Number(_), _) | (_, JsonValue::Number(_)) => {
+            to_number(left)?.partial_cmp(&to_number(right)?)
+        }
+        _ => Some(to_string(left).cmp(&to_string(right))),
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn create_features() -> GrowthBookFeatures {
+        GrowthBookFeatures::parse(&json!({
+            "features": {
+                "checkout-v2": {
+                    "defaultValue": false,
+                    "rules": [
+                        { "condition": { "country": { "$in": ["CH", "LI"] } }, "force": true },
+                        {
+                            "condition": { "$or": [{ "plan": "pro" }, { "orders": { "$gte": 10 } }] },
+                            "force": true,
+                            "coverage": 0.0
+                        }
+                    ]
+                },
+                "button-color": {
+                    "defaultValue": "grey",
+                    "rules": [
+                        {
+                            "key": "button-test",
+                            "variations": ["red", "blue"],
+                            "weights": [0.5, 0.5],
+                            "hashVersion": 2,
+                            "meta": [{ "key": "control" }, { "key": "treatment" }]
+                        }
+                    ]
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn hash_as_growthbook() {
+        // The reference values of the GrowthBook SDK test cases.
+        assert_eq!(fnv1a32("a"), 0xe40c_292c);
+        assert_eq!(hash("", "a", 1), Some(0.22));
+        assert_eq!(hash("", "b", 1), Some(0.077));
+        assert_eq!(hash("b", "a", 1), Some(0.946));
+        assert_eq!(hash("ef", "d", 1), Some(0.652));
+        assert_eq!(hash("asdf", "8952klfjas09ujk", 1), Some(0.549));
+        assert_eq!(hash("", "123", 1), Some(0.011));
+        assert_eq!(hash("", "___)((*\":&", 1), Some(0.563));
+        assert_eq!(hash("seed", "a", 2), Some(0.0505));
+        assert_eq!(hash("seed", "b", 2), Some(0.2696));
+        assert_eq!(hash("foo", "ab", 2), Some(0.2575));
+        assert_eq!(hash("foo", "def", 2), Some(0.2019));
+        assert_eq!(hash("89123klj", "8952klfjas09ujkasdf", 2), Some(0.124));
+        assert_eq!(hash("seed", "ab", 3), None);
+    }
+
+    #[test]
+    fn compute_bucket_ranges() {
+        assert_eq!(bucket_ranges(2, 1.0, None), vec![(0.0, 0.5), (0.5, 1.0)]);
+        assert_eq!(
+            bucket_ranges(2, 0.5, Some(&vec![json!(0.4), json!(0.6)])),
+            vec![(0.0, 0.2), (0.4, 0.7)]
+        );
+    }
+
+    #[test]
+    fn evaluate_force_rules() {
+        let features = create_features();
+
+        let details = features
+            .evaluate("checkout-v2", &json!({ "id": "alice", "country": "CH" }))
+            .unwrap();
+        assert_eq!(details.value, Value::Bool(true));
+        assert_eq!(details.reason, Some(EvaluationReason::TargetingMatch));
+
+        // Nobody is within the coverage of the second rule.
+        let details = features
+            .evaluate("checkout-v2", &json!({ "id": "bob", "plan": "pro" }))
+            .unwrap();
+        assert_eq!(details.value, Value::Bool(false));
+        assert_eq!(details.reason, Some(EvaluationReason::Default));
+
+        let error = features.evaluate("missing", &json!({})).unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+    }
+
+    #[test]
+    fn assign_experiment_variations() {
+        let features = create_features();
+
+        let mut counts = HashMap::new();
+        for index in 0..1000 {
+            let details = features
+                .evaluate("button-color", &json!({ "id": format!("user-{index}") }))
+                .unwrap();
+            assert_eq!(details.reason, Some(EvaluationReason::Split));
+
+            let flag_metadata = details.flag_metadata.unwrap();
+            assert_eq!(flag_metadata.values["experimentKey"], "button-test".into());
+            *counts.entry(details.variant.unwrap()).or_insert(0) += 1;
+        }
+        assert!((400..600).contains(&counts["control"]), "{counts:?}");
+
+        // Users without a hash attribute are left out of the experiment.
+        let details = features.evaluate("button-color", &json!({})).unwrap();
+        assert_eq!(details.value, Value::String("grey".to_string()));
+    }
+
+    #[test]
+    fn evaluate_conditions() {
+        let attributes = json!({
+            "id": "alice",
+            "age": 32,
+            "version": "1.4.2",
+            "tags": ["beta", "staff"],
+            "company": { "name": "Acme" }
+        });
+
+        let cases = [
+            (json!({ "id": "alice" }), true),
+            (json!({ "age": { "$gt": 30, "$lt": 40 } }), true),
+            (json!({ "version": { "$vgte": "1.10.0" } }), false),
+            (json!({ "tags": { "$all": ["beta", "staff"] } }), true),
+            (
+                json!({ "tags": { "$elemMatch": { "$eq": "staff" } } }),
+                true,
+            ),
+            (json!({ "tags": { "$size": 2 } }), true),
+            (
+                json!({ "company.name": { "$in": ["Acme", "Initech"] } }),
+                true,
+            ),
+            (json!({ "email": { "$exists": false } }), true),
+            (json!({ "$nor": [{ "age": 32 }] }), false),
+            (json!({ "$not": { "id": "bob" } }), true),
+            (json!({ "id": { "$regex": "^a" } }), false),
+        ];
+
+        for (condition, expected) in cases {
+            assert_eq!(
+                evaluate_condition(&condition, &attributes),
+                expected,
+                "{condition}"
+            );
+        }
+    }
+
+    #[test]
+    fn map_attributes() {
+        let context = EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("country", "CH");
+
+        assert_eq!(
+            attributes(&context),
+            json!({ "id": "alice", "country": "CH" })
+        );
+    }
+
+    #[test]
+    fn reject_encrypted_features() {
+        let error = GrowthBookFeatures::parse(&json!({ "encryptedFeatures": "..." }))
+            .err()
+            .unwrap();
+        assert_eq!(error.kind, ProviderErrorKind::Misconfigured);
+    }
+}
@@ -0,0 +1,967 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use async_trait::async_trait;
+use time::OffsetDateTime;
+
+use crate::{
+    Clock, EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationReason,
+    EvaluationResult, FlagMetadata, StructValue, SystemClock, Value,
+};
+
+use super::{
+    fractional_variant, ActivationWindow, EventEmitter, FeatureProvider, FlagValue, ProviderEvent,
+    ProviderEventType, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+#[cfg(feature = "serde_json")]
+use super::Targeting;
+
+type VariantResolver = Arc<dyn Fn(&EvaluationContext) -> Option<String> + Send + Sync>;
+
+// ============================================================
+//  InMemoryFlag
+// ============================================================
+
+/// A flag served by [`InMemoryProvider`].
+#[derive(Clone, Default)]
+pub struct InMemoryFlag {
+    /// The values of the flag keyed by variant.
+    pub variants: HashMap<String, Value>,
+
+    /// The variant resolved for this flag.
+    pub default_variant: String,
+
+    /// The metadata returned along with every resolution.
+    pub flag_metadata: FlagMetadata,
+
+    resolver: Option<VariantResolver>,
+
+    fractional: Option<Vec<(String, u32)>>,
+
+    prerequisites: Vec<(String, String)>,
+
+    activation_windows: Vec<ActivationWindow>,
+
+    #[cfg(feature = "serde_json")]
+    targeting: Option<Targeting>,
+}
+
+impl InMemoryFlag {
+    /// Create a flag resolving to `default_variant`, which is expected to be added with
+    /// [`Self::with_variant`].
+    pub fn new(default_variant: impl Into<String>) -> Self {
+        Self {
+            variants: HashMap::new(),
+            default_variant: default_variant.into(),
+            flag_metadata: FlagMetadata::default(),
+            resolver: None,
+            fractional: None,
+            prerequisites: Vec::new(),
+            activation_windows: Vec::new(),
+            #[cfg(feature = "serde_json")]
+            targeting: None,
+        }
+    }
+
+    /// Create a flag with a single variant holding `value`.
+    ///
+    /// The variant is named after the value for bool, int, float and string values, and
+    /// `"default"` otherwise.
+    pub fn with_value(value: impl Into<Value>) -> Self {
+        let value = value.into();
+        let variant = match &value {
+            Value::Bool(value) => value.to_string(),
+            Value::Int(value) => value.to_string(),
+            Value::Float(value) => value.to_string(),
+            Value::String(value) => value.clone(),
+            Value::Array(_) | Value::Struct(_) => "default".to_string(),
+        };
+
+        Self::new(variant.clone()).with_variant(variant, value)
+    }
+
+    /// Add a variant named `name` holding `value`.
+    #[must_use]
+    pub fn with_variant(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.variants.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set the metadata returned along with every resolution.
+    #[must_use]
+    pub fn with_flag_metadata(mut self, flag_metadata: FlagMetadata) -> Self {
+        self.flag_metadata = flag_metadata;
+        self
+    }
+
+    /// Resolve the variant returned by `resolver` for the evaluation context, or the default
+    /// variant when it returns `None`.
+    ///
+    /// ```
+    /// use open_feature::provider::InMemoryFlag;
+    ///
+    /// let flag = InMemoryFlag::new("off")
+    ///     .with_variant("on", true)
+    ///     .with_variant("off", false)
+    ///     .with_resolver(|context| {
+    ///         let email = context.custom_fields.get("email")?.as_str()?;
+    ///         email.ends_with("@example.com").then(|| "on".to_string())
+    ///     });
+    /// ```
+    #[must_use]
+    pub fn with_resolver<F>(mut self, resolver: F) -> Self
+    where
+        F: Fn(&EvaluationContext) -> Option<String> + Send + Sync + 'static,
+    {
+        self.resolver = Some(Arc::new(resolver));
+        self
+    }
+
+    /// Serve each variant of `distribution` to a share of the subjects proportional to its
+    /// weight, consistently bucketing them by flag key and targeting key with
+    /// [`fractional_variant`](super::fractional_variant). It takes precedence over the resolver.
+    ///
+    /// ```
+    /// use open_feature::provider::InMemoryFlag;
+    ///
+    /// // Serve the new checkout to 20% of the users.
+    /// let flag = InMemoryFlag::new("off")
+    ///     .with_variant("on", true)
+    ///     .with_variant("off", false)
+    ///     .with_fractional([("on", 20), ("off", 80)]);
+    /// ```
+    #[must_use]
+    pub fn with_fractional<S: Into<String>>(
+        mut self,
+        distribution: impl IntoIterator<Item = (S, u32)>,
+    ) -> Self {
+        self.fractional = Some(
+            distribution
+                .into_iter()
+                .map(|(variant, weight)| (variant.into(), weight))
+                .collect(),
+        );
+        self
+    }
+
+    /// Resolve the variant targeted by `targeting` for the evaluation context, or the default
+    /// variant when it resolves to `null`. It takes precedence over the fractional distribution
+    /// and the resolver.
+    #[cfg(feature = "serde_json")]
+    #[must_use]
+    pub fn with_targeting(mut self, targeting: Targeting) -> Self {
+        self.targeting = Some(targeting);
+        self
+    }
+
+    /// Require flag `flag_key` to resolve to `variant` for this flag to be evaluated. Otherwise,
+    /// or if flag `flag_key` is not defined, the default variant is served with the `DISABLED`
+    /// reason.
+    ///
+    /// Prerequisites are only supported between flags of the same [`InMemoryProvider`], and
+    /// flags requiring themselves, directly or not, fail to resolve.
+    ///
+    /// ```
+    /// use open_feature::provider::{InMemoryFlag, InMemoryProvider};
+    ///
+    /// let provider = InMemoryProvider::default()
+    ///     .with_flag("new-checkout", InMemoryFlag::with_value(true))
+    ///     .with_flag(
+    ///         "express-payment",
+    ///         InMemoryFlag::new("off")
+    ///             .with_variant("on", true)
+    ///             .with_variant("off", false)
+    ///             .with_prerequisite("new-checkout", "true")
+    ///             .with_fractional([("on", 50), ("off", 50)]),
+    ///     );
+    /// ```
+    #[must_use]
+    pub fn with_prerequisite(
+        mut self,
+        flag_key: impl Into<String>,
+        variant: impl Into<String>,
+    ) -> Self {
+        self.prerequisites.push((flag_key.into(), variant.into()));
+        self
+    }
+
+    /// Only evaluate this flag within `window`, or within any of the windows if called several
+    /// times. Otherwise, the default variant is served with the `DISABLED` reason.
+    ///
+    /// Windows are checked against the clock of the [`InMemoryProvider`], at the as-of time of
+    /// the evaluation context if any (see [`Clock::evaluation_time`]).
+    #[must_use]
+    pub fn with_activation_window(mut self, window: ActivationWindow) -> Self {
+        self.activation_windows.push(window);
+        self
+    }
+
+    /// Return whether the flag is active at time `at`.
+    fn is_active(&self, at: OffsetDateTime) -> bool {
+        self.activation_windows.is_empty()
+            || self
+                .activation_windows
+                .iter()
+                .any(|window| window.contains(at))
+    }
+
+    fn resolve_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+        at: OffsetDateTime,
+    ) -> EvaluationResult<ResolutionDetails<Value>> {
+        let (variant, reason) = match self.targeted_variant(flag_key, evaluation_context, at)? {
+            Some(variant) => (variant, EvaluationReason::TargetingMatch),
+            None if self.is_targeted() => (self.default_variant.clone(), EvaluationReason::Default),
+            None => (self.default_variant.clone(), EvaluationReason::Static),
+        };
+
+        self.details(variant, reason)
+    }
+
+    fn details(
+        &self,
+        variant: String,
+        reason: EvaluationReason,
+    ) -> EvaluationResult<ResolutionDetails<Value>> {
+        let value = self.variants.get(&variant).ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::General(
+                    "Variant not found".to_string(),
+                ))
+                .message(format!("Variant \"{}\" is not defined", variant))
+                .build()
+        })?;
+
+        Ok(ResolutionDetails {
+            value: value.clone(),
+            variant: Some(variant),
+            reason: Some(reason),
+            flag_metadata: if self.flag_metadata.values.is_empty() {
+                None
+            } else {
+                Some(self.flag_metadata.clone())
+            },
+        })
+    }
+
+    /// Return the variant targeted for the evaluation context, if any.
+    // Without targeting rules, resolvers do not fail.
+    #[allow(clippy::unnecessary_wraps)]
+    fn targeted_variant(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+        #[cfg_attr(not(feature = "serde_json"), allow(unused_variables))] at: OffsetDateTime,
+    ) -> EvaluationResult<Option<String>> {
+        #[cfg(feature = "serde_json")]
+        if let Some(targeting) = &self.targeting {
+            return targeting.variant(flag_key, evaluation_context, at);
+        }
+
+        if let Some(distribution) = &self.fractional {
+            let bucketing_value = format!(
+                "{}{}",
+                flag_key,
+                evaluation_context
+                    .targeting_key
+                    .as_deref()
+                    .unwrap_or_default()
+            );
+
+            return Ok(fractional_variant(&bucketing_value, distribution).cloned());
+        }
+
+        Ok(self
+            .resolver
+            .as_ref()
+            .and_then(|resolver| resolver(evaluation_context)))
+    }
+
+    fn is_targeted(&self) -> bool {
+        #[cfg(feature = "serde_json")]
+        if self.targeting.is_some() {
+            return true;
+        }
+
+        self.fractional.is_some() || self.resolver.is_some()
+    }
+}
+
+/// Flags compare equal if their resolver is the same instance.
+impl PartialEq for InMemoryFlag {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(feature = "serde_json")]
+        if self.targeting != other.targeting {
+            return false;
+        }
+
+        self.variants == other.variants
+            && self.default_variant == other.default_variant
+            && self.flag_metadata == other.flag_metadata
+            && self.fractional == other.fractional
+            && self.prerequisites == other.prerequisites
+            && self.activation_windows == other.activation_windows
+            && match (&self.resolver, &other.resolver) {
+                (Some(resolver), Some(other_resolver)) => Arc::ptr_eq(resolver, other_resolver),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+impl fmt::Debug for InMemoryFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("InMemoryFlag");
+        debug
+            .field("variants", &self.variants)
+            .field("default_variant", &self.default_variant)
+            .field("flag_metadata", &self.flag_metadata)
+            .field("fractional", &self.fractional)
+            .field("prerequisites", &self.prerequisites)
+            .field("activation_windows", &self.activation_windows)
+            .field("resolver", &self.resolver.is_some());
+
+        #[cfg(feature = "serde_json")]
+        debug.field("targeting", &self.targeting);
+
+        debug.finish()
+    }
+}
+
+// ============================================================
+//  InMemoryProvider
+// ============================================================
+
+/// A provider serving flags defined in code, mostly useful for tests and examples.
+///
+/// Flags are conveniently defined with the [`flags!`](crate::flags) macro. All the clones share
+/// the same flags, so a clone kept aside can update them after the provider is set, emitting
+/// `PROVIDER_CONFIGURATION_CHANGED` events.
+#[derive(Clone)]
+pub struct InMemoryProvider {
+    metadata: ProviderMetadata,
+    flags: Arc<RwLock<HashMap<String, InMemoryFlag>>>,
+    events: EventEmitter,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for InMemoryProvider {
+    fn default() -> Self {
+        Self {
+            metadata: ProviderMetadata::new("In-memory Provider"),
+            flags: Arc::new(RwLock::new(HashMap::new())),
+            events: EventEmitter::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+}
+
+impl fmt::Debug for InMemoryProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryProvider")
+            .field("metadata", &self.metadata)
+            .field("flags", &self.flags)
+            .field("events", &self.events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl InMemoryProvider {
+    /// Set the metadata of the provider, such as to name a provider built on top of this one.
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: ProviderMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Check the activation windows of the flags against `clock` rather than the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Add or replace flag `flag_key`.
+    #[must_use]
+    pub fn with_flag(mut self, flag_key: impl Into<String>, flag: InMemoryFlag) -> Self {
+        self.add_flag(flag_key, flag);
+        self
+    }
+
+    /// Add or replace flag `flag_key`.
+    pub fn add_flag(&mut self, flag_key: impl Into<String>, flag: InMemoryFlag) {
+        self.flags.write().unwrap().insert(flag_key.into(), flag);
+    }
+
+    /// Add or replace flag `flag_key` and emit the corresponding
+    /// `PROVIDER_CONFIGURATION_CHANGED` event.
+    pub fn set_flag(&self, flag_key: impl Into<String>, flag: InMemoryFlag) {
+        let flag_key = flag_key.into();
+
+        self.flags.write().unwrap().insert(flag_key.clone(), flag);
+
+        self.events.emit(
+            ProviderEvent::builder()
+                .event_type(ProviderEventType::ConfigurationChanged)
+                .provider_name(self.metadata.name.clone())
+                .flags_changed(vec![flag_key])
+                .build(),
+        );
+    }
+
+    /// Replace all the flags with `flags` at once, and emit a `PROVIDER_CONFIGURATION_CHANGED`
+    /// event listing the flags added, removed or changed, if any.
+    pub fn set_flags(&self, flags: HashMap<String, InMemoryFlag>) {
+        let mut previous = self.flags.write().unwrap();
+
+        let mut flags_changed: Vec<_> = flags
+            .iter()
+            .filter(|(flag_key, flag)| previous.get(*flag_key) != Some(*flag))
+            .map(|(flag_key, _)| flag_key.clone())
+            .chain(
+                previous
+                    .keys()
+                    .filter(|flag_key| !flags.contains_key(*flag_key))
+                    .cloned(),
+            )
+            .collect();
+
+        *previous = flags;
+        drop(previous);
+
+        if flags_changed.is_empty() {
+            return;
+        }
+
+        flags_changed.sort();
+
+        self.events.emit(
+            ProviderEvent::builder()
+                .event_type(ProviderEventType::ConfigurationChanged)
+                .provider_name(self.metadata.name.clone())
+                .flags_changed(flags_changed)
+                .build(),
+        );
+    }
+
+    /// Return flag `flag_key`, if defined.
+    pub fn flag(&self, flag_key: &str) -> Option<InMemoryFlag> {
+        self.flags.read().unwrap().get(flag_key).cloned()
+    }
+
+    fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let at = self.clock.evaluation_time(evaluation_context);
+        let flags = self.flags.read().unwrap();
+        let details = resolve_value(&flags, flag_key, evaluation_context, at, &mut Vec::new())?;
+        drop(flags);
+
+        let value = T::from_value(details.value).ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::TypeMismatch)
+                .message(format!("Expected a {:?} flag", T::FLAG_TYPE))
+                .build()
+        })?;
+
+        Ok(ResolutionDetails {
+            value,
+            variant: details.variant,
+            reason: details.reason,
+            flag_metadata: details.flag_metadata,
+        })
+    }
+}
+
+/// Resolve flag `flag_key` of `flags` at time `at`, once its prerequisites are met. `resolving`
+/// holds the flags whose prerequisites are being resolved, to detect cycles.
+fn resolve_value<'a>(
+    flags: &'a HashMap<String, InMemoryFlag>,
+    flag_key: &'a str,
+    evaluation_context: &EvaluationContext,
+    at: OffsetDateTime,
+    resolving: &mut Vec<&'a str>,
+) -> EvaluationResult<ResolutionDetails<Value>> {
+    let flag = flags.get(flag_key).ok_or_else(|| {
+        EvaluationError::builder()
+            .code(EvaluationErrorCode::FlagNotFound)
+            .message(format!("Flag \"{}\" is not defined", flag_key))
+            .build()
+    })?;
+
+    if !flag.is_active(at) {
+        return flag.details(flag.default_variant.clone(), EvaluationReason::Disabled);
+    }
+
+    if flag.prerequisites.is_empty() {
+        return flag.resolve_value(flag_key, evaluation_context, at);
+    }
+
+    if resolving.contains(&flag_key) {
+        return Err(EvaluationError::builder()
+            .code(EvaluationErrorCode::General(
+                "Prerequisite cycle".to_string(),
+            ))
+            .message(format!("Flag \"{}\" requires itself", flag_key))
+            .build());
+    }
+
+    resolving.push(flag_key);
+
+    for (prerequisite, variant) in &flag.prerequisites {
+        let met = match resolve_value(flags, prerequisite, evaluation_context, at, resolving) {
+            Ok(details) => details.variant.as_ref() == Some(variant),
+            Err(error) if error.code == EvaluationErrorCode::FlagNotFound => false,
+            Err(error) => return Err(error),
+        };
+
+        if !met {
+            resolving.pop();
+            return flag.details(flag.default_variant.clone(), EvaluationReason::Disabled);
+        }
+    }
+
+    resolving.pop();
+
+    flag.resolve_value(flag_key, evaluation_context, at)
+}
+
+#[async_trait]
+impl FeatureProvider for InMemoryProvider {
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    fn status(&self) -> ProviderStatus {
+        ProviderStatus::Ready
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        Some(self.events.clone())
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let at = self.clock.evaluation_time(evaluation_context);
+        let flags = self.flags.read().unwrap();
+
+        Ok(flags
+            .keys()
+            .filter_map(|flag_key| {
+                Some((
+                    flag_key.clone(),
+                    resolve_value(&flags, flag_key, evaluation_context, at, &mut Vec::new())
+                        .ok()?,
+                ))
+            })
+            .collect())
+    }
+}
+
+// ============================================================
+//  flags!
+// ============================================================
+
+/// Build an [`InMemoryProvider`](crate::provider::InMemoryProvider) out of a list of flags.
+///
+/// A flag either holds a single value of given type, or a set of variants along with the
+/// default one.
+///
+/// ```
+/// use open_feature::flags;
+///
+/// let provider = flags! {
+///     "checkout-v2" => bool: true,
+///     "tier" => variants { "gold" => 1, "silver" => 2 }, default "silver",
+///     "greeting" => String: "hello",
+/// };
+/// ```
+#[macro_export]
+macro_rules! flags {
+    (@flag $provider:ident;) => {};
+    (@flag $provider:ident;
+        $flag_key:literal => variants { $($variant:literal => $value:expr),* $(,)? },
+        default $default_variant:literal
+        $(, $($rest:tt)*)?
+    ) => {
+        $provider.add_flag(
+            $flag_key,
+            $crate::provider::InMemoryFlag::new($default_variant)
+                $(.with_variant($variant, $value))*,
+        );
+        $crate::flags!(@flag $provider; $($($rest)*)?);
+    };
+    (@flag $provider:ident; $flag_key:literal => $ty:ty : $value:expr $(, $($rest:tt)*)?) => {
+        $provider.add_flag(
+            $flag_key,
+            $crate::provider::InMemoryFlag::with_value(<$ty>::from($value)),
+        );
+        $crate::flags!(@flag $provider; $($($rest)*)?);
+    };
+    ($($flags:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut provider = $crate::provider::InMemoryProvider::default();
+        $crate::flags!(@flag provider; $($flags)*);
+        provider
+    }};
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use time::Duration;
+
+    use super::*;
+    use crate::{FixedClock, OpenFeature};
+
+    #[tokio::test]
+    async fn resolve_flags() {
+        let provider = flags! {
+            "checkout-v2" => bool: true,
+            "tier" => variants { "gold" => 1, "silver" => 2 }, default "silver",
+            "ratio" => f64: 0.5,
+        };
+        let context = EvaluationContext::default();
+
+        let result = provider
+            .resolve_bool_value("checkout-v2", &context)
+            .await
+            .unwrap();
+        assert!(result.value);
+        assert_eq!(result.variant, Some("true".to_string()));
+        assert_eq!(result.reason, Some(EvaluationReason::Static));
+
+        let result = provider.resolve_int_value("tier", &context).await.unwrap();
+        assert_eq!(result.value, 2);
+        assert_eq!(result.variant, Some("silver".to_string()));
+
+        let result = provider
+            .resolve_float_value("ratio", &context)
+            .await
+            .unwrap();
+        assert!((result.value - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn resolve_errors() {
+        let provider = flags! {
+            "tier" => variants { "gold" => "Gold" }, default "platinum",
+            "name" => String: "hello",
+        };
+        let context = EvaluationContext::default();
+
+        let error = provider
+            .resolve_bool_value("missing", &context)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+
+        let error = provider
+            .resolve_bool_value("name", &context)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::TypeMismatch);
+
+        let error = provider
+            .resolve_string_value("tier", &context)
+            .await
+            .unwrap_err();
+        assert!(matches!(error.code, EvaluationErrorCode::General(_)));
+    }
+
+    #[tokio::test]
+    async fn evaluate_with_client() {
+        let mut api = OpenFeature::default();
+        api.set_provider(flags! {
+            "tier" => variants { "gold" => "Gold", "silver" => "Silver" }, default "gold",
+        })
+        .await
+        .unwrap();
+
+        let client = api.create_client();
+
+        assert_eq!(
+            client.get_string_value("tier", None, None).await.unwrap(),
+            "Gold"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_with_resolver() {
+        let provider = InMemoryProvider::default().with_flag(
+            "checkout-v2",
+            InMemoryFlag::new("off")
+                .with_variant("on", true)
+                .with_variant("off", false)
+                .with_resolver(|context| {
+                    (context.targeting_key.as_deref() == Some("alice")).then(|| "on".to_string())
+                }),
+        );
+
+        let result = provider
+            .resolve_bool_value(
+                "checkout-v2",
+                &EvaluationContext::default().with_targeting_key("alice"),
+            )
+            .await
+            .unwrap();
+        assert!(result.value);
+        assert_eq!(result.reason, Some(EvaluationReason::TargetingMatch));
+
+        let result = provider
+            .resolve_bool_value(
+                "checkout-v2",
+                &EvaluationContext::default().with_targeting_key("bob"),
+            )
+            .await
+            .unwrap();
+        assert!(!result.value);
+        assert_eq!(result.variant, Some("off".to_string()));
+        assert_eq!(result.reason, Some(EvaluationReason::Default));
+    }
+
+    #[tokio::test]
+    async fn resolve_fractions() {
+        let provider = InMemoryProvider::default().with_flag(
+            "checkout-v2",
+            InMemoryFlag::new("off")
+                .with_variant("on", true)
+                .with_variant("off", false)
+                .with_fractional([("on", 20), ("off", 80)]),
+        );
+
+        let mut served = 0;
+        for index in 0..1000 {
+            let context =
+                EvaluationContext::default().with_targeting_key(format!("user-{}", index));
+            let result = provider
+                .resolve_bool_value("checkout-v2", &context)
+                .await
+                .unwrap();
+            assert_eq!(result.reason, Some(EvaluationReason::TargetingMatch));
+
+            let again = provider
+                .resolve_bool_value("checkout-v2", &context)
+                .await
+                .unwrap();
+            assert_eq!(result.value, again.value);
+
+            if result.value {
+                served += 1;
+            }
+        }
+
+        assert!((150..250).contains(&served), "{}", served);
+    }
+
+    #[tokio::test]
+    async fn resolve_prerequisites() {
+        let flag = || {
+            InMemoryFlag::new("off")
+                .with_variant("on", true)
+                .with_variant("off", false)
+        };
+        let provider = InMemoryProvider::default()
+            .with_flag(
+                "new-checkout",
+                flag().with_resolver(|context| {
+                    context.targeting_key.as_ref().map(|_| "on".to_string())
+                }),
+            )
+            .with_flag(
+                "express-payment",
+                InMemoryFlag::with_value(true).with_prerequisite("new-checkout", "on"),
+            )
+            .with_flag("orphan", flag().with_prerequisite("missing", "on"))
+            .with_flag("ping", flag().with_prerequisite("pong", "on"))
+            .with_flag("pong", flag().with_prerequisite("ping", "on"));
+
+        let result = provider
+            .resolve_bool_value(
+                "express-payment",
+                &EvaluationContext::default().with_targeting_key("alice"),
+            )
+            .await
+            .unwrap();
+        assert!(result.value);
+        assert_eq!(result.reason, Some(EvaluationReason::Static));
+
+        let result = provider
+            .resolve_bool_value("express-payment", &EvaluationContext::default())
+            .await
+            .unwrap();
+        assert_eq!(result.reason, Some(EvaluationReason::Disabled));
+
+        let result = provider
+            .resolve_bool_value("orphan", &EvaluationContext::default())
+            .await
+            .unwrap();
+        assert!(!result.value);
+        assert_eq!(result.reason, Some(EvaluationReason::Disabled));
+
+        let error = provider
+            .resolve_bool_value("ping", &EvaluationContext::default())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.code,
+            EvaluationErrorCode::General("Prerequisite cycle".to_string())
+        );
+        assert_eq!(error.message.unwrap(), "Flag \"ping\" requires itself");
+    }
+
+    #[tokio::test]
+    async fn resolve_activation_windows() {
+        let launch = OffsetDateTime::now_utc();
+        let provider = InMemoryProvider::default()
+            .with_clock(FixedClock(launch - Duration::hours(1)))
+            .with_flag(
+                "new-checkout",
+                InMemoryFlag::new("off")
+                    .with_variant("on", true)
+                    .with_variant("off", false)
+                    .with_resolver(|_| Some("on".to_string()))
+                    .with_activation_window(ActivationWindow::starting_at(launch)),
+            );
+
+        let result = provider
+            .resolve_bool_value("new-checkout", &EvaluationContext::default())
+            .await
+            .unwrap();
+        assert!(!result.value);
+        assert_eq!(result.reason, Some(EvaluationReason::Disabled));
+
+        let result = provider
+            .resolve_bool_value(
+                "new-checkout",
+                &EvaluationContext::default().with_as_of(launch),
+            )
+            .await
+            .unwrap();
+        assert!(result.value);
+        assert_eq!(result.reason, Some(EvaluationReason::TargetingMatch));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[tokio::test]
+    async fn resolve_timestamp_targeting() {
+        let launch = OffsetDateTime::now_utc();
+        let flag = InMemoryFlag::new("off")
+            .with_variant("on", true)
+            .with_variant("off", false)
+            .with_targeting(Targeting::new(serde_json::json!({
+                "if": [{ ">=": [{ "var": "$flagd.timestamp" }, launch.unix_timestamp()] }, "on", null]
+            })));
+        let provider = InMemoryProvider::default()
+            .with_clock(FixedClock(launch - Duration::hours(1)))
+            .with_flag("new-checkout", flag);
+
+        let result = provider
+            .resolve_bool_value("new-checkout", &EvaluationContext::default())
+            .await
+            .unwrap();
+        assert!(!result.value);
+
+        let result = provider
+            .resolve_bool_value(
+                "new-checkout",
+                &EvaluationContext::default().with_as_of(launch + Duration::hours(1)),
+            )
+            .await
+            .unwrap();
+        assert!(result.value);
+    }
+
+    #[tokio::test]
+    async fn replace_flags() {
+        let provider = flags! {
+            "checkout-v2" => bool: true,
+            "tier" => String: "gold",
+            "ratio" => f64: 0.5,
+        };
+        let mut events = provider.event_emitter().unwrap().subscribe();
+
+        provider.set_flags(
+            flags! {
+                "checkout-v2" => bool: true,
+                "tier" => String: "silver",
+                "greeting" => String: "hello",
+            }
+            .flags
+            .read()
+            .unwrap()
+            .clone(),
+        );
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(
+            event.flags_changed,
+            Some(vec![
+                "greeting".to_string(),
+                "ratio".to_string(),
+                "tier".to_string()
+            ])
+        );
+        assert!(provider.flag("ratio").is_none());
+    }
+
+    #[test]
+    fn empty() {
+        let provider = flags! {};
+
+        assert!(provider.flag("any").is_none());
+    }
+}
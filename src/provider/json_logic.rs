@@ -0,0 +1,725 @@
+use std::cmp::Ordering;
+
+use serde_json::{Map, Value as JsonValue};
+
+use super::bucketing;
+
+// ============================================================
+//  JsonLogic
+// ============================================================
+
+/// Apply the [JsonLogic](https://jsonlogic.com) `rule` to `data`.
+///
+/// An object with a single key is an operation, an array is a list of rules, and anything else
+/// is a literal.
+pub(crate) fn apply(rule: &JsonValue, data: &JsonValue) -> Result<JsonValue, String> {
+    match rule {
+        JsonValue::Object(operation) if operation.len() == 1 => {
+            let (operator, arguments) = operation.iter().next().expect("A single operation");
+            let arguments = match arguments {
+                JsonValue::Array(arguments) => arguments.as_slice(),
+                argument => std::slice::from_ref(argument),
+            };
+
+            apply_operation(operator, arguments, data)
+        }
+        JsonValue::Array(rules) => rules
+            .iter()
+            .map(|rule| apply(rule, data))
+            .collect::<Result<_, _>>()
+            .map(JsonValue::Array),
+        rule => Ok(rule.clone()),
+    }
+}
+
+fn apply_operation(
+    operator: &str,
+    arguments: &[JsonValue],
+    data: &JsonValue,
+) -> Result<JsonValue, String> {
+    // Operations evaluating their arguments lazily.
+    match operator {
+        "if" | "?:" => return apply_if(arguments, data),
+        "and" => return apply_and_or(arguments, data, false),
+        "or" => return apply_and_or(arguments, data, true),
+        "map" | "filter" | "all" | "some" | "none" => {
+            return apply_iteration(operator, arguments, data)
+        }
+        "reduce" => return apply_reduce(arguments, data),
+        _ => {}
+    }
+
+    let values = arguments
+        .iter()
+        .map(|argument| apply(argument, data))
+        .collect::<Result<Vec<_>, _>>()?;
+    let value = |index: usize| values.get(index).unwrap_or(&JsonValue::Null);
+
+    Ok(match operator {
+        "var" => var(value(0), value(1), data),
+        "==" => loose_equals(value(0), value(1)).into(),
+        "!=" => (!loose_equals(value(0), value(1))).into(),
+        "===" => (value(0) == value(1)).into(),
+        "!==" => (value(0) != value(1)).into(),
+        "!" => (!is_truthy(value(0))).into(),
+        "!!" => is_truthy(value(0)).into(),
+        "<" => compare_chain(&values, Ordering::is_lt).into(),
+        "<=" => compare_chain(&values, Ordering::is_le).into(),
+        ">" => compare_chain(&values, Ordering::is_gt).into(),
+        ">=" => compare_chain(&values, Ordering::is_ge).into(),
+        "in" => match value(1) {
+            JsonValue::Array(items) => items.contains(value(0)),
+            JsonValue::String(text) => text.contains(&to_string(value(0))),
+            _ => false,
+        }
+        .into(),
+        "cat" => values.iter().map(to_string).collect::<String>().into(),
+        "substr" => substr(value(0), value(1), value(2)).into(),
+        "+" => number(numbers(&values)?.iter().sum()),
+        "*" => number(numbers(&values)?.iter().product()),
+        "-" => match numbers(&values)?.as_slice() {
+            [value] => number(-value),
+            [left, right, ..] => number(left - right),
+            [] => JsonValue::Null,
+        },
+        "/" => match numbers(&values)?.as_slice() {
+            [_, right, ..] if *right == 0.0 => JsonValue::Null,
+            [left, right, ..] => number(left / right),
+            _ => JsonValue::Null,
+        },
+        "%" => match numbers(&values)?.as_slice() {
+            [_, right, ..] if *right == 0.0 => JsonValue::Null,
+            [left, right, ..] => number(left % right),
+            _ => JsonValue::Null,
+        },
+        "min" => numbers(&values)?
+            .into_iter()
+            .reduce(f64::min)
+            .map_or(JsonValue::Null, number),
+        "max" => numbers(&values)?
+            .into_iter()
+            .reduce(f64::max)
+            .map_or(JsonValue::Null, number),
+        "merge" => JsonValue::Array(
+            values
+                .into_iter()
+                .flat_map(|value| match value {
+                    JsonValue::Array(items) => items,
+                    value => vec![value],
+                })
+                .collect(),
+        ),
+        "missing" => missing(&values, data),
+        "missing_some" => {
+            let keys = match value(1) {
+                JsonValue::Array(keys) => keys.as_slice(),
+                _ => &[],
+            };
+            let missing_keys = missing(keys, data);
+            #[allow(clippy::cast_precision_loss)]
+            let found = (keys.len() - missing_keys.as_array().map_or(0, Vec::len)) as f64;
+
+            if to_number(value(0)).map_or(false, |needed| found >= needed) {
+                JsonValue::Array(Vec::new())
+            } else {
+                missing_keys
+            }
+        }
+        "starts_with" => match (value(0), value(1)) {
+            (JsonValue::String(text), JsonValue::String(prefix)) => {
+                text.starts_with(prefix.as_str())
+            }
+            _ => false,
+        }
+        .into(),
+        "ends_with" => match (value(0), value(1)) {
+            (JsonValue::String(text), JsonValue::String(suffix)) => text.ends_with(suffix.as_str()),
+            _ => false,
+        }
+        .into(),
+        "sem_ver" => sem_ver(value(0), value(1), value(2))?.into(),
+        "fractional" => fractional(&values, data)?,
+        operator => return Err(format!("Unknown operator \"{}\"", operator)),
+    })
+}
+
+/// Apply `rule` to every item of the array `arguments[0]` evaluates to, for `map` and `filter`,
+/// or check it holds for `all`, `some` or `none` of them.
+fn apply_iteration(
+    operator: &str,
+    arguments: &[JsonValue],
+    data: &JsonValue,
+) -> Result<JsonValue, String> {
+    let items = match arguments
+        .first()
+        .map(|items| apply(items, data))
+        .transpose()?
+    {
+        Some(JsonValue::Array(items)) => items,
+        _ => Vec::new(),
+    };
+    let rule = arguments.get(1).unwrap_or(&JsonValue::Null);
+    let mut results = items.iter().map(|item| apply(rule, item));
+
+    Ok(match operator {
+        "map" => JsonValue::Array(results.collect::<Result<_, _>>()?),
+        "filter" => {
+            let mut filtered = Vec::new();
+
+            for (item, result) in items.iter().zip(results) {
+                if is_truthy(&result?) {
+                    filtered.push(item.clone());
+                }
+            }
+
+            JsonValue::Array(filtered)
+        }
+        "all" => (!items.is_empty() && try_all(&mut results, true)?).into(),
+        "some" => (!try_all(&mut results, false)?).into(),
+        _ => try_all(&mut results, false)?.into(),
+    })
+}
+
+/// Return whether every result has the truthiness `expected`, stopping at the first that does
+/// not.
+fn try_all(
+    results: &mut impl Iterator<Item = Result<JsonValue, String>>,
+    expected: bool,
+) -> Result<bool, String> {
+    for result in results {
+        if is_truthy(&result?) != expected {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// `{"reduce": [items, rule, initial]}`, applying `rule` to `{"current", "accumulator"}`.
+fn apply_reduce(arguments: &[JsonValue], data: &JsonValue) -> Result<JsonValue, String> {
+    let argument = |index: usize| {
+        arguments
+            .get(index)
+            .map_or(Ok(JsonValue::Null), |argument| apply(argument, data))
+    };
+
+    let items = match argument(0)? {
+        JsonValue::Array(items) => items,
+        _ => Vec::new(),
+    };
+    let rule = arguments.get(1).unwrap_or(&JsonValue::Null);
+
+    items
+        .into_iter()
+        .try_fold(argument(2)?, |accumulator, current| {
+            apply(
+                rule,
+                &object([
+                    ("current".to_string(), current),
+                    ("accumulator".to_string(), accumulator),
+                ]),
+            )
+        })
+}
+
+/// Return the keys of `keys` whose value is missing from `data`. A single array argument holds
+/// the keys.
+fn missing(keys: &[JsonValue], data: &JsonValue) -> JsonValue {
+    let keys = match keys {
+        [JsonValue::Array(keys)] => keys.as_slice(),
+        keys => keys,
+    };
+
+    JsonValue::Array(
+        keys.iter()
+            .filter(|key| var(key, &JsonValue::Null, data).is_null())
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Return the characters of `text` from `start`, and up to `length`. Negative values count from
+/// the end.
+fn substr(text: &JsonValue, start: &JsonValue, length: &JsonValue) -> String {
+    let chars: Vec<char> = to_string(text).chars().collect();
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let len = chars.len() as i64;
+    #[allow(clippy::cast_possible_truncation)]
+    let index = |value: &JsonValue| to_number(value).map(|value| value as i64);
+
+    let start = match index(start).unwrap_or(0) {
+        start if start < 0 => (len + start).max(0),
+        start => start.min(len),
+    };
+    let end = match length {
+        JsonValue::Null => len,
+        length => match index(length).unwrap_or(0) {
+            length if length < 0 => (len + length).max(start),
+            length => (start + length).min(len),
+        },
+    };
+
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    chars[start as usize..end as usize].iter().collect()
+}
+
+/// `{"if": [condition, then, condition, then, ..., else]}`
+fn apply_if(arguments: &[JsonValue], data: &JsonValue) -> Result<JsonValue, String> {
+    for pair in arguments.chunks(2) {
+        match pair {
+            [condition, then] => {
+                if is_truthy(&apply(condition, data)?) {
+                    return apply(then, data);
+                }
+            }
+            [otherwise] => return apply(otherwise, data),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(JsonValue::Null)
+}
+
+/// Return the first argument that is falsy for `and`, or truthy for `or`, or the last one.
+fn apply_and_or(
+    arguments: &[JsonValue],
+    data: &JsonValue,
+    short_circuit_on: bool,
+) -> Result<JsonValue, String> {
+    let mut value = JsonValue::Null;
+
+    for argument in arguments {
+        value = apply(argument, data)?;
+
+        if is_truthy(&value) == short_circuit_on {
+            break;
+        }
+    }
+
+    Ok(value)
+}
+
+/// Return the value at the dot-separated `path` of `data`, or `default` if missing.
+fn var(path: &JsonValue, default: &JsonValue, data: &JsonValue) -> JsonValue {
+    let path = match path {
+        JsonValue::Null => String::new(),
+        path => to_string(path),
+    };
+
+    if path.is_empty() {
+        return data.clone();
+    }
+
+    path.split('.')
+        .try_fold(data, |data, key| match data {
+            JsonValue::Object(fields) => fields.get(key),
+            JsonValue::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+            _ => None,
+        })
+        .filter(|value| !value.is_null())
+        .unwrap_or(default)
+        .clone()
+}
+
+// ============================================================
+//  Coercions
+// ============================================================
+
+/// Return whether `value` is truthy: anything but `false`, `null`, `0`, `""` and `[]`.
+pub(crate) fn is_truthy(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Null => false,
+        JsonValue::Bool(value) => *value,
+        JsonValue::Number(value) => value.as_f64().map_or(false, |value| value != 0.0),
+        JsonValue::String(value) => !value.is_empty(),
+        JsonValue::Array(items) => !items.is_empty(),
+        JsonValue::Object(_) => true,
+    }
+}
+
+pub(crate) fn to_string(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Null => String::new(),
+        JsonValue::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+pub(crate) fn to_number(value: &JsonValue) -> Option<f64> {
+    match value {
+        JsonValue::Null => Some(0.0),
+        JsonValue::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+        JsonValue::Number(value) => value.as_f64(),
+        JsonValue::String(value) => value.trim().parse().ok(),
+        JsonValue::Array(_) | JsonValue::Object(_) => None,
+    }
+}
+
+/// Compare values of the same type as is, and other values as numbers.
+fn loose_equals(left: &JsonValue, right: &JsonValue) -> bool {
+    match (left, right) {
+        (JsonValue::Null, JsonValue::Null) => true,
+        (JsonValue::Null, _) | (_, JsonValue::Null) => false,
+        (JsonValue::String(left), JsonValue::String(right)) => left == right,
+        (JsonValue::Array(_) | JsonValue::Object(_), _)
+        | (_, JsonValue::Array(_) | JsonValue::Object(_)) => left == right,
+        (left, right) => match (to_number(left), to_number(right)) {
+            (Some(left), Some(right)) => (left - right).abs() < f64::EPSILON,
+            _ => false,
+        },
+    }
+}
+
+/// Compare strings lexicographically, and other values as numbers.
+fn compare(left: &JsonValue, right: &JsonValue) -> Option<Ordering> {
+    match (left, right) {
+        (JsonValue::String(left), JsonValue::String(right)) => Some(left.cmp(right)),
+        (left, right) => to_number(left)?.partial_cmp(&to_number(right)?),
+    }
+}
+
+/// Return whether each value compares with the next one as expected, such as for
+/// `{"<": [1, x, 10]}`.
+fn compare_chain(values: &[JsonValue], expected: fn(Ordering) -> bool) -> bool {
+    values.len() >= 2
+        && values
+            .windows(2)
+            .all(|pair| compare(&pair[0], &pair[1]).map_or(false, expected))
+}
+
+fn numbers(values: &[JsonValue]) -> Result<Vec<f64>, String> {
+    values
+        .iter()
+        .map(|value| to_number(value).ok_or_else(|| format!("{} is not a number", value)))
+        .collect()
+}
+
+/// Return `value` as a JSON number, integral if it is a whole number.
+fn number(value: f64) -> JsonValue {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        return JsonValue::from(value as i64);
+    }
+
+    serde_json::Number::from_f64(value).map_or(JsonValue::Null, JsonValue::Number)
+}
+
+/// Build the data of a rule out of `fields`.
+pub(crate) fn object(fields: impl IntoIterator<Item = (String, JsonValue)>) -> JsonValue {
+    JsonValue::Object(fields.into_iter().collect::<Map<_, _>>())
+}
+
+// ============================================================
+//  flagd operators
+// ============================================================
+
+/// `{"sem_ver": [version, operator, target]}`, comparing semantic versions with `=`, `!=`, `<`,
+/// `<=`, `>`, `>=`, `^` (same major version) or `~` (same minor version).
+fn sem_ver(version: &JsonValue, operator: &JsonValue, target: &JsonValue) -> Result<bool, String> {
+    let version = SemVer::parse(&to_string(version))?;
+    let target = SemVer::parse(&to_string(target))?;
+    let ordering = version.cmp(&target);
+
+    Ok(match to_string(operator).as_str() {
+        "=" => ordering.is_eq(),
+        "!=" => ordering.is_ne(),
+        "<" => ordering.is_lt(),
+        "<=" => ordering.is_le(),
+        ">" => ordering.is_gt(),
+        ">=" => ordering.is_ge(),
+        "^" => version.major == target.major,
+        "~" => version.major == target.major && version.minor == target.minor,
+        operator => return Err(format!("Unknown sem_ver operator \"{}\"", operator)),
+    })
+}
+
+/// A semantic version, ordered as specified by [semver](https://semver.org), ignoring build
+/// metadata.
+#[derive(PartialEq, Eq, Debug)]
+pub(crate) struct SemVer {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre_release: Vec<String>,
+}
+
+impl SemVer {
+    pub(crate) fn parse(version: &str) -> Result<Self, String> {
+        let invalid = || format!("\"{}\" is not a semantic version", version);
+
+        let text = version.trim().trim_start_matches(['v', 'V']);
+        let text = text.split('+').next().unwrap_or_default();
+        let (core, pre_release) = match text.split_once('-') {
+            Some((core, pre_release)) => (core, pre_release.split('.').map(String::from).collect()),
+            None => (text, Vec::new()),
+        };
+
+        let mut numbers = core.split('.').map(str::parse::<u64>);
+        let mut number = || numbers.next().unwrap_or(Ok(0)).map_err(|_| invalid());
+        let semver = Self {
+            major: number()?,
+            minor: number()?,
+            patch: number()?,
+            pre_release,
+        };
+
+        if numbers.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(semver)
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(
+                || match (self.pre_release.is_empty(), other.pre_release.is_empty()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => {
+                        for (left, right) in self.pre_release.iter().zip(&other.pre_release) {
+                            let ordering = match (left.parse::<u64>(), right.parse::<u64>()) {
+                                (Ok(left), Ok(right)) => left.cmp(&right),
+                                (Ok(_), Err(_)) => Ordering::Less,
+                                (Err(_), Ok(_)) => Ordering::Greater,
+                                (Err(_), Err(_)) => left.cmp(right),
+                            };
+
+                            if ordering.is_ne() {
+                                return ordering;
+                            }
+                        }
+
+                        self.pre_release.len().cmp(&other.pre_release.len())
+                    }
+                },
+            )
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `{"fractional": [bucketing value, [variant, weight], ...]}`, consistently serving each
+/// variant to a share of the subjects proportional to its weight, 1 by default.
+///
+/// The bucketing value defaults to the flag key followed by the targeting key.
+fn fractional(values: &[JsonValue], data: &JsonValue) -> Result<JsonValue, String> {
+    let (bucketing_value, distribution) = match values {
+        [JsonValue::String(bucketing_value), distribution @ ..] => {
+            (bucketing_value.clone(), distribution)
+        }
+        distribution => {
+            let path = |path: &str| to_string(&var(&path.into(), &JsonValue::Null, data));
+            (
+                format!("{}{}", path("$flagd.flagKey"), path("targetingKey")),
+                distribution,
+            )
+        }
+    };
+
+    let mut weights = Vec::with_capacity(distribution.len());
+
+    for variant in distribution {
+        match variant.as_array().map(Vec::as_slice) {
+            Some([variant]) => weights.push((variant, 1)),
+            Some([variant, weight]) => weights.push((
+                variant,
+                weight
+                    .as_u64()
+                    .and_then(|weight| u32::try_from(weight).ok())
+                    .ok_or_else(|| format!("{} is not a weight", weight))?,
+            )),
+            _ => return Err(format!("{} is not a [variant, weight] pair", variant)),
+        }
+    }
+
+    Ok(bucketing::fractional_variant(&bucketing_value, &weights)
+        .map_or(JsonValue::Null, |variant| (*variant).clone()))
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn apply_rules() {
+        let data = json!({ "email": "ada@example.com", "age": 36, "tags": ["beta"] });
+        let cases = [
+            (json!({ "var": "email" }), json!("ada@example.com")),
+            (json!({ "var": ["missing", "none"] }), json!("none")),
+            (json!({ "var": "tags.0" }), json!("beta")),
+            (json!({ "==": [{ "var": "age" }, "36"] }), json!(true)),
+            (json!({ "===": [{ "var": "age" }, "36"] }), json!(false)),
+            (json!({ "<": [18, { "var": "age" }, 65] }), json!(true)),
+            (json!({ ">=": [{ "var": "age" }, 40] }), json!(false)),
+            (json!({ "in": ["beta", { "var": "tags" }] }), json!(true)),
+            (
+                json!({ "in": ["@example", { "var": "email" }] }),
+                json!(true),
+            ),
+            (json!({ "!": [{ "var": "missing" }] }), json!(true)),
+            (json!({ "and": [true, "", 1] }), json!("")),
+            (json!({ "or": [0, "", "first"] }), json!("first")),
+            (json!({ "cat": ["v", 2] }), json!("v2")),
+            (
+                json!({ "if": [{ "<": [{ "var": "age" }, 18] }, "minor", { "<": [{ "var": "age" }, 65] }, "adult", "senior"] }),
+                json!("adult"),
+            ),
+            (json!({ "if": [false, "never"] }), json!(null)),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(apply(&rule, &data).unwrap(), expected, "{}", rule);
+        }
+
+        assert_eq!(
+            apply(&json!({ "nope": [] }), &data).unwrap_err(),
+            "Unknown operator \"nope\""
+        );
+    }
+
+    #[test]
+    fn apply_arithmetic_and_arrays() {
+        let data = json!({ "scores": [3, 5, 8], "name": "checkout" });
+        let cases = [
+            (json!({ "+": [1, "2", 3.5] }), json!(6.5)),
+            (json!({ "-": [10, 4] }), json!(6)),
+            (json!({ "-": [2] }), json!(-2)),
+            (json!({ "*": [2, 3] }), json!(6)),
+            (json!({ "/": [7, 2] }), json!(3.5)),
+            (json!({ "/": [7, 0] }), json!(null)),
+            (json!({ "%": [7, 4] }), json!(3)),
+            (json!({ "max": [{ "var": "scores.0" }, 9, 4] }), json!(9)),
+            (
+                json!({ "merge": [[1], 2, [3, [4]]] }),
+                json!([1, 2, 3, [4]]),
+            ),
+            (
+                json!({ "map": [{ "var": "scores" }, { "*": [{ "var": "" }, 2] }] }),
+                json!([6, 10, 16]),
+            ),
+            (
+                json!({ "filter": [{ "var": "scores" }, { ">": [{ "var": "" }, 4] }] }),
+                json!([5, 8]),
+            ),
+            (
+                json!({ "reduce": [{ "var": "scores" }, { "+": [{ "var": "current" }, { "var": "accumulator" }] }, 0] }),
+                json!(16),
+            ),
+            (
+                json!({ "all": [{ "var": "scores" }, { ">": [{ "var": "" }, 2] }] }),
+                json!(true),
+            ),
+            (json!({ "all": [[], true] }), json!(false)),
+            (
+                json!({ "some": [{ "var": "scores" }, { ">": [{ "var": "" }, 7] }] }),
+                json!(true),
+            ),
+            (
+                json!({ "none": [{ "var": "scores" }, { ">": [{ "var": "" }, 7] }] }),
+                json!(false),
+            ),
+            (json!({ "missing": ["name", "email"] }), json!(["email"])),
+            (json!({ "missing_some": [1, ["name", "email"]] }), json!([])),
+            (
+                json!({ "missing_some": [2, ["name", "email"]] }),
+                json!(["email"]),
+            ),
+            (json!({ "substr": [{ "var": "name" }, 5] }), json!("out")),
+            (json!({ "substr": [{ "var": "name" }, -3, 2] }), json!("ou")),
+            (
+                json!({ "substr": [{ "var": "name" }, 0, -3] }),
+                json!("check"),
+            ),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(apply(&rule, &data).unwrap(), expected, "{}", rule);
+        }
+    }
+
+    #[test]
+    fn apply_flagd_operators() {
+        let data = json!({ "email": "ada@example.com", "version": "1.4.2" });
+        let cases = [
+            (
+                json!({ "starts_with": [{ "var": "email" }, "ada@"] }),
+                json!(true),
+            ),
+            (
+                json!({ "ends_with": [{ "var": "email" }, "@example.org"] }),
+                json!(false),
+            ),
+            (
+                json!({ "sem_ver": [{ "var": "version" }, ">=", "1.4.0"] }),
+                json!(true),
+            ),
+            (
+                json!({ "sem_ver": ["v1.4.2", "=", "1.4.2+build.7"] }),
+                json!(true),
+            ),
+            (
+                json!({ "sem_ver": ["1.4.2-beta.2", "<", "1.4.2-beta.11"] }),
+                json!(true),
+            ),
+            (
+                json!({ "sem_ver": ["1.4.2-rc.1", "<", "1.4.2"] }),
+                json!(true),
+            ),
+            (
+                json!({ "sem_ver": [{ "var": "version" }, "^", "1.0.0"] }),
+                json!(true),
+            ),
+            (
+                json!({ "sem_ver": [{ "var": "version" }, "~", "1.3.9"] }),
+                json!(false),
+            ),
+        ];
+
+        for (rule, expected) in cases {
+            assert_eq!(apply(&rule, &data).unwrap(), expected, "{}", rule);
+        }
+
+        assert!(apply(&json!({ "sem_ver": ["1.x", "=", "1.0.0"] }), &data).is_err());
+    }
+
+    #[test]
+    fn apply_fractional() {
+        let rule = json!({ "fractional": [["red", 50], ["blue", 50]] });
+        let variant = |targeting_key: &str| {
+            let data = json!({ "targetingKey": targeting_key, "$flagd": { "flagKey": "color" } });
+            apply(&rule, &data).unwrap()
+        };
+
+        let mut counts = std::collections::HashMap::new();
+        for index in 0..1000 {
+            *counts
+                .entry(variant(&format!("user-{}", index)))
+                .or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 2);
+        assert!((400..600).contains(&counts[&json!("red")]));
+
+        assert_eq!(variant("user-1"), variant("user-1"));
+
+        let rule = json!({ "fractional": [{ "var": "email" }, ["only"]] });
+        assert_eq!(
+            apply(&rule, &json!({ "email": "ada@example.com" })).unwrap(),
+            json!("only")
+        );
+    }
+}
@@ -0,0 +1,571 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use async_trait::async_trait;
+use futures_util::{Stream, StreamExt};
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::{
+    api::{ApiResource, DynamicObject, GroupVersionKind},
+    runtime::{watcher, WatchStreamExt},
+    Api, Client, Resource,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value as JsonValue;
+use tokio::task::JoinHandle;
+
+use crate::{
+    Clock, EvaluationContext, EvaluationResult, ProviderError, ProviderErrorKind, StructValue,
+    Value,
+};
+
+use super::{
+    file_provider, flagd, EventEmitter, FeatureProvider, InMemoryFlag, InMemoryProvider,
+    ProviderEvent, ProviderEventType, ProviderMetadata, ResolutionDetails,
+};
+
+// ============================================================
+//  KubernetesProvider
+// ============================================================
+
+/// A provider serving flags defined in a Kubernetes resource, watched through the Kubernetes API
+/// so that flags managed with GitOps are updated live.
+///
+/// The resource is either a ConfigMap, holding flags in the
+/// [`FlagdConfiguration`](super::FlagdConfiguration) format under a data key, as JSON or, with
+/// the `yaml` feature, as YAML if the key has a YAML extension, or a `FeatureFlag` resource of
+/// the [OpenFeature Operator](https://github.com/open-feature/open-feature-operator), holding them
+/// under `spec.flagSpec`:
+///
+/// ```yaml
+/// apiVersion: core.openfeature.dev/v1beta1
+/// kind: FeatureFlag
+/// metadata:
+///   name: checkout-flags
+/// spec:
+///   flagSpec:
+///     flags:
+///       new-checkout:
+///         state: ENABLED
+///         variants: { "on": true, "off": false }
+///         defaultVariant: "off"
+/// ```
+///
+/// The resource is read when the provider is initialized, then watched. The flags are swapped at
+/// once when it changes, emitting a `PROVIDER_CONFIGURATION_CHANGED` event listing the changed
+/// flags. A resource failing to load, deleted, or failing to be watched
+/// [`MAX_WATCH_ERRORS`](Self::MAX_WATCH_ERRORS) times in a row, keeps the current flags and emits
+/// a `PROVIDER_ERROR` event, putting the provider in the `ERROR` status until it loads again,
+/// which emits a `PROVIDER_READY` event. Fewer watch errors, such as an API server restarting,
+/// are retried with backoff without changing the status. A resource failing to load on
+/// initialization fails it.
+///
+/// Without a client, one is inferred from the environment: the service account of the pod, or
+/// the local kubeconfig.
+///
+/// ```no_run
+/// use open_feature::provider::KubernetesProvider;
+///
+/// let provider = KubernetesProvider::config_map("payments", "checkout-flags")
+///     .with_key("flags.yaml");
+///
+/// let provider = KubernetesProvider::feature_flag("payments", "checkout-flags");
+/// ```
+pub struct KubernetesProvider {
+    resource: FlagResource,
+    client: Option<Client>,
+    watcher: Option<JoinHandle<()>>,
+}
+
+/// The kind of resource flags are defined in.
+#[derive(Clone, Debug)]
+enum ResourceKind {
+    ConfigMap { key: String },
+    FeatureFlag,
+}
+
+impl KubernetesProvider {
+    /// The ConfigMap data key flags are read from by default.
+    pub const DEFAULT_KEY: &'static str = "flags.json";
+
+    /// The number of consecutive watch errors putting the provider in the `ERROR` status.
+    pub const MAX_WATCH_ERRORS: u32 = 5;
+
+    /// Create a provider serving the flags of ConfigMap `name` in `namespace`, under
+    /// [`Self::DEFAULT_KEY`].
+    pub fn config_map(namespace: impl Into<String>, name: impl Into<String>) -> Self {
+        Self::new(
+            namespace.into(),
+            name.into(),
+            ResourceKind::ConfigMap {
+                key: Self::DEFAULT_KEY.to_string(),
+            },
+        )
+    }
+
+    /// Create a provider serving the flags of `FeatureFlag` resource `name` in `namespace`.
+    pub fn feature_flag(namespace: impl Into<String>, name: impl Into<String>) -> Self {
+        Self::new(namespace.into(), name.into(), ResourceKind::FeatureFlag)
+    }
+
+    fn new(namespace: String, name: String, kind: ResourceKind) -> Self {
+        Self {
+            resource: FlagResource {
+                namespace,
+                name,
+                kind,
+                flags: InMemoryProvider::default()
+                    .with_metadata(ProviderMetadata::new("Kubernetes Provider")),
+                failing: Arc::default(),
+            },
+            client: None,
+            watcher: None,
+        }
+    }
+
+    /// Read the flags of a ConfigMap under data key `key`. Has no effect on a `FeatureFlag`
+    /// resource.
+    #[must_use]
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        if let ResourceKind::ConfigMap { .. } = self.resource.kind {
+            self.resource.kind = ResourceKind::ConfigMap { key: key.into() };
+        }
+        self
+    }
+
+    /// Talk to the Kubernetes API with `client`, rather than one inferred from the environment.
+    #[must_use]
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Check the activation windows of the flags against `clock` rather than the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.resource.flags = self.resource.flags.with_clock(clock);
+        self
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for KubernetesProvider {
+    async fn initialize(&mut self, _context: &EvaluationContext) -> Result<(), ProviderError> {
+        let client = match self.client.clone() {
+            Some(client) => client,
+            None => Client::try_default().await.map_err(|error| {
+                ProviderError::new(ProviderErrorKind::Misconfigured, error.to_string())
+                    .with_source(error)
+            })?,
+        };
+
+        let namespace = &self.resource.namespace;
+        let watcher = match &self.resource.kind {
+            ResourceKind::ConfigMap { .. } => {
+                let api = Api::<ConfigMap>::namespaced(client, namespace);
+                self.resource.clone().watch(api).await
+            }
+            ResourceKind::FeatureFlag => {
+                let api = Api::<DynamicObject>::namespaced_with(
+                    client,
+                    namespace,
+                    &feature_flag_resource(),
+                );
+                self.resource.clone().watch(api).await
+            }
+        };
+
+        self.watcher =
+            Some(watcher.map_err(|message| {
+                ProviderError::new(ProviderErrorKind::Misconfigured, message)
+            })?);
+        Ok(())
+    }
+
+    async fn shutdown(&self) {
+        if let Some(watcher) = &self.watcher {
+            watcher.abort();
+        }
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.resource.flags.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.resource.flags.event_emitter()
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resource
+            .flags
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resource
+            .flags
+            .resolve_int_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resource
+            .flags
+            .resolve_float_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resource
+            .flags
+            .resolve_string_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resource
+            .flags
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.resource.flags.resolve_all(evaluation_context).await
+    }
+}
+
+/// The `FeatureFlag` custom resource of the OpenFeature Operator.
+fn feature_flag_resource() -> ApiResource {
+    ApiResource::from_gvk(&GroupVersionKind::gvk(
+        "core.openfeature.dev",
+        "v1beta1",
+        "FeatureFlag",
+    ))
+}
+
+// ============================================================
+//  FlagResource
+// ============================================================
+
+/// The flags of a resource, shared with the task watching it.
+#[derive(Clone)]
+struct FlagResource {
+    namespace: String,
+    name: String,
+    kind: ResourceKind,
+    flags: InMemoryProvider,
+    failing: Arc<AtomicBool>,
+}
+
+impl FlagResource {
+    /// Read the resource through `api`, then spawn a task watching it for changes.
+    async fn watch<K>(self, api: Api<K>) -> Result<JoinHandle<()>, String>
+    where
+        K: Resource + Clone + Debug + DeserializeOwned + Serialize + Send + 'static,
+    {
+        let object = api
+            .get(&self.name)
+            .await
+            .map_err(|error| error.to_string())?;
+        self.load(self.parse(&object));
+
+        let config = watcher::Config::default().fields(&format!("metadata.name={}", self.name));
+
+        Ok(tokio::spawn(
+            self.follow(watcher(api, config).default_backoff()),
+        ))
+    }
+
+    /// Load the resource on every watch event of `events`, only failing on a deletion or after
+    /// [`KubernetesProvider::MAX_WATCH_ERRORS`] consecutive errors.
+    async fn follow<K, S>(self, events: S)
+    where
+        K: Serialize,
+        S: Stream<Item = Result<watcher::Event<K>, watcher::Error>>,
+    {
+        let mut events = Box::pin(events);
+        let mut errors = 0;
+
+        while let Some(event) = events.next().await {
+            let event = match event {
+                Ok(event) => {
+                    errors = 0;
+                    event
+                }
+                Err(error) => {
+                    errors += 1;
+                    if errors == KubernetesProvider::MAX_WATCH_ERRORS {
+                        self.load(Err(error.to_string()));
+                    }
+                    continue;
+                }
+            };
+
+            match event {
+                watcher::Event::Apply(object) | watcher::Event::InitApply(object) => {
+                    self.load(self.parse(&object));
+                }
+                watcher::Event::Delete(_) => {
+                    self.load(Err(format!("{} was deleted", self.name)));
+                }
+                watcher::Event::Init | watcher::Event::InitDone => {}
+            }
+        }
+    }
+
+    fn parse<K: Serialize>(&self, object: &K) -> Result<HashMap<String, InMemoryFlag>, String> {
+        let object = serde_json::to_value(object).map_err(|error| error.to_string())?;
+
+        parse_resource(&self.kind, &object)
+            .map_err(|message| format!("{}/{}: {}", self.namespace, self.name, message))
+    }
+
+    /// Swap in the flags of `result`, emitting `PROVIDER_ERROR` on failure and `PROVIDER_READY`
+    /// on recovery.
+    fn load(&self, result: Result<HashMap<String, InMemoryFlag>, String>) {
+        let event = match result {
+            Ok(flags) => {
+                self.flags.set_flags(flags);
+
+                if !self.failing.swap(false, Ordering::Relaxed) {
+                    return;
+                }
+
+                ProviderEvent::builder()
+                    .event_type(ProviderEventType::Ready)
+                    .provider_name(self.flags.metadata().name.clone())
+                    .build()
+            }
+            Err(message) => {
+                self.failing.store(true, Ordering::Relaxed);
+
+                ProviderEvent::builder()
+                    .event_type(ProviderEventType::Error)
+                    .provider_name(self.flags.metadata().name.clone())
+                    .message(message)
+                    .build()
+            }
+        };
+
+        if let Some(events) = self.flags.event_emitter() {
+            events.emit(event);
+        }
+    }
+}
+
+// ============================================================
+//  Flag definitions
+// ============================================================
+
+/// Parse the flags of `object`, a resource of `kind` as JSON.
+fn parse_resource(
+    kind: &ResourceKind,
+    object: &JsonValue,
+) -> Result<HashMap<String, InMemoryFlag>, String> {
+    match kind {
+        ResourceKind::ConfigMap { key } => {
+            let text = object
+                .get("data")
+                .and_then(|data| data.get(key))
+                .and_then(JsonValue::as_str)
+                .ok_or_else(|| format!("The ConfigMap has no \"{key}\" data key"))?;
+
+            file_provider::parse_file(Path::new(key), text)
+        }
+        ResourceKind::FeatureFlag => {
+            let flag_spec = object
+                .get("spec")
+                .and_then(|spec| spec.get("flagSpec"))
+                .ok_or("The FeatureFlag has no spec.flagSpec")?;
+
+            flagd::parse_definitions(flag_spec)
+        }
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn definitions(default_variant: &str) -> JsonValue {
+        json!({
+            "flags": {
+                "new-checkout": {
+                    "state": "ENABLED",
+                    "variants": { "on": true, "off": false },
+                    "defaultVariant": default_variant
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn parse_resources() {
+        let config_map = json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "data": { "flags.json": definitions("on").to_string() }
+        });
+        let flags = parse_resource(
+            &ResourceKind::ConfigMap {
+                key: KubernetesProvider::DEFAULT_KEY.to_string(),
+            },
+            &config_map,
+        )
+        .unwrap();
+        assert!(flags.contains_key("new-checkout"));
+
+        let error = parse_resource(
+            &ResourceKind::ConfigMap {
+                key: "flags.yaml".to_string(),
+            },
+            &config_map,
+        )
+        .unwrap_err();
+        assert_eq!(error, "The ConfigMap has no \"flags.yaml\" data key");
+
+        let feature_flag = json!({
+            "apiVersion": "core.openfeature.dev/v1beta1",
+            "kind": "FeatureFlag",
+            "spec": { "flagSpec": definitions("off") }
+        });
+        let flags = parse_resource(&ResourceKind::FeatureFlag, &feature_flag).unwrap();
+        assert!(flags.contains_key("new-checkout"));
+    }
+
+    #[tokio::test]
+    async fn load() {
+        let provider = KubernetesProvider::feature_flag("payments", "checkout-flags");
+        let resource = &provider.resource;
+        let mut events = provider.event_emitter().unwrap().subscribe();
+
+        let feature_flag = |default_variant: &str| {
+            let mut object = DynamicObject::new("checkout-flags", &feature_flag_resource());
+            object.data = json!({ "spec": { "flagSpec": definitions(default_variant) } });
+            object
+        };
+        let resolve = || async {
+            resource
+                .flags
+                .resolve_bool_value("new-checkout", &EvaluationContext::default())
+                .await
+                .unwrap()
+                .value
+        };
+
+        resource.load(resource.parse(&feature_flag("off")));
+        assert!(!resolve().await);
+
+        resource.load(resource.parse(&feature_flag("on")));
+        assert!(resolve().await);
+
+        resource.load(Err("checkout-flags was deleted".to_string()));
+        assert!(resolve().await);
+
+        resource.load(resource.parse(&feature_flag("on")));
+
+        let event_types: Vec<_> = std::iter::from_fn(|| events.try_recv().ok())
+            .map(|event| event.event_type)
+            .collect();
+        assert_eq!(
+            event_types,
+            vec![
+                ProviderEventType::ConfigurationChanged,
+                ProviderEventType::ConfigurationChanged,
+                ProviderEventType::Error,
+                ProviderEventType::Ready
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn follow_watch_events() {
+        let provider = KubernetesProvider::feature_flag("payments", "checkout-flags");
+        let mut events = provider.event_emitter().unwrap().subscribe();
+
+        let feature_flag = |default_variant: &str| {
+            let mut object = DynamicObject::new("checkout-flags", &feature_flag_resource());
+            object.data = json!({ "spec": { "flagSpec": definitions(default_variant) } });
+            object
+        };
+        let errors = |count| (0..count).map(|_| Err(watcher::Error::NoResourceVersion));
+
+        let watch_events = std::iter::once(Ok(watcher::Event::Apply(feature_flag("off"))))
+            .chain(errors(KubernetesProvider::MAX_WATCH_ERRORS - 1))
+            .chain([
+                Ok(watcher::Event::Init),
+                Ok(watcher::Event::InitApply(feature_flag("on"))),
+                Ok(watcher::Event::InitDone),
+            ])
+            .chain(errors(KubernetesProvider::MAX_WATCH_ERRORS + 1))
+            .chain([
+                Ok(watcher::Event::Apply(feature_flag("on"))),
+                Ok(watcher::Event::Delete(feature_flag("on"))),
+            ]);
+        provider
+            .resource
+            .clone()
+            .follow(futures_util::stream::iter(watch_events))
+            .await;
+
+        let value = provider
+            .resource
+            .flags
+            .resolve_bool_value("new-checkout", &EvaluationContext::default())
+            .await
+            .unwrap()
+            .value;
+        assert!(value);
+
+        let event_types: Vec<_> = std::iter::from_fn(|| events.try_recv().ok())
+            .map(|event| event.event_type)
+            .collect();
+        assert_eq!(
+            event_types,
+            vec![
+                ProviderEventType::ConfigurationChanged,
+                ProviderEventType::ConfigurationChanged,
+                ProviderEventType::Error,
+                ProviderEventType::Ready,
+                ProviderEventType::Error
+            ]
+        );
+    }
+}
@@ -0,0 +1,729 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use launchdarkly_server_sdk::{Client, Context};
+use serde_json::{json, Map, Value as JsonValue};
+
+use crate::{
+    serde_json::field_value_to_json, EvaluationContext, EvaluationError, EvaluationErrorCode,
+    EvaluationReason, EvaluationResult, FlagMetadata, ProviderError, ProviderErrorKind,
+    StructValue, TrackingEventDetails, Value,
+};
+
+use super::{FeatureProvider, FlagType, FlagValue, ProviderMetadata, ResolutionDetails};
+
+/// The attribute of the evaluation context holding the kind of the LaunchDarkly context.
+const KIND_ATTRIBUTE: &str = "kind";
+
+/// The attribute of the evaluation context listing the private attributes of the LaunchDarkly
+/// context.
+const PRIVATE_ATTRIBUTES_ATTRIBUTE: &str = "privateAttributes";
+
+// ============================================================
+//  LaunchDarklyClient
+// ============================================================
+
+/// The LaunchDarkly client flags are evaluated with, implemented by the [`Client`] of the
+/// `launchdarkly-server-sdk` crate, and by fakes in tests.
+///
+/// Contexts and evaluation reasons are exchanged in the JSON format of LaunchDarkly, which the
+/// types of the SDK serialize to and deserialize from.
+#[async_trait]
+pub trait LaunchDarklyClient: Send + Sync + 'static {
+    /// Wait until the client has received its flags, returning `false` if it failed to.
+    async fn wait_for_initialization(&self) -> bool {
+        true
+    }
+
+    /// Evaluate `flag_key` for `context`, in the JSON format of LaunchDarkly contexts.
+    fn variation_detail(&self, context: &JsonValue, flag_key: &str) -> LaunchDarklyDetail;
+
+    /// Record that `context` performed the action `event_name`, with an optional metric `value`
+    /// and custom `data`.
+    #[allow(unused_variables)]
+    fn track(&self, context: &JsonValue, event_name: &str, value: Option<f64>, data: JsonValue) {}
+
+    /// Flush the pending events and stop the client.
+    fn close(&self) {}
+}
+
+/// The evaluation of a flag by a [`LaunchDarklyClient`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct LaunchDarklyDetail {
+    /// The value of the flag, if any.
+    pub value: Option<JsonValue>,
+
+    /// The index of the variation the value is, if any.
+    pub variation_index: Option<i64>,
+
+    /// The evaluation reason in the JSON format of LaunchDarkly, such as
+    /// `{"kind": "RULE_MATCH", "ruleIndex": 0, "ruleId": "beta-testers"}`.
+    pub reason: JsonValue,
+}
+
+/// The client of the LaunchDarkly server SDK, started with
+/// [`Client::start_with_default_executor`] before the provider is initialized, which waits up to
+/// five seconds for the flags.
+#[async_trait]
+impl LaunchDarklyClient for Client {
+    async fn wait_for_initialization(&self) -> bool {
+        Client::wait_for_initialization(self, SDK_INITIALIZATION_TIMEOUT).await == Some(true)
+    }
+
+    fn variation_detail(&self, context: &JsonValue, flag_key: &str) -> LaunchDarklyDetail {
+        let Ok(context) = serde_json::from_value::<Context>(context.clone()) else {
+            return LaunchDarklyDetail {
+                value: None,
+                variation_index: None,
+                reason: json!({ "kind": "ERROR", "errorKind": "EXCEPTION" }),
+            };
+        };
+
+        let detail = self.json_variation_detail(&context, flag_key, JsonValue::Null);
+
+        LaunchDarklyDetail {
+            value: detail.value.filter(|value| !value.is_null()),
+            variation_index: detail.variation_index.map(|index| index as i64),
+            reason: serde_json::to_value(detail.reason).unwrap_or(JsonValue::Null),
+        }
+    }
+
+    fn track(&self, context: &JsonValue, event_name: &str, value: Option<f64>, data: JsonValue) {
+        let Ok(context) = serde_json::from_value::<Context>(context.clone()) else {
+            return;
+        };
+
+        match value {
+            Some(value) => self.track_metric(context, event_name, value, data),
+            None => {
+                let _ = self.track_data(context, event_name, data);
+            }
+        }
+    }
+
+    fn close(&self) {
+        Client::close(self);
+    }
+}
+
+/// The time the client of the LaunchDarkly server SDK is given to receive its flags.
+const SDK_INITIALIZATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ============================================================
+//  LaunchDarklyProvider
+// ============================================================
+
+/// A provider evaluating flags with [LaunchDarkly](https://launchdarkly.com) through a
+/// [`LaunchDarklyClient`].
+///
+/// The evaluation context is translated into a LaunchDarkly context whose key is the targeting
+/// key, whose kind is the `kind` attribute, `user` by default, and whose attributes are the other
+/// ones, `privateAttributes` listing those to redact. Multi-kind contexts have kind `multi` and an
+/// attribute per kind, holding a struct with the key as `targetingKey` or `key`:
+///
+/// ```
+/// # use open_feature::{EvaluationContext, EvaluationContextFieldValue, StructValue};
+/// let context = EvaluationContext::default()
+///     .with_custom_field("kind", "multi")
+///     .with_custom_field(
+///         "user",
+///         EvaluationContextFieldValue::new_struct(
+///             StructValue::default()
+///                 .with_field("targetingKey", "alice")
+///                 .with_field("name", "Alice"),
+///         ),
+///     )
+///     .with_custom_field(
+///         "organization",
+///         EvaluationContextFieldValue::new_struct(
+///             StructValue::default().with_field("key", "acme"),
+///         ),
+///     );
+/// ```
+///
+/// The variant is the index of the variation, and the flag metadata hold the rule or prerequisite
+/// behind the evaluation, along with whether it is part of an experiment.
+///
+/// ```no_run
+/// use launchdarkly_server_sdk::{Client, ConfigBuilder};
+/// use open_feature::{provider::LaunchDarklyProvider, OpenFeature};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::build(ConfigBuilder::new("sdk-key").build()?)?;
+/// client.start_with_default_executor();
+///
+/// OpenFeature::singleton_mut()
+///     .await
+///     .set_provider(LaunchDarklyProvider::new(client))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct LaunchDarklyProvider<C> {
+    metadata: ProviderMetadata,
+    client: C,
+}
+
+impl<C: LaunchDarklyClient> LaunchDarklyProvider<C> {
+    /// Create a provider evaluating flags with `client`.
+    pub fn new(client: C) -> Self {
+        Self {
+            metadata: ProviderMetadata::new("LaunchDarkly Provider"),
+            client,
+        }
+    }
+
+    /// Return the client flags are evaluated with.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let context = to_launchdarkly_context(evaluation_context)?;
+        let detail = self.client.variation_detail(&context, flag_key);
+        let (reason, flag_metadata) = parse_reason(&detail.reason)?;
+
+        let value = detail.value.ok_or_else(|| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::General("Missing value".to_string()))
+                .message(format!("Flag \"{flag_key}\" has no value for this context"))
+                .build()
+        })?;
+
+        let value = match (T::FLAG_TYPE, Value::try_from(value)?) {
+            #[allow(clippy::cast_precision_loss)]
+            (FlagType::Float, Value::Int(value)) => Value::Float(value as f64),
+            (_, value) => value,
+        };
+
+        Ok(ResolutionDetails {
+            value: T::from_value(value).ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::TypeMismatch)
+                    .message(format!("Expected a {:?} flag", T::FLAG_TYPE))
+                    .build()
+            })?,
+            variant: detail.variation_index.map(|index| index.to_string()),
+            reason: Some(reason),
+            flag_metadata: (!flag_metadata.values.is_empty()).then_some(flag_metadata),
+        })
+    }
+}
+
+#[async_trait]
+impl<C: LaunchDarklyClient> FeatureProvider for LaunchDarklyProvider<C> {
+    async fn initialize(&mut self, _context: &EvaluationContext) -> Result<(), ProviderError> {
+        if self.client.wait_for_initialization().await {
+            Ok(())
+        } else {
+            Err(ProviderError::new(
+                ProviderErrorKind::Unavailable,
+                "The LaunchDarkly client failed to initialize",
+            ))
+        }
+    }
+
+    async fn shutdown(&self) {
+        self.client.close();
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        let Ok(context) = to_launchdarkly_context(evaluation_context) else {
+            return;
+        };
+
+        let data: Map<_, _> = tracking_event_details
+            .custom_fields
+            .iter()
+            .filter_map(|(key, value)| Some((key.clone(), field_value_to_json(value)?)))
+            .collect();
+        let data = if data.is_empty() {
+            JsonValue::Null
+        } else {
+            JsonValue::Object(data)
+        };
+
+        self.client
+            .track(&context, event_name, tracking_event_details.value, data);
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context)
+    }
+}
+
+// ============================================================
+//  Contexts
+// ============================================================
+
+/// Translate `evaluation_context` into a LaunchDarkly context.
+fn to_launchdarkly_context(evaluation_context: &EvaluationContext) -> EvaluationResult<JsonValue> {
+    let JsonValue::Object(mut attributes) = JsonValue::from(evaluation_context) else {
+        unreachable!("Evaluation contexts convert to JSON objects");
+    };
+
+    let kind = match attributes.remove(KIND_ATTRIBUTE) {
+        None => "user".to_string(),
+        Some(JsonValue::String(kind)) => kind,
+        Some(_) => return Err(invalid_context("The context kind is not a string")),
+    };
+
+    if kind != "multi" {
+        let mut context = single_kind_context(&kind, attributes)?;
+        context.insert(KIND_ATTRIBUTE.to_string(), kind.into());
+        return Ok(JsonValue::Object(context));
+    }
+
+    attributes.remove("targetingKey");
+    if attributes.is_empty() {
+        return Err(invalid_context("The multi-kind context has no kind"));
+    }
+
+    let mut context = Map::new();
+    context.insert(KIND_ATTRIBUTE.to_string(), kind.into());
+
+    for (kind, attributes) in attributes {
+        let JsonValue::Object(attributes) = attributes else {
+            return Err(invalid_context(format!(
+                "The context of kind \"{kind}\" is not a struct"
+            )));
+        };
+
+        let single = single_kind_context(&kind, attributes)?;
+        context.insert(kind, JsonValue::Object(single));
+    }
+
+    Ok(JsonValue::Object(context))
+}
+
+/// Return the LaunchDarkly context of `kind` for `attributes`, without its kind.
+fn single_kind_context(
+    kind: &str,
+    mut attributes: Map<String, JsonValue>,
+) -> EvaluationResult<Map<String, JsonValue>> {
+    let valid_kind = !kind.is_empty()
+        && kind != KIND_ATTRIBUTE
+        && kind
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if !valid_kind {
+        return Err(invalid_context(format!("Invalid context kind \"{kind}\"")));
+    }
+
+    let key = match attributes.remove("targetingKey") {
+        Some(key) => {
+            attributes.remove("key");
+            Some(key)
+        }
+        None => attributes.remove("key"),
+    };
+    let Some(JsonValue::String(key)) = key else {
+        return Err(EvaluationError::builder()
+            .code(EvaluationErrorCode::TargetingKeyMissing)
+            .message(format!("The context of kind \"{kind}\" has no key"))
+            .build());
+    };
+
+    // LaunchDarkly rejects contexts whose built-in attributes have another type.
+    if attributes
+        .get("name")
+        .map_or(false, |name| !name.is_string())
+    {
+        attributes.remove("name");
+    }
+    if attributes
+        .get("anonymous")
+        .map_or(false, |anonymous| !anonymous.is_boolean())
+    {
+        attributes.remove("anonymous");
+    }
+
+    let mut context = Map::new();
+    context.insert("key".to_string(), key.into());
+
+    if let Some(private_attributes) = attributes.remove(PRIVATE_ATTRIBUTES_ATTRIBUTE) {
+        let private_attributes: Vec<_> = private_attributes
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|attribute| attribute.is_string())
+            .cloned()
+            .collect();
+        if !private_attributes.is_empty() {
+            context.insert(
+                "_meta".to_string(),
+                json!({ "privateAttributes": private_attributes }),
+            );
+        }
+    }
+
+    context.extend(attributes);
+    Ok(context)
+}
+
+fn invalid_context(message: impl Into<String>) -> EvaluationError {
+    EvaluationError::builder()
+        .code(EvaluationErrorCode::InvalidContext)
+        .message(message)
+        .build()
+}
+
+// ============================================================
+//  Reasons
+// ============================================================
+
+/// Translate a LaunchDarkly evaluation reason into the reason and flag metadata of a resolution,
+/// or into the error it reports.
+fn parse_reason(reason: &JsonValue) -> EvaluationResult<(EvaluationReason, FlagMetadata)> {
+    let kind = reason.get("kind").and_then(JsonValue::as_str);
+    let in_experiment = reason
+        .get("inExperiment")
+        .and_then(JsonValue::as_bool)
+        .unwrap_or(false);
+
+    let mut flag_metadata = FlagMetadata::default();
+    if let Some(rule_id) = reason.get("ruleId").and_then(JsonValue::as_str) {
+        flag_metadata.add_value("ruleId", rule_id);
+    }
+    if let Some(rule_index) = reason.get("ruleIndex").and_then(JsonValue::as_i64) {
+        flag_metadata.add_value("ruleIndex", rule_index);
+    }
+    if let Some(prerequisite_key) = reason.get("prerequisiteKey").and_then(JsonValue::as_str) {
+        flag_metadata.add_value("prerequisiteKey", prerequisite_key);
+    }
+    if in_experiment {
+        flag_metadata.add_value("inExperiment", true);
+    }
+
+    let reason = match kind {
+        Some("OFF") => EvaluationReason::Disabled,
+        Some("FALLTHROUGH") if in_experiment => EvaluationReason::Split,
+        Some("FALLTHROUGH") => EvaluationReason::Default,
+        Some("TARGET_MATCH" | "RULE_MATCH") => EvaluationReason::TargetingMatch,
+        Some("ERROR") => {
+            let error_kind = reason.get("errorKind").and_then(JsonValue::as_str);
+            return Err(EvaluationError::builder()
+                .code(parse_error_kind(error_kind.unwrap_or("EXCEPTION")))
+                .message(format!(
+                    "LaunchDarkly failed the evaluation with {}",
+                    error_kind.unwrap_or("an unknown error")
+                ))
+                .build());
+        }
+        Some(kind) => EvaluationReason::Other(kind.to_string()),
+        None => EvaluationReason::Unknown,
+    };
+
+    Ok((reason, flag_metadata))
+}
+
+fn parse_error_kind(error_kind: &str) -> EvaluationErrorCode {
+    match error_kind {
+        "CLIENT_NOT_READY" => EvaluationErrorCode::ProviderNotReady,
+        "FLAG_NOT_FOUND" => EvaluationErrorCode::FlagNotFound,
+        "MALFORMED_FLAG" => EvaluationErrorCode::ParseError,
+        "USER_NOT_SPECIFIED" => EvaluationErrorCode::TargetingKeyMissing,
+        "WRONG_TYPE" => EvaluationErrorCode::TypeMismatch,
+        error_kind => EvaluationErrorCode::General(error_kind.to_string()),
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Mutex};
+
+    use super::*;
+    use crate::EvaluationContextFieldValue;
+
+    /// Serves the details of a fixed map of flags, recording the contexts it is given.
+    #[derive(Default)]
+    struct FakeClient {
+        flags: HashMap<String, LaunchDarklyDetail>,
+        contexts: Mutex<Vec<JsonValue>>,
+    }
+
+    impl FakeClient {
+        fn with_flag(mut self, flag_key: &str, value: JsonValue, reason: JsonValue) -> Self {
+            self.flags.insert(
+                flag_key.to_string(),
+                LaunchDarklyDetail {
+                    value: Some(value),
+                    variation_index: Some(1),
+                    reason,
+                },
+            );
+            self
+        }
+    }
+
+    #[async_trait]
+    impl LaunchDarklyClient for FakeClient {
+        fn variation_detail(&self, context: &JsonValue, flag_key: &str) -> LaunchDarklyDetail {
+            self.contexts.lock().unwrap().push(context.clone());
+
+            self.flags
+                .get(flag_key)
+                .cloned()
+                .unwrap_or_else(|| LaunchDarklyDetail {
+                    value: None,
+                    variation_index: None,
+                    reason: json!({ "kind": "ERROR", "errorKind": "FLAG_NOT_FOUND" }),
+                })
+        }
+    }
+
+    #[test]
+    fn translate_single_kind_context() {
+        let context = EvaluationContext::default()
+            .with_targeting_key("acme")
+            .with_custom_field("kind", "organization")
+            .with_custom_field("name", "Acme")
+            .with_custom_field("anonymous", "no")
+            .with_custom_field("plan", "pro")
+            .with_custom_field("privateAttributes", vec!["plan".to_string()]);
+
+        assert_eq!(
+            to_launchdarkly_context(&context).unwrap(),
+            json!({
+                "kind": "organization",
+                "key": "acme",
+                "name": "Acme",
+                "plan": "pro",
+                "_meta": { "privateAttributes": ["plan"] }
+            })
+        );
+
+        let context = EvaluationContext::default().with_targeting_key("alice");
+        assert_eq!(
+            to_launchdarkly_context(&context).unwrap(),
+            json!({ "kind": "user", "key": "alice" })
+        );
+    }
+
+    #[test]
+    fn translate_multi_kind_context() {
+        let context = EvaluationContext::default()
+            .with_custom_field("kind", "multi")
+            .with_custom_field(
+                "user",
+                EvaluationContextFieldValue::new_struct(
+                    StructValue::default()
+                        .with_field("targetingKey", "alice")
+                        .with_field("email", "alice@example.com"),
+                ),
+            )
+            .with_custom_field(
+                "organization",
+                EvaluationContextFieldValue::new_struct(
+                    StructValue::default().with_field("key", "acme"),
+                ),
+            );
+
+        assert_eq!(
+            to_launchdarkly_context(&context).unwrap(),
+            json!({
+                "kind": "multi",
+                "user": { "key": "alice", "email": "alice@example.com" },
+                "organization": { "key": "acme" }
+            })
+        );
+    }
+
+    #[test]
+    fn reject_invalid_context() {
+        let error = to_launchdarkly_context(&EvaluationContext::default()).unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::TargetingKeyMissing);
+
+        let context = EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("kind", "user profile");
+        let error = to_launchdarkly_context(&context).unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::InvalidContext);
+
+        let context = EvaluationContext::default()
+            .with_custom_field("kind", "multi")
+            .with_custom_field("user", "alice");
+        let error = to_launchdarkly_context(&context).unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::InvalidContext);
+    }
+
+    #[test]
+    fn translate_reasons() {
+        let cases = [
+            (json!({ "kind": "OFF" }), EvaluationReason::Disabled),
+            (json!({ "kind": "FALLTHROUGH" }), EvaluationReason::Default),
+            (
+                json!({ "kind": "FALLTHROUGH", "inExperiment": true }),
+                EvaluationReason::Split,
+            ),
+            (
+                json!({ "kind": "TARGET_MATCH" }),
+                EvaluationReason::TargetingMatch,
+            ),
+            (
+                json!({ "kind": "PREREQUISITE_FAILED", "prerequisiteKey": "billing" }),
+                EvaluationReason::Other("PREREQUISITE_FAILED".to_string()),
+            ),
+            (json!({}), EvaluationReason::Unknown),
+        ];
+
+        for (reason, expected) in cases {
+            assert_eq!(parse_reason(&reason).unwrap().0, expected, "{reason}");
+        }
+
+        let (reason, flag_metadata) =
+            parse_reason(&json!({ "kind": "RULE_MATCH", "ruleIndex": 2, "ruleId": "beta" }))
+                .unwrap();
+        assert_eq!(reason, EvaluationReason::TargetingMatch);
+        assert_eq!(
+            flag_metadata,
+            FlagMetadata::default()
+                .with_value("ruleId", "beta")
+                .with_value("ruleIndex", 2)
+        );
+
+        let error =
+            parse_reason(&json!({ "kind": "ERROR", "errorKind": "WRONG_TYPE" })).unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::TypeMismatch);
+    }
+
+    #[tokio::test]
+    async fn resolve_values() {
+        let client = FakeClient::default()
+            .with_flag(
+                "checkout-v2",
+                json!(true),
+                json!({ "kind": "TARGET_MATCH" }),
+            )
+            .with_flag("discount", json!(10), json!({ "kind": "FALLTHROUGH" }));
+        let mut provider = LaunchDarklyProvider::new(client);
+        provider
+            .initialize(&EvaluationContext::default())
+            .await
+            .unwrap();
+
+        let context = EvaluationContext::default().with_targeting_key("alice");
+
+        let details = provider
+            .resolve_bool_value("checkout-v2", &context)
+            .await
+            .unwrap();
+        assert!(details.value);
+        assert_eq!(details.variant.as_deref(), Some("1"));
+        assert_eq!(details.reason, Some(EvaluationReason::TargetingMatch));
+
+        let details = provider
+            .resolve_float_value("discount", &context)
+            .await
+            .unwrap();
+        assert_eq!(details.value.to_string(), "10");
+
+        let error = provider
+            .resolve_string_value("discount", &context)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::TypeMismatch);
+
+        let error = provider
+            .resolve_bool_value("missing", &context)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+
+        assert_eq!(
+            provider.client().contexts.lock().unwrap()[0],
+            json!({ "kind": "user", "key": "alice" })
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_with_sdk_client() {
+        let test_data = launchdarkly_server_sdk::TestData::new();
+        test_data.update(test_data.flag("checkout-v2").variation_for_all(true));
+
+        let config = launchdarkly_server_sdk::ConfigBuilder::new("sdk-key")
+            .data_source(&test_data)
+            .event_processor(&launchdarkly_server_sdk::NullEventProcessorBuilder::new())
+            .build()
+            .unwrap();
+        let client = Client::build(config).unwrap();
+        client.start_with_default_executor();
+
+        let mut provider = LaunchDarklyProvider::new(client);
+        provider
+            .initialize(&EvaluationContext::default())
+            .await
+            .unwrap();
+
+        let context = EvaluationContext::default().with_targeting_key("alice");
+
+        let details = provider
+            .resolve_bool_value("checkout-v2", &context)
+            .await
+            .unwrap();
+        assert!(details.value);
+        assert_eq!(details.variant, Some("0".to_string()));
+        assert_eq!(details.reason, Some(EvaluationReason::Default));
+
+        let error = provider
+            .resolve_bool_value("missing", &context)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+
+        provider.shutdown().await;
+    }
+}
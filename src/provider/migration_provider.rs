@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    EvaluationContext, EvaluationResult, ProviderError, StructValue, TrackingEventDetails, Value,
+};
+
+use super::{
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+// ============================================================
+//  MigrationProvider
+// ============================================================
+
+/// A provider that delegates to `inner`, resolving a configured percentage of the subjects of
+/// an old flag with its replacement, so that consumers can be migrated from one flag to another
+/// gradually.
+///
+/// Subjects are bucketed by the SHA-256 of the new key and their targeting key, so a given
+/// subject keeps resolving the same flag as long as the percentage does not decrease.
+/// Resolutions without a targeting key always resolve the old flag. The key actually resolved
+/// is recorded in the `migration_key` field of the flag metadata.
+///
+/// ```no_run
+/// use open_feature::{
+///     provider::{MigrationProvider, NoOpProvider},
+///     EvaluationContext, OpenFeature,
+/// };
+///
+/// # async fn example() -> Result<(), open_feature::SdkError> {
+/// let provider = MigrationProvider::new(NoOpProvider::default())
+///     .with_migration("checkout", "checkout-v2", 25.0);
+///
+/// let mut api = OpenFeature::singleton_mut().await;
+/// api.set_provider(provider).await?;
+///
+/// // Resolves "checkout-v2" for a quarter of the users.
+/// let context = EvaluationContext::default().with_targeting_key("user-42");
+/// let client = api.create_client();
+/// let enabled = client
+///     .get_bool_value("checkout", Some(&context), None)
+///     .await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MigrationProvider<P> {
+    inner: P,
+    migrations: HashMap<String, Migration>,
+}
+
+struct Migration {
+    new_key: String,
+    percentage: f64,
+}
+
+impl<P: FeatureProvider> MigrationProvider<P> {
+    /// Create a provider delegating to `inner` without any migration.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            migrations: HashMap::new(),
+        }
+    }
+
+    /// Resolve `old_key` as `new_key` for `percentage` (from `0.0` to `100.0`) of the subjects,
+    /// replacing its current migration if any.
+    #[must_use]
+    pub fn with_migration(
+        mut self,
+        old_key: impl Into<String>,
+        new_key: impl Into<String>,
+        percentage: f64,
+    ) -> Self {
+        self.add_migration(old_key, new_key, percentage);
+        self
+    }
+
+    /// Resolve `old_key` as `new_key` for `percentage` (from `0.0` to `100.0`) of the subjects,
+    /// replacing its current migration if any.
+    pub fn add_migration(
+        &mut self,
+        old_key: impl Into<String>,
+        new_key: impl Into<String>,
+        percentage: f64,
+    ) {
+        self.migrations.insert(
+            old_key.into(),
+            Migration {
+                new_key: new_key.into(),
+                percentage: percentage.clamp(0.0, 100.0),
+            },
+        );
+    }
+
+    /// Return the key `flag_key` is resolved as for `evaluation_context`.
+    pub fn resolve_key<'a>(
+        &'a self,
+        flag_key: &'a str,
+        evaluation_context: &EvaluationContext,
+    ) -> &'a str {
+        let Some(migration) = self.migrations.get(flag_key) else {
+            return flag_key;
+        };
+        let Some(targeting_key) = &evaluation_context.targeting_key else {
+            return flag_key;
+        };
+
+        if bucket(&migration.new_key, targeting_key) < migration.percentage {
+            &migration.new_key
+        } else {
+            flag_key
+        }
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        if !self.migrations.contains_key(flag_key) {
+            return T::resolve(&self.inner, flag_key, evaluation_context).await;
+        }
+
+        let migration_key = self.resolve_key(flag_key, evaluation_context);
+        let mut details = T::resolve(&self.inner, migration_key, evaluation_context).await?;
+
+        details.flag_metadata = Some(
+            details
+                .flag_metadata
+                .unwrap_or_default()
+                .with_value("migration_key", migration_key.to_string()),
+        );
+
+        Ok(details)
+    }
+}
+
+/// Return the bucket of `targeting_key` for `flag_key`, from `0.0` (included) to `100.0`
+/// (excluded).
+fn bucket(flag_key: &str, targeting_key: &str) -> f64 {
+    let mut hasher = Sha256::new();
+    hasher.update(flag_key.as_bytes());
+    hasher.update(targeting_key.as_bytes());
+
+    let hash = hasher.finalize();
+    let value = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]);
+
+    f64::from(value) / (f64::from(u32::MAX) + 1.0) * 100.0
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for MigrationProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.inner.initialize(context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.inner
+            .track(event_name, evaluation_context, tracking_event_details)
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    /// Return the flags of `inner`, with the migrated old flags holding the details of their
+    /// replacement.
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let mut flags = self.inner.resolve_all(evaluation_context).await?;
+
+        for old_key in self.migrations.keys() {
+            let migration_key = self.resolve_key(old_key, evaluation_context);
+
+            if migration_key != old_key {
+                if let Some(details) = flags.get(migration_key).cloned() {
+                    flags.insert(old_key.clone(), details);
+                }
+            }
+        }
+
+        Ok(flags)
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{flags, FlagMetadataValue, OpenFeature};
+
+    fn create_inner() -> crate::provider::InMemoryProvider {
+        flags! {
+            "checkout" => bool: false,
+            "checkout-v2" => bool: true,
+        }
+    }
+
+    fn create_context(targeting_key: usize) -> EvaluationContext {
+        EvaluationContext::default().with_targeting_key(format!("user-{}", targeting_key))
+    }
+
+    #[tokio::test]
+    async fn migrate_percentage() {
+        let provider =
+            MigrationProvider::new(create_inner()).with_migration("checkout", "checkout-v2", 25.0);
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider).await.unwrap();
+
+        let client = api.create_client();
+
+        let mut migrated = 0;
+        for targeting_key in 0..1000 {
+            let context = create_context(targeting_key);
+            let value = client
+                .get_bool_value("checkout", Some(&context), None)
+                .await
+                .unwrap();
+
+            // Sticky.
+            assert_eq!(
+                client
+                    .get_bool_value("checkout", Some(&context), None)
+                    .await
+                    .unwrap(),
+                value
+            );
+
+            if value {
+                migrated += 1;
+            }
+        }
+
+        assert!((200..300).contains(&migrated), "{} migrated", migrated);
+
+        // No targeting key.
+        assert!(!client.get_bool_value("checkout", None, None).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn record_migration_key() {
+        let provider =
+            MigrationProvider::new(create_inner()).with_migration("checkout", "checkout-v2", 100.0);
+
+        let details = provider
+            .resolve_bool_value("checkout", &create_context(0))
+            .await
+            .unwrap();
+
+        assert!(details.value);
+        assert_eq!(
+            details.flag_metadata.unwrap().values.get("migration_key"),
+            Some(&FlagMetadataValue::String("checkout-v2".to_string()))
+        );
+
+        let flags = provider.resolve_all(&create_context(0)).await.unwrap();
+        assert_eq!(flags.get("checkout").unwrap().value, Value::Bool(true));
+    }
+}
@@ -1,13 +1,163 @@
+/// A provider resolving old flag keys as new ones.
+mod alias_provider;
+pub use alias_provider::AliasProvider;
+
+/// A provider serving the feature flags of AWS AppConfig.
+#[cfg(feature = "appconfig")]
+mod appconfig_provider;
+#[cfg(feature = "appconfig")]
+pub use appconfig_provider::AppConfigProvider;
+
+/// A provider passing on only permitted evaluation context attributes.
+mod attribute_filter_provider;
+pub use attribute_filter_provider::{AttributeFilter, AttributeFilterProvider};
+
+/// A provider evaluating the feature flags of Azure App Configuration.
+#[cfg(feature = "azure")]
+mod azure_app_config_provider;
+#[cfg(feature = "azure")]
+pub use azure_app_config_provider::AzureAppConfigProvider;
+
+/// Consistent bucketing of subjects.
+mod bucketing;
+pub use bucketing::{bucket_ratio, fractional_variant};
+
+/// A provider failing fast while its inner provider keeps failing.
+mod circuit_breaker_provider;
+pub use circuit_breaker_provider::{CircuitBreakerProvider, CircuitState};
+
+/// A provider evaluating ConfigCat flags locally.
+#[cfg(feature = "configcat")]
+mod configcat_provider;
+#[cfg(feature = "configcat")]
+pub use configcat_provider::ConfigCatProvider;
+
+/// A provider enforcing limits on evaluation contexts.
+mod context_limit_provider;
+pub use context_limit_provider::{
+    ContextLimitPolicy, ContextLimitProvider, ContextLimitViolation, ContextLimits,
+};
+
 /// Evaluation details.
 mod details;
 pub use details::ResolutionDetails;
 
+/// A provider resolving flags from environment variables.
+mod env_var_provider;
+pub use env_var_provider::EnvVarProvider;
+
+/// Events emitted by providers.
+mod event;
+pub use event::{EventEmitter, ProviderEvent, ProviderEventType};
+
 /// Feature provider trait.
 mod feature_provider;
 pub use feature_provider::{
     FeatureProvider, MockFeatureProvider, ProviderMetadata, ProviderStatus,
 };
 
+/// A provider serving flags defined in a file.
+#[cfg(feature = "serde_json")]
+mod file_provider;
+#[cfg(feature = "serde_json")]
+pub use file_provider::FileProvider;
+
+/// The flagd flag configuration format.
+#[cfg(feature = "serde_json")]
+mod flagd;
+#[cfg(feature = "serde_json")]
+pub use flagd::FlagdConfiguration;
+
+/// A provider evaluating GrowthBook features locally.
+#[cfg(feature = "growthbook")]
+mod growthbook_provider;
+#[cfg(feature = "growthbook")]
+pub use growthbook_provider::GrowthBookProvider;
+
+/// Generic resolution over flag value types.
+mod flag_value;
+pub use flag_value::{FlagType, FlagValue};
+
+/// A provider serving flags defined in code.
+mod in_memory_provider;
+pub use in_memory_provider::{InMemoryFlag, InMemoryProvider};
+
+/// A provider migrating subjects from one flag to another.
+mod migration_provider;
+pub use migration_provider::MigrationProvider;
+
+/// A provider combining the resolutions of several providers.
+mod multi_provider;
+pub use multi_provider::{MultiProvider, MultiProviderMismatch, MultiProviderStrategy};
+
+/// A provider prefixing flag keys with a namespace.
+mod namespace_provider;
+pub use namespace_provider::NamespaceProvider;
+
+/// A provider serving flags defined in a Kubernetes resource.
+#[cfg(feature = "kubernetes")]
+mod kubernetes_provider;
+#[cfg(feature = "kubernetes")]
+pub use kubernetes_provider::KubernetesProvider;
+
+/// A provider evaluating flags with a LaunchDarkly client.
+#[cfg(feature = "launchdarkly")]
+mod launchdarkly_provider;
+#[cfg(feature = "launchdarkly")]
+pub use launchdarkly_provider::{LaunchDarklyClient, LaunchDarklyDetail, LaunchDarklyProvider};
+
+/// The JsonLogic engine applying targeting rules.
+#[cfg(feature = "serde_json")]
+mod json_logic;
+
+/// A provider evaluating flags with an OFREP backend.
+#[cfg(feature = "ofrep")]
+mod ofrep_provider;
+#[cfg(feature = "ofrep")]
+pub use ofrep_provider::OfrepProvider;
+
 /// The default no-op provider.
 mod no_op_provider;
 pub use no_op_provider::NoOpProvider;
+
+/// The polling loop of remote providers.
+mod polling_provider;
+pub use polling_provider::{Fetched, PollingProvider, PollingSource};
+
+/// A scheduler shared by polling providers.
+mod polling_scheduler;
+#[cfg(feature = "analytics")]
+pub(crate) use polling_scheduler::backoff;
+pub use polling_scheduler::{PollingScheduler, PollingTask};
+
+/// A provider hashing sensitive evaluation context attributes.
+mod privacy_provider;
+pub use privacy_provider::PrivacyProvider;
+
+/// A provider retrying transient failures.
+mod retry_provider;
+pub use retry_provider::RetryProvider;
+
+/// Time windows flags are active within.
+mod schedule;
+pub use schedule::{ActivationWindow, Recurrence};
+
+/// Targeting rules in the flagd format.
+#[cfg(feature = "serde_json")]
+mod targeting;
+#[cfg(feature = "serde_json")]
+pub use targeting::Targeting;
+
+/// A provider routing resolutions to the provider of a tenant.
+mod tenant_routing_provider;
+pub use tenant_routing_provider::TenantRoutingProvider;
+
+/// A provider transforming resolved values.
+mod transform_provider;
+pub use transform_provider::TransformProvider;
+
+/// A provider comparing a candidate configuration with the live one.
+mod shadow_provider;
+pub use shadow_provider::{
+    FlagShadowReport, ShadowDiff, ShadowOutcome, ShadowProvider, ShadowRecorder, ShadowReport,
+};
@@ -0,0 +1,706 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+
+use crate::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationResult, ProviderError,
+    StructValue, TrackingEventDetails, Value,
+};
+
+use super::{
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+type MismatchHandler = Box<dyn Fn(&MultiProviderMismatch) + Send + Sync>;
+
+// ============================================================
+//  MultiProviderStrategy
+// ============================================================
+
+/// How a [`MultiProvider`] combines the resolutions of its providers.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum MultiProviderStrategy {
+    /// Return the result of the first provider having the flag, skipping the ones failing with
+    /// `FLAG_NOT_FOUND`. Other errors are returned as is.
+    #[default]
+    FirstMatch,
+
+    /// Return the result of the first provider resolving the flag successfully, skipping the
+    /// ones failing for any reason.
+    FirstSuccessful,
+
+    /// Resolve the flag with all the providers concurrently and return the result of the first
+    /// one having the flag, reporting the resolved values to the mismatch handler if they
+    /// differ. Errors other than `FLAG_NOT_FOUND` are returned as is.
+    Comparison,
+}
+
+// ============================================================
+//  MultiProviderMismatch
+// ============================================================
+
+/// The values of a flag resolved differently by the providers of a [`MultiProvider`] with the
+/// [`MultiProviderStrategy::Comparison`] strategy.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MultiProviderMismatch {
+    /// The key of the flag.
+    pub flag_key: String,
+
+    /// The value resolved by each provider having the flag, keyed by provider name, in order.
+    pub values: Vec<(String, Value)>,
+}
+
+// ============================================================
+//  MultiProvider
+// ============================================================
+
+/// A provider resolving flags with an ordered list of providers, according to a
+/// [`MultiProviderStrategy`], such as to migrate from a vendor to another gradually by serving
+/// every flag from whichever has it.
+///
+/// The name of the provider a resolution comes from is recorded in the `provider` field of the
+/// flag metadata. The providers are initialized concurrently, and the multi-provider fails to
+/// initialize only if all of them do. Providers failing to initialize, not ready or in a fatal
+/// state are skipped. The events of all the providers are forwarded, and the status is the least
+/// severe of theirs, so that the multi-provider is ready as long as one of its providers is.
+///
+/// ```no_run
+/// use open_feature::provider::{InMemoryProvider, MultiProvider, MultiProviderStrategy};
+///
+/// # let new_vendor = InMemoryProvider::default();
+/// # let old_vendor = InMemoryProvider::default();
+/// let provider = MultiProvider::new(MultiProviderStrategy::FirstMatch)
+///     .with_provider(new_vendor)
+///     .with_provider(old_vendor);
+/// ```
+pub struct MultiProvider {
+    metadata: ProviderMetadata,
+    strategy: MultiProviderStrategy,
+    providers: Vec<Box<dyn FeatureProvider>>,
+    /// The indexes of the providers that failed to initialize.
+    failed: HashSet<usize>,
+    mismatch_handler: Option<MismatchHandler>,
+    events: EventEmitter,
+    forwarders: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl MultiProvider {
+    /// Create a provider without providers, combining their resolutions with `strategy`.
+    pub fn new(strategy: MultiProviderStrategy) -> Self {
+        Self {
+            metadata: ProviderMetadata::new("Multi Provider"),
+            strategy,
+            providers: Vec::new(),
+            failed: HashSet::new(),
+            mismatch_handler: None,
+            events: EventEmitter::default(),
+            forwarders: Mutex::default(),
+        }
+    }
+
+    /// Append `provider`, consulted after the ones added before, and return the multi-provider.
+    #[must_use]
+    pub fn with_provider<P: FeatureProvider>(mut self, provider: P) -> Self {
+        self.add_provider(provider);
+        self
+    }
+
+    /// Append `provider`, consulted after the ones added before.
+    pub fn add_provider<P: FeatureProvider>(&mut self, provider: P) {
+        self.providers.push(Box::new(provider));
+    }
+
+    /// Call `handler` with every mismatch found by the [`MultiProviderStrategy::Comparison`]
+    /// strategy, for example to log it.
+    #[must_use]
+    pub fn with_mismatch_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&MultiProviderMismatch) + Send + Sync + 'static,
+    {
+        self.mismatch_handler = Some(Box::new(handler));
+        self
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let (provider, mut details) = match self.strategy {
+            MultiProviderStrategy::FirstMatch => {
+                self.resolve_first::<T>(flag_key, evaluation_context, |error| {
+                    error.code == EvaluationErrorCode::FlagNotFound
+                })
+                .await?
+            }
+            MultiProviderStrategy::FirstSuccessful => {
+                self.resolve_first::<T>(flag_key, evaluation_context, |_| true)
+                    .await?
+            }
+            MultiProviderStrategy::Comparison => {
+                self.resolve_all_and_compare::<T>(flag_key, evaluation_context)
+                    .await?
+            }
+        };
+
+        details.flag_metadata = Some(
+            details
+                .flag_metadata
+                .unwrap_or_default()
+                .with_value("provider", provider.metadata().name.clone()),
+        );
+
+        Ok(details)
+    }
+
+    /// Return the result of the first available provider not failing with an error to `skip`.
+    async fn resolve_first<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+        skip: impl Fn(&EvaluationError) -> bool,
+    ) -> EvaluationResult<(&dyn FeatureProvider, ResolutionDetails<T>)> {
+        let mut last_error = None;
+
+        for (index, provider) in self.providers.iter().enumerate() {
+            if let Err(error) = self.check_available(index) {
+                last_error = Some(error);
+                continue;
+            }
+
+            match T::resolve(provider.as_ref(), flag_key, evaluation_context).await {
+                Ok(details) => return Ok((provider.as_ref(), details)),
+                Err(error) if skip(&error) => last_error = Some(error),
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| flag_not_found(flag_key)))
+    }
+
+    async fn resolve_all_and_compare<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<(&dyn FeatureProvider, ResolutionDetails<T>)> {
+        let providers: Vec<_> = self
+            .providers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.check_available(*index).is_ok())
+            .map(|(_, provider)| provider.as_ref())
+            .collect();
+
+        let results = join_all(
+            providers
+                .iter()
+                .map(|provider| T::resolve(*provider, flag_key, evaluation_context)),
+        )
+        .await;
+
+        let mut resolutions = Vec::new();
+
+        for (provider, result) in providers.into_iter().zip(results) {
+            match result {
+                Ok(details) => resolutions.push((provider, details)),
+                Err(error) if error.code == EvaluationErrorCode::FlagNotFound => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        let values: Vec<_> = resolutions
+            .iter()
+            .map(|(provider, details)| (provider.metadata().name.clone(), details.value.to_value()))
+            .collect();
+
+        if values.iter().any(|(_, value)| *value != values[0].1) {
+            if let Some(handler) = &self.mismatch_handler {
+                handler(&MultiProviderMismatch {
+                    flag_key: flag_key.to_string(),
+                    values,
+                });
+            }
+        }
+
+        resolutions
+            .into_iter()
+            .next()
+            .ok_or_else(|| flag_not_found(flag_key))
+    }
+
+    /// Fail if the provider at `index` failed to initialize, is not ready or is in a fatal
+    /// state, and should not be consulted.
+    fn check_available(&self, index: usize) -> EvaluationResult<()> {
+        let provider = self.providers[index].as_ref();
+        let code = match self.status_of(index) {
+            ProviderStatus::NotReady => EvaluationErrorCode::ProviderNotReady,
+            ProviderStatus::Fatal => EvaluationErrorCode::ProviderFatal,
+            ProviderStatus::Ready | ProviderStatus::Error | ProviderStatus::STALE => return Ok(()),
+        };
+
+        Err(EvaluationError::builder()
+            .code(code)
+            .message(format!(
+                "Provider \"{}\" is not available",
+                provider.metadata().name
+            ))
+            .build())
+    }
+
+    /// Return the status of the provider at `index`, `NOT_READY` if it failed to initialize.
+    fn status_of(&self, index: usize) -> ProviderStatus {
+        if self.failed.contains(&index) {
+            ProviderStatus::NotReady
+        } else {
+            self.providers[index].status()
+        }
+    }
+}
+
+fn flag_not_found(flag_key: &str) -> EvaluationError {
+    EvaluationError::builder()
+        .code(EvaluationErrorCode::FlagNotFound)
+        .message(format!("No provider has flag \"{}\"", flag_key))
+        .build()
+}
+
+/// Rank `status` from the least to the most severe.
+fn severity(status: ProviderStatus) -> u8 {
+    match status {
+        ProviderStatus::Ready => 0,
+        ProviderStatus::STALE => 1,
+        ProviderStatus::Error => 2,
+        ProviderStatus::NotReady => 3,
+        ProviderStatus::Fatal => 4,
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for MultiProvider {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        let results = join_all(
+            self.providers
+                .iter_mut()
+                .map(|provider| provider.initialize(context)),
+        )
+        .await;
+
+        let errors: Vec<_> = results
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, result)| result.err().map(|error| (index, error)))
+            .collect();
+        self.failed = errors.iter().map(|(index, _)| *index).collect();
+
+        if !errors.is_empty() && errors.len() == self.providers.len() {
+            let message = errors
+                .iter()
+                .map(|(index, error)| {
+                    format!("{}: {}", self.providers[*index].metadata().name, error)
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            let retryable = errors.iter().any(|(_, error)| error.is_retryable());
+            let (_, first_error) = errors.into_iter().next().unwrap();
+
+            return Err(ProviderError::new(
+                first_error.kind.clone(),
+                format!("Every provider failed to initialize: {}", message),
+            )
+            .with_retryable(retryable)
+            .with_source(first_error));
+        }
+
+        let forwarders = self.forwarders.get_mut().unwrap();
+
+        for emitter in self.providers.iter().filter_map(|p| p.event_emitter()) {
+            let mut receiver = emitter.subscribe();
+            let events = self.events.clone();
+
+            forwarders.push(tokio::spawn(async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) => events.emit(event),
+                        Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            }));
+        }
+
+        Ok(())
+    }
+
+    async fn shutdown(&self) {
+        for forwarder in self.forwarders.lock().unwrap().drain(..) {
+            forwarder.abort();
+        }
+
+        for provider in &self.providers {
+            provider.shutdown().await;
+        }
+    }
+
+    fn status(&self) -> ProviderStatus {
+        (0..self.providers.len())
+            .map(|index| self.status_of(index))
+            .min_by_key(|status| severity(*status))
+            .unwrap_or(ProviderStatus::Ready)
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        Some(self.events.clone())
+    }
+
+    /// Track the event with all the providers.
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        join_all(self.providers.iter().map(|provider| {
+            provider.track(event_name, evaluation_context, tracking_event_details)
+        }))
+        .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    /// Merge the flags of all the providers supporting it, earlier providers taking precedence.
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let mut flags = HashMap::new();
+        let mut first_error = None;
+        let mut supported = false;
+
+        for provider in self.providers.iter().rev() {
+            match provider.resolve_all(evaluation_context).await {
+                Ok(provider_flags) => {
+                    flags.extend(provider_flags);
+                    supported = true;
+                }
+                Err(error) => first_error = Some(error),
+            }
+        }
+
+        match first_error {
+            Some(error) if !supported => Err(error),
+            _ => Ok(flags),
+        }
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        flags,
+        provider::{InMemoryFlag, InMemoryProvider, MockFeatureProvider, ProviderEventType},
+        FlagMetadataValue, OpenFeature, ProviderErrorKind,
+    };
+
+    fn old_vendor() -> InMemoryProvider {
+        flags! {
+            "checkout-v2" => bool: false,
+            "tier" => String: "gold",
+        }
+        .with_metadata(ProviderMetadata::new("Old Vendor"))
+    }
+
+    fn new_vendor() -> InMemoryProvider {
+        flags! {
+            "checkout-v2" => bool: true,
+        }
+        .with_metadata(ProviderMetadata::new("New Vendor"))
+    }
+
+    #[tokio::test]
+    async fn first_match() {
+        let mut api = OpenFeature::default();
+        api.set_provider(
+            MultiProvider::new(MultiProviderStrategy::FirstMatch)
+                .with_provider(new_vendor())
+                .with_provider(old_vendor()),
+        )
+        .await
+        .unwrap();
+
+        let client = api.create_client();
+
+        assert!(client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+
+        let details = client.get_string_details("tier", None, None).await.unwrap();
+        assert_eq!(details.value, "gold");
+        assert_eq!(
+            details.flag_metadata.values.get("provider"),
+            Some(&FlagMetadataValue::String("Old Vendor".to_string()))
+        );
+
+        let error = client
+            .get_int_details("missing", None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+    }
+
+    #[tokio::test]
+    async fn first_successful() {
+        let failing = || {
+            let mut provider = MockFeatureProvider::new();
+            provider
+                .expect_metadata()
+                .return_const(ProviderMetadata::new("Failing Vendor"));
+            provider.expect_status().returning(|| ProviderStatus::Error);
+            provider.expect_resolve_string_value().returning(|_, _| {
+                Err(EvaluationError::builder()
+                    .code(EvaluationErrorCode::General("Unreachable".to_string()))
+                    .build())
+            });
+            provider
+        };
+
+        let provider = MultiProvider::new(MultiProviderStrategy::FirstSuccessful)
+            .with_provider(failing())
+            .with_provider(old_vendor());
+
+        let details = provider
+            .resolve_string_value("tier", &EvaluationContext::default())
+            .await
+            .unwrap();
+        assert_eq!(details.value, "gold");
+
+        // The failing provider fails with something else than FLAG_NOT_FOUND.
+        let provider = MultiProvider::new(MultiProviderStrategy::FirstMatch)
+            .with_provider(failing())
+            .with_provider(old_vendor());
+
+        assert!(provider
+            .resolve_string_value("tier", &EvaluationContext::default())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn skip_unavailable_providers() {
+        let unavailable = || {
+            let mut provider = MockFeatureProvider::new();
+            provider
+                .expect_metadata()
+                .return_const(ProviderMetadata::new("Fatal Vendor"));
+            provider.expect_status().returning(|| ProviderStatus::Fatal);
+            provider
+        };
+        let context = EvaluationContext::default();
+
+        let provider = MultiProvider::new(MultiProviderStrategy::FirstMatch)
+            .with_provider(unavailable())
+            .with_provider(old_vendor());
+
+        assert_eq!(provider.status(), ProviderStatus::Ready);
+        assert_eq!(
+            provider
+                .resolve_string_value("tier", &context)
+                .await
+                .unwrap()
+                .value,
+            "gold"
+        );
+
+        let provider =
+            MultiProvider::new(MultiProviderStrategy::FirstMatch).with_provider(unavailable());
+
+        assert_eq!(provider.status(), ProviderStatus::Fatal);
+        assert_eq!(
+            provider
+                .resolve_string_value("tier", &context)
+                .await
+                .unwrap_err()
+                .code,
+            EvaluationErrorCode::ProviderFatal
+        );
+    }
+
+    #[tokio::test]
+    async fn initialize_as_long_as_one_provider_does() {
+        let failing = || {
+            let mut provider = MockFeatureProvider::new();
+            provider
+                .expect_metadata()
+                .return_const(ProviderMetadata::new("Failing Vendor"));
+            provider.expect_initialize().returning(|_| {
+                Err(ProviderError::new(
+                    ProviderErrorKind::Unauthorized,
+                    "Invalid API key",
+                ))
+            });
+            provider.expect_event_emitter().returning(|| None);
+            provider
+        };
+        let context = EvaluationContext::default();
+
+        let mut provider = MultiProvider::new(MultiProviderStrategy::FirstMatch)
+            .with_provider(failing())
+            .with_provider(old_vendor());
+        provider.initialize(&context).await.unwrap();
+
+        assert_eq!(provider.status(), ProviderStatus::Ready);
+        assert_eq!(
+            provider
+                .resolve_string_value("tier", &context)
+                .await
+                .unwrap()
+                .value,
+            "gold"
+        );
+
+        let mut provider = MultiProvider::new(MultiProviderStrategy::FirstMatch)
+            .with_provider(failing())
+            .with_provider(failing());
+        let error = provider.initialize(&context).await.unwrap_err();
+
+        assert_eq!(error.kind, ProviderErrorKind::Unauthorized);
+        assert_eq!(
+            error.message,
+            "Every provider failed to initialize: \
+             Failing Vendor: UNAUTHORIZED: Invalid API key; \
+             Failing Vendor: UNAUTHORIZED: Invalid API key"
+        );
+        assert_eq!(provider.status(), ProviderStatus::NotReady);
+    }
+
+    #[tokio::test]
+    async fn comparison() {
+        let mismatches = Arc::new(Mutex::new(Vec::new()));
+        let recorded = mismatches.clone();
+
+        let provider = MultiProvider::new(MultiProviderStrategy::Comparison)
+            .with_provider(old_vendor())
+            .with_provider(new_vendor())
+            .with_mismatch_handler(move |mismatch| recorded.lock().unwrap().push(mismatch.clone()));
+
+        let context = EvaluationContext::default();
+
+        assert!(
+            !provider
+                .resolve_bool_value("checkout-v2", &context)
+                .await
+                .unwrap()
+                .value
+        );
+        assert_eq!(
+            provider
+                .resolve_string_value("tier", &context)
+                .await
+                .unwrap()
+                .value,
+            "gold"
+        );
+
+        assert_eq!(
+            *mismatches.lock().unwrap(),
+            vec![MultiProviderMismatch {
+                flag_key: "checkout-v2".to_string(),
+                values: vec![
+                    ("Old Vendor".to_string(), Value::Bool(false)),
+                    ("New Vendor".to_string(), Value::Bool(true)),
+                ],
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn forward_events() {
+        let vendor = new_vendor();
+        let mut provider = MultiProvider::new(MultiProviderStrategy::FirstMatch)
+            .with_provider(old_vendor())
+            .with_provider(vendor.clone());
+
+        provider
+            .initialize(&EvaluationContext::default())
+            .await
+            .unwrap();
+
+        let mut events = provider.event_emitter().unwrap().subscribe();
+        vendor.set_flag("tier", InMemoryFlag::with_value("silver"));
+
+        let event = events.recv().await.unwrap();
+        assert_eq!(event.event_type, ProviderEventType::ConfigurationChanged);
+        assert!(event.affects("tier"));
+
+        provider.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn resolve_all() {
+        let provider = MultiProvider::new(MultiProviderStrategy::FirstMatch)
+            .with_provider(new_vendor())
+            .with_provider(old_vendor());
+
+        let flags = provider
+            .resolve_all(&EvaluationContext::default())
+            .await
+            .unwrap();
+
+        assert_eq!(flags.len(), 2);
+        assert_eq!(flags["checkout-v2"].value, Value::Bool(true));
+    }
+}
@@ -0,0 +1,247 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{EvaluationContext, EvaluationResult, ProviderError, StructValue, Value};
+
+use super::{
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+// ============================================================
+//  NamespaceProvider
+// ============================================================
+
+/// A provider that delegates to `inner` with flag keys prefixed by a namespace, such as
+/// `payments/`, so that several teams can share a backend without key collisions while
+/// application code stays prefix-free.
+///
+/// The prefix is stripped from the flag keys `inner` returns: [`FeatureProvider::resolve_all`]
+/// only returns the flags of the namespace, and `PROVIDER_CONFIGURATION_CHANGED` events only
+/// list them.
+///
+/// ```no_run
+/// use open_feature::{
+///     provider::{InMemoryProvider, NamespaceProvider},
+///     OpenFeature,
+/// };
+///
+/// # async fn example() -> Result<(), open_feature::SdkError> {
+/// let provider = NamespaceProvider::new(InMemoryProvider::default(), "payments/");
+///
+/// let mut api = OpenFeature::singleton_mut().await;
+/// api.set_provider(provider).await?;
+///
+/// // Resolves "payments/new-checkout".
+/// let client = api.create_client();
+/// let enabled = client.get_bool_value("new-checkout", None, None).await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct NamespaceProvider<P> {
+    inner: P,
+    prefix: String,
+    events: Mutex<Option<EventEmitter>>,
+}
+
+impl<P: FeatureProvider> NamespaceProvider<P> {
+    /// Create a provider delegating to `inner` with flag keys prefixed by `prefix`.
+    pub fn new(inner: P, prefix: impl Into<String>) -> Self {
+        Self {
+            inner,
+            prefix: prefix.into(),
+            events: Mutex::new(None),
+        }
+    }
+
+    /// Return `flag_key` prefixed by the namespace.
+    pub fn prefixed(&self, flag_key: &str) -> String {
+        format!("{}{}", self.prefix, flag_key)
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        T::resolve(&self.inner, &self.prefixed(flag_key), evaluation_context).await
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for NamespaceProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.inner.initialize(context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    /// Forward the events of `inner` with the flag keys stripped of the prefix, dropping the
+    /// configuration changes outside of the namespace.
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        let mut events = self.events.lock().unwrap();
+
+        if events.is_none() {
+            let mut receiver = self.inner.event_emitter()?.subscribe();
+            let emitter = EventEmitter::default();
+            let prefix = self.prefix.clone();
+
+            *events = Some(emitter.clone());
+
+            tokio::spawn(async move {
+                loop {
+                    let mut event = match receiver.recv().await {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    };
+
+                    if let Some(flags_changed) = event.flags_changed {
+                        let flags_changed: Vec<_> = flags_changed
+                            .iter()
+                            .filter_map(|flag_key| flag_key.strip_prefix(&prefix))
+                            .map(ToString::to_string)
+                            .collect();
+
+                        if flags_changed.is_empty() {
+                            continue;
+                        }
+
+                        event.flags_changed = Some(flags_changed);
+                    }
+
+                    emitter.emit(event);
+                }
+            });
+        }
+
+        events.clone()
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        Ok(self
+            .inner
+            .resolve_all(evaluation_context)
+            .await?
+            .into_iter()
+            .filter_map(|(flag_key, details)| {
+                Some((flag_key.strip_prefix(&self.prefix)?.to_string(), details))
+            })
+            .collect())
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{flags, provider::InMemoryFlag, OpenFeature};
+
+    fn create_inner() -> crate::provider::InMemoryProvider {
+        flags! {
+            "payments/new-checkout" => bool: true,
+            "search/new-checkout" => bool: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_in_namespace() {
+        let provider = NamespaceProvider::new(create_inner(), "payments/");
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider).await.unwrap();
+
+        let client = api.create_client();
+
+        assert!(client
+            .get_bool_value("new-checkout", None, None)
+            .await
+            .unwrap());
+        assert!(client
+            .get_bool_value("payments/new-checkout", None, None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn strip_prefix() {
+        let inner = create_inner();
+        let provider = NamespaceProvider::new(inner.clone(), "payments/");
+
+        let flags = provider
+            .resolve_all(&EvaluationContext::default())
+            .await
+            .unwrap();
+        assert_eq!(flags.keys().collect::<Vec<_>>(), vec!["new-checkout"]);
+
+        let mut events = provider.event_emitter().unwrap().subscribe();
+
+        inner.set_flag("search/new-checkout", InMemoryFlag::with_value(true));
+        inner.set_flag("payments/new-checkout", InMemoryFlag::with_value(false));
+
+        let event = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.flags_changed, Some(vec!["new-checkout".to_string()]));
+    }
+}
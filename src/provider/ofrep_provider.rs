@@ -0,0 +1,992 @@
+use std::{
+    collections::HashMap,
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use serde_json::{json, Map, Value as JsonValue};
+use tokio::task::JoinHandle;
+
+use crate::{
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationReason, EvaluationResult,
+    FlagMetadata, ProviderError, ProviderErrorKind, StructValue, Value,
+};
+
+use super::{
+    polling_scheduler::backoff, EventEmitter, FeatureProvider, FlagType, FlagValue,
+    PollingScheduler, PollingTask, ProviderEvent, ProviderEventType, ProviderMetadata,
+    ResolutionDetails,
+};
+
+/// The delay before reconnecting to a lost event stream, doubled with every failed attempt.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// The maximum delay before reconnecting to a lost event stream.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// The time after which an event stream sending nothing, not even a comment, is deemed lost.
+const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+// ============================================================
+//  OfrepProvider
+// ============================================================
+
+/// A provider evaluating flags remotely through the
+/// [OpenFeature Remote Evaluation Protocol](https://github.com/open-feature/protocol), so that
+/// any compliant flag backend can be used with only a base URL and headers.
+///
+/// Single flags are evaluated with the single evaluation endpoint, while
+/// [`FeatureProvider::resolve_all`] and [`FeatureProvider::resolve_bulk`] use the bulk evaluation
+/// endpoint, sending the ETag of the
+/// previous response to only download changed configurations.
+///
+/// With a polling interval, the flags are fetched in bulk for the evaluation context the
+/// provider is initialized with, and resolutions for that context are served from the fetched
+/// flags. `PROVIDER_CONFIGURATION_CHANGED` events are emitted whenever polled flags change.
+///
+/// So that changes propagate within seconds rather than a polling interval, the provider can
+/// also listen to a stream of server-sent events notifying changes, in which case the flags are
+/// only polled while the stream is lost.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use open_feature::provider::OfrepProvider;
+///
+/// let provider = OfrepProvider::new("https://flags.example.com")
+///     .with_header("Authorization", "Bearer secret")
+///     .with_polling_interval(Duration::from_secs(30))
+///     .with_event_stream("/ofrep/v1/flags/changes");
+/// ```
+pub struct OfrepProvider {
+    metadata: ProviderMetadata,
+    api: OfrepApi,
+    polling_interval: Option<Duration>,
+    scheduler: PollingScheduler,
+    polling_task: Option<PollingTask>,
+    stream_url: Option<String>,
+    stream_task: Option<StreamTask>,
+    events: EventEmitter,
+}
+
+impl OfrepProvider {
+    /// The time after which a request is abandoned by default.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// The interval the flags are polled at while the event stream is lost, unless a polling
+    /// interval is set.
+    pub const DEFAULT_FALLBACK_POLLING_INTERVAL: Duration = Duration::from_secs(30);
+
+    /// Create a provider evaluating flags with the OFREP backend at `base_url`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            metadata: ProviderMetadata::new("OFREP Provider"),
+            api: OfrepApi {
+                client: http_client(Self::DEFAULT_TIMEOUT),
+                stream_client: stream_client(Self::DEFAULT_TIMEOUT),
+                base_url: base_url.into().trim_end_matches('/').to_string(),
+                headers: Vec::new(),
+                bulk: Arc::new(Mutex::new(None)),
+                retry_after: Arc::new(Mutex::new(None)),
+            },
+            polling_interval: None,
+            scheduler: PollingScheduler::global().clone(),
+            polling_task: None,
+            stream_url: None,
+            stream_task: None,
+            events: EventEmitter::default(),
+        }
+    }
+
+    /// Send header `name` with every request, such as for authentication.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.api.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Abandon requests taking longer than `timeout`.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.api.client = http_client(timeout);
+        self.api.stream_client = stream_client(timeout);
+        self
+    }
+
+    /// Fetch the flags in bulk every `interval` once initialized, through the global
+    /// [`PollingScheduler`] unless another one is set.
+    #[must_use]
+    pub fn with_polling_interval(mut self, interval: Duration) -> Self {
+        self.polling_interval = Some(interval);
+        self
+    }
+
+    /// Poll through `scheduler` instead of the global [`PollingScheduler`].
+    #[must_use]
+    pub fn with_scheduler(mut self, scheduler: PollingScheduler) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Listen to the server-sent events at `url` once initialized, a path being relative to the
+    /// base URL, and fetch the flags in bulk on every event.
+    ///
+    /// The stream is reconnected when lost, and the flags are polled in the meantime, every
+    /// [`Self::DEFAULT_FALLBACK_POLLING_INTERVAL`] unless a polling interval is set.
+    #[must_use]
+    pub fn with_event_stream(mut self, url: impl Into<String>) -> Self {
+        let url = url.into();
+
+        self.stream_url = Some(if url.starts_with('/') {
+            format!("{}{}", self.api.base_url, url)
+        } else {
+            url
+        });
+        self
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let context = JsonValue::from(evaluation_context);
+
+        let details = match self.polled_flag(flag_key, &context) {
+            Some(details) => details,
+            None => self.api.evaluate(flag_key, &context).await?,
+        };
+
+        let value = match (T::FLAG_TYPE, details.value) {
+            #[allow(clippy::cast_precision_loss)]
+            (FlagType::Float, Value::Int(value)) => Value::Float(value as f64),
+            (_, value) => value,
+        };
+
+        Ok(ResolutionDetails {
+            value: T::from_value(value).ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::TypeMismatch)
+                    .message(format!("Expected a {:?} flag", T::FLAG_TYPE))
+                    .build()
+            })?,
+            variant: details.variant,
+            reason: details.reason,
+            flag_metadata: details.flag_metadata,
+        })
+    }
+
+    /// Return the polled details of `flag_key`, if polling for `context`.
+    fn polled_flag(&self, flag_key: &str, context: &JsonValue) -> Option<ResolutionDetails<Value>> {
+        self.polling_task.as_ref()?;
+
+        let bulk = self.api.bulk.lock().unwrap();
+        let bulk = bulk.as_ref().filter(|bulk| bulk.context == *context)?;
+
+        bulk.flags.get(flag_key).cloned()
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for OfrepProvider {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        let interval = match (self.polling_interval, &self.stream_url) {
+            (Some(interval), _) => interval,
+            (None, Some(_)) => Self::DEFAULT_FALLBACK_POLLING_INTERVAL,
+            (None, None) => return Ok(()),
+        };
+
+        let context = JsonValue::from(context);
+
+        match self.api.refresh(&context).await {
+            Err(error) if !error.is_retryable() => return Err(error),
+            // A transient failure is retried by the next poll.
+            _ => {}
+        }
+
+        let refresher = Refresher {
+            api: self.api.clone(),
+            events: self.events.clone(),
+            provider_name: self.metadata.name.clone(),
+            context,
+            failing: Arc::default(),
+        };
+        let streaming = Arc::new(AtomicBool::new(false));
+
+        self.polling_task = Some(self.scheduler.schedule(interval, {
+            let refresher = refresher.clone();
+            let streaming = streaming.clone();
+
+            move || {
+                let refresher = refresher.clone();
+                let streaming = streaming.load(Ordering::Relaxed);
+
+                async move {
+                    if streaming {
+                        return Ok(());
+                    }
+
+                    refresher.refresh().await
+                }
+            }
+        }));
+
+        if let Some(url) = &self.stream_url {
+            self.stream_task = Some(StreamTask(tokio::spawn(listen(
+                url.clone(),
+                refresher,
+                streaming,
+            ))));
+        }
+
+        Ok(())
+    }
+
+    async fn shutdown(&self) {
+        if let Some(polling_task) = &self.polling_task {
+            polling_task.cancel();
+        }
+        if let Some(stream_task) = &self.stream_task {
+            stream_task.0.abort();
+        }
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        Some(self.events.clone())
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    /// Evaluate all the flags with the bulk evaluation endpoint. Flags failing to evaluate are
+    /// left out.
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.api
+            .evaluate_all(&JsonValue::from(evaluation_context))
+            .await
+    }
+
+    /// Flags left out of the bulk evaluation response are not found.
+    async fn resolve_bulk(
+        &self,
+        flags: &[(String, FlagType)],
+        evaluation_context: &EvaluationContext,
+    ) -> HashMap<String, EvaluationResult<ResolutionDetails<Value>>> {
+        let result = self
+            .api
+            .evaluate_all(&JsonValue::from(evaluation_context))
+            .await;
+
+        flags
+            .iter()
+            .map(|(flag_key, _)| {
+                let details = match &result {
+                    Ok(evaluated) => evaluated.get(flag_key).cloned().ok_or_else(|| {
+                        EvaluationError::builder()
+                            .code(EvaluationErrorCode::FlagNotFound)
+                            .message(format!("Flag \"{}\" is not defined", flag_key))
+                            .build()
+                    }),
+                    Err(error) => Err(error.clone()),
+                };
+
+                (flag_key.clone(), details)
+            })
+            .collect()
+    }
+}
+
+// ============================================================
+//  Refreshes
+// ============================================================
+
+/// Fetches the flags in bulk for the evaluation context of the provider, and emits the changes.
+#[derive(Clone)]
+struct Refresher {
+    api: OfrepApi,
+    events: EventEmitter,
+    provider_name: String,
+    context: JsonValue,
+    failing: Arc<AtomicBool>,
+}
+
+impl Refresher {
+    /// Refresh the flags, emitting `PROVIDER_ERROR` on failure and `PROVIDER_READY` on recovery.
+    async fn refresh(&self) -> Result<(), ProviderError> {
+        let flags_changed = match self.api.refresh(&self.context).await {
+            Ok(flags_changed) => flags_changed,
+            Err(error) => {
+                self.failing.store(true, Ordering::Relaxed);
+                self.events.emit(
+                    ProviderEvent::builder()
+                        .event_type(ProviderEventType::Error)
+                        .provider_name(self.provider_name.clone())
+                        .message(error.to_string())
+                        .build(),
+                );
+                return Err(error);
+            }
+        };
+
+        if self.failing.swap(false, Ordering::Relaxed) {
+            self.events.emit(
+                ProviderEvent::builder()
+                    .event_type(ProviderEventType::Ready)
+                    .provider_name(self.provider_name.clone())
+                    .build(),
+            );
+        }
+
+        if !flags_changed.is_empty() {
+            self.events.emit(
+                ProviderEvent::builder()
+                    .event_type(ProviderEventType::ConfigurationChanged)
+                    .provider_name(self.provider_name.clone())
+                    .flags_changed(flags_changed)
+                    .build(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// A task listening to an event stream, aborted when dropped.
+struct StreamTask(JoinHandle<()>);
+
+impl Drop for StreamTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Listen to the event stream at `url` until aborted, refreshing the flags on every event and
+/// once connected, as changes might have been missed in the meantime. The stream is reconnected
+/// when lost, and `streaming` is set while it is up.
+async fn listen(url: String, refresher: Refresher, streaming: Arc<AtomicBool>) {
+    let mut last_event_id = None;
+    let mut failures = 0;
+
+    loop {
+        if let Ok(mut response) = refresher.api.connect(&url, last_event_id.as_deref()).await {
+            streaming.store(true, Ordering::Relaxed);
+            failures = 0;
+
+            let _ = refresher.refresh().await;
+
+            let mut parser = EventStreamParser::default();
+
+            while let Ok(Ok(Some(chunk))) =
+                tokio::time::timeout(STREAM_IDLE_TIMEOUT, response.chunk()).await
+            {
+                let events = parser.push(&chunk);
+
+                if let Some(id) = events.iter().rev().find_map(|event| event.id.clone()) {
+                    last_event_id = Some(id);
+                }
+                if !events.is_empty() {
+                    let _ = refresher.refresh().await;
+                }
+            }
+
+            streaming.store(false, Ordering::Relaxed);
+        }
+
+        tokio::time::sleep(backoff(RECONNECT_DELAY, failures, MAX_RECONNECT_DELAY)).await;
+        failures = failures.saturating_add(1);
+    }
+}
+
+// ============================================================
+//  OfrepApi
+// ============================================================
+
+/// The flags last fetched in bulk.
+struct BulkEvaluation {
+    context: JsonValue,
+    etag: Option<String>,
+    flags: HashMap<String, ResolutionDetails<Value>>,
+}
+
+/// The HTTP side of [`OfrepProvider`], shared with its polling task.
+#[derive(Clone)]
+struct OfrepApi {
+    client: reqwest::Client,
+    stream_client: reqwest::Client,
+    base_url: String,
+    headers: Vec<(String, String)>,
+    bulk: Arc<Mutex<Option<BulkEvaluation>>>,
+    retry_after: Arc<Mutex<Option<Instant>>>,
+}
+
+impl OfrepApi {
+    /// Evaluate `flag_key` with the single evaluation endpoint.
+    async fn evaluate(
+        &self,
+        flag_key: &str,
+        context: &JsonValue,
+    ) -> EvaluationResult<ResolutionDetails<Value>> {
+        let url = format!("{}/ofrep/v1/evaluate/flags/{}", self.base_url, flag_key);
+        let response = self.post(&url, context, None).await?;
+
+        match response.status().as_u16() {
+            200 | 400 | 404 => {
+                let not_found = response.status().as_u16() == 404;
+
+                match response.json::<JsonValue>().await {
+                    Ok(body) => parse_evaluation(&body),
+                    Err(_) if not_found => Err(EvaluationError::builder()
+                        .code(EvaluationErrorCode::FlagNotFound)
+                        .message(format!("Flag \"{}\" is not defined", flag_key))
+                        .build()),
+                    Err(error) => Err(invalid_response(error)),
+                }
+            }
+            _ => Err(self.status_error(&response).into()),
+        }
+    }
+
+    /// Evaluate all the flags with the bulk evaluation endpoint, reusing the previous result if
+    /// unchanged.
+    async fn evaluate_all(
+        &self,
+        context: &JsonValue,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.refresh(context).await?;
+
+        let bulk = self.bulk.lock().unwrap();
+
+        Ok(bulk
+            .as_ref()
+            .map(|bulk| bulk.flags.clone())
+            .unwrap_or_default())
+    }
+
+    /// Fetch all the flags with the bulk evaluation endpoint, and return the keys of the flags
+    /// that changed since the previous fetch.
+    async fn refresh(&self, context: &JsonValue) -> Result<Vec<String>, ProviderError> {
+        let url = format!("{}/ofrep/v1/evaluate/flags", self.base_url);
+        let etag = self
+            .bulk
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|bulk| bulk.context == *context)
+            .and_then(|bulk| bulk.etag.clone());
+
+        let response = self.post(&url, context, etag.as_deref()).await?;
+
+        match response.status().as_u16() {
+            200 => {}
+            304 => return Ok(Vec::new()),
+            _ => return Err(self.status_error(&response)),
+        }
+
+        let etag = response
+            .headers()
+            .get("etag")
+            .and_then(|etag| etag.to_str().ok())
+            .map(ToString::to_string);
+        let body = response.json::<JsonValue>().await.map_err(|error| {
+            ProviderError::new(ProviderErrorKind::InvalidResponse, error.to_string())
+                .with_source(error)
+        })?;
+
+        let flags = parse_bulk_evaluation(&body)?;
+
+        let mut bulk = self.bulk.lock().unwrap();
+        let flags_changed = changed_flags(
+            bulk.as_ref()
+                .filter(|bulk| bulk.context == *context)
+                .map(|bulk| &bulk.flags),
+            &flags,
+        );
+
+        *bulk = Some(BulkEvaluation {
+            context: context.clone(),
+            etag,
+            flags,
+        });
+
+        Ok(flags_changed)
+    }
+
+    /// Open the event stream at `url`, resuming after `last_event_id` if any.
+    async fn connect(
+        &self,
+        url: &str,
+        last_event_id: Option<&str>,
+    ) -> Result<reqwest::Response, ProviderError> {
+        let mut request = self
+            .stream_client
+            .get(url)
+            .header("Accept", "text/event-stream");
+
+        for (name, value) in &self.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(last_event_id) = last_event_id {
+            request = request.header("Last-Event-ID", last_event_id);
+        }
+
+        let response = request.send().await.map_err(|error| {
+            ProviderError::new(ProviderErrorKind::Network, error.to_string()).with_source(error)
+        })?;
+
+        match response.status().as_u16() {
+            200 => Ok(response),
+            _ => Err(self.status_error(&response)),
+        }
+    }
+
+    async fn post(
+        &self,
+        url: &str,
+        context: &JsonValue,
+        etag: Option<&str>,
+    ) -> Result<reqwest::Response, ProviderError> {
+        let retry_after = *self.retry_after.lock().unwrap();
+        if let Some(retry_after) = retry_after {
+            if Instant::now() < retry_after {
+                return Err(ProviderError::new(
+                    ProviderErrorKind::RateLimited,
+                    "Rate limited by the OFREP backend",
+                ));
+            }
+        }
+
+        let mut request = self.client.post(url).json(&json!({ "context": context }));
+
+        for (name, value) in &self.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(etag) = etag {
+            request = request.header("If-None-Match", etag);
+        }
+
+        request.send().await.map_err(|error| {
+            let kind = if error.is_timeout() {
+                ProviderErrorKind::Timeout
+            } else {
+                ProviderErrorKind::Network
+            };
+
+            ProviderError::new(kind, error.to_string()).with_source(error)
+        })
+    }
+
+    /// Return the error corresponding to the unexpected status of `response`, recording when to
+    /// retry if rate limited.
+    fn status_error(&self, response: &reqwest::Response) -> ProviderError {
+        let status = response.status().as_u16();
+
+        let kind = match status {
+            401 | 403 => ProviderErrorKind::Unauthorized,
+            429 => {
+                let retry_after = response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|retry_after| retry_after.to_str().ok())
+                    .and_then(|retry_after| retry_after.parse().ok())
+                    .map(Duration::from_secs);
+
+                if let Some(retry_after) = retry_after {
+                    *self.retry_after.lock().unwrap() = Some(Instant::now() + retry_after);
+                }
+
+                ProviderErrorKind::RateLimited
+            }
+            500..=599 => ProviderErrorKind::Unavailable,
+            _ => ProviderErrorKind::InvalidResponse,
+        };
+
+        ProviderError::new(kind, format!("The OFREP backend answered with {}", status))
+    }
+}
+
+fn http_client(timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .expect("The HTTP client can be built")
+}
+
+/// Return a client for event streams, which are only bounded by `connect_timeout` as they stay
+/// open.
+fn stream_client(connect_timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(connect_timeout)
+        .build()
+        .expect("The HTTP client can be built")
+}
+
+fn invalid_response(error: reqwest::Error) -> EvaluationError {
+    ProviderError::new(ProviderErrorKind::InvalidResponse, error.to_string())
+        .with_source(error)
+        .into()
+}
+
+// ============================================================
+//  Payloads
+// ============================================================
+
+/// Parse the body of a single evaluation, or of a flag of a bulk evaluation.
+fn parse_evaluation(body: &JsonValue) -> EvaluationResult<ResolutionDetails<Value>> {
+    if let Some(error_code) = body.get("errorCode").and_then(JsonValue::as_str) {
+        return Err(EvaluationError {
+            code: parse_error_code(error_code),
+            message: body
+                .get("errorDetails")
+                .and_then(JsonValue::as_str)
+                .map(ToString::to_string),
+        });
+    }
+
+    let value = body.get("value").ok_or_else(|| {
+        EvaluationError::builder()
+            .code(EvaluationErrorCode::ParseError)
+            .message("The evaluation has no value")
+            .build()
+    })?;
+
+    let flag_metadata = body
+        .get("metadata")
+        .and_then(JsonValue::as_object)
+        .map(parse_metadata)
+        .filter(|flag_metadata| !flag_metadata.values.is_empty());
+
+    Ok(ResolutionDetails {
+        value: Value::try_from(value)?,
+        variant: body
+            .get("variant")
+            .and_then(JsonValue::as_str)
+            .map(ToString::to_string),
+        reason: body
+            .get("reason")
+            .and_then(JsonValue::as_str)
+            .map(parse_reason),
+        flag_metadata,
+    })
+}
+
+/// Parse the body of a bulk evaluation, leaving out the flags failing to evaluate.
+fn parse_bulk_evaluation(
+    body: &JsonValue,
+) -> Result<HashMap<String, ResolutionDetails<Value>>, ProviderError> {
+    let flags = body
+        .get("flags")
+        .and_then(JsonValue::as_array)
+        .ok_or_else(|| {
+            ProviderError::new(
+                ProviderErrorKind::InvalidResponse,
+                "The bulk evaluation has no flags",
+            )
+        })?;
+
+    Ok(flags
+        .iter()
+        .filter_map(|flag| {
+            let key = flag.get("key")?.as_str()?;
+            Some((key.to_string(), parse_evaluation(flag).ok()?))
+        })
+        .collect())
+}
+
+fn parse_error_code(error_code: &str) -> EvaluationErrorCode {
+    match error_code {
+        "PROVIDER_NOT_READY" => EvaluationErrorCode::ProviderNotReady,
+        "PROVIDER_FATAL" => EvaluationErrorCode::ProviderFatal,
+        "FLAG_NOT_FOUND" => EvaluationErrorCode::FlagNotFound,
+        "PARSE_ERROR" => EvaluationErrorCode::ParseError,
+        "TYPE_MISMATCH" => EvaluationErrorCode::TypeMismatch,
+        "TARGETING_KEY_MISSING" => EvaluationErrorCode::TargetingKeyMissing,
+        "INVALID_CONTEXT" => EvaluationErrorCode::InvalidContext,
+        error_code => EvaluationErrorCode::General(error_code.to_string()),
+    }
+}
+
+fn parse_reason(reason: &str) -> EvaluationReason {
+    match reason {
+        "STATIC" => EvaluationReason::Static,
+        "DEFAULT" => EvaluationReason::Default,
+        "TARGETING_MATCH" => EvaluationReason::TargetingMatch,
+        "SPLIT" => EvaluationReason::Split,
+        "CACHED" => EvaluationReason::Cached,
+        "DISABLED" => EvaluationReason::Disabled,
+        "UNKNOWN" => EvaluationReason::Unknown,
+        "ERROR" => EvaluationReason::Error,
+        reason => EvaluationReason::Other(reason.to_string()),
+    }
+}
+
+/// Parse flag metadata, leaving out the values that are not booleans, numbers or strings.
+fn parse_metadata(metadata: &Map<String, JsonValue>) -> FlagMetadata {
+    let mut flag_metadata = FlagMetadata::default();
+
+    for (key, value) in metadata {
+        match value {
+            JsonValue::Bool(value) => flag_metadata.add_value(key, *value),
+            JsonValue::Number(value) => match (value.as_i64(), value.as_f64()) {
+                (Some(value), _) => flag_metadata.add_value(key, value),
+                (None, Some(value)) => flag_metadata.add_value(key, value),
+                (None, None) => {}
+            },
+            JsonValue::String(value) => flag_metadata.add_value(key, value.as_str()),
+            _ => {}
+        }
+    }
+
+    flag_metadata
+}
+
+/// Return the sorted keys of the flags that are added, removed or changed in `current`.
+fn changed_flags(
+    previous: Option<&HashMap<String, ResolutionDetails<Value>>>,
+    current: &HashMap<String, ResolutionDetails<Value>>,
+) -> Vec<String> {
+    let Some(previous) = previous else {
+        return current.keys().cloned().collect();
+    };
+
+    let mut flags_changed: Vec<_> = current
+        .iter()
+        .filter(|(key, details)| {
+            previous.get(*key).map_or(true, |previous| {
+                previous.value != details.value || previous.variant != details.variant
+            })
+        })
+        .map(|(key, _)| key.clone())
+        .chain(
+            previous
+                .keys()
+                .filter(|key| !current.contains_key(*key))
+                .cloned(),
+        )
+        .collect();
+
+    flags_changed.sort();
+    flags_changed
+}
+
+// ============================================================
+//  Event streams
+// ============================================================
+
+/// A server-sent event, of which only the ID matters, as events only notify that the flags
+/// changed.
+#[derive(Clone, Default, PartialEq, Debug)]
+struct StreamEvent {
+    id: Option<String>,
+}
+
+/// Splits the chunks of an event stream into events, following the
+/// [specification](https://html.spec.whatwg.org/multipage/server-sent-events.html).
+#[derive(Default)]
+struct EventStreamParser {
+    buffer: Vec<u8>,
+    event: StreamEvent,
+    pending: bool,
+}
+
+impl EventStreamParser {
+    /// Add `chunk` to the stream, and return the events it completes.
+    fn push(&mut self, chunk: &[u8]) -> Vec<StreamEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if mem::take(&mut self.pending) {
+                    events.push(mem::take(&mut self.event));
+                }
+                continue;
+            }
+
+            let (field, value) = line.split_once(':').map_or((line, ""), |(field, value)| {
+                (field, value.strip_prefix(' ').unwrap_or(value))
+            });
+
+            // Comments, such as heartbeats, have an empty field name and are ignored like
+            // unknown fields.
+            match field {
+                "id" => self.event.id = Some(value.to_string()),
+                "event" | "data" => {}
+                _ => continue,
+            }
+
+            self.pending = true;
+        }
+
+        events
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EvaluationContextFieldValue;
+
+    #[test]
+    fn parse_success() {
+        let details = parse_evaluation(&json!({
+            "key": "discount",
+            "value": 0.1,
+            "reason": "TARGETING_MATCH",
+            "variant": "ten-percent",
+            "metadata": { "team": "growth", "version": 3, "nested": {} }
+        }))
+        .unwrap();
+
+        assert_eq!(details.value, Value::Float(0.1));
+        assert_eq!(details.reason, Some(EvaluationReason::TargetingMatch));
+        assert_eq!(details.variant, Some("ten-percent".to_string()));
+        assert_eq!(
+            details.flag_metadata,
+            Some(
+                FlagMetadata::default()
+                    .with_value("team", "growth")
+                    .with_value("version", 3)
+            )
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let error = parse_evaluation(&json!({
+            "key": "discount",
+            "errorCode": "TARGETING_KEY_MISSING",
+            "errorDetails": "A targeting key is required"
+        }))
+        .unwrap_err();
+
+        assert_eq!(error.code, EvaluationErrorCode::TargetingKeyMissing);
+        assert_eq!(
+            error.message,
+            Some("A targeting key is required".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_bulk() {
+        let flags = parse_bulk_evaluation(&json!({
+            "flags": [
+                { "key": "checkout-v2", "value": true, "reason": "STATIC" },
+                { "key": "discount", "errorCode": "PARSE_ERROR" }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(flags.len(), 1);
+        assert_eq!(flags["checkout-v2"].value, Value::Bool(true));
+
+        let error = parse_bulk_evaluation(&json!({})).unwrap_err();
+        assert_eq!(error.kind, ProviderErrorKind::InvalidResponse);
+    }
+
+    #[test]
+    fn convert_context() {
+        let context = EvaluationContext::default()
+            .with_targeting_key("alice")
+            .with_custom_field("age", 42)
+            .with_custom_field("opaque", EvaluationContextFieldValue::new_struct(()));
+
+        assert_eq!(
+            JsonValue::from(&context),
+            json!({ "targetingKey": "alice", "age": 42 })
+        );
+    }
+
+    #[test]
+    fn detect_changed_flags() {
+        let flags = |values: &[(&str, bool)]| -> HashMap<_, _> {
+            values
+                .iter()
+                .map(|(key, value)| ((*key).to_string(), ResolutionDetails::new(*value)))
+                .collect()
+        };
+
+        let previous = flags(&[("a", true), ("b", true), ("c", true)]);
+        let current = flags(&[("a", true), ("b", false), ("d", true)]);
+
+        assert_eq!(
+            changed_flags(Some(&previous), &current),
+            vec!["b".to_string(), "c".to_string(), "d".to_string()]
+        );
+        assert!(changed_flags(Some(&current), &current).is_empty());
+    }
+
+    #[test]
+    fn parse_event_stream() {
+        let mut parser = EventStreamParser::default();
+
+        assert_eq!(parser.push(b": heartbeat\n\n"), []);
+        assert_eq!(
+            parser.push(b"event: configuration_change\r\ndata: {}\r\n\r\ndata"),
+            [StreamEvent::default()]
+        );
+        assert_eq!(
+            parser.push(b": {}\nid: 42\n\n"),
+            [StreamEvent {
+                id: Some("42".to_string())
+            }]
+        );
+    }
+}
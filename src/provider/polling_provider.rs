@@ -0,0 +1,526 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use arc_swap::ArcSwapOption;
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+
+use crate::{hash::hex, FlagMetadata, ProviderError};
+
+use super::{EventEmitter, PollingScheduler, PollingTask, ProviderEvent, ProviderEventType};
+
+// ============================================================
+//  PollingSource
+// ============================================================
+
+/// The remote side of a [`PollingProvider`]: where the flag configuration is fetched from, and
+/// how it is parsed.
+#[async_trait]
+pub trait PollingSource: Send + Sync + 'static {
+    /// The parsed flag configuration.
+    type Configuration: Send + Sync + 'static;
+
+    /// Fetch the flag configuration, sending `etag` if the backend supports conditional
+    /// requests, so that it can answer that nothing changed.
+    async fn fetch(&self, etag: Option<&str>) -> Result<Fetched, ProviderError>;
+
+    /// Parse a fetched `payload`.
+    fn parse(&self, payload: &[u8]) -> Result<Self::Configuration, ProviderError>;
+
+    /// Return the keys of the flags that changed from `previous` to `current`, or `None` if any
+    /// flag might have changed, which is the default.
+    ///
+    /// No event is emitted when no flag changed.
+    fn flags_changed(
+        &self,
+        _previous: &Self::Configuration,
+        _current: &Self::Configuration,
+    ) -> Option<Vec<String>> {
+        None
+    }
+}
+
+/// The result of [`PollingSource::fetch`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Fetched {
+    /// The configuration did not change since the sent ETag.
+    NotModified,
+
+    /// The configuration, with its ETag if the backend sent one.
+    Payload {
+        /// The raw configuration, as sent by the backend.
+        payload: Vec<u8>,
+
+        /// The ETag to send with the next fetch.
+        etag: Option<String>,
+    },
+}
+
+// ============================================================
+//  PollingProvider
+// ============================================================
+
+/// The polling loop of a remote provider, so that provider authors only implement how to fetch
+/// and parse their flag configuration with a [`PollingSource`].
+///
+/// Once started, the configuration is fetched every interval through a [`PollingScheduler`], with
+/// [`Self::DEFAULT_JITTER`] by default so that a fleet of instances does not poll in lockstep.
+/// Payloads are only parsed when they changed: the backend can answer that nothing changed since
+/// the last ETag, and identical payloads are detected with their hash. A
+/// `PROVIDER_CONFIGURATION_CHANGED` event is emitted whenever a new configuration replaces
+/// another one, a `PROVIDER_ERROR` event whenever a poll fails, and a `PROVIDER_READY` event
+/// once polls succeed again.
+///
+/// A provider embeds one, forwards its lifecycle to it, and resolves flags from
+/// [`Self::configuration`]:
+///
+/// ```no_run
+/// use open_feature::{
+///     async_trait,
+///     provider::{EventEmitter, FeatureProvider, PollingProvider, PollingSource},
+///     EvaluationContext, ProviderError,
+/// };
+/// # use open_feature::{
+/// #     provider::{Fetched, ProviderMetadata, ResolutionDetails},
+/// #     EvaluationResult, StructValue,
+/// # };
+/// #
+/// # struct MySource;
+/// #
+/// # #[async_trait]
+/// # impl PollingSource for MySource {
+/// #     type Configuration = Vec<u8>;
+/// #
+/// #     async fn fetch(&self, _etag: Option<&str>) -> Result<Fetched, ProviderError> {
+/// #         unimplemented!()
+/// #     }
+/// #
+/// #     fn parse(&self, payload: &[u8]) -> Result<Self::Configuration, ProviderError> {
+/// #         Ok(payload.to_vec())
+/// #     }
+/// # }
+///
+/// struct MyProvider {
+///     polling: PollingProvider<MySource>,
+///     // ...
+/// #   metadata: ProviderMetadata,
+/// }
+///
+/// #[async_trait]
+/// impl FeatureProvider for MyProvider {
+///     async fn initialize(&mut self, _context: &EvaluationContext) -> Result<(), ProviderError> {
+///         match self.polling.start().await {
+///             Err(error) if !error.is_retryable() => Err(error),
+///             // A transient failure is retried by the next poll.
+///             _ => Ok(()),
+///         }
+///     }
+///
+///     async fn shutdown(&self) {
+///         self.polling.stop();
+///     }
+///
+///     fn event_emitter(&self) -> Option<EventEmitter> {
+///         Some(self.polling.event_emitter())
+///     }
+///
+///     // ...
+/// #   fn metadata(&self) -> &ProviderMetadata {
+/// #       &self.metadata
+/// #   }
+/// #
+/// #   async fn resolve_bool_value(
+/// #       &self,
+/// #       _flag_key: &str,
+/// #       _evaluation_context: &EvaluationContext,
+/// #   ) -> EvaluationResult<ResolutionDetails<bool>> {
+/// #       unimplemented!()
+/// #   }
+/// #
+/// #   async fn resolve_int_value(
+/// #       &self,
+/// #       _flag_key: &str,
+/// #       _evaluation_context: &EvaluationContext,
+/// #   ) -> EvaluationResult<ResolutionDetails<i64>> {
+/// #       unimplemented!()
+/// #   }
+/// #
+/// #   async fn resolve_float_value(
+/// #       &self,
+/// #       _flag_key: &str,
+/// #       _evaluation_context: &EvaluationContext,
+/// #   ) -> EvaluationResult<ResolutionDetails<f64>> {
+/// #       unimplemented!()
+/// #   }
+/// #
+/// #   async fn resolve_string_value(
+/// #       &self,
+/// #       _flag_key: &str,
+/// #       _evaluation_context: &EvaluationContext,
+/// #   ) -> EvaluationResult<ResolutionDetails<String>> {
+/// #       unimplemented!()
+/// #   }
+/// #
+/// #   async fn resolve_struct_value(
+/// #       &self,
+/// #       _flag_key: &str,
+/// #       _evaluation_context: &EvaluationContext,
+/// #   ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+/// #       unimplemented!()
+/// #   }
+/// }
+/// ```
+pub struct PollingProvider<S: PollingSource> {
+    state: Arc<PollingState<S>>,
+    interval: Duration,
+    scheduler: PollingScheduler,
+    task: Option<PollingTask>,
+}
+
+impl<S: PollingSource> PollingProvider<S> {
+    /// The jitter of the polling intervals by default.
+    pub const DEFAULT_JITTER: f64 = PollingScheduler::DEFAULT_JITTER;
+
+    /// Create a loop polling `source` every `interval` once started, emitting events on behalf
+    /// of provider `provider_name`.
+    pub fn new(provider_name: impl Into<String>, source: S, interval: Duration) -> Self {
+        Self {
+            state: Arc::new(PollingState {
+                source,
+                provider_name: provider_name.into(),
+                configuration: ArcSwapOption::empty(),
+                last_fetch: Mutex::new(LastFetch::default()),
+                events: EventEmitter::default(),
+                failing: AtomicBool::new(false),
+            }),
+            interval,
+            scheduler: PollingScheduler::global().clone(),
+            task: None,
+        }
+    }
+
+    /// Poll through `scheduler` instead of the global [`PollingScheduler`].
+    #[must_use]
+    pub fn with_scheduler(mut self, scheduler: PollingScheduler) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Randomly stretch or shrink every interval by up to `jitter` (from `0.0` to `1.0`) of
+    /// itself.
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.scheduler = self.scheduler.with_jitter(jitter);
+        self
+    }
+
+    /// Return the polled source.
+    pub fn source(&self) -> &S {
+        &self.state.source
+    }
+
+    /// Return the last parsed configuration, if any was fetched yet.
+    pub fn configuration(&self) -> Option<Arc<S::Configuration>> {
+        self.state.configuration.load_full()
+    }
+
+    /// Return the emitter of the configuration changes and poll failures.
+    pub fn event_emitter(&self) -> EventEmitter {
+        self.state.events.clone()
+    }
+
+    /// Return the number of consecutive failed polls.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.task
+            .as_ref()
+            .map_or(0, PollingTask::consecutive_failures)
+    }
+
+    /// Fetch the configuration, and keep polling it every interval, until stopped. Polling goes
+    /// on even if the first fetch fails, which is returned.
+    pub async fn start(&mut self) -> Result<(), ProviderError> {
+        self.stop();
+
+        let result = self.state.poll().await;
+
+        let state = self.state.clone();
+
+        self.task = Some(self.scheduler.schedule(self.interval, move || {
+            let state = state.clone();
+
+            async move { state.poll().await }
+        }));
+
+        result
+    }
+
+    /// Stop polling.
+    pub fn stop(&self) {
+        if let Some(task) = &self.task {
+            task.cancel();
+        }
+    }
+
+    /// Fetch the configuration now, such as when notified of a change.
+    pub async fn refresh(&self) -> Result<(), ProviderError> {
+        self.state.poll().await
+    }
+}
+
+/// The state of a [`PollingProvider`], shared with its polling task.
+struct PollingState<S: PollingSource> {
+    source: S,
+    provider_name: String,
+    configuration: ArcSwapOption<S::Configuration>,
+    last_fetch: Mutex<LastFetch>,
+    events: EventEmitter,
+    failing: AtomicBool,
+}
+
+/// What identifies the last parsed payload.
+#[derive(Default)]
+struct LastFetch {
+    etag: Option<String>,
+    digest: Option<String>,
+}
+
+impl<S: PollingSource> PollingState<S> {
+    /// Fetch the configuration, and replace the current one if it changed, emitting
+    /// `PROVIDER_ERROR` on failure and `PROVIDER_READY` on recovery.
+    async fn poll(&self) -> Result<(), ProviderError> {
+        let result = self.fetch().await;
+
+        let event = match &result {
+            Ok(()) if self.failing.swap(false, Ordering::Relaxed) => ProviderEvent::builder()
+                .event_type(ProviderEventType::Ready)
+                .provider_name(self.provider_name.clone())
+                .build(),
+            Ok(()) => return result,
+            Err(error) => {
+                self.failing.store(true, Ordering::Relaxed);
+
+                ProviderEvent::builder()
+                    .event_type(ProviderEventType::Error)
+                    .provider_name(self.provider_name.clone())
+                    .message(error.to_string())
+                    .build()
+            }
+        };
+
+        self.events.emit(event);
+        result
+    }
+
+    /// Fetch the configuration, and replace the current one if it changed.
+    async fn fetch(&self) -> Result<(), ProviderError> {
+        // Also keeps polls from overlapping.
+        let mut last_fetch = self.last_fetch.lock().await;
+
+        let (payload, etag) = match self.source.fetch(last_fetch.etag.as_deref()).await? {
+            Fetched::NotModified => return Ok(()),
+            Fetched::Payload { payload, etag } => (payload, etag),
+        };
+
+        let digest = hex(&Sha256::digest(&payload));
+
+        if last_fetch.digest.as_ref() != Some(&digest) {
+            let configuration = Arc::new(self.source.parse(&payload)?);
+            let previous = self.configuration.swap(Some(configuration.clone()));
+
+            if let Some(previous) = previous {
+                self.changed(&previous, &configuration, &digest);
+            }
+
+            last_fetch.digest = Some(digest);
+        }
+
+        // Only kept once parsed, so that an invalid payload is fetched again.
+        last_fetch.etag = etag;
+
+        Ok(())
+    }
+
+    /// Emit a configuration change from `previous` to `current`, unless no flag changed. The
+    /// `digest` of the current payload is sent as the `configurationHash` metadata.
+    fn changed(&self, previous: &S::Configuration, current: &S::Configuration, digest: &str) {
+        let flags_changed = self.source.flags_changed(previous, current);
+
+        if flags_changed.as_ref().map_or(false, Vec::is_empty) {
+            return;
+        }
+
+        let mut event = ProviderEvent::builder()
+            .event_type(ProviderEventType::ConfigurationChanged)
+            .provider_name(self.provider_name.clone())
+            .event_metadata(FlagMetadata::default().with_value("configurationHash", digest))
+            .build();
+        event.flags_changed = flags_changed;
+
+        self.events.emit(event);
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, VecDeque},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex as StdMutex,
+        },
+    };
+
+    use super::*;
+    use crate::ProviderErrorKind;
+
+    /// Serves scripted fetches of `key=value` lists, and records the sent ETags.
+    #[derive(Default)]
+    struct ScriptedSource {
+        fetches: StdMutex<VecDeque<Fetched>>,
+        etags: StdMutex<Vec<Option<String>>>,
+        parses: AtomicUsize,
+    }
+
+    impl ScriptedSource {
+        fn new(fetches: impl IntoIterator<Item = Fetched>) -> Self {
+            Self {
+                fetches: StdMutex::new(fetches.into_iter().collect()),
+                ..Self::default()
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PollingSource for ScriptedSource {
+        type Configuration = HashMap<String, String>;
+
+        async fn fetch(&self, etag: Option<&str>) -> Result<Fetched, ProviderError> {
+            self.etags.lock().unwrap().push(etag.map(str::to_string));
+
+            self.fetches
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(|| ProviderError::new(ProviderErrorKind::Network, "Unreachable"))
+        }
+
+        fn parse(&self, payload: &[u8]) -> Result<Self::Configuration, ProviderError> {
+            self.parses.fetch_add(1, Ordering::SeqCst);
+
+            std::str::from_utf8(payload)
+                .unwrap()
+                .split(',')
+                .map(|entry| match entry.split_once('=') {
+                    Some((key, value)) => Ok((key.to_string(), value.to_string())),
+                    None => Err(ProviderError::new(
+                        ProviderErrorKind::InvalidResponse,
+                        "Missing =",
+                    )),
+                })
+                .collect()
+        }
+
+        fn flags_changed(
+            &self,
+            previous: &Self::Configuration,
+            current: &Self::Configuration,
+        ) -> Option<Vec<String>> {
+            let mut keys: Vec<_> = previous
+                .keys()
+                .chain(current.keys())
+                .filter(|key| previous.get(*key) != current.get(*key))
+                .cloned()
+                .collect();
+            keys.sort();
+            keys.dedup();
+
+            Some(keys)
+        }
+    }
+
+    fn payload(payload: &str, etag: &str) -> Fetched {
+        Fetched::Payload {
+            payload: payload.as_bytes().to_vec(),
+            etag: Some(etag.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn deduplicate_unchanged_payloads() {
+        let source = ScriptedSource::new([
+            payload("a=1", "1"),
+            Fetched::NotModified,
+            payload("a=1", "2"),
+            payload("a", "3"),
+            payload("a=2,b=1", "4"),
+        ]);
+        let mut polling = PollingProvider::new("Test Provider", source, Duration::from_secs(60));
+        let mut events = polling.event_emitter().subscribe();
+
+        polling.start().await.unwrap();
+        polling.refresh().await.unwrap();
+        polling.refresh().await.unwrap();
+        polling.refresh().await.unwrap_err();
+        polling.refresh().await.unwrap();
+        polling.stop();
+
+        assert_eq!(polling.source().parses.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            *polling.source().etags.lock().unwrap(),
+            [None, Some("1"), Some("1"), Some("2"), Some("2")].map(|etag| etag.map(String::from))
+        );
+        assert_eq!(polling.configuration().unwrap()["b"], "1");
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.event_type, ProviderEventType::Error);
+        assert_eq!(
+            event.message.as_deref(),
+            Some("INVALID_RESPONSE: Missing =")
+        );
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.event_type, ProviderEventType::ConfigurationChanged);
+        assert_eq!(event.provider_name, "Test Provider");
+        assert_eq!(
+            event.flags_changed,
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(
+            event.event_metadata.values.get("configurationHash"),
+            Some(&"69d0e313a5d8738f49568fb4056414738194a694198658fb1b14675346657361".into())
+        );
+        assert_eq!(
+            events.try_recv().unwrap().event_type,
+            ProviderEventType::Ready
+        );
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn poll_on_interval() {
+        let source = ScriptedSource::new([payload("a=1", "1"), payload("a=2", "2")]);
+        let mut polling = PollingProvider::new("Test Provider", source, Duration::from_millis(10))
+            .with_scheduler(PollingScheduler::new(1));
+
+        polling.start().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(25)).await;
+
+        assert_eq!(polling.configuration().unwrap()["a"], "2");
+
+        // The script is exhausted.
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        polling.stop();
+
+        assert!(polling.consecutive_failures() > 0);
+    }
+}
@@ -0,0 +1,289 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use tokio::{sync::Semaphore, task::JoinHandle};
+
+use crate::ProviderError;
+
+lazy_static! {
+    /// The scheduler shared by all the polling providers by default.
+    static ref GLOBAL: PollingScheduler =
+        PollingScheduler::new(PollingScheduler::DEFAULT_MAX_CONCURRENCY)
+            .with_jitter(PollingScheduler::DEFAULT_JITTER);
+}
+
+// ============================================================
+//  PollingScheduler
+// ============================================================
+
+/// A scheduler running the periodic polls of providers, such as fetching a flag configuration,
+/// instead of each provider spawning its own timer task.
+///
+/// At most a given number of polls run at the same time, and a task failing repeatedly polls
+/// less often: its interval doubles with every consecutive failure, up to a maximum backoff,
+/// and is reset by the first success.
+///
+/// So that a fleet of instances restarting together does not poll the backend at the same
+/// instant, the first poll can be delayed by a random startup jitter, and every interval can be
+/// randomly stretched or shrunk by a jitter ratio.
+///
+/// ```no_run
+/// use std::{sync::Arc, time::Duration};
+///
+/// use open_feature::provider::PollingScheduler;
+/// # use open_feature::ProviderError;
+/// #
+/// # struct RemoteProvider;
+/// #
+/// # impl RemoteProvider {
+/// #     async fn fetch_configuration(&self) -> Result<(), ProviderError> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// #
+/// # async fn example() {
+/// # let provider = Arc::new(RemoteProvider);
+///
+/// let task = PollingScheduler::global().schedule(Duration::from_secs(30), move || {
+///     let provider = provider.clone();
+///     async move { provider.fetch_configuration().await }
+/// });
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PollingScheduler {
+    permits: Arc<Semaphore>,
+    max_backoff: Duration,
+    startup_jitter: Duration,
+    jitter: f64,
+}
+
+impl PollingScheduler {
+    /// The number of polls running at the same time by default.
+    pub const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+    /// The maximum interval between the polls of a failing task by default.
+    pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+    /// The jitter of the intervals of the global scheduler.
+    pub const DEFAULT_JITTER: f64 = 0.1;
+
+    /// Create a scheduler running at most `max_concurrency` polls at the same time.
+    pub fn new(max_concurrency: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrency.max(1))),
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+            startup_jitter: Duration::ZERO,
+            jitter: 0.0,
+        }
+    }
+
+    /// Return the scheduler shared by all the polling providers by default, with a jitter of
+    /// [`Self::DEFAULT_JITTER`]. Providers polling through it can be given a clone with other
+    /// settings instead.
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Set the maximum interval between the polls of a failing task. Tasks polling less often
+    /// than that are not slowed down.
+    #[must_use]
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Delay the first poll of every task by a random duration up to `startup_jitter`.
+    #[must_use]
+    pub fn with_startup_jitter(mut self, startup_jitter: Duration) -> Self {
+        self.startup_jitter = startup_jitter;
+        self
+    }
+
+    /// Randomly stretch or shrink every interval by up to `jitter` (from `0.0` to `1.0`) of
+    /// itself, such as `0.1` for intervals of 30 seconds varying from 27 to 33 seconds.
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Call `poll` every `interval`, starting after a first interval, until the returned task is
+    /// cancelled or dropped.
+    pub fn schedule<F, Fut>(&self, interval: Duration, poll: F) -> PollingTask
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), ProviderError>> + Send,
+    {
+        let permits = self.permits.clone();
+        let max_backoff = self.max_backoff.max(interval);
+        let startup_jitter = self.startup_jitter;
+        let jitter = self.jitter;
+        let failures = Arc::new(AtomicU32::new(0));
+
+        let handle = tokio::spawn({
+            let failures = failures.clone();
+
+            async move {
+                tokio::time::sleep(startup_jitter.mul_f64(rand::random())).await;
+
+                loop {
+                    let delay = backoff(interval, failures.load(Ordering::Relaxed), max_backoff);
+                    tokio::time::sleep(jittered(delay, jitter, rand::random())).await;
+
+                    let Ok(_permit) = permits.acquire().await else {
+                        break;
+                    };
+
+                    match poll().await {
+                        Ok(()) => failures.store(0, Ordering::Relaxed),
+                        Err(_) => {
+                            failures.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+            }
+        });
+
+        PollingTask { handle, failures }
+    }
+}
+
+/// Return `interval` doubled `failures` times, capped to `max_backoff`.
+pub(crate) fn backoff(interval: Duration, failures: u32, max_backoff: Duration) -> Duration {
+    interval
+        .checked_mul(2_u32.saturating_pow(failures))
+        .map_or(max_backoff, |delay| delay.min(max_backoff))
+}
+
+/// Return `delay` stretched or shrunk by up to `jitter` of itself, according to `sample` (from
+/// `0.0` to `1.0`).
+pub(super) fn jittered(delay: Duration, jitter: f64, sample: f64) -> Duration {
+    delay.mul_f64(1.0 + jitter * (2.0 * sample - 1.0))
+}
+
+// ============================================================
+//  PollingTask
+// ============================================================
+
+/// A task scheduled by a [`PollingScheduler`], cancelled when dropped.
+#[derive(Debug)]
+pub struct PollingTask {
+    handle: JoinHandle<()>,
+    failures: Arc<AtomicU32>,
+}
+
+impl PollingTask {
+    /// Return the number of consecutive failed polls.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    /// Stop polling.
+    pub fn cancel(&self) {
+        self.handle.abort();
+    }
+}
+
+impl Drop for PollingTask {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+    use crate::ProviderErrorKind;
+
+    #[test]
+    fn double_interval_on_failure() {
+        let interval = Duration::from_secs(10);
+        let max_backoff = Duration::from_secs(60);
+
+        assert_eq!(backoff(interval, 0, max_backoff), interval);
+        assert_eq!(backoff(interval, 2, max_backoff), Duration::from_secs(40));
+        assert_eq!(backoff(interval, 3, max_backoff), max_backoff);
+        assert_eq!(backoff(interval, u32::MAX, max_backoff), max_backoff);
+    }
+
+    #[test]
+    fn jitter_interval() {
+        let interval = Duration::from_secs(30);
+
+        assert_eq!(jittered(interval, 0.1, 0.0), Duration::from_secs(27));
+        assert_eq!(jittered(interval, 0.1, 0.5), interval);
+        assert_eq!(jittered(interval, 0.1, 1.0), Duration::from_secs(33));
+        assert_eq!(jittered(interval, 0.0, 1.0), interval);
+    }
+
+    #[test]
+    fn jitter_global_scheduler() {
+        assert!(
+            (PollingScheduler::global().jitter - PollingScheduler::DEFAULT_JITTER).abs()
+                < f64::EPSILON
+        );
+        assert!(PollingScheduler::new(1).jitter.abs() < f64::EPSILON);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limit_concurrency() {
+        let scheduler = PollingScheduler::new(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..5)
+            .map(|_| {
+                let running = running.clone();
+                let max_running = max_running.clone();
+
+                scheduler.schedule(Duration::from_millis(5), move || {
+                    let running = running.clone();
+                    let max_running = max_running.clone();
+
+                    async move {
+                        let count = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_running.fetch_max(count, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    }
+                })
+            })
+            .collect();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(tasks);
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn count_failures() {
+        let task = PollingScheduler::new(1).schedule(Duration::from_millis(10), || async {
+            Err(ProviderError::new(
+                ProviderErrorKind::Network,
+                "Unreachable",
+            ))
+        });
+
+        // Polled after 10, 30 and 70 ms.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        task.cancel();
+
+        assert_eq!(task.consecutive_failures(), 3);
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+
+use crate::{
+    hash::salted_sha256_hex, EvaluationContext, EvaluationContextFieldValue, EvaluationResult,
+    ProviderError, StructValue, TrackingEventDetails, Value,
+};
+
+use super::{EventEmitter, FeatureProvider, ProviderMetadata, ProviderStatus, ResolutionDetails};
+
+// ============================================================
+//  PrivacyProvider
+// ============================================================
+
+/// A provider that delegates to `inner` after hashing the targeting key and the sensitive
+/// attributes of the evaluation context, so that they never leave the process in clear, such
+/// as when `inner` is backed by a third-party service.
+///
+/// Values are replaced with the hex-encoded salted SHA-256 of their string form. Hashing is
+/// deterministic, so a given subject is still bucketed consistently. Sensitive attributes
+/// holding a struct cannot be hashed, and are removed.
+///
+/// ```no_run
+/// use open_feature::provider::{NoOpProvider, PrivacyProvider};
+///
+/// let provider = PrivacyProvider::new(NoOpProvider::default(), "my-salt")
+///     .with_sensitive_attribute("email");
+/// ```
+pub struct PrivacyProvider<P> {
+    inner: P,
+    salt: String,
+    sensitive_attributes: HashSet<String>,
+}
+
+impl<P: FeatureProvider> PrivacyProvider<P> {
+    /// Create a provider delegating to `inner`, hashing the targeting key with `salt`.
+    pub fn new(inner: P, salt: impl Into<String>) -> Self {
+        Self {
+            inner,
+            salt: salt.into(),
+            sensitive_attributes: HashSet::new(),
+        }
+    }
+
+    /// Hash attribute `name` as well as the targeting key.
+    #[must_use]
+    pub fn with_sensitive_attribute(mut self, name: impl Into<String>) -> Self {
+        self.add_sensitive_attribute(name);
+        self
+    }
+
+    /// Hash attribute `name` as well as the targeting key.
+    pub fn add_sensitive_attribute(&mut self, name: impl Into<String>) {
+        self.sensitive_attributes.insert(name.into());
+    }
+
+    /// Return the hex-encoded salted SHA-256 of `value`.
+    pub fn hash(&self, value: &str) -> String {
+        salted_sha256_hex(self.salt.as_bytes(), value.as_bytes())
+    }
+
+    /// Return `evaluation_context` with the targeting key and sensitive attributes hashed.
+    pub fn anonymize(&self, evaluation_context: &EvaluationContext) -> EvaluationContext {
+        let mut context = evaluation_context.clone();
+
+        context.targeting_key = context
+            .targeting_key
+            .map(|targeting_key| self.hash(&targeting_key));
+
+        for name in &self.sensitive_attributes {
+            let Some(value) = context.custom_fields.get(name) else {
+                continue;
+            };
+
+            match self.anonymize_value(value) {
+                Some(value) => context.add_custom_field(name, value),
+                None => {
+                    context.custom_fields.remove(name);
+                }
+            }
+        }
+
+        context
+    }
+
+    /// Hash `value`, element-wise for lists. Opaque structs can't be hashed and are dropped.
+    fn anonymize_value(
+        &self,
+        value: &EvaluationContextFieldValue,
+    ) -> Option<EvaluationContextFieldValue> {
+        let value = match value {
+            EvaluationContextFieldValue::Bool(value) => value.to_string(),
+            EvaluationContextFieldValue::Int(value) => value.to_string(),
+            EvaluationContextFieldValue::Float(value) => value.to_string(),
+            EvaluationContextFieldValue::String(value) => value.clone(),
+            EvaluationContextFieldValue::DateTime(value) => value.to_string(),
+            EvaluationContextFieldValue::List(values) => {
+                return Some(EvaluationContextFieldValue::List(
+                    values
+                        .iter()
+                        .filter_map(|value| self.anonymize_value(value))
+                        .collect(),
+                ))
+            }
+            EvaluationContextFieldValue::Struct(_) => return None,
+        };
+
+        Some(self.hash(&value).into())
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for PrivacyProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        let context = self.anonymize(context);
+        self.inner.initialize(&context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.inner
+            .track(
+                event_name,
+                &self.anonymize(evaluation_context),
+                tracking_event_details,
+            )
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.inner
+            .resolve_bool_value(flag_key, &self.anonymize(evaluation_context))
+            .await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.inner
+            .resolve_int_value(flag_key, &self.anonymize(evaluation_context))
+            .await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.inner
+            .resolve_float_value(flag_key, &self.anonymize(evaluation_context))
+            .await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.inner
+            .resolve_string_value(flag_key, &self.anonymize(evaluation_context))
+            .await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.inner
+            .resolve_struct_value(flag_key, &self.anonymize(evaluation_context))
+            .await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.inner
+            .resolve_all(&self.anonymize(evaluation_context))
+            .await
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::NoOpProvider;
+
+    fn create_provider() -> PrivacyProvider<NoOpProvider> {
+        PrivacyProvider::new(NoOpProvider::default(), "salt")
+            .with_sensitive_attribute("email")
+            .with_sensitive_attribute("address")
+            .with_sensitive_attribute("groups")
+    }
+
+    #[test]
+    fn hash_with_salt() {
+        let provider = create_provider();
+
+        // SHA-256 of "saltalice".
+        assert_eq!(
+            provider.hash("alice"),
+            "3baa379b47fbc36edfe4f8aa050d10d0c63d69e10e2f393ef568b995f8f83f57"
+        );
+    }
+
+    #[test]
+    fn anonymize_sensitive_attributes() {
+        let provider = create_provider();
+
+        let context = provider.anonymize(
+            &EvaluationContext::default()
+                .with_targeting_key("alice")
+                .with_custom_field("email", "alice@example.com")
+                .with_custom_field("address", EvaluationContextFieldValue::new_struct(42))
+                .with_custom_field("groups", vec!["staff"])
+                .with_custom_field("country", "FR"),
+        );
+
+        assert_eq!(context.targeting_key, Some(provider.hash("alice")));
+        assert_eq!(
+            context.custom_fields.get("email").unwrap().as_str(),
+            Some(provider.hash("alice@example.com").as_str())
+        );
+        assert!(!context.custom_fields.contains_key("address"));
+        assert_eq!(
+            context.custom_fields.get("groups").unwrap().as_list(),
+            Some(&[provider.hash("staff").into()][..])
+        );
+        assert_eq!(
+            context.custom_fields.get("country").unwrap().as_str(),
+            Some("FR")
+        );
+
+        // Consistent across evaluations.
+        assert_eq!(
+            provider
+                .anonymize(&EvaluationContext::default().with_targeting_key("alice"))
+                .targeting_key,
+            context.targeting_key
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use std::{collections::HashMap, time::Duration};
+
+use async_trait::async_trait;
+
+use crate::{
+    EvaluationContext, EvaluationResult, ProviderError, StructValue, TrackingEventDetails, Value,
+};
+
+use super::{
+    polling_scheduler::{backoff, jittered},
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+// ============================================================
+//  RetryProvider
+// ============================================================
+
+/// A provider that delegates to `inner`, retrying the resolutions failing with a retryable
+/// error, as told by [`EvaluationError::is_retryable`](crate::EvaluationError::is_retryable),
+/// before returning the last error.
+///
+/// The delay before a retry starts at the initial backoff and doubles with every attempt, up to
+/// the maximum backoff. It can be randomly stretched or shrunk by a jitter ratio, so that
+/// clients failing together do not retry at the same instant.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use open_feature::provider::{NoOpProvider, RetryProvider};
+///
+/// let provider = RetryProvider::new(NoOpProvider::default())
+///     .with_max_attempts(5)
+///     .with_backoff(Duration::from_millis(50), Duration::from_secs(1))
+///     .with_jitter(0.2);
+/// ```
+pub struct RetryProvider<P> {
+    inner: P,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: f64,
+}
+
+impl<P: FeatureProvider> RetryProvider<P> {
+    /// The number of attempts of a resolution by default, including the first one.
+    pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+    /// The delay before the first retry by default.
+    pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+    /// The maximum delay between two attempts by default.
+    pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(2);
+
+    /// Create a provider delegating to `inner` with the default retry policy, without jitter.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            max_attempts: Self::DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: Self::DEFAULT_INITIAL_BACKOFF,
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+            jitter: 0.0,
+        }
+    }
+
+    /// Set the number of attempts of a resolution, including the first one. `1` disables
+    /// retries.
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry, and the maximum delay between two attempts.
+    #[must_use]
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Randomly stretch or shrink every delay by up to `jitter` (from `0.0` to `1.0`) of
+    /// itself.
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let mut retries = 0;
+
+        loop {
+            match T::resolve(&self.inner, flag_key, evaluation_context).await {
+                Err(error) if error.is_retryable() && retries + 1 < self.max_attempts => {
+                    let delay = backoff(self.initial_backoff, retries, self.max_backoff);
+                    tokio::time::sleep(jittered(delay, self.jitter, rand::random())).await;
+
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for RetryProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.inner.initialize(context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.inner
+            .track(event_name, evaluation_context, tracking_event_details)
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.inner.resolve_all(evaluation_context).await
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::{
+        provider::MockFeatureProvider, EvaluationError, EvaluationErrorCode, ProviderError,
+        ProviderErrorKind,
+    };
+
+    /// Return a provider failing `failures` times with `error` before resolving `true`, and its
+    /// number of calls.
+    fn failing_provider(
+        failures: u32,
+        error: EvaluationError,
+    ) -> (MockFeatureProvider, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counted = calls.clone();
+
+        let mut provider = MockFeatureProvider::new();
+        provider.expect_resolve_bool_value().returning(move |_, _| {
+            if counted.fetch_add(1, Ordering::SeqCst) < failures {
+                Err(error.clone())
+            } else {
+                Ok(ResolutionDetails::new(true))
+            }
+        });
+
+        (provider, calls)
+    }
+
+    #[tokio::test]
+    async fn retry_transient_errors() {
+        let (inner, calls) = failing_provider(
+            2,
+            ProviderError::new(ProviderErrorKind::Unavailable, "503").into(),
+        );
+        let provider = RetryProvider::new(inner)
+            .with_backoff(Duration::from_millis(10), Duration::from_millis(15));
+
+        let started_at = std::time::Instant::now();
+        let details = provider
+            .resolve_bool_value("checkout-v2", &EvaluationContext::default())
+            .await
+            .unwrap();
+
+        assert!(details.value);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        // Waited 10ms, then 15ms instead of 20ms.
+        assert!(started_at.elapsed() >= Duration::from_millis(25));
+    }
+
+    #[tokio::test]
+    async fn give_up() {
+        let (inner, calls) = failing_provider(
+            5,
+            ProviderError::new(ProviderErrorKind::Timeout, "Timed out").into(),
+        );
+        let provider = RetryProvider::new(inner)
+            .with_max_attempts(2)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(1));
+
+        let error = provider
+            .resolve_bool_value("checkout-v2", &EvaluationContext::default())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error.code, EvaluationErrorCode::Provider(_)));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn fail_fast_on_terminal_errors() {
+        let (inner, calls) = failing_provider(
+            1,
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .build(),
+        );
+        let provider = RetryProvider::new(inner);
+
+        let error = provider
+            .resolve_bool_value("checkout-v2", &EvaluationContext::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,214 @@
+use time::{OffsetDateTime, Time, UtcOffset, Weekday};
+
+// ============================================================
+//  ActivationWindow
+// ============================================================
+
+/// A period a flag is active within, so that it can be switched on at a launch time, or only
+/// during office hours, without a deploy.
+///
+/// A window starts at `start` and ends right before `end`, each of them being unbounded if not
+/// set, and is further restricted to the occurrences of its `recurrence`, if any.
+///
+/// ```
+/// use open_feature::provider::{ActivationWindow, InMemoryFlag, Recurrence};
+/// use time::{format_description::well_known::Rfc3339, OffsetDateTime, Time, Weekday};
+///
+/// let launch = OffsetDateTime::parse("2026-11-02T09:00:00Z", &Rfc3339).unwrap();
+/// let (opening, closing) = (Time::from_hms(9, 0, 0).unwrap(), Time::from_hms(17, 0, 0).unwrap());
+///
+/// // Serve the new checkout from the launch on, during office hours.
+/// let flag = InMemoryFlag::new("off")
+///     .with_variant("on", true)
+///     .with_variant("off", false)
+///     .with_activation_window(
+///         ActivationWindow::starting_at(launch).with_recurrence(
+///             Recurrence::daily(opening, closing).on([
+///                 Weekday::Monday,
+///                 Weekday::Tuesday,
+///                 Weekday::Wednesday,
+///                 Weekday::Thursday,
+///                 Weekday::Friday,
+///             ]),
+///         ),
+///     );
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ActivationWindow {
+    /// The time the window starts at, if any.
+    pub start: Option<OffsetDateTime>,
+
+    /// The time the window ends at, if any.
+    pub end: Option<OffsetDateTime>,
+
+    /// The recurring periods the window is restricted to, if any.
+    pub recurrence: Option<Recurrence>,
+}
+
+impl ActivationWindow {
+    /// Create a window starting at `start`, with no end.
+    pub fn starting_at(start: OffsetDateTime) -> Self {
+        Self {
+            start: Some(start),
+            ..Self::default()
+        }
+    }
+
+    /// Create a window from `start` until right before `end`.
+    pub fn between(start: OffsetDateTime, end: OffsetDateTime) -> Self {
+        Self {
+            start: Some(start),
+            end: Some(end),
+            recurrence: None,
+        }
+    }
+
+    /// End the window right before `end`.
+    #[must_use]
+    pub fn with_end(mut self, end: OffsetDateTime) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Restrict the window to the occurrences of `recurrence`.
+    #[must_use]
+    pub fn with_recurrence(mut self, recurrence: Recurrence) -> Self {
+        self.recurrence = Some(recurrence);
+        self
+    }
+
+    /// Return whether the window is open at time `at`.
+    pub fn contains(&self, at: OffsetDateTime) -> bool {
+        self.start.map_or(true, |start| start <= at)
+            && self.end.map_or(true, |end| at < end)
+            && self
+                .recurrence
+                .as_ref()
+                .map_or(true, |recurrence| recurrence.contains(at))
+    }
+}
+
+// ============================================================
+//  Recurrence
+// ============================================================
+
+/// A period recurring every day, or on some days of the week only, from a time of the day until
+/// right before another one, at a given UTC offset.
+///
+/// A period ending at an earlier time than it starts spans midnight, and belongs to the day it
+/// starts on: a Friday night period from 22:00 until 06:00 ends on Saturday morning.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Recurrence {
+    /// The time of the day the period starts at.
+    pub from: Time,
+
+    /// The time of the day the period ends right before.
+    pub until: Time,
+
+    /// The days of the week the period starts on, or every day if empty.
+    pub weekdays: Vec<Weekday>,
+
+    /// The offset the times of the day are expressed at.
+    pub offset: UtcOffset,
+}
+
+impl Recurrence {
+    /// Create a period recurring every day from `from` until right before `until`, in UTC.
+    pub fn daily(from: Time, until: Time) -> Self {
+        Self {
+            from,
+            until,
+            weekdays: Vec::new(),
+            offset: UtcOffset::UTC,
+        }
+    }
+
+    /// Only start the period on `weekdays`.
+    #[must_use]
+    pub fn on(mut self, weekdays: impl IntoIterator<Item = Weekday>) -> Self {
+        self.weekdays = weekdays.into_iter().collect();
+        self
+    }
+
+    /// Express the times of the day at `offset` rather than in UTC.
+    #[must_use]
+    pub fn with_offset(mut self, offset: UtcOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Return whether an occurrence of the period is ongoing at time `at`.
+    pub fn contains(&self, at: OffsetDateTime) -> bool {
+        let at = at.to_offset(self.offset);
+        let time = at.time();
+
+        if self.from <= self.until {
+            self.from <= time && time < self.until && self.starts_on(at.weekday())
+        } else if self.from <= time {
+            self.starts_on(at.weekday())
+        } else {
+            time < self.until && self.starts_on(at.weekday().previous())
+        }
+    }
+
+    fn starts_on(&self, weekday: Weekday) -> bool {
+        self.weekdays.is_empty() || self.weekdays.contains(&weekday)
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use time::format_description::well_known::Rfc3339;
+
+    use super::*;
+
+    fn datetime(text: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(text, &Rfc3339).unwrap()
+    }
+
+    fn time(hour: u8, minute: u8) -> Time {
+        Time::from_hms(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn window_bounds() {
+        let window = ActivationWindow::between(
+            datetime("2026-11-02T09:00:00Z"),
+            datetime("2026-11-09T09:00:00Z"),
+        );
+
+        assert!(!window.contains(datetime("2026-11-02T08:59:59Z")));
+        assert!(window.contains(datetime("2026-11-02T09:00:00Z")));
+        assert!(window.contains(datetime("2026-11-02T10:00:00+01:00")));
+        assert!(!window.contains(datetime("2026-11-09T09:00:00Z")));
+
+        let window = ActivationWindow::default();
+        assert!(window.contains(datetime("1970-01-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn recurring_periods() {
+        let office_hours = Recurrence::daily(time(9, 0), time(17, 0))
+            .on([Weekday::Monday, Weekday::Friday])
+            .with_offset(UtcOffset::from_hms(1, 0, 0).unwrap());
+
+        // Monday.
+        assert!(office_hours.contains(datetime("2026-11-02T08:00:00Z")));
+        assert!(!office_hours.contains(datetime("2026-11-02T16:00:00Z")));
+        // Tuesday.
+        assert!(!office_hours.contains(datetime("2026-11-03T08:00:00Z")));
+
+        let nights = Recurrence::daily(time(22, 0), time(6, 0)).on([Weekday::Friday]);
+
+        // Friday night and Saturday morning.
+        assert!(nights.contains(datetime("2026-11-06T23:00:00Z")));
+        assert!(nights.contains(datetime("2026-11-07T05:59:00Z")));
+        assert!(!nights.contains(datetime("2026-11-07T06:00:00Z")));
+        // Friday morning.
+        assert!(!nights.contains(datetime("2026-11-06T05:00:00Z")));
+    }
+}
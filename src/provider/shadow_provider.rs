@@ -0,0 +1,445 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    EvaluationContext, EvaluationErrorCode, EvaluationResult, ProviderError, StructValue,
+    TrackingEventDetails, Value,
+};
+
+use super::{
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+/// The number of diffs kept as samples for each flag.
+const MAX_SAMPLES: usize = 10;
+
+type MismatchHandler = Box<dyn Fn(&ShadowDiff) + Send + Sync>;
+
+// ============================================================
+//  ShadowProvider
+// ============================================================
+
+/// A provider resolving every flag with both a `live` and a `candidate` provider, returning the
+/// result of `live` and recording where `candidate` disagrees.
+///
+/// It is meant to check a configuration change against real traffic before deploying it. Both
+/// providers are called concurrently, so the latency of an evaluation is the one of the slowest.
+///
+/// ```no_run
+/// use open_feature::{
+///     provider::{InMemoryProvider, ShadowProvider},
+///     OpenFeature,
+/// };
+///
+/// # async fn example() -> Result<(), open_feature::SdkError> {
+/// # let live = InMemoryProvider::default();
+/// # let candidate = InMemoryProvider::default();
+/// let provider = ShadowProvider::new(live, candidate);
+/// let recorder = provider.recorder();
+///
+/// OpenFeature::singleton_mut()
+///     .await
+///     .set_provider(provider)
+///     .await?;
+///
+/// // Later on.
+/// println!("{}", recorder.report());
+/// # Ok(())
+/// # }
+/// ```
+pub struct ShadowProvider<L, C> {
+    live: L,
+    candidate: C,
+    recorder: ShadowRecorder,
+    mismatch_handler: Option<MismatchHandler>,
+}
+
+impl<L: FeatureProvider, C: FeatureProvider> ShadowProvider<L, C> {
+    /// Create a new instance serving `live` and shadowing it with `candidate`.
+    pub fn new(live: L, candidate: C) -> Self {
+        Self {
+            live,
+            candidate,
+            recorder: ShadowRecorder::default(),
+            mismatch_handler: None,
+        }
+    }
+
+    /// Call `handler` with every mismatch, for example to log it.
+    #[must_use]
+    pub fn with_mismatch_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&ShadowDiff) + Send + Sync + 'static,
+    {
+        self.mismatch_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Return the recorder of the comparisons, which remains usable once the provider is moved
+    /// into the API.
+    pub fn recorder(&self) -> ShadowRecorder {
+        self.recorder.clone()
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let (live, candidate) = tokio::join!(
+            T::resolve(&self.live, flag_key, evaluation_context),
+            T::resolve(&self.candidate, flag_key, evaluation_context)
+        );
+
+        let diff = ShadowDiff {
+            flag_key: flag_key.to_string(),
+            targeting_key: evaluation_context.targeting_key.clone(),
+            live: ShadowOutcome::of(&live),
+            candidate: ShadowOutcome::of(&candidate),
+        };
+
+        if diff.is_mismatch() {
+            if let Some(handler) = &self.mismatch_handler {
+                handler(&diff);
+            }
+        }
+
+        self.recorder.record(diff);
+
+        live
+    }
+}
+
+#[async_trait]
+impl<L: FeatureProvider, C: FeatureProvider> FeatureProvider for ShadowProvider<L, C> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        let (live, _) = tokio::join!(
+            self.live.initialize(context),
+            self.candidate.initialize(context)
+        );
+
+        // A failing candidate only skips the comparisons, as its resolutions fail.
+        live
+    }
+
+    async fn shutdown(&self) {
+        tokio::join!(self.live.shutdown(), self.candidate.shutdown());
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.live.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.live.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.live.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.live
+            .track(event_name, evaluation_context, tracking_event_details)
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.live.resolve_all(evaluation_context).await
+    }
+}
+
+// ============================================================
+//  ShadowDiff
+// ============================================================
+
+/// The outcome of a single resolution.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ShadowOutcome {
+    /// The resolved value.
+    Value(Value),
+
+    /// The code of the resolution error.
+    Error(EvaluationErrorCode),
+}
+
+impl ShadowOutcome {
+    fn of<T: FlagValue>(result: &EvaluationResult<ResolutionDetails<T>>) -> Self {
+        match result {
+            Ok(details) => Self::Value(details.value.to_value()),
+            Err(error) => Self::Error(error.code.clone()),
+        }
+    }
+}
+
+impl fmt::Display for ShadowOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value(value) => write!(f, "{:?}", value),
+            Self::Error(code) => write!(f, "error {}", code),
+        }
+    }
+}
+
+/// The outcomes of both providers for a single evaluation.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ShadowDiff {
+    /// The key of evaluated flag.
+    pub flag_key: String,
+
+    /// The targeting key of the evaluation context.
+    pub targeting_key: Option<String>,
+
+    /// The outcome of the live provider, which was returned.
+    pub live: ShadowOutcome,
+
+    /// The outcome of the candidate provider.
+    pub candidate: ShadowOutcome,
+}
+
+impl ShadowDiff {
+    /// Return `true` if the providers disagree.
+    pub fn is_mismatch(&self) -> bool {
+        self.live != self.candidate
+    }
+}
+
+// ============================================================
+//  ShadowRecorder
+// ============================================================
+
+/// Collects the comparisons made by a [`ShadowProvider`]. All the clones share the same records.
+#[derive(Clone, Default, Debug)]
+pub struct ShadowRecorder {
+    flags: Arc<Mutex<BTreeMap<String, FlagShadowReport>>>,
+}
+
+impl ShadowRecorder {
+    fn record(&self, diff: ShadowDiff) {
+        let mut flags = self.flags.lock().unwrap();
+        let flag = flags.entry(diff.flag_key.clone()).or_default();
+
+        flag.evaluations += 1;
+
+        if diff.is_mismatch() {
+            flag.mismatches += 1;
+
+            if flag.samples.len() < MAX_SAMPLES {
+                flag.samples.push(diff);
+            }
+        }
+    }
+
+    /// Forget everything recorded so far.
+    pub fn reset(&self) {
+        self.flags.lock().unwrap().clear();
+    }
+
+    /// Return the comparisons recorded so far.
+    pub fn report(&self) -> ShadowReport {
+        ShadowReport {
+            flags: self.flags.lock().unwrap().clone(),
+        }
+    }
+}
+
+// ============================================================
+//  ShadowReport
+// ============================================================
+
+/// The comparisons of every shadowed flag, ordered by flag key.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ShadowReport {
+    /// The comparisons of each flag.
+    pub flags: BTreeMap<String, FlagShadowReport>,
+}
+
+/// The comparisons of a single flag.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct FlagShadowReport {
+    /// The number of evaluations.
+    pub evaluations: usize,
+
+    /// The number of evaluations where the providers disagree.
+    pub mismatches: usize,
+
+    /// The first few mismatches.
+    pub samples: Vec<ShadowDiff>,
+}
+
+impl ShadowReport {
+    /// Return `true` if the providers always agreed.
+    pub fn is_clean(&self) -> bool {
+        self.flags.values().all(|flag| flag.mismatches == 0)
+    }
+}
+
+impl fmt::Display for ShadowReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mismatching = self
+            .flags
+            .values()
+            .filter(|flag| flag.mismatches > 0)
+            .count();
+
+        writeln!(
+            f,
+            "Shadow evaluation: {}/{} flags mismatching",
+            mismatching,
+            self.flags.len()
+        )?;
+
+        for (flag_key, flag) in &self.flags {
+            writeln!(
+                f,
+                "  {}: {}/{} mismatching",
+                flag_key, flag.mismatches, flag.evaluations
+            )?;
+
+            for diff in &flag.samples {
+                writeln!(
+                    f,
+                    "    {}: {} -> {}",
+                    diff.targeting_key
+                        .as_deref()
+                        .unwrap_or("<no targeting key>"),
+                    diff.live,
+                    diff.candidate
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::flags;
+
+    #[tokio::test]
+    async fn report_mismatches() {
+        let live = flags! {
+            "checkout-v2" => bool: false,
+            "tier" => String: "gold",
+        };
+        let candidate = flags! {
+            "checkout-v2" => bool: true,
+            "tier" => String: "gold",
+        };
+
+        let mismatches = Arc::new(AtomicUsize::new(0));
+        let counter = mismatches.clone();
+
+        let provider = ShadowProvider::new(live, candidate).with_mismatch_handler(move |_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+        let recorder = provider.recorder();
+        let context = EvaluationContext::default().with_targeting_key("alice");
+
+        let result = provider
+            .resolve_bool_value("checkout-v2", &context)
+            .await
+            .unwrap();
+        assert!(!result.value, "live value should be returned");
+
+        provider
+            .resolve_string_value("tier", &context)
+            .await
+            .unwrap();
+
+        let report = recorder.report();
+        assert!(!report.is_clean());
+        assert_eq!(mismatches.load(Ordering::Relaxed), 1);
+        assert_eq!(report.flags["tier"].mismatches, 0);
+        assert_eq!(
+            report.to_string(),
+            "Shadow evaluation: 1/2 flags mismatching\n  checkout-v2: 1/1 mismatching\n    alice: Bool(false) -> Bool(true)\n  tier: 0/1 mismatching\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn compare_errors() {
+        let live = flags! { "tier" => String: "gold" };
+        let candidate = flags! {};
+
+        let provider = ShadowProvider::new(live, candidate);
+        let context = EvaluationContext::default();
+
+        assert!(provider
+            .resolve_string_value("tier", &context)
+            .await
+            .is_ok());
+        assert!(provider
+            .resolve_string_value("missing", &context)
+            .await
+            .is_err());
+
+        let report = provider.recorder().report();
+        assert_eq!(
+            report.flags["tier"].samples[0].candidate,
+            ShadowOutcome::Error(EvaluationErrorCode::FlagNotFound)
+        );
+        assert_eq!(report.flags["missing"].mismatches, 0);
+    }
+}
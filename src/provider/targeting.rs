@@ -0,0 +1,156 @@
+use serde_json::Value as JsonValue;
+use time::OffsetDateTime;
+
+use crate::{EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationResult};
+
+use super::json_logic;
+
+// ============================================================
+//  Targeting
+// ============================================================
+
+/// A targeting rule in the [flagd](https://flagd.dev/reference/flag-definitions/) format: a
+/// [JsonLogic](https://jsonlogic.com) rule resolving to the name of the variant to serve, or to
+/// `null` to serve the default one.
+///
+/// The rule is applied to the evaluation context as a JSON object, holding the custom fields
+/// along with the `targetingKey`, and a `$flagd` object with the `flagKey` and the `timestamp`
+/// of the evaluation in seconds.
+///
+/// ```
+/// use open_feature::provider::{InMemoryFlag, Targeting};
+/// use serde_json::json;
+///
+/// let flag = InMemoryFlag::new("off")
+///     .with_variant("on", true)
+///     .with_variant("off", false)
+///     .with_targeting(Targeting::new(json!({
+///         "if": [{ "in": ["@example.com", { "var": "email" }] }, "on", null]
+///     })));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Targeting {
+    rule: JsonValue,
+}
+
+impl Targeting {
+    /// Create a targeting applying `rule`.
+    pub fn new(rule: JsonValue) -> Self {
+        Self { rule }
+    }
+
+    /// Return the JsonLogic rule.
+    pub fn rule(&self) -> &JsonValue {
+        &self.rule
+    }
+
+    /// Return the variant of flag `flag_key` targeted for `evaluation_context` when evaluated
+    /// `at` a time, or `None` if the default one is to be served.
+    pub fn variant(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+        at: OffsetDateTime,
+    ) -> EvaluationResult<Option<String>> {
+        let timestamp = at.unix_timestamp();
+
+        let mut data = JsonValue::from(evaluation_context);
+        if let JsonValue::Object(fields) = &mut data {
+            fields.insert(
+                "$flagd".to_string(),
+                json_logic::object([
+                    ("flagKey".to_string(), flag_key.into()),
+                    ("timestamp".to_string(), timestamp.into()),
+                ]),
+            );
+        }
+
+        let parse_error = |message: String| {
+            EvaluationError::builder()
+                .code(EvaluationErrorCode::ParseError)
+                .message(format!(
+                    "Invalid targeting of flag \"{}\": {}",
+                    flag_key, message
+                ))
+                .build()
+        };
+
+        match json_logic::apply(&self.rule, &data).map_err(parse_error)? {
+            JsonValue::Null => Ok(None),
+            JsonValue::String(variant) => Ok(Some(variant)),
+            JsonValue::Bool(variant) => Ok(Some(variant.to_string())),
+            value => Err(parse_error(format!(
+                "Expected a variant name, got {}",
+                value
+            ))),
+        }
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn target_variants() {
+        let targeting = Targeting::new(json!({
+            "if": [
+                { "==": [{ "var": "$flagd.flagKey" }, "new-checkout"] },
+                { "if": [{ "==": [{ "var": "targetingKey" }, "user-1"] }, "on", null] },
+                "off"
+            ]
+        }));
+        let now = OffsetDateTime::now_utc();
+
+        let context = EvaluationContext::default().with_targeting_key("user-1");
+        assert_eq!(
+            targeting.variant("new-checkout", &context, now).unwrap(),
+            Some("on".to_string())
+        );
+        assert_eq!(
+            targeting
+                .variant("new-checkout", &EvaluationContext::default(), now)
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            targeting.variant("other", &context, now).unwrap(),
+            Some("off".to_string())
+        );
+
+        let error = Targeting::new(json!({ "var": "age" }))
+            .variant(
+                "new-checkout",
+                &EvaluationContext::default().with_custom_field("age", 3),
+                now,
+            )
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::ParseError);
+    }
+
+    #[test]
+    fn target_timestamp() {
+        let launch = OffsetDateTime::now_utc();
+        let targeting = Targeting::new(json!({
+            "if": [{ ">=": [{ "var": "$flagd.timestamp" }, launch.unix_timestamp()] }, "on", "off"]
+        }));
+        let context = EvaluationContext::default();
+
+        assert_eq!(
+            targeting
+                .variant("new-checkout", &context, launch - time::Duration::hours(1))
+                .unwrap(),
+            Some("off".to_string())
+        );
+        assert_eq!(
+            targeting.variant("new-checkout", &context, launch).unwrap(),
+            Some("on".to_string())
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::{EvaluationContext, EvaluationResult, ProviderError, StructValue, Value};
+
+use super::{
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+// ============================================================
+//  TenantRoutingProvider
+// ============================================================
+
+/// A provider routing every resolution to the provider of a tenant, selected by an evaluation
+/// context attribute, such as `tenant_id` selecting the flag environment of a customer.
+/// Resolutions without the attribute, or for an unknown tenant, go to the default provider.
+///
+/// All the clones share the same routes, so a clone kept aside can add and remove tenants once
+/// the provider is set. Providers of tenants added after initialization are initialized right
+/// away.
+///
+/// ```no_run
+/// use open_feature::{
+///     provider::{InMemoryProvider, TenantRoutingProvider},
+///     OpenFeature,
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = TenantRoutingProvider::new("tenant_id", InMemoryProvider::default())
+///     .with_tenant("acme", InMemoryProvider::default());
+///
+/// OpenFeature::singleton_mut()
+///     .await
+///     .set_provider(provider.clone())
+///     .await?;
+///
+/// // Later on.
+/// provider
+///     .add_tenant("globex", InMemoryProvider::default())
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TenantRoutingProvider {
+    metadata: ProviderMetadata,
+    attribute: String,
+    routes: Arc<RwLock<Routes>>,
+}
+
+struct Routes {
+    default: Box<dyn FeatureProvider>,
+    tenants: HashMap<String, Box<dyn FeatureProvider>>,
+    /// The evaluation context the providers were initialized with, once initialized.
+    evaluation_context: Option<EvaluationContext>,
+}
+
+impl TenantRoutingProvider {
+    /// Create a provider selecting tenants with context attribute `attribute`, and routing to
+    /// `default` when there is none.
+    pub fn new<P: FeatureProvider>(attribute: impl Into<String>, default: P) -> Self {
+        Self {
+            metadata: ProviderMetadata::new("Tenant Routing Provider"),
+            attribute: attribute.into(),
+            routes: Arc::new(RwLock::new(Routes {
+                default: Box::new(default),
+                tenants: HashMap::new(),
+                evaluation_context: None,
+            })),
+        }
+    }
+
+    /// Route the resolutions of `tenant` to `provider`, which is initialized along with the
+    /// default one.
+    #[must_use]
+    pub fn with_tenant<P: FeatureProvider>(self, tenant: impl Into<String>, provider: P) -> Self {
+        self.routes
+            .try_write()
+            .expect("Routes are not in use while building the provider")
+            .tenants
+            .insert(tenant.into(), Box::new(provider));
+        self
+    }
+
+    /// Route the resolutions of `tenant` to `provider`, replacing and shutting down its current
+    /// provider if any. `provider` is initialized first if the routing provider already is, and
+    /// is shut down rather than added if its initialization fails.
+    pub async fn add_tenant<P: FeatureProvider>(
+        &self,
+        tenant: impl Into<String>,
+        mut provider: P,
+    ) -> Result<(), ProviderError> {
+        let evaluation_context = self.routes.read().await.evaluation_context.clone();
+
+        if let Some(evaluation_context) = evaluation_context {
+            if let Err(error) = provider.initialize(&evaluation_context).await {
+                provider.shutdown().await;
+                return Err(error);
+            }
+        }
+
+        let replaced = self
+            .routes
+            .write()
+            .await
+            .tenants
+            .insert(tenant.into(), Box::new(provider));
+
+        if let Some(replaced) = replaced {
+            replaced.shutdown().await;
+        }
+
+        Ok(())
+    }
+
+    /// Stop routing the resolutions of `tenant` to its own provider, and shut it down.
+    /// Return `false` if the tenant had no provider.
+    pub async fn remove_tenant(&self, tenant: &str) -> bool {
+        let removed = self.routes.write().await.tenants.remove(tenant);
+
+        match removed {
+            Some(provider) => {
+                provider.shutdown().await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Return the tenants with their own provider, sorted.
+    pub async fn tenants(&self) -> Vec<String> {
+        let mut tenants: Vec<_> = self.routes.read().await.tenants.keys().cloned().collect();
+        tenants.sort();
+        tenants
+    }
+
+    fn tenant<'a>(&self, evaluation_context: &'a EvaluationContext) -> Option<&'a str> {
+        evaluation_context
+            .custom_fields
+            .get(&self.attribute)
+            .and_then(|value| value.as_str())
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let routes = self.routes.read().await;
+
+        T::resolve(
+            routes.route(self.tenant(evaluation_context)),
+            flag_key,
+            evaluation_context,
+        )
+        .await
+    }
+}
+
+impl Routes {
+    fn route(&self, tenant: Option<&str>) -> &dyn FeatureProvider {
+        tenant
+            .and_then(|tenant| self.tenants.get(tenant))
+            .unwrap_or(&self.default)
+            .as_ref()
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for TenantRoutingProvider {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        let mut routes = self.routes.write().await;
+
+        routes.default.initialize(context).await?;
+        for provider in routes.tenants.values_mut() {
+            provider.initialize(context).await?;
+        }
+
+        routes.evaluation_context = Some(context.clone());
+        Ok(())
+    }
+
+    async fn shutdown(&self) {
+        let routes = self.routes.read().await;
+
+        routes.default.shutdown().await;
+        for provider in routes.tenants.values() {
+            provider.shutdown().await;
+        }
+    }
+
+    fn status(&self) -> ProviderStatus {
+        match self.routes.try_read() {
+            Ok(routes) => routes.default.status(),
+            // Being initialized.
+            Err(_) => ProviderStatus::NotReady,
+        }
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.routes
+            .try_read()
+            .ok()
+            .and_then(|routes| routes.default.event_emitter())
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        let routes = self.routes.read().await;
+
+        routes
+            .route(self.tenant(evaluation_context))
+            .resolve_all(evaluation_context)
+            .await
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{flags, OpenFeature};
+
+    #[tokio::test]
+    async fn route_by_tenant() {
+        let provider = TenantRoutingProvider::new("tenant_id", flags! { "tier" => String: "free" })
+            .with_tenant("acme", flags! { "tier" => String: "enterprise" });
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+
+        let client = api.create_client();
+        let tier = |tenant: &str| {
+            let context = EvaluationContext::default().with_custom_field("tenant_id", tenant);
+            let client = &client;
+            async move { client.get_string_value("tier", Some(&context), None).await }
+        };
+
+        assert_eq!(tier("acme").await.unwrap(), "enterprise");
+        assert_eq!(tier("globex").await.unwrap(), "free");
+        assert_eq!(
+            client.get_string_value("tier", None, None).await.unwrap(),
+            "free"
+        );
+
+        provider
+            .add_tenant("globex", flags! { "tier" => String: "pro" })
+            .await
+            .unwrap();
+        assert!(provider.remove_tenant("acme").await);
+
+        assert_eq!(provider.tenants().await, vec!["globex".to_string()]);
+        assert_eq!(tier("acme").await.unwrap(), "free");
+        assert_eq!(tier("globex").await.unwrap(), "pro");
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::{
+    EvaluationContext, EvaluationResult, FlagMetadataValue, ProviderError, StructValue,
+    TrackingEventDetails, Value,
+};
+
+use super::{
+    EventEmitter, FeatureProvider, FlagValue, ProviderMetadata, ProviderStatus, ResolutionDetails,
+};
+
+type Transform = Box<dyn Fn(Value) -> Option<Value> + Send + Sync>;
+
+// ============================================================
+//  TransformProvider
+// ============================================================
+
+/// A provider that delegates to `inner` and applies transformations to the resolved values of
+/// given flags, such as mapping legacy strings to new ones or clamping numbers to a safe range.
+///
+/// When a transformation changes a value, the original value and variant are moved to the
+/// `original_value` and `original_variant` fields of the flag metadata, and the variant is
+/// cleared. Struct values are not recorded, as flag metadata cannot hold them.
+///
+/// ```no_run
+/// use open_feature::provider::{NoOpProvider, TransformProvider};
+///
+/// let provider = TransformProvider::new(NoOpProvider::default())
+///     .with_transform("max-retries", |retries: i64| retries.clamp(0, 10))
+///     .with_transform("theme", |theme: String| match theme.as_str() {
+///         "blue" => "ocean".to_string(),
+///         _ => theme,
+///     });
+/// ```
+pub struct TransformProvider<P> {
+    inner: P,
+    transforms: HashMap<String, Transform>,
+}
+
+impl<P: FeatureProvider> TransformProvider<P> {
+    /// Create a provider delegating to `inner` without any transformation.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            transforms: HashMap::new(),
+        }
+    }
+
+    /// Apply `transform` to the values of `flag_key` resolved as `T`, replacing its current
+    /// transformation if any. Values of other types are left untouched.
+    #[must_use]
+    pub fn with_transform<T, F>(mut self, flag_key: impl Into<String>, transform: F) -> Self
+    where
+        T: FlagValue,
+        F: Fn(T) -> T + Send + Sync + 'static,
+    {
+        self.add_transform(flag_key, transform);
+        self
+    }
+
+    /// Apply `transform` to the values of `flag_key` resolved as `T`, replacing its current
+    /// transformation if any. Values of other types are left untouched.
+    pub fn add_transform<T, F>(&mut self, flag_key: impl Into<String>, transform: F)
+    where
+        T: FlagValue,
+        F: Fn(T) -> T + Send + Sync + 'static,
+    {
+        self.transforms.insert(
+            flag_key.into(),
+            Box::new(move |value| T::from_value(value).map(|value| transform(value).to_value())),
+        );
+    }
+
+    fn transform(
+        &self,
+        flag_key: &str,
+        details: ResolutionDetails<Value>,
+    ) -> ResolutionDetails<Value> {
+        let Some(value) = self
+            .transforms
+            .get(flag_key)
+            .and_then(|transform| transform(details.value.clone()))
+        else {
+            return details;
+        };
+
+        if value == details.value {
+            return details;
+        }
+
+        let mut flag_metadata = details.flag_metadata.unwrap_or_default();
+
+        match details.value {
+            Value::Bool(value) => flag_metadata.add_value("original_value", value),
+            Value::Int(value) => flag_metadata.add_value("original_value", value),
+            Value::Float(value) => flag_metadata.add_value("original_value", value),
+            Value::String(value) => flag_metadata.add_value("original_value", value),
+            Value::Array(_) | Value::Struct(_) => (),
+        }
+
+        if let Some(variant) = details.variant {
+            flag_metadata.add_value("original_variant", FlagMetadataValue::String(variant));
+        }
+
+        ResolutionDetails {
+            value,
+            variant: None,
+            reason: details.reason,
+            flag_metadata: Some(flag_metadata),
+        }
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let details = T::resolve(&self.inner, flag_key, evaluation_context).await?;
+
+        if !self.transforms.contains_key(flag_key) {
+            return Ok(details);
+        }
+
+        let details = self.transform(
+            flag_key,
+            ResolutionDetails {
+                value: details.value.to_value(),
+                variant: details.variant,
+                reason: details.reason,
+                flag_metadata: details.flag_metadata,
+            },
+        );
+
+        Ok(ResolutionDetails {
+            // Transformations keep the type of the value.
+            value: T::from_value(details.value).expect("Transformed value of the same type"),
+            variant: details.variant,
+            reason: details.reason,
+            flag_metadata: details.flag_metadata,
+        })
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for TransformProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.inner.initialize(context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.inner
+            .track(event_name, evaluation_context, tracking_event_details)
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        Ok(self
+            .inner
+            .resolve_all(evaluation_context)
+            .await?
+            .into_iter()
+            .map(|(flag_key, details)| {
+                let details = self.transform(&flag_key, details);
+                (flag_key, details)
+            })
+            .collect())
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{flags, FlagMetadata, OpenFeature};
+
+    #[tokio::test]
+    async fn transform_values() {
+        let provider = TransformProvider::new(flags! {
+            "max-retries" => i64: 50,
+            "theme" => String: "dark",
+        })
+        .with_transform("max-retries", |retries: i64| retries.clamp(0, 10))
+        .with_transform("theme", |theme: String| match theme.as_str() {
+            "blue" => "ocean".to_string(),
+            _ => theme,
+        });
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider).await.unwrap();
+
+        let client = api.create_client();
+
+        let details = client
+            .get_int_details("max-retries", None, None)
+            .await
+            .unwrap();
+        assert_eq!(details.value, 10);
+        assert_eq!(
+            details.flag_metadata,
+            FlagMetadata::default()
+                .with_value("original_value", 50)
+                .with_value("original_variant", "50".to_string())
+        );
+
+        // Unchanged value.
+        let details = client
+            .get_string_details("theme", None, None)
+            .await
+            .unwrap();
+        assert_eq!(details.value, "dark");
+        assert_eq!(details.flag_metadata, FlagMetadata::default());
+    }
+
+    #[tokio::test]
+    async fn ignore_other_types() {
+        let provider = TransformProvider::new(flags! { "max-retries" => i64: 50 })
+            .with_transform("max-retries", |retries: f64| retries.min(10.0));
+
+        let details = provider
+            .resolve_int_value("max-retries", &EvaluationContext::default())
+            .await
+            .unwrap();
+
+        assert_eq!(details.value, 50);
+        assert!(details.flag_metadata.is_none());
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{EvaluationError, EvaluationResult, StructValue, Value};
+use crate::{
+    EvaluationContext, EvaluationContextFieldValue, EvaluationError, EvaluationResult, StructValue,
+    Value,
+};
 
 impl TryFrom<serde_json::Value> for Value {
     type Error = EvaluationError;
@@ -16,6 +19,74 @@ impl TryFrom<&serde_json::Value> for Value {
     }
 }
 
+impl From<&Value> for serde_json::Value {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Bool(value) => Self::Bool(*value),
+            Value::Int(value) => Self::from(*value),
+            Value::Float(value) => Self::from(*value),
+            Value::String(value) => Self::String(value.clone()),
+            Value::Array(array) => Self::Array(array.iter().map(Self::from).collect()),
+            Value::Struct(value) => Self::Object(
+                value
+                    .fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Self::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<Value> for serde_json::Value {
+    fn from(value: Value) -> Self {
+        Self::from(&value)
+    }
+}
+
+/// Convert `context` to the JSON object evaluated by flagd and sent to OFREP backends: the
+/// custom fields along with the `targetingKey`. Date-times are converted to Unix timestamps, and
+/// struct fields are left out unless they hold a [`StructValue`] or a [`Value`].
+impl From<&EvaluationContext> for serde_json::Value {
+    fn from(context: &EvaluationContext) -> Self {
+        let mut json = serde_json::Map::new();
+
+        if let Some(targeting_key) = &context.targeting_key {
+            json.insert("targetingKey".to_string(), targeting_key.clone().into());
+        }
+
+        for (key, value) in &context.custom_fields {
+            if let Some(value) = field_value_to_json(value) {
+                json.insert(key.clone(), value);
+            }
+        }
+
+        Self::Object(json)
+    }
+}
+
+pub(crate) fn field_value_to_json(
+    value: &EvaluationContextFieldValue,
+) -> Option<serde_json::Value> {
+    Some(match value {
+        EvaluationContextFieldValue::Bool(value) => (*value).into(),
+        EvaluationContextFieldValue::Int(value) => (*value).into(),
+        EvaluationContextFieldValue::Float(value) => (*value).into(),
+        EvaluationContextFieldValue::String(value) => value.clone().into(),
+        EvaluationContextFieldValue::DateTime(value) => value.unix_timestamp().into(),
+        EvaluationContextFieldValue::List(values) => {
+            serde_json::Value::Array(values.iter().filter_map(field_value_to_json).collect())
+        }
+        EvaluationContextFieldValue::Struct(value) => {
+            if let Some(value) = value.downcast_ref::<StructValue>() {
+                serde_json::Value::from(&Value::Struct(value.clone()))
+            } else {
+                serde_json::Value::from(value.downcast_ref::<Value>()?)
+            }
+        }
+    })
+}
+
 fn json_value_to_value(value: &serde_json::Value) -> EvaluationResult<Value> {
     match value {
         serde_json::Value::Bool(value) => Ok(Value::Bool(*value)),
@@ -108,6 +179,24 @@ mod tests {
         assert_eq!(expected_value, Value::try_from(json).unwrap());
     }
 
+    #[test]
+    fn convert_to_json() {
+        let value = Value::Struct(
+            StructValue::default()
+                .with_field("name", "Bob")
+                .with_field("phones", Value::Array(vec![123.into(), 45.5.into()])),
+        );
+
+        assert_eq!(
+            serde_json::Value::from(&value),
+            serde_json::json!({ "name": "Bob", "phones": [123, 45.5] })
+        );
+        assert_eq!(
+            Value::try_from(serde_json::Value::from(value.clone())).unwrap(),
+            value
+        );
+    }
+
     #[test]
     fn convert_invalid_data() {
         let json = serde_json::Value::Null;
@@ -0,0 +1,152 @@
+//! Assertions on evaluation results, failing with a snapshot of the details so that tests about
+//! flag behavior read and fail the same way.
+
+// Expected values are taken by value, as with `assert_eq!`.
+#![allow(clippy::needless_pass_by_value)]
+
+use std::fmt::Debug;
+
+use crate::{
+    EvaluationDetails, EvaluationErrorCode, EvaluationReason, EvaluationResult, FlagMetadataValue,
+};
+
+use super::{snapshot_text, SnapshotValue};
+
+/// Panic unless `details` hold `value`, resolved for `reason`.
+///
+/// ```
+/// use open_feature::{testing::assertions::assert_resolved, EvaluationDetails, EvaluationReason};
+///
+/// let details = EvaluationDetails {
+///     flag_key: "checkout-v2".to_string(),
+///     value: true,
+///     reason: Some(EvaluationReason::TargetingMatch),
+///     ..Default::default()
+/// };
+///
+/// assert_resolved(&details, true, EvaluationReason::TargetingMatch);
+/// ```
+#[track_caller]
+pub fn assert_resolved<T>(
+    details: &EvaluationDetails<T>,
+    value: impl Into<T>,
+    reason: EvaluationReason,
+) where
+    T: SnapshotValue + PartialEq + Debug,
+{
+    let value = value.into();
+
+    assert!(
+        details.value == value && details.reason.as_ref() == Some(&reason),
+        "Expected flag \"{}\" to resolve {:?} for reason {}, got:\n{}",
+        details.flag_key,
+        value,
+        reason.to_string(),
+        snapshot_text(details)
+    );
+}
+
+/// Panic unless `details` were resolved for `reason`.
+#[track_caller]
+pub fn assert_reason<T: SnapshotValue>(details: &EvaluationDetails<T>, reason: EvaluationReason) {
+    assert!(
+        details.reason.as_ref() == Some(&reason),
+        "Expected flag \"{}\" to resolve for reason {}, got:\n{}",
+        details.flag_key,
+        reason.to_string(),
+        snapshot_text(details)
+    );
+}
+
+/// Panic unless `details` resolved `variant`.
+#[track_caller]
+pub fn assert_variant<T: SnapshotValue>(details: &EvaluationDetails<T>, variant: &str) {
+    assert!(
+        details.variant.as_deref() == Some(variant),
+        "Expected flag \"{}\" to resolve variant \"{}\", got:\n{}",
+        details.flag_key,
+        variant,
+        snapshot_text(details)
+    );
+}
+
+/// Panic unless the flag metadata of `details` hold `value` at `key`.
+#[track_caller]
+pub fn assert_metadata<T: SnapshotValue>(
+    details: &EvaluationDetails<T>,
+    key: &str,
+    value: impl Into<FlagMetadataValue>,
+) {
+    let value = value.into();
+
+    assert!(
+        details.flag_metadata.values.get(key) == Some(&value),
+        "Expected flag \"{}\" to have metadata {} = {:?}, got:\n{}",
+        details.flag_key,
+        key,
+        value,
+        snapshot_text(details)
+    );
+}
+
+/// Panic unless `result` failed with `code`.
+#[track_caller]
+pub fn assert_error<T: Debug>(result: &EvaluationResult<T>, code: EvaluationErrorCode) {
+    match result {
+        Err(error) if error.code == code => {}
+        Err(error) => panic!("Expected error {}, got error {}", code, error),
+        Ok(value) => panic!("Expected error {}, got {:?}", code, value),
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EvaluationError, FlagMetadata};
+
+    fn create_details() -> EvaluationDetails<String> {
+        EvaluationDetails {
+            flag_key: "tier".to_string(),
+            value: "gold".to_string(),
+            reason: Some(EvaluationReason::Split),
+            variant: Some("gold".to_string()),
+            flag_metadata: FlagMetadata::default().with_value("experiment", "tiers-2024"),
+        }
+    }
+
+    #[test]
+    fn pass_matching_details() {
+        let details = create_details();
+
+        assert_resolved(&details, "gold", EvaluationReason::Split);
+        assert_reason(&details, EvaluationReason::Split);
+        assert_variant(&details, "gold");
+        assert_metadata(&details, "experiment", "tiers-2024");
+
+        let result: EvaluationResult<bool> = Err(EvaluationError::builder()
+            .code(EvaluationErrorCode::FlagNotFound)
+            .build());
+        assert_error(&result, EvaluationErrorCode::FlagNotFound);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = r#"Expected flag "tier" to resolve "silver" for reason SPLIT, got:
+flag_key: "tier"
+value: "gold"
+reason: "SPLIT""#
+    )]
+    fn report_mismatching_details() {
+        assert_resolved(&create_details(), "silver", EvaluationReason::Split);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected error FLAG_NOT_FOUND, got 42")]
+    fn report_missing_error() {
+        assert_error(&Ok(42), EvaluationErrorCode::FlagNotFound);
+    }
+}
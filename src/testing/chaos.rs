@@ -0,0 +1,391 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use typed_builder::TypedBuilder;
+
+use crate::{
+    provider::{
+        EventEmitter, FeatureProvider, ProviderMetadata, ProviderStatus, ResolutionDetails,
+    },
+    EvaluationContext, EvaluationError, EvaluationErrorCode, EvaluationResult, ProviderError,
+    ProviderErrorKind, StructValue, TrackingEventDetails, Value,
+};
+
+// ============================================================
+//  ChaosConfig
+// ============================================================
+
+/// The faults injected by a [`ChaosProvider`].
+#[derive(Clone, TypedBuilder, Debug)]
+pub struct ChaosConfig {
+    /// The latency added to every resolution.
+    #[builder(default)]
+    pub latency: Duration,
+
+    /// The upper bound of a random latency added on top of `latency`.
+    #[builder(default)]
+    pub latency_jitter: Duration,
+
+    /// The probability (from `0.0` to `1.0`) that a resolution fails with `error_code`.
+    #[builder(default)]
+    pub error_rate: f64,
+
+    /// The error code of injected errors.
+    #[builder(default = EvaluationErrorCode::Provider(ProviderError::new(
+        ProviderErrorKind::Unavailable,
+        "Injected by chaos provider",
+    )))]
+    pub error_code: EvaluationErrorCode,
+
+    /// The probability (from `0.0` to `1.0`) that a resolution fails as if the flag was stored
+    /// with another type.
+    #[builder(default)]
+    pub wrong_type_rate: f64,
+
+    /// The seed of the random generator, to make a failing run reproducible.
+    #[builder(default, setter(strip_option))]
+    pub seed: Option<u64>,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+// ============================================================
+//  ChaosProvider
+// ============================================================
+
+/// A provider that delegates to `inner` while injecting latency and faults, to exercise the
+/// resilience of the code built on top of it.
+pub struct ChaosProvider<P> {
+    inner: P,
+    config: ChaosConfig,
+    enabled: AtomicBool,
+    rng: Mutex<StdRng>,
+}
+
+/// The outcome rolled for a single resolution.
+enum Fault {
+    None,
+    Error,
+    WrongType,
+}
+
+impl<P: FeatureProvider> ChaosProvider<P> {
+    /// Create a new instance injecting faults described by `config` into `inner`.
+    pub fn new(inner: P, config: ChaosConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Self {
+            inner,
+            config,
+            enabled: AtomicBool::new(true),
+            rng: Mutex::new(rng),
+        }
+    }
+
+    /// Turn fault injection on or off. When off, calls are delegated untouched.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Return `true` if faults are being injected.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Wait for the injected latency and roll the fault of current resolution.
+    async fn inject(&self) -> Fault {
+        if !self.is_enabled() {
+            return Fault::None;
+        }
+
+        let (jitter, roll) = {
+            let mut rng = self.rng.lock().unwrap();
+
+            let jitter = if self.config.latency_jitter.is_zero() {
+                Duration::ZERO
+            } else {
+                rng.gen_range(Duration::ZERO..=self.config.latency_jitter)
+            };
+
+            (jitter, rng.gen::<f64>())
+        };
+
+        let latency = self.config.latency + jitter;
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+
+        if roll < self.config.error_rate {
+            Fault::Error
+        } else if roll < self.config.error_rate + self.config.wrong_type_rate {
+            Fault::WrongType
+        } else {
+            Fault::None
+        }
+    }
+
+    fn fault_error<T>(&self, fault: &Fault, flag_key: &str) -> Option<EvaluationResult<T>> {
+        match fault {
+            Fault::None => None,
+            Fault::Error => Some(Err(EvaluationError::builder()
+                .code(self.config.error_code.clone())
+                .message(format!("Chaos provider failed resolving flag {}", flag_key))
+                .build())),
+            Fault::WrongType => Some(Err(EvaluationError::builder()
+                .code(EvaluationErrorCode::TypeMismatch)
+                .message(format!(
+                    "Chaos provider resolved flag {} with an unexpected type",
+                    flag_key
+                ))
+                .build())),
+        }
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for ChaosProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.inner.initialize(context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.inner
+            .track(event_name, evaluation_context, tracking_event_details)
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        let fault = self.inject().await;
+        match self.fault_error(&fault, flag_key) {
+            Some(error) => error,
+            None => {
+                self.inner
+                    .resolve_bool_value(flag_key, evaluation_context)
+                    .await
+            }
+        }
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        let fault = self.inject().await;
+        match self.fault_error(&fault, flag_key) {
+            Some(error) => error,
+            None => {
+                self.inner
+                    .resolve_int_value(flag_key, evaluation_context)
+                    .await
+            }
+        }
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        let fault = self.inject().await;
+        match self.fault_error(&fault, flag_key) {
+            Some(error) => error,
+            None => {
+                self.inner
+                    .resolve_float_value(flag_key, evaluation_context)
+                    .await
+            }
+        }
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        let fault = self.inject().await;
+        match self.fault_error(&fault, flag_key) {
+            Some(error) => error,
+            None => {
+                self.inner
+                    .resolve_string_value(flag_key, evaluation_context)
+                    .await
+            }
+        }
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        let fault = self.inject().await;
+        match self.fault_error(&fault, flag_key) {
+            Some(error) => error,
+            None => {
+                self.inner
+                    .resolve_struct_value(flag_key, evaluation_context)
+                    .await
+            }
+        }
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.inner.resolve_all(evaluation_context).await
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::provider::MockFeatureProvider;
+
+    fn create_inner() -> MockFeatureProvider {
+        let mut inner = MockFeatureProvider::new();
+        inner.expect_initialize().returning(|_| Ok(()));
+        inner
+            .expect_resolve_int_value()
+            .return_const(Ok(ResolutionDetails::new(100)));
+        inner
+    }
+
+    #[tokio::test]
+    async fn no_fault_by_default() {
+        let provider = ChaosProvider::new(create_inner(), ChaosConfig::default());
+
+        let result = provider
+            .resolve_int_value("key", &EvaluationContext::default())
+            .await;
+
+        assert_eq!(result.unwrap().value, 100);
+    }
+
+    #[tokio::test]
+    async fn inject_errors() {
+        let provider = ChaosProvider::new(
+            create_inner(),
+            ChaosConfig::builder()
+                .error_rate(1.0)
+                .error_code(EvaluationErrorCode::FlagNotFound)
+                .build(),
+        );
+
+        let error = provider
+            .resolve_int_value("key", &EvaluationContext::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+    }
+
+    #[tokio::test]
+    async fn inject_wrong_type() {
+        let provider = ChaosProvider::new(
+            create_inner(),
+            ChaosConfig::builder().wrong_type_rate(1.0).build(),
+        );
+
+        let error = provider
+            .resolve_int_value("key", &EvaluationContext::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.code, EvaluationErrorCode::TypeMismatch);
+    }
+
+    #[tokio::test]
+    async fn inject_latency() {
+        let provider = ChaosProvider::new(
+            create_inner(),
+            ChaosConfig::builder()
+                .latency(Duration::from_millis(20))
+                .build(),
+        );
+
+        let start = Instant::now();
+        provider
+            .resolve_int_value("key", &EvaluationContext::default())
+            .await
+            .unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn disable_injection() {
+        let provider = ChaosProvider::new(
+            create_inner(),
+            ChaosConfig::builder().error_rate(1.0).build(),
+        );
+        provider.set_enabled(false);
+
+        assert!(provider
+            .resolve_int_value("key", &EvaluationContext::default())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn seeded_rolls_are_reproducible() {
+        let config = ChaosConfig::builder().error_rate(0.5).seed(42).build();
+        let first = ChaosProvider::new(create_inner(), config.clone());
+        let second = ChaosProvider::new(create_inner(), config);
+        let context = EvaluationContext::default();
+
+        for _ in 0..20 {
+            assert_eq!(
+                first.resolve_int_value("key", &context).await.is_ok(),
+                second.resolve_int_value("key", &context).await.is_ok()
+            );
+        }
+    }
+}
@@ -0,0 +1,387 @@
+use std::ops::Range;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{EvaluationContext, EvaluationContextFieldValue};
+
+const FIRST_NAMES: [&str; 12] = [
+    "alice", "bob", "carol", "dave", "erin", "frank", "grace", "heidi", "ivan", "judy", "mallory",
+    "oscar",
+];
+
+const LAST_NAMES: [&str; 10] = [
+    "smith", "johnson", "garcia", "martin", "muller", "rossi", "silva", "tanaka", "kim", "dubois",
+];
+
+const EMAIL_DOMAINS: [&str; 4] = ["example.com", "example.org", "mail.test", "corp.test"];
+
+/// ISO 3166-1 alpha-2 codes, roughly weighted by the size of an online population.
+const COUNTRIES: [(&str, u32); 10] = [
+    ("US", 30),
+    ("IN", 15),
+    ("BR", 10),
+    ("DE", 8),
+    ("GB", 8),
+    ("FR", 7),
+    ("JP", 7),
+    ("CL", 5),
+    ("CA", 5),
+    ("AU", 5),
+];
+
+const PLANS: [(&str, u32); 3] = [("free", 80), ("pro", 17), ("enterprise", 3)];
+
+// ============================================================
+//  ContextGenerator
+// ============================================================
+
+/// Generates randomized but realistic [`EvaluationContext`]s out of a declarative spec.
+///
+/// Given the same seed, the same sequence of contexts is generated, so a failing property test or
+/// simulation can be reproduced.
+///
+/// ```no_run
+/// use open_feature::{
+///     provider::InMemoryProvider,
+///     testing::{simulate, ContextGenerator, FieldGenerator},
+/// };
+///
+/// # async fn example() {
+/// # let provider = InMemoryProvider::default();
+/// let mut generator = ContextGenerator::default()
+///     .with_seed(42)
+///     .with_field("email", FieldGenerator::email())
+///     .with_field("country", FieldGenerator::country())
+///     .with_field("plan", FieldGenerator::plan())
+///     .with_field("age", FieldGenerator::int(18..80));
+///
+/// let report = simulate::<bool>(&provider, "checkout-v2", |index| generator.generate(index), 10_000)
+///     .await;
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ContextGenerator {
+    targeting_key: TargetingKeyGenerator,
+    fields: Vec<(String, FieldGenerator)>,
+    rng: StdRng,
+}
+
+impl Default for ContextGenerator {
+    fn default() -> Self {
+        Self {
+            targeting_key: TargetingKeyGenerator::default(),
+            fields: Vec::new(),
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl ContextGenerator {
+    /// Seed the random generator, making the generated contexts reproducible.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Set how targeting keys are generated.
+    #[must_use]
+    pub fn with_targeting_key(mut self, targeting_key: TargetingKeyGenerator) -> Self {
+        self.targeting_key = targeting_key;
+        self
+    }
+
+    /// Generate custom field `key` with `generator`. Fields are generated in the order they are
+    /// added.
+    #[must_use]
+    pub fn with_field(mut self, key: impl Into<String>, generator: FieldGenerator) -> Self {
+        self.fields.push((key.into(), generator));
+        self
+    }
+
+    /// Generate the context of the subject at `index` of the population.
+    pub fn generate(&mut self, index: usize) -> EvaluationContext {
+        let mut context = EvaluationContext::default();
+        context.targeting_key = self.targeting_key.generate(&mut self.rng, index);
+
+        for (key, generator) in &self.fields {
+            if let Some(value) = generator.generate(&mut self.rng) {
+                context.add_custom_field(key.clone(), value);
+            }
+        }
+
+        context
+    }
+
+    /// Generate the contexts of a population of `n` subjects.
+    pub fn generate_many(&mut self, n: usize) -> Vec<EvaluationContext> {
+        (0..n).map(|index| self.generate(index)).collect()
+    }
+}
+
+// ============================================================
+//  TargetingKeyGenerator
+// ============================================================
+
+/// How the targeting key of generated contexts is produced.
+#[derive(Clone, Debug)]
+pub enum TargetingKeyGenerator {
+    /// No targeting key.
+    None,
+
+    /// The given prefix followed by the index of the subject, such as `user-42`.
+    Sequential(String),
+
+    /// A random UUID-like identifier.
+    Random,
+}
+
+impl Default for TargetingKeyGenerator {
+    fn default() -> Self {
+        Self::Sequential("user-".to_string())
+    }
+}
+
+impl TargetingKeyGenerator {
+    fn generate(&self, rng: &mut StdRng, index: usize) -> Option<String> {
+        match self {
+            Self::None => None,
+            Self::Sequential(prefix) => Some(format!("{}{}", prefix, index)),
+            Self::Random => {
+                let high: u64 = rng.gen();
+                let low: u64 = rng.gen();
+
+                Some(format!(
+                    "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+                    high >> 32,
+                    (high >> 16) & 0xffff,
+                    high & 0xffff,
+                    low >> 48,
+                    low & 0xffff_ffff_ffff
+                ))
+            }
+        }
+    }
+}
+
+// ============================================================
+//  FieldGenerator
+// ============================================================
+
+/// How the value of a custom field of generated contexts is produced.
+#[derive(Clone, Debug)]
+pub enum FieldGenerator {
+    /// Always the same value.
+    Constant(EvaluationContextFieldValue),
+
+    /// One of the values, picked with a probability proportional to its weight.
+    OneOf(Vec<(EvaluationContextFieldValue, u32)>),
+
+    /// An int picked uniformly in the range.
+    Int(Range<i64>),
+
+    /// A float picked uniformly in the range.
+    Float(Range<f64>),
+
+    /// `true` with given probability.
+    Bool(f64),
+
+    /// An email address at one of given domains.
+    Email(Vec<String>),
+
+    /// The field is only set with given probability, and generated by the inner generator.
+    Optional(f64, Box<FieldGenerator>),
+}
+
+impl FieldGenerator {
+    /// One of `values`, all equally likely.
+    pub fn one_of<I, V>(values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<EvaluationContextFieldValue>,
+    {
+        Self::OneOf(values.into_iter().map(|value| (value.into(), 1)).collect())
+    }
+
+    /// One of `values`, each picked with a probability proportional to its weight.
+    pub fn weighted<I, V>(values: I) -> Self
+    where
+        I: IntoIterator<Item = (V, u32)>,
+        V: Into<EvaluationContextFieldValue>,
+    {
+        Self::OneOf(
+            values
+                .into_iter()
+                .map(|(value, weight)| (value.into(), weight))
+                .collect(),
+        )
+    }
+
+    /// An int picked uniformly in `range`.
+    pub fn int(range: Range<i64>) -> Self {
+        Self::Int(range)
+    }
+
+    /// A bool being `true` with given `probability`.
+    pub fn bool(probability: f64) -> Self {
+        Self::Bool(probability)
+    }
+
+    /// An email address such as `alice.garcia42@example.com`.
+    pub fn email() -> Self {
+        Self::Email(EMAIL_DOMAINS.iter().map(ToString::to_string).collect())
+    }
+
+    /// A country code, weighted towards the most populated markets.
+    pub fn country() -> Self {
+        Self::weighted(COUNTRIES)
+    }
+
+    /// A subscription plan among `free`, `pro` and `enterprise`, most subjects being on `free`.
+    pub fn plan() -> Self {
+        Self::weighted(PLANS)
+    }
+
+    /// Only set the field with given `probability`.
+    #[must_use]
+    pub fn optional(self, probability: f64) -> Self {
+        Self::Optional(probability, Box::new(self))
+    }
+
+    fn generate(&self, rng: &mut StdRng) -> Option<EvaluationContextFieldValue> {
+        let value = match self {
+            Self::Constant(value) => value.clone(),
+            Self::OneOf(values) => {
+                let total: u32 = values.iter().map(|(_, weight)| weight).sum();
+                if total == 0 {
+                    return None;
+                }
+
+                let mut roll = rng.gen_range(0..total);
+                let (value, _) = values
+                    .iter()
+                    .find(|(_, weight)| {
+                        if roll < *weight {
+                            true
+                        } else {
+                            roll -= weight;
+                            false
+                        }
+                    })
+                    .unwrap();
+
+                value.clone()
+            }
+            Self::Int(range) => EvaluationContextFieldValue::Int(rng.gen_range(range.clone())),
+            Self::Float(range) => EvaluationContextFieldValue::Float(rng.gen_range(range.clone())),
+            Self::Bool(probability) => {
+                EvaluationContextFieldValue::Bool(rng.gen_bool(*probability))
+            }
+            Self::Email(domains) => {
+                let first = FIRST_NAMES[rng.gen_range(0..FIRST_NAMES.len())];
+                let last = LAST_NAMES[rng.gen_range(0..LAST_NAMES.len())];
+                let number = rng.gen_range(1..100);
+                let domain = &domains[rng.gen_range(0..domains.len())];
+
+                EvaluationContextFieldValue::String(format!(
+                    "{}.{}{}@{}",
+                    first, last, number, domain
+                ))
+            }
+            Self::Optional(probability, generator) => {
+                if !rng.gen_bool(*probability) {
+                    return None;
+                }
+
+                return generator.generate(rng);
+            }
+        };
+
+        Some(value)
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn create_generator(seed: u64) -> ContextGenerator {
+        ContextGenerator::default()
+            .with_seed(seed)
+            .with_field("email", FieldGenerator::email())
+            .with_field("country", FieldGenerator::country())
+            .with_field("plan", FieldGenerator::plan())
+            .with_field("age", FieldGenerator::int(18..80))
+            .with_field("beta", FieldGenerator::bool(0.5).optional(0.5))
+    }
+
+    #[test]
+    fn generate_fields() {
+        let contexts = create_generator(42).generate_many(100);
+
+        assert_eq!(contexts[7].targeting_key, Some("user-7".to_string()));
+
+        for context in &contexts {
+            let email = context.custom_fields["email"].as_str().unwrap();
+            assert!(email.contains('@'));
+
+            let country = context.custom_fields["country"].as_str().unwrap();
+            assert!(COUNTRIES.iter().any(|(code, _)| *code == country));
+
+            let age = context.custom_fields["age"].as_i64().unwrap();
+            assert!((18..80).contains(&age));
+        }
+
+        assert!(contexts
+            .iter()
+            .any(|context| context.custom_fields.contains_key("beta")));
+        assert!(contexts
+            .iter()
+            .any(|context| !context.custom_fields.contains_key("beta")));
+    }
+
+    #[test]
+    fn seeded_generation_is_reproducible() {
+        assert_eq!(
+            create_generator(7).generate_many(20),
+            create_generator(7).generate_many(20)
+        );
+    }
+
+    #[test]
+    fn weighted_distribution() {
+        let mut generator = ContextGenerator::default()
+            .with_seed(1)
+            .with_targeting_key(TargetingKeyGenerator::None)
+            .with_field("plan", FieldGenerator::weighted([("free", 9), ("pro", 1)]));
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for context in generator.generate_many(10_000) {
+            assert!(context.targeting_key.is_none());
+
+            let plan = context.custom_fields["plan"].as_str().unwrap().to_string();
+            *counts.entry(plan).or_default() += 1;
+        }
+
+        assert!((8_700..9_300).contains(&counts["free"]));
+    }
+
+    #[test]
+    fn random_targeting_keys() {
+        let mut generator = ContextGenerator::default()
+            .with_seed(3)
+            .with_targeting_key(TargetingKeyGenerator::Random);
+
+        let first = generator.generate(0).targeting_key.unwrap();
+        let second = generator.generate(0).targeting_key.unwrap();
+
+        assert_eq!(first.len(), 36);
+        assert_ne!(first, second);
+    }
+}
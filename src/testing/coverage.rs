@@ -0,0 +1,488 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    provider::{
+        EventEmitter, FeatureProvider, ProviderMetadata, ProviderStatus, ResolutionDetails,
+    },
+    EvaluationContext, EvaluationResult, ProviderError, StructValue, TrackingEventDetails, Value,
+};
+
+// ============================================================
+//  CoverageCollector
+// ============================================================
+
+/// Collects the flags and variants exercised during a test run.
+///
+/// All the clones share the same records, so the collector can be handed to a
+/// [`CoverageProvider`] and inspected once the tests are done.
+#[derive(Clone, Default, Debug)]
+pub struct CoverageCollector {
+    state: Arc<Mutex<CoverageState>>,
+}
+
+#[derive(Default, Debug)]
+struct CoverageState {
+    expected: BTreeMap<String, BTreeSet<String>>,
+    exercised: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl CoverageCollector {
+    /// Declare the `variants` of `flag_key` that are required to be exercised.
+    pub fn expect_variants<I, S>(&self, flag_key: impl Into<String>, variants: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.state
+            .lock()
+            .unwrap()
+            .expected
+            .entry(flag_key.into())
+            .or_default()
+            .extend(variants.into_iter().map(Into::into));
+    }
+
+    /// Declare that both `true` and `false` of bool flag `flag_key` are required to be exercised.
+    pub fn expect_bool(&self, flag_key: impl Into<String>) {
+        self.expect_variants(flag_key, ["true", "false"]);
+    }
+
+    /// Record that `variant` of `flag_key` was resolved once.
+    pub fn record(&self, flag_key: impl Into<String>, variant: impl Into<String>) {
+        *self
+            .state
+            .lock()
+            .unwrap()
+            .exercised
+            .entry(flag_key.into())
+            .or_default()
+            .entry(variant.into())
+            .or_default() += 1;
+    }
+
+    /// Forget everything recorded so far, keeping the expectations.
+    pub fn reset(&self) {
+        self.state.lock().unwrap().exercised.clear();
+    }
+
+    /// Build a report out of the expectations and the records.
+    pub fn report(&self) -> CoverageReport {
+        let state = self.state.lock().unwrap();
+
+        let flag_keys: BTreeSet<&String> = state
+            .expected
+            .keys()
+            .chain(state.exercised.keys())
+            .collect();
+
+        let flags = flag_keys
+            .into_iter()
+            .map(|flag_key| {
+                let exercised = state.exercised.get(flag_key).cloned().unwrap_or_default();
+                let missing = state
+                    .expected
+                    .get(flag_key)
+                    .map(|expected| {
+                        expected
+                            .iter()
+                            .filter(|variant| !exercised.contains_key(*variant))
+                            .cloned()
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                FlagCoverage {
+                    flag_key: flag_key.clone(),
+                    exercised,
+                    missing,
+                }
+            })
+            .collect();
+
+        CoverageReport { flags }
+    }
+
+    /// Panic with the rendered report if any expected variant was not exercised.
+    pub fn assert_complete(&self) {
+        let report = self.report();
+
+        assert!(report.is_complete(), "{}", report);
+    }
+}
+
+// ============================================================
+//  CoverageReport
+// ============================================================
+
+/// The coverage of every flag either expected or exercised, ordered by flag key.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct CoverageReport {
+    /// The coverage of each flag.
+    pub flags: Vec<FlagCoverage>,
+}
+
+/// The coverage of a single flag.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct FlagCoverage {
+    /// The key of the flag.
+    pub flag_key: String,
+
+    /// The exercised variants along with the number of resolutions.
+    pub exercised: BTreeMap<String, usize>,
+
+    /// The expected variants that were never resolved.
+    pub missing: BTreeSet<String>,
+}
+
+impl FlagCoverage {
+    /// Return `true` if all the expected variants were exercised.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+impl CoverageReport {
+    /// Return `true` if all the expected variants of all the flags were exercised.
+    pub fn is_complete(&self) -> bool {
+        self.flags.iter().all(FlagCoverage::is_complete)
+    }
+
+    /// Return the coverage of given `flag_key`, if it was either expected or exercised.
+    pub fn flag(&self, flag_key: &str) -> Option<&FlagCoverage> {
+        self.flags.iter().find(|flag| flag.flag_key == flag_key)
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let complete = self.flags.iter().filter(|flag| flag.is_complete()).count();
+
+        writeln!(
+            f,
+            "Flag coverage: {}/{} flags fully covered",
+            complete,
+            self.flags.len()
+        )?;
+
+        for flag in &self.flags {
+            let mark = if flag.is_complete() { 'x' } else { ' ' };
+            let exercised = flag
+                .exercised
+                .iter()
+                .map(|(variant, count)| format!("{} ({})", variant, count))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            write!(f, "  [{}] {}: ", mark, flag.flag_key)?;
+
+            if exercised.is_empty() {
+                write!(f, "never evaluated")?;
+            } else {
+                write!(f, "{}", exercised)?;
+            }
+
+            if !flag.missing.is_empty() {
+                let missing = flag.missing.iter().cloned().collect::<Vec<_>>().join(", ");
+                write!(f, "; missing: {}", missing)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================
+//  CoverageProvider
+// ============================================================
+
+/// A provider that delegates to `inner` and records every successful resolution into a
+/// [`CoverageCollector`].
+///
+/// The variant returned by the provider is recorded, or the resolved value when it is not set.
+/// Bool flags are always recorded as their resolved value, `true` or `false`, as expected by
+/// [`CoverageCollector::expect_bool`], whatever their variants are named.
+pub struct CoverageProvider<P> {
+    inner: P,
+    collector: CoverageCollector,
+}
+
+impl<P: FeatureProvider> CoverageProvider<P> {
+    /// Create a new instance recording the resolutions of `inner` into `collector`.
+    pub fn new(inner: P, collector: CoverageCollector) -> Self {
+        Self { inner, collector }
+    }
+
+    /// Return the collector this provider records into.
+    pub fn collector(&self) -> &CoverageCollector {
+        &self.collector
+    }
+
+    fn record<T: VariantLabel>(
+        &self,
+        flag_key: &str,
+        result: &EvaluationResult<ResolutionDetails<T>>,
+    ) {
+        if let Ok(details) = result {
+            self.collector.record(flag_key, T::recorded(details));
+        }
+    }
+}
+
+#[async_trait]
+impl<P: FeatureProvider> FeatureProvider for CoverageProvider<P> {
+    async fn initialize(&mut self, context: &EvaluationContext) -> Result<(), ProviderError> {
+        self.inner.initialize(context).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+
+    fn status(&self) -> ProviderStatus {
+        self.inner.status()
+    }
+
+    fn metadata(&self) -> &ProviderMetadata {
+        self.inner.metadata()
+    }
+
+    fn event_emitter(&self) -> Option<EventEmitter> {
+        self.inner.event_emitter()
+    }
+
+    async fn track(
+        &self,
+        event_name: &str,
+        evaluation_context: &EvaluationContext,
+        tracking_event_details: &TrackingEventDetails,
+    ) {
+        self.inner
+            .track(event_name, evaluation_context, tracking_event_details)
+            .await;
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        let result = self
+            .inner
+            .resolve_bool_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, &result);
+        result
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        let result = self
+            .inner
+            .resolve_int_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, &result);
+        result
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        let result = self
+            .inner
+            .resolve_float_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, &result);
+        result
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        let result = self
+            .inner
+            .resolve_string_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, &result);
+        result
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        let result = self
+            .inner
+            .resolve_struct_value(flag_key, evaluation_context)
+            .await;
+        self.record(flag_key, &result);
+        result
+    }
+
+    async fn resolve_all(
+        &self,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<HashMap<String, ResolutionDetails<Value>>> {
+        self.inner.resolve_all(evaluation_context).await
+    }
+}
+
+/// The label used for a resolved value that comes without a variant.
+trait VariantLabel: Sized {
+    fn label(&self) -> String;
+
+    /// Return what is recorded for `details`: the variant, or the label of the value without one.
+    fn recorded(details: &ResolutionDetails<Self>) -> String {
+        match &details.variant {
+            Some(variant) => variant.clone(),
+            None => details.value.label(),
+        }
+    }
+}
+
+impl VariantLabel for bool {
+    fn label(&self) -> String {
+        self.to_string()
+    }
+
+    fn recorded(details: &ResolutionDetails<Self>) -> String {
+        details.value.label()
+    }
+}
+
+impl VariantLabel for i64 {
+    fn label(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl VariantLabel for f64 {
+    fn label(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl VariantLabel for String {
+    fn label(&self) -> String {
+        self.clone()
+    }
+}
+
+impl VariantLabel for StructValue {
+    fn label(&self) -> String {
+        "<struct>".to_string()
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::MockFeatureProvider;
+
+    #[test]
+    fn report_missing_variants() {
+        let collector = CoverageCollector::default();
+        collector.expect_bool("checkout-v2");
+        collector.expect_variants("tier", ["gold", "silver"]);
+
+        collector.record("checkout-v2", "true");
+        collector.record("checkout-v2", "true");
+        collector.record("tier", "gold");
+        collector.record("checkout-v2", "false");
+
+        let report = collector.report();
+        assert!(!report.is_complete());
+
+        let checkout = report.flag("checkout-v2").unwrap();
+        assert!(checkout.is_complete());
+        assert_eq!(checkout.exercised.get("true"), Some(&2));
+
+        let tier = report.flag("tier").unwrap();
+        assert_eq!(tier.missing, BTreeSet::from(["silver".to_string()]));
+
+        assert_eq!(
+            report.to_string(),
+            "Flag coverage: 1/2 flags fully covered\n  [x] checkout-v2: false (1), true (2)\n  [ ] tier: gold (1); missing: silver\n"
+        );
+    }
+
+    #[test]
+    fn report_never_evaluated() {
+        let collector = CoverageCollector::default();
+        collector.expect_bool("flag");
+
+        let report = collector.report();
+
+        assert_eq!(
+            report.flag("flag").unwrap().missing.len(),
+            2,
+            "both sides should be missing"
+        );
+        assert!(report.to_string().contains("never evaluated"));
+    }
+
+    #[test]
+    fn reset_keeps_expectations() {
+        let collector = CoverageCollector::default();
+        collector.expect_variants("tier", ["gold"]);
+        collector.record("tier", "gold");
+        collector.assert_complete();
+
+        collector.reset();
+
+        assert!(!collector.report().is_complete());
+    }
+
+    #[tokio::test]
+    async fn provider_records_resolutions() {
+        let mut inner = MockFeatureProvider::new();
+        inner.expect_initialize().returning(|_| Ok(()));
+        inner
+            .expect_resolve_bool_value()
+            .return_const(Ok(ResolutionDetails::builder()
+                .value(true)
+                .variant("on")
+                .build()));
+        inner
+            .expect_resolve_string_value()
+            .return_const(Ok(ResolutionDetails::builder()
+                .value("Gold".to_string())
+                .variant("gold")
+                .build()));
+
+        let collector = CoverageCollector::default();
+        collector.expect_variants("flag", ["true"]);
+        let provider = CoverageProvider::new(inner, collector.clone());
+        let context = EvaluationContext::default();
+
+        provider.resolve_bool_value("flag", &context).await.unwrap();
+        provider
+            .resolve_string_value("tier", &context)
+            .await
+            .unwrap();
+
+        let report = collector.report();
+        assert!(report.flag("flag").unwrap().is_complete());
+        assert_eq!(report.flag("flag").unwrap().exercised.get("true"), Some(&1));
+        assert_eq!(report.flag("tier").unwrap().exercised.get("gold"), Some(&1));
+    }
+}
@@ -0,0 +1,567 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    provider::{FeatureProvider, ProviderMetadata, ResolutionDetails},
+    EvaluationContext, EvaluationDetails, EvaluationError, EvaluationErrorCode, EvaluationResult,
+    Hook, HookContext, HookStage, OpenFeature, StructValue, Value,
+};
+
+use super::provider_test_kit::ConformanceReport;
+
+// ============================================================
+//  InvocationLog
+// ============================================================
+
+/// A single invocation of a hook stage or of the provider.
+#[derive(Clone, Debug)]
+pub struct Invocation {
+    /// The name of the recording hook, or `provider`.
+    pub name: String,
+
+    /// The stage run, or `None` for the provider.
+    pub stage: Option<HookStage>,
+
+    /// The evaluation context seen by the invocation.
+    pub evaluation_context: EvaluationContext,
+}
+
+impl fmt::Display for Invocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.stage {
+            Some(stage) => write!(f, "{}:{}", self.name, stage),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+/// The ordered invocations of a harness run, shared by the recording hooks and the provider.
+#[derive(Clone, Default, Debug)]
+pub struct InvocationLog(Arc<Mutex<Vec<Invocation>>>);
+
+impl InvocationLog {
+    /// Return all the invocations so far.
+    pub fn invocations(&self) -> Vec<Invocation> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Return all the invocations so far rendered as `name:stage`, e.g. `client:before`.
+    pub fn entries(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    fn push(&self, name: &str, stage: Option<HookStage>, evaluation_context: &EvaluationContext) {
+        self.0.lock().unwrap().push(Invocation {
+            name: name.to_string(),
+            stage,
+            evaluation_context: evaluation_context.clone(),
+        });
+    }
+}
+
+// ============================================================
+//  RecordingHook
+// ============================================================
+
+/// A hook recording each stage it runs into an [`InvocationLog`], which can be told to fail or to
+/// return an evaluation context.
+pub struct RecordingHook {
+    name: String,
+    log: InvocationLog,
+    failing_stage: Option<HookStage>,
+    evaluation_context: Option<EvaluationContext>,
+}
+
+impl RecordingHook {
+    /// Create a new hook recording into `log` under `name`.
+    pub fn new(name: impl Into<String>, log: &InvocationLog) -> Self {
+        Self {
+            name: name.into(),
+            log: log.clone(),
+            failing_stage: None,
+            evaluation_context: None,
+        }
+    }
+
+    /// Make the hook fail in `stage`. Only [`HookStage::Before`] and [`HookStage::After`] are
+    /// able to report an error, the others are ignored.
+    #[must_use]
+    pub fn failing_in(mut self, stage: HookStage) -> Self {
+        self.failing_stage = Some(stage);
+        self
+    }
+
+    /// Make the `before` stage return `evaluation_context`.
+    #[must_use]
+    pub fn returning_context(mut self, evaluation_context: EvaluationContext) -> Self {
+        self.evaluation_context = Some(evaluation_context);
+        self
+    }
+
+    fn record(&self, stage: HookStage, context: &HookContext<'_>) -> Result<(), EvaluationError> {
+        self.log
+            .push(&self.name, Some(stage), context.evaluation_context);
+
+        if self.failing_stage == Some(stage) {
+            Err(EvaluationError::builder()
+                .code(EvaluationErrorCode::General(format!(
+                    "{} failed in {}",
+                    self.name, stage
+                )))
+                .build())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[async_trait]
+impl Hook for RecordingHook {
+    async fn before<'a>(
+        &self,
+        context: &HookContext<'a>,
+    ) -> Result<Option<EvaluationContext>, EvaluationError> {
+        self.record(HookStage::Before, context)?;
+        Ok(self.evaluation_context.clone())
+    }
+
+    async fn after<'a>(
+        &self,
+        context: &HookContext<'a>,
+        _details: &EvaluationDetails<Value>,
+    ) -> Result<(), EvaluationError> {
+        self.record(HookStage::After, context)
+    }
+
+    async fn error<'a>(&self, context: &HookContext<'a>, _error: &EvaluationError) {
+        let _ = self.record(HookStage::Error, context);
+    }
+
+    async fn finally<'a>(&self, context: &HookContext<'a>) {
+        let _ = self.record(HookStage::Finally, context);
+    }
+}
+
+// ============================================================
+//  HookHarness
+// ============================================================
+
+/// Drive the full hook pipeline of a client with controllable hooks and provider.
+///
+/// Combine [`RecordingHook`]s with the hook under test to check where it runs and what it sees:
+///
+/// ```no_run
+/// use open_feature::{
+///     testing::{HookHarness, RecordingHook},
+///     EvaluationErrorCode,
+/// };
+/// # use open_feature::{async_trait, Hook};
+/// #
+/// # #[derive(Default)]
+/// # struct MyValidationHook;
+/// #
+/// # #[async_trait]
+/// # impl Hook for MyValidationHook {}
+///
+/// # async fn example() {
+/// let harness = HookHarness::new();
+/// let first = RecordingHook::new("first", harness.log());
+/// let run = harness
+///     .with_hook(first)
+///     .with_hook(MyValidationHook::default())
+///     .with_provider_error(EvaluationErrorCode::FlagNotFound)
+///     .run()
+///     .await;
+///
+/// run.assert_entries(&["first:before", "provider", "first:error", "first:finally"]);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HookHarness {
+    log: InvocationLog,
+    hooks: Vec<Arc<dyn Hook>>,
+    provider_outcome: Result<bool, EvaluationErrorCode>,
+    evaluation_context: EvaluationContext,
+}
+
+impl HookHarness {
+    /// Create a new harness with no hook and a provider resolving `true`.
+    pub fn new() -> Self {
+        Self {
+            log: InvocationLog::default(),
+            hooks: Vec::new(),
+            provider_outcome: Ok(true),
+            evaluation_context: EvaluationContext::default(),
+        }
+    }
+
+    /// Return the log shared by the harness, to create [`RecordingHook`]s.
+    pub fn log(&self) -> &InvocationLog {
+        &self.log
+    }
+
+    /// Register `hook` on the client, after the already registered ones.
+    #[must_use]
+    pub fn with_hook(mut self, hook: impl Hook) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Make the provider resolve the flag to `value`.
+    #[must_use]
+    pub fn with_provider_value(mut self, value: bool) -> Self {
+        self.provider_outcome = Ok(value);
+        self
+    }
+
+    /// Make the provider fail with `code`.
+    #[must_use]
+    pub fn with_provider_error(mut self, code: EvaluationErrorCode) -> Self {
+        self.provider_outcome = Err(code);
+        self
+    }
+
+    /// Set the invocation evaluation context.
+    #[must_use]
+    pub fn with_evaluation_context(mut self, evaluation_context: EvaluationContext) -> Self {
+        self.evaluation_context = evaluation_context;
+        self
+    }
+
+    /// Evaluate a bool flag through the pipeline.
+    pub async fn run(&self) -> HookRun {
+        self.log.0.lock().unwrap().clear();
+
+        let mut api = OpenFeature::default();
+        api.set_provider(HarnessProvider {
+            metadata: ProviderMetadata::new("Hook Harness Provider"),
+            log: self.log.clone(),
+            outcome: self.provider_outcome.clone(),
+        })
+        .await
+        .unwrap();
+
+        let mut client = api.create_client();
+        for hook in &self.hooks {
+            client.add_hook(hook.clone());
+        }
+
+        let result = client
+            .get_bool_details(HARNESS_FLAG_KEY, Some(&self.evaluation_context), None)
+            .await;
+
+        HookRun {
+            invocations: self.log.invocations(),
+            result,
+        }
+    }
+
+    /// Check that the pipeline of the SDK follows the ordering, context merging and error
+    /// handling rules of the specification.
+    pub async fn verify_pipeline() -> ConformanceReport {
+        let mut report = ConformanceReport::default();
+
+        let harness = HookHarness::new();
+        let run = harness
+            .clone()
+            .with_hook(RecordingHook::new("a", harness.log()))
+            .with_hook(RecordingHook::new("b", harness.log()))
+            .run()
+            .await;
+        report.check("order/success", || {
+            run.check_entries(&[
+                "a:before",
+                "b:before",
+                "provider",
+                "b:after",
+                "a:after",
+                "b:finally",
+                "a:finally",
+            ])
+        });
+
+        let harness = HookHarness::new();
+        let run = harness
+            .clone()
+            .with_hook(RecordingHook::new("a", harness.log()))
+            .with_hook(RecordingHook::new("b", harness.log()))
+            .with_provider_error(EvaluationErrorCode::FlagNotFound)
+            .run()
+            .await;
+        report.check("order/provider_error", || {
+            run.check_entries(&[
+                "a:before",
+                "b:before",
+                "provider",
+                "b:error",
+                "a:error",
+                "b:finally",
+                "a:finally",
+            ])?;
+            run.check_error(&EvaluationErrorCode::FlagNotFound)
+        });
+
+        let harness = HookHarness::new();
+        let run = harness
+            .clone()
+            .with_hook(RecordingHook::new("a", harness.log()).failing_in(HookStage::Before))
+            .with_hook(RecordingHook::new("b", harness.log()))
+            .run()
+            .await;
+        report.check("order/before_error", || {
+            run.check_entries(&["a:before", "b:error", "a:error", "b:finally", "a:finally"])
+        });
+
+        let harness = HookHarness::new();
+        let run = harness
+            .clone()
+            .with_hook(RecordingHook::new("a", harness.log()))
+            .with_hook(RecordingHook::new("b", harness.log()).failing_in(HookStage::After))
+            .run()
+            .await;
+        report.check("order/after_error", || {
+            run.check_entries(&[
+                "a:before",
+                "b:before",
+                "provider",
+                "b:after",
+                "b:error",
+                "a:error",
+                "b:finally",
+                "a:finally",
+            ])?;
+            run.check_error(&EvaluationErrorCode::General(
+                "b failed in after".to_string(),
+            ))
+        });
+
+        let harness = HookHarness::new();
+        let run = harness
+            .clone()
+            .with_hook(
+                RecordingHook::new("a", harness.log())
+                    .returning_context(EvaluationContext::default().with_custom_field("key", "a")),
+            )
+            .with_hook(RecordingHook::new("b", harness.log()))
+            .with_evaluation_context(
+                EvaluationContext::default()
+                    .with_targeting_key("invocation")
+                    .with_custom_field("key", "invocation"),
+            )
+            .run()
+            .await;
+        report.check("context/before_merge", || {
+            let expected = EvaluationContext::default()
+                .with_targeting_key("invocation")
+                .with_custom_field("key", "a");
+
+            for name in ["b:before", "provider", "a:after", "a:finally"] {
+                let seen = run
+                    .context_of(name)
+                    .ok_or_else(|| format!("{} not run", name))?;
+                if *seen != expected {
+                    return Err(format!("{} saw {:?}", name, seen));
+                }
+            }
+
+            Ok(())
+        });
+
+        report
+    }
+}
+
+// ============================================================
+//  HookRun
+// ============================================================
+
+/// The outcome of a [`HookHarness`] run.
+#[derive(Debug)]
+pub struct HookRun {
+    /// The invocations of hook stages and provider, in order.
+    pub invocations: Vec<Invocation>,
+
+    /// The result returned to the caller.
+    pub result: EvaluationResult<EvaluationDetails<bool>>,
+}
+
+impl HookRun {
+    /// Return the invocations rendered as `name:stage`.
+    pub fn entries(&self) -> Vec<String> {
+        self.invocations.iter().map(ToString::to_string).collect()
+    }
+
+    /// Return the evaluation context seen by the first invocation rendered as `name`.
+    pub fn context_of(&self, name: &str) -> Option<&EvaluationContext> {
+        self.invocations
+            .iter()
+            .find(|invocation| invocation.to_string() == name)
+            .map(|invocation| &invocation.evaluation_context)
+    }
+
+    /// Panic if the invocations differ from `expected`.
+    pub fn assert_entries(&self, expected: &[&str]) {
+        if let Err(message) = self.check_entries(expected) {
+            panic!("{}", message);
+        }
+    }
+
+    fn check_entries(&self, expected: &[&str]) -> Result<(), String> {
+        let entries = self.entries();
+
+        if entries == expected {
+            Ok(())
+        } else {
+            Err(format!("invoked {:?}, expected {:?}", entries, expected))
+        }
+    }
+
+    fn check_error(&self, code: &EvaluationErrorCode) -> Result<(), String> {
+        match &self.result {
+            Err(error) if error.code == *code => Ok(()),
+            result => Err(format!("returned {:?}, expected error {:?}", result, code)),
+        }
+    }
+}
+
+// ============================================================
+//  HarnessProvider
+// ============================================================
+
+const HARNESS_FLAG_KEY: &str = "hook-harness-flag";
+
+struct HarnessProvider {
+    metadata: ProviderMetadata,
+    log: InvocationLog,
+    outcome: Result<bool, EvaluationErrorCode>,
+}
+
+fn type_mismatch<T>() -> EvaluationResult<ResolutionDetails<T>> {
+    Err(EvaluationError::builder()
+        .code(EvaluationErrorCode::TypeMismatch)
+        .build())
+}
+
+#[async_trait]
+impl FeatureProvider for HarnessProvider {
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        _flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.log.push("provider", None, evaluation_context);
+
+        match &self.outcome {
+            Ok(value) => Ok(ResolutionDetails::new(*value)),
+            Err(code) => Err(EvaluationError::builder().code(code.clone()).build()),
+        }
+    }
+
+    async fn resolve_int_value(
+        &self,
+        _flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        type_mismatch()
+    }
+
+    async fn resolve_float_value(
+        &self,
+        _flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        type_mismatch()
+    }
+
+    async fn resolve_string_value(
+        &self,
+        _flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        type_mismatch()
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        _flag_key: &str,
+        _evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        type_mismatch()
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use spec::spec;
+
+    use super::*;
+
+    #[spec(
+        number = "4.4.2",
+        text = "Hooks MUST be evaluated in the following order: before: API, Client, Invocation, Provider; after: Provider, Invocation, Client, API; error (if applicable): Provider, Invocation, Client, API; finally: Provider, Invocation, Client, API"
+    )]
+    #[spec(
+        number = "4.3.4",
+        text = "Any evaluation context returned from a before hook MUST be passed to subsequent before hooks (via HookContext)."
+    )]
+    #[spec(
+        number = "4.4.5",
+        text = "If an error occurs in the before or after hooks, the error hooks MUST be invoked."
+    )]
+    #[spec(
+        number = "4.4.6",
+        text = "If an error occurs during the evaluation of before or after hooks, any remaining hooks in the before or after stages MUST NOT be invoked."
+    )]
+    #[tokio::test]
+    async fn pipeline_conformance() {
+        HookHarness::verify_pipeline().await.assert_passed();
+    }
+
+    #[tokio::test]
+    async fn run_custom_hook() {
+        struct DenyHook;
+
+        #[async_trait]
+        impl Hook for DenyHook {
+            async fn before<'a>(
+                &self,
+                _context: &HookContext<'a>,
+            ) -> Result<Option<EvaluationContext>, EvaluationError> {
+                Err(EvaluationError::builder()
+                    .code(EvaluationErrorCode::InvalidContext)
+                    .build())
+            }
+        }
+
+        let harness = HookHarness::new();
+        let run = harness
+            .clone()
+            .with_hook(RecordingHook::new("outer", harness.log()))
+            .with_hook(DenyHook)
+            .run()
+            .await;
+
+        run.assert_entries(&["outer:before", "outer:error", "outer:finally"]);
+        assert_eq!(
+            run.result.unwrap_err().code,
+            EvaluationErrorCode::InvalidContext
+        );
+    }
+}
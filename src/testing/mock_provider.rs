@@ -0,0 +1,409 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    provider::{FeatureProvider, FlagType, FlagValue, ProviderMetadata, ResolutionDetails},
+    EvaluationContext, EvaluationContextFieldValue, EvaluationError, EvaluationErrorCode,
+    EvaluationReason, EvaluationResult, StructValue, Value,
+};
+
+// ============================================================
+//  MockProvider
+// ============================================================
+
+/// A provider resolving flags as stubbed by the test, and recording its calls, so that tests do
+/// not hand-roll fake providers.
+///
+/// Stubs added later take precedence, so that a test can override a general stub for some
+/// evaluation contexts. Flags without a matching stub are not found.
+///
+/// All the clones share the same stubs and calls, so the provider can be stubbed and inspected
+/// after being handed to the API.
+///
+/// ```
+/// use open_feature::{testing::MockProvider, EvaluationErrorCode};
+///
+/// let provider = MockProvider::new();
+/// provider.when_flag("checkout-v2").return_value(false);
+/// provider
+///     .when_flag("checkout-v2")
+///     .with_context_containing("plan", "pro")
+///     .with_variant("on")
+///     .return_value(true);
+/// provider
+///     .when_flag("legacy")
+///     .return_error(EvaluationErrorCode::FlagNotFound);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockProvider {
+    metadata: ProviderMetadata,
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default, Debug)]
+struct MockState {
+    stubs: Vec<Stub>,
+    calls: Vec<MockCall>,
+}
+
+/// A resolution recorded by a [`MockProvider`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct MockCall {
+    /// The key of the resolved flag.
+    pub flag_key: String,
+
+    /// The type the flag was resolved as.
+    pub flag_type: FlagType,
+
+    /// The evaluation context of the resolution.
+    pub evaluation_context: EvaluationContext,
+}
+
+impl Default for MockProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockProvider {
+    /// Create a provider with no stub.
+    pub fn new() -> Self {
+        Self {
+            metadata: ProviderMetadata::new("Mock Provider"),
+            state: Arc::default(),
+        }
+    }
+
+    /// Start stubbing the resolutions of `flag_key`.
+    pub fn when_flag(&self, flag_key: impl Into<String>) -> StubBuilder<'_> {
+        StubBuilder {
+            provider: self,
+            stub: Stub {
+                flag_key: flag_key.into(),
+                targeting_key: None,
+                fields: Vec::new(),
+                latency: Duration::ZERO,
+                variant: None,
+                reason: EvaluationReason::Static,
+                result: Ok(Value::Bool(false)),
+            },
+        }
+    }
+
+    /// Return the resolutions made so far.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// Return the number of resolutions of `flag_key` made so far.
+    pub fn call_count(&self, flag_key: &str) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .calls
+            .iter()
+            .filter(|call| call.flag_key == flag_key)
+            .count()
+    }
+
+    /// Panic unless `flag_key` was resolved exactly `times` times.
+    #[track_caller]
+    pub fn assert_called(&self, flag_key: &str, times: usize) {
+        let count = self.call_count(flag_key);
+
+        assert!(
+            count == times,
+            "Expected flag \"{}\" to be resolved {} times, but it was resolved {} times",
+            flag_key,
+            times,
+            count
+        );
+    }
+
+    /// Forget the resolutions made so far, keeping the stubs.
+    pub fn reset_calls(&self) {
+        self.state.lock().unwrap().calls.clear();
+    }
+
+    async fn resolve<T: FlagValue>(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<T>> {
+        let stub = {
+            let mut state = self.state.lock().unwrap();
+
+            state.calls.push(MockCall {
+                flag_key: flag_key.to_string(),
+                flag_type: T::FLAG_TYPE,
+                evaluation_context: evaluation_context.clone(),
+            });
+
+            state
+                .stubs
+                .iter()
+                .rev()
+                .find(|stub| stub.matches(flag_key, evaluation_context))
+                .cloned()
+        };
+
+        let Some(stub) = stub else {
+            return Err(EvaluationError::builder()
+                .code(EvaluationErrorCode::FlagNotFound)
+                .message(format!("Flag \"{}\" is not stubbed", flag_key))
+                .build());
+        };
+
+        if !stub.latency.is_zero() {
+            tokio::time::sleep(stub.latency).await;
+        }
+
+        let value = stub.result?;
+        let value = T::FLAG_TYPE
+            .cast(value)
+            .and_then(T::from_value)
+            .ok_or_else(|| {
+                EvaluationError::builder()
+                    .code(EvaluationErrorCode::TypeMismatch)
+                    .message(format!(
+                        "Flag \"{}\" is not stubbed as a {:?} flag",
+                        flag_key,
+                        T::FLAG_TYPE
+                    ))
+                    .build()
+            })?;
+
+        Ok(ResolutionDetails {
+            value,
+            variant: stub.variant,
+            reason: Some(stub.reason),
+            flag_metadata: None,
+        })
+    }
+}
+
+#[async_trait]
+impl FeatureProvider for MockProvider {
+    fn metadata(&self) -> &ProviderMetadata {
+        &self.metadata
+    }
+
+    async fn resolve_bool_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<bool>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_int_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<i64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_float_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<f64>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_string_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<String>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+
+    async fn resolve_struct_value(
+        &self,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<StructValue>> {
+        self.resolve(flag_key, evaluation_context).await
+    }
+}
+
+// ============================================================
+//  Stubs
+// ============================================================
+
+/// A stubbed resolution of a flag, for the evaluation contexts it matches.
+#[derive(Clone, Debug)]
+struct Stub {
+    flag_key: String,
+    targeting_key: Option<String>,
+    fields: Vec<(String, EvaluationContextFieldValue)>,
+    latency: Duration,
+    variant: Option<String>,
+    reason: EvaluationReason,
+    result: Result<Value, EvaluationError>,
+}
+
+impl Stub {
+    fn matches(&self, flag_key: &str, evaluation_context: &EvaluationContext) -> bool {
+        self.flag_key == flag_key
+            && self.targeting_key.as_ref().map_or(true, |targeting_key| {
+                evaluation_context.targeting_key.as_ref() == Some(targeting_key)
+            })
+            && self
+                .fields
+                .iter()
+                .all(|(key, value)| evaluation_context.custom_fields.get(key) == Some(value))
+    }
+}
+
+/// Describes a stub of a [`MockProvider`], which is added by one of the `return_*` functions.
+#[must_use = "the stub is only added by a return_* function"]
+pub struct StubBuilder<'a> {
+    provider: &'a MockProvider,
+    stub: Stub,
+}
+
+impl StubBuilder<'_> {
+    /// Only match evaluation contexts with custom field `key` set to `value`.
+    pub fn with_context_containing(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<EvaluationContextFieldValue>,
+    ) -> Self {
+        self.stub.fields.push((key.into(), value.into()));
+        self
+    }
+
+    /// Only match evaluation contexts with `targeting_key`.
+    pub fn with_targeting_key(mut self, targeting_key: impl Into<String>) -> Self {
+        self.stub.targeting_key = Some(targeting_key.into());
+        self
+    }
+
+    /// Wait for `latency` before resolving.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.stub.latency = latency;
+        self
+    }
+
+    /// Resolve with `variant`.
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        self.stub.variant = Some(variant.into());
+        self
+    }
+
+    /// Resolve with `reason` instead of `STATIC`.
+    pub fn with_reason(mut self, reason: EvaluationReason) -> Self {
+        self.stub.reason = reason;
+        self
+    }
+
+    /// Resolve the flag as `value`. Resolving it as another type fails with a type mismatch.
+    pub fn return_value(mut self, value: impl Into<Value>) {
+        self.stub.result = Ok(value.into());
+        self.add();
+    }
+
+    /// Fail resolving the flag with `code`.
+    pub fn return_error(mut self, code: EvaluationErrorCode) {
+        self.stub.result = Err(EvaluationError::builder()
+            .code(code)
+            .message("Stubbed error")
+            .build());
+        self.add();
+    }
+
+    fn add(self) {
+        self.provider.state.lock().unwrap().stubs.push(self.stub);
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::OpenFeature;
+
+    #[tokio::test]
+    async fn resolve_stubs() {
+        let provider = MockProvider::new();
+        provider.when_flag("checkout-v2").return_value(false);
+        provider
+            .when_flag("checkout-v2")
+            .with_context_containing("plan", "pro")
+            .with_variant("on")
+            .with_reason(EvaluationReason::TargetingMatch)
+            .return_value(true);
+        provider.when_flag("discount").return_value(10);
+
+        let mut api = OpenFeature::default();
+        api.set_provider(provider.clone()).await.unwrap();
+        let client = api.create_client();
+
+        let pro = EvaluationContext::default().with_custom_field("plan", "pro");
+        let details = client
+            .get_bool_details("checkout-v2", Some(&pro), None)
+            .await
+            .unwrap();
+        assert!(details.value);
+        assert_eq!(details.variant, Some("on".to_string()));
+        assert_eq!(details.reason, Some(EvaluationReason::TargetingMatch));
+
+        assert!(!client
+            .get_bool_value("checkout-v2", None, None)
+            .await
+            .unwrap());
+        assert_eq!(
+            client.get_int_value("discount", None, None).await.unwrap(),
+            10
+        );
+
+        let error = client
+            .get_string_value("discount", None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::TypeMismatch);
+
+        provider.assert_called("checkout-v2", 2);
+        assert_eq!(provider.calls()[0].evaluation_context, pro);
+    }
+
+    #[tokio::test]
+    async fn simulate_failures() {
+        let provider = MockProvider::new();
+        provider
+            .when_flag("legacy")
+            .with_latency(Duration::from_millis(20))
+            .return_error(EvaluationErrorCode::ProviderNotReady);
+
+        let context = EvaluationContext::default();
+
+        let start = Instant::now();
+        let error = provider
+            .resolve_bool_value("legacy", &context)
+            .await
+            .unwrap_err();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert_eq!(error.code, EvaluationErrorCode::ProviderNotReady);
+
+        let error = provider
+            .resolve_bool_value("unknown", &context)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, EvaluationErrorCode::FlagNotFound);
+
+        provider.reset_calls();
+        provider.assert_called("legacy", 0);
+    }
+}
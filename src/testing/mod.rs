@@ -0,0 +1,30 @@
+/// Fault injection for resilience testing.
+mod chaos;
+pub use chaos::{ChaosConfig, ChaosProvider};
+
+/// Randomized evaluation contexts.
+mod context_generator;
+pub use context_generator::{ContextGenerator, FieldGenerator, TargetingKeyGenerator};
+
+/// Flag and variant coverage reporting.
+mod coverage;
+pub use coverage::{CoverageCollector, CoverageProvider, CoverageReport, FlagCoverage};
+
+/// Conformance harness of the hook pipeline.
+mod hook_harness;
+pub use hook_harness::{HookHarness, HookRun, Invocation, InvocationLog, RecordingHook};
+
+/// A provider resolving stubbed flags.
+mod mock_provider;
+pub use mock_provider::{MockCall, MockProvider, StubBuilder};
+
+/// Canonical rendering of evaluation details for snapshot tests.
+mod snapshot;
+pub use snapshot::{snapshot_json, snapshot_text, SnapshotFormatter, SnapshotValue};
+
+/// Rollout simulation over synthetic populations.
+mod simulation;
+pub use simulation::{simulate, SimulationReport};
+
+pub mod assertions;
+pub mod provider_test_kit;
@@ -0,0 +1,402 @@
+//! A reusable conformance suite for [`FeatureProvider`] implementations.
+//!
+//! Provider authors describe a few flags their provider serves, and the kit checks the provider
+//! against the expectations of the specification: metadata, lifecycle, typed resolution, and
+//! error codes.
+//!
+//! ```no_run
+//! use open_feature::{
+//!     provider::{InMemoryFlag, InMemoryProvider},
+//!     testing::provider_test_kit::ProviderTestKit,
+//! };
+//!
+//! #[tokio::test]
+//! async fn conformance() {
+//!     ProviderTestKit::new(|| {
+//!         InMemoryProvider::default()
+//!             .with_flag("enabled", InMemoryFlag::with_value(true))
+//!             .with_flag("tier", InMemoryFlag::with_value("gold"))
+//!     })
+//!     .with_flag("enabled", true)
+//!     .with_flag("tier", "gold")
+//!     .run()
+//!     .await
+//!     .assert_passed();
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::{
+    provider::{FeatureProvider, FlagValue, ProviderStatus, ResolutionDetails},
+    EvaluationContext, EvaluationErrorCode, EvaluationReason, EvaluationResult, StructValue, Value,
+};
+
+// ============================================================
+//  ProviderTestKit
+// ============================================================
+
+/// The conformance suite. See the [module documentation](self).
+pub struct ProviderTestKit<F> {
+    factory: F,
+    flags: Vec<(String, Value)>,
+    missing_flag_key: String,
+    evaluation_context: EvaluationContext,
+}
+
+impl<F, P> ProviderTestKit<F>
+where
+    F: Fn() -> P,
+    P: FeatureProvider,
+{
+    /// Create a new suite. `factory` is called whenever a fresh provider instance is needed.
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            flags: Vec::new(),
+            missing_flag_key: "provider-test-kit-missing-flag".to_string(),
+            evaluation_context: EvaluationContext::default(),
+        }
+    }
+
+    /// Declare that the provider resolves `flag_key` to `value`. The type of `value` tells which
+    /// resolution function is expected to succeed.
+    #[must_use]
+    pub fn with_flag(mut self, flag_key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.flags.push((flag_key.into(), value.into()));
+        self
+    }
+
+    /// Set the key of a flag the provider does not know. A reasonable default is used otherwise.
+    #[must_use]
+    pub fn with_missing_flag_key(mut self, flag_key: impl Into<String>) -> Self {
+        self.missing_flag_key = flag_key.into();
+        self
+    }
+
+    /// Set the evaluation context used for initialization and resolution.
+    #[must_use]
+    pub fn with_evaluation_context(mut self, evaluation_context: EvaluationContext) -> Self {
+        self.evaluation_context = evaluation_context;
+        self
+    }
+
+    /// Run all the checks and return the outcome of each.
+    pub async fn run(&self) -> ConformanceReport {
+        let mut report = ConformanceReport::default();
+
+        let mut provider = (self.factory)();
+
+        report.check("metadata", || {
+            if provider.metadata().name.is_empty() {
+                Err("metadata name is empty".to_string())
+            } else {
+                Ok(())
+            }
+        });
+
+        let result = provider.initialize(&self.evaluation_context).await;
+
+        let status = provider.status();
+        report.check("lifecycle/initialize", || match result {
+            Err(error) => Err(format!("initialization failed: {error}")),
+            Ok(()) if status != ProviderStatus::Ready => {
+                Err(format!("status is {:?} after initialization", status))
+            }
+            Ok(()) => Ok(()),
+        });
+
+        for (flag_key, expected) in &self.flags {
+            self.check_flag(&mut report, &provider, flag_key, expected)
+                .await;
+        }
+
+        for kind in FlagKind::ALL {
+            let result = kind
+                .resolve(&provider, &self.missing_flag_key, &self.evaluation_context)
+                .await;
+
+            report.check(&format!("flag_not_found/{}", kind.name()), || {
+                expect_error(result, &EvaluationErrorCode::FlagNotFound)
+            });
+        }
+
+        report
+    }
+
+    async fn check_flag(
+        &self,
+        report: &mut ConformanceReport,
+        provider: &P,
+        flag_key: &str,
+        expected: &Value,
+    ) {
+        let Some(kind) = FlagKind::of(expected) else {
+            report.check(&format!("resolve/{}", flag_key), || {
+                Err("array values cannot be resolved as a flag".to_string())
+            });
+            return;
+        };
+
+        let result = kind
+            .resolve(provider, flag_key, &self.evaluation_context)
+            .await;
+
+        report.check(&format!("resolve/{}", flag_key), || match result {
+            Ok(details) if details.reason == Some(EvaluationReason::Error) => {
+                Err("reason is ERROR in a successful resolution".to_string())
+            }
+            Ok(details) if details.value != *expected => Err(format!(
+                "resolved {:?}, expected {:?}",
+                details.value, expected
+            )),
+            Ok(_) => Ok(()),
+            Err(error) => Err(format!("resolution failed: {:?}", error)),
+        });
+
+        let result = kind
+            .mismatching()
+            .resolve(provider, flag_key, &self.evaluation_context)
+            .await;
+
+        report.check(&format!("type_mismatch/{}", flag_key), || {
+            expect_error(result, &EvaluationErrorCode::TypeMismatch)
+        });
+    }
+}
+
+fn expect_error(
+    result: EvaluationResult<ResolutionDetails<Value>>,
+    code: &EvaluationErrorCode,
+) -> Result<(), String> {
+    match result {
+        Err(error) if error.code == *code => Ok(()),
+        Err(error) => Err(format!("error code is {}, expected {}", error.code, code)),
+        Ok(details) => Err(format!(
+            "resolved {:?}, expected error {}",
+            details.value, code
+        )),
+    }
+}
+
+// ============================================================
+//  FlagKind
+// ============================================================
+
+#[derive(Clone, Copy)]
+enum FlagKind {
+    Bool,
+    Int,
+    Float,
+    String,
+    Struct,
+}
+
+impl FlagKind {
+    const ALL: [FlagKind; 5] = [
+        FlagKind::Bool,
+        FlagKind::Int,
+        FlagKind::Float,
+        FlagKind::String,
+        FlagKind::Struct,
+    ];
+
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bool(_) => Some(Self::Bool),
+            Value::Int(_) => Some(Self::Int),
+            Value::Float(_) => Some(Self::Float),
+            Value::String(_) => Some(Self::String),
+            Value::Struct(_) => Some(Self::Struct),
+            Value::Array(_) => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::String => "string",
+            Self::Struct => "struct",
+        }
+    }
+
+    /// Another kind no provider is expected to convert this kind into.
+    fn mismatching(self) -> Self {
+        match self {
+            Self::Bool => Self::Int,
+            _ => Self::Bool,
+        }
+    }
+
+    async fn resolve(
+        self,
+        provider: &dyn FeatureProvider,
+        flag_key: &str,
+        evaluation_context: &EvaluationContext,
+    ) -> EvaluationResult<ResolutionDetails<Value>> {
+        match self {
+            Self::Bool => resolve_as::<bool>(provider, flag_key, evaluation_context).await,
+            Self::Int => resolve_as::<i64>(provider, flag_key, evaluation_context).await,
+            Self::Float => resolve_as::<f64>(provider, flag_key, evaluation_context).await,
+            Self::String => resolve_as::<String>(provider, flag_key, evaluation_context).await,
+            Self::Struct => resolve_as::<StructValue>(provider, flag_key, evaluation_context).await,
+        }
+    }
+}
+
+async fn resolve_as<T: FlagValue>(
+    provider: &dyn FeatureProvider,
+    flag_key: &str,
+    evaluation_context: &EvaluationContext,
+) -> EvaluationResult<ResolutionDetails<Value>> {
+    let details = T::resolve(provider, flag_key, evaluation_context).await?;
+
+    Ok(ResolutionDetails {
+        value: details.value.to_value(),
+        variant: details.variant,
+        reason: details.reason,
+        flag_metadata: details.flag_metadata,
+    })
+}
+
+// ============================================================
+//  ConformanceReport
+// ============================================================
+
+/// The outcome of a [`ProviderTestKit`] run.
+#[derive(Clone, Default, Debug)]
+pub struct ConformanceReport {
+    /// The name of each check along with its failure message, if any.
+    pub checks: Vec<(String, Result<(), String>)>,
+}
+
+impl ConformanceReport {
+    pub(crate) fn check(&mut self, name: &str, check: impl FnOnce() -> Result<(), String>) {
+        self.checks.push((name.to_string(), check()));
+    }
+
+    /// Return `true` if all the checks passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|(_, outcome)| outcome.is_ok())
+    }
+
+    /// Return the failed checks along with their messages.
+    pub fn failures(&self) -> Vec<(&str, &str)> {
+        self.checks
+            .iter()
+            .filter_map(|(name, outcome)| match outcome {
+                Ok(()) => None,
+                Err(message) => Some((name.as_str(), message.as_str())),
+            })
+            .collect()
+    }
+
+    /// Panic with the rendered report if any check failed.
+    pub fn assert_passed(&self) {
+        assert!(self.passed(), "{}", self);
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failures = self.failures();
+
+        writeln!(
+            f,
+            "Provider conformance: {}/{} checks passed",
+            self.checks.len() - failures.len(),
+            self.checks.len()
+        )?;
+
+        for (name, message) in failures {
+            writeln!(f, "  {}: {}", name, message)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        provider::{MockFeatureProvider, NoOpProvider, ProviderMetadata},
+        EvaluationError,
+    };
+
+    fn error<T>(code: EvaluationErrorCode) -> EvaluationResult<T> {
+        Err(EvaluationError::builder().code(code).build())
+    }
+
+    fn create_provider() -> MockFeatureProvider {
+        let mut provider = MockFeatureProvider::new();
+        provider
+            .expect_metadata()
+            .return_const(ProviderMetadata::new("Test Provider"));
+        provider.expect_initialize().returning(|_| Ok(()));
+        provider.expect_status().returning(|| ProviderStatus::Ready);
+        provider
+            .expect_resolve_bool_value()
+            .returning(|flag_key, _| match flag_key {
+                "enabled" => Ok(ResolutionDetails::new(true)),
+                "tier" => error(EvaluationErrorCode::TypeMismatch),
+                _ => error(EvaluationErrorCode::FlagNotFound),
+            });
+        provider
+            .expect_resolve_int_value()
+            .returning(|flag_key, _| match flag_key {
+                "enabled" => error(EvaluationErrorCode::TypeMismatch),
+                _ => error(EvaluationErrorCode::FlagNotFound),
+            });
+        provider
+            .expect_resolve_string_value()
+            .returning(|flag_key, _| match flag_key {
+                "tier" => Ok(ResolutionDetails::new("gold")),
+                _ => error(EvaluationErrorCode::FlagNotFound),
+            });
+        provider
+            .expect_resolve_float_value()
+            .returning(|_, _| error(EvaluationErrorCode::FlagNotFound));
+        provider
+            .expect_resolve_struct_value()
+            .returning(|_, _| error(EvaluationErrorCode::FlagNotFound));
+        provider
+    }
+
+    #[tokio::test]
+    async fn conformant_provider() {
+        let report = ProviderTestKit::new(create_provider)
+            .with_flag("enabled", true)
+            .with_flag("tier", "gold")
+            .run()
+            .await;
+
+        report.assert_passed();
+        assert_eq!(report.checks.len(), 11);
+    }
+
+    #[tokio::test]
+    async fn report_failures() {
+        let report = ProviderTestKit::new(NoOpProvider::default)
+            .with_flag("enabled", true)
+            .run()
+            .await;
+
+        assert!(!report.passed());
+
+        let failures: Vec<&str> = report
+            .failures()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert!(failures.contains(&"lifecycle/initialize"));
+        assert!(failures.contains(&"resolve/enabled"));
+        assert!(failures.contains(&"flag_not_found/bool"));
+    }
+}
@@ -0,0 +1,234 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    provider::{FeatureProvider, FlagValue},
+    EvaluationContext, Value,
+};
+
+// ============================================================
+//  simulate
+// ============================================================
+
+/// Evaluate `flag_key` as `T` against `n` contexts produced by `population`, and report how the
+/// population is distributed among variants.
+///
+/// `population` is called with the index of each synthetic subject (from `0` to `n - 1`).
+///
+/// ```no_run
+/// use open_feature::{provider::InMemoryProvider, testing::simulate, EvaluationContext};
+///
+/// # async fn example() {
+/// # let provider = InMemoryProvider::default();
+/// let report = simulate::<bool>(&provider, "checkout-v2", |index| {
+///     EvaluationContext::default().with_targeting_key(format!("user-{}", index))
+/// }, 10_000)
+/// .await;
+///
+/// assert!((report.share("on") - 0.1).abs() < 0.01);
+/// # }
+/// ```
+pub async fn simulate<T: FlagValue>(
+    provider: &dyn FeatureProvider,
+    flag_key: &str,
+    mut population: impl FnMut(usize) -> EvaluationContext,
+    n: usize,
+) -> SimulationReport {
+    let mut report = SimulationReport {
+        flag_key: flag_key.to_string(),
+        evaluations: n,
+        ..Default::default()
+    };
+
+    for index in 0..n {
+        let context = population(index);
+
+        match T::resolve(provider, flag_key, &context).await {
+            Ok(details) => {
+                let variant = match details.variant {
+                    Some(variant) => variant,
+                    None => value_label(&details.value.to_value()),
+                };
+                let reason = details.reason.unwrap_or_default().to_string();
+
+                *report.variants.entry(variant).or_default() += 1;
+                *report.reasons.entry(reason).or_default() += 1;
+            }
+            Err(error) => {
+                *report.errors.entry(error.code.to_string()).or_default() += 1;
+            }
+        }
+    }
+
+    report
+}
+
+fn value_label(value: &Value) -> String {
+    match value {
+        Value::Bool(value) => value.to_string(),
+        Value::Int(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::String(value) => value.clone(),
+        Value::Array(_) => "<array>".to_string(),
+        Value::Struct(_) => "<struct>".to_string(),
+    }
+}
+
+// ============================================================
+//  SimulationReport
+// ============================================================
+
+/// The outcome of a [`simulate`] run.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct SimulationReport {
+    /// The key of simulated flag.
+    pub flag_key: String,
+
+    /// The number of evaluations.
+    pub evaluations: usize,
+
+    /// The number of subjects served by each variant. Values resolved without a variant are
+    /// counted under their own rendering (e.g. `true`).
+    pub variants: BTreeMap<String, usize>,
+
+    /// The number of successful evaluations per reason, which tells which kind of rule fired.
+    pub reasons: BTreeMap<String, usize>,
+
+    /// The number of failed evaluations per error code.
+    pub errors: BTreeMap<String, usize>,
+}
+
+impl SimulationReport {
+    /// Return the share (from `0.0` to `1.0`) of the population served by `variant`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn share(&self, variant: &str) -> f64 {
+        if self.evaluations == 0 {
+            return 0.0;
+        }
+
+        self.variants.get(variant).copied().unwrap_or_default() as f64 / self.evaluations as f64
+    }
+
+    /// Return the number of failed evaluations.
+    pub fn error_count(&self) -> usize {
+        self.errors.values().sum()
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percentage = |count: usize| {
+            if self.evaluations == 0 {
+                0.0
+            } else {
+                count as f64 * 100.0 / self.evaluations as f64
+            }
+        };
+
+        writeln!(
+            f,
+            "Simulated flag {} over {} contexts",
+            self.flag_key, self.evaluations
+        )?;
+
+        for (title, counts) in [
+            ("variants", &self.variants),
+            ("reasons", &self.reasons),
+            ("errors", &self.errors),
+        ] {
+            if counts.is_empty() {
+                continue;
+            }
+
+            writeln!(f, "  {}:", title)?;
+
+            for (key, count) in counts {
+                writeln!(f, "    {}: {} ({:.1}%)", key, count, percentage(*count))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        provider::{MockFeatureProvider, ResolutionDetails},
+        EvaluationError, EvaluationErrorCode, EvaluationReason,
+    };
+
+    fn create_provider() -> MockFeatureProvider {
+        let mut provider = MockFeatureProvider::new();
+        provider
+            .expect_resolve_bool_value()
+            .returning(|_, context| {
+                let index: usize = context
+                    .targeting_key
+                    .as_deref()
+                    .and_then(|key| key.parse().ok())
+                    .ok_or_else(|| {
+                        EvaluationError::builder()
+                            .code(EvaluationErrorCode::TargetingKeyMissing)
+                            .build()
+                    })?;
+
+                Ok(ResolutionDetails::builder()
+                    .value(index % 10 == 0)
+                    .variant(if index % 10 == 0 { "on" } else { "off" })
+                    .reason(EvaluationReason::Split)
+                    .build())
+            });
+        provider
+    }
+
+    #[tokio::test]
+    async fn report_distribution() {
+        let provider = create_provider();
+
+        let report = simulate::<bool>(
+            &provider,
+            "checkout-v2",
+            |index| EvaluationContext::default().with_targeting_key(index.to_string()),
+            1000,
+        )
+        .await;
+
+        assert_eq!(report.evaluations, 1000);
+        assert_eq!(report.variants.get("on"), Some(&100));
+        assert_eq!(report.variants.get("off"), Some(&900));
+        assert_eq!(report.reasons.get("SPLIT"), Some(&1000));
+        assert_eq!(report.error_count(), 0);
+        assert!((report.share("on") - 0.1).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn report_errors() {
+        let provider = create_provider();
+
+        let report = simulate::<bool>(
+            &provider,
+            "checkout-v2",
+            |index| {
+                if index % 2 == 0 {
+                    EvaluationContext::default()
+                } else {
+                    EvaluationContext::default().with_targeting_key(index.to_string())
+                }
+            },
+            10,
+        )
+        .await;
+
+        assert_eq!(report.errors.get("TARGETING_KEY_MISSING"), Some(&5));
+        assert_eq!(
+            report.to_string(),
+            "Simulated flag checkout-v2 over 10 contexts\n  variants:\n    off: 5 (50.0%)\n  reasons:\n    SPLIT: 5 (50.0%)\n  errors:\n    TARGETING_KEY_MISSING: 5 (50.0%)\n"
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use crate::{EvaluationDetails, FlagMetadataValue, StructValue, Value};
+
+// ============================================================
+//  SnapshotValue
+// ============================================================
+
+/// Types of evaluated values that can be rendered into a snapshot.
+pub trait SnapshotValue {
+    /// Convert `self` into a [`Value`] to be rendered.
+    fn snapshot_value(&self) -> Value;
+}
+
+impl SnapshotValue for bool {
+    fn snapshot_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+impl SnapshotValue for i64 {
+    fn snapshot_value(&self) -> Value {
+        Value::Int(*self)
+    }
+}
+
+impl SnapshotValue for f64 {
+    fn snapshot_value(&self) -> Value {
+        Value::Float(*self)
+    }
+}
+
+impl SnapshotValue for String {
+    fn snapshot_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl SnapshotValue for StructValue {
+    fn snapshot_value(&self) -> Value {
+        Value::Struct(self.clone())
+    }
+}
+
+impl SnapshotValue for Value {
+    fn snapshot_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+// ============================================================
+//  SnapshotFormatter
+// ============================================================
+
+/// Render [`EvaluationDetails`] into a canonical representation suitable for snapshot testing.
+///
+/// Fields are always written in the same order and map entries are sorted by key, so that the
+/// output only changes when the evaluation result does. Volatile flag metadata (such as
+/// timestamps) can be redacted.
+#[derive(Clone, Default, Debug)]
+pub struct SnapshotFormatter {
+    redacted_metadata: BTreeSet<String>,
+}
+
+impl SnapshotFormatter {
+    /// Replace the value of flag metadata `key` with a fixed placeholder.
+    #[must_use]
+    pub fn redact_metadata(mut self, key: impl Into<String>) -> Self {
+        self.redacted_metadata.insert(key.into());
+        self
+    }
+
+    /// Render `details` as an indented, YAML-like text block.
+    pub fn to_text<T: SnapshotValue>(&self, details: &EvaluationDetails<T>) -> String {
+        let mut out = String::new();
+
+        if let Node::Map(fields) = self.to_node(details) {
+            for (key, value) in &fields {
+                write_text_entry(&mut out, key, value, 0);
+            }
+        }
+
+        out
+    }
+
+    /// Render `details` as pretty-printed JSON.
+    pub fn to_json<T: SnapshotValue>(&self, details: &EvaluationDetails<T>) -> String {
+        let mut out = String::new();
+        write_json(&mut out, &self.to_node(details), 0);
+        out.push('\n');
+        out
+    }
+
+    fn to_node<T: SnapshotValue>(&self, details: &EvaluationDetails<T>) -> Node {
+        let mut metadata: Vec<(String, Node)> = details
+            .flag_metadata
+            .values
+            .iter()
+            .map(|(key, value)| {
+                let node = if self.redacted_metadata.contains(key) {
+                    Node::Redacted
+                } else {
+                    Node::from(value)
+                };
+
+                (key.clone(), node)
+            })
+            .collect();
+        metadata.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        Node::Map(vec![
+            (
+                "flag_key".to_string(),
+                Node::String(details.flag_key.clone()),
+            ),
+            (
+                "value".to_string(),
+                Node::from(&details.value.snapshot_value()),
+            ),
+            (
+                "reason".to_string(),
+                details
+                    .reason
+                    .as_ref()
+                    .map_or(Node::Null, |reason| Node::String(reason.to_string())),
+            ),
+            (
+                "variant".to_string(),
+                details
+                    .variant
+                    .as_ref()
+                    .map_or(Node::Null, |variant| Node::String(variant.clone())),
+            ),
+            ("flag_metadata".to_string(), Node::Map(metadata)),
+        ])
+    }
+}
+
+/// Render `details` with the default [`SnapshotFormatter`] as text.
+pub fn snapshot_text<T: SnapshotValue>(details: &EvaluationDetails<T>) -> String {
+    SnapshotFormatter::default().to_text(details)
+}
+
+/// Render `details` with the default [`SnapshotFormatter`] as JSON.
+pub fn snapshot_json<T: SnapshotValue>(details: &EvaluationDetails<T>) -> String {
+    SnapshotFormatter::default().to_json(details)
+}
+
+// ============================================================
+//  Rendering
+// ============================================================
+
+const REDACTED: &str = "[redacted]";
+
+enum Node {
+    Null,
+    Redacted,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Node>),
+    Map(Vec<(String, Node)>),
+}
+
+impl From<&Value> for Node {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Bool(value) => Node::Bool(*value),
+            Value::Int(value) => Node::Int(*value),
+            Value::Float(value) => Node::Float(*value),
+            Value::String(value) => Node::String(value.clone()),
+            Value::Array(array) => Node::Array(array.iter().map(Node::from).collect()),
+            Value::Struct(value) => {
+                let mut fields: Vec<(String, Node)> = value
+                    .fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Node::from(value)))
+                    .collect();
+                fields.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+                Node::Map(fields)
+            }
+        }
+    }
+}
+
+impl From<&FlagMetadataValue> for Node {
+    fn from(value: &FlagMetadataValue) -> Self {
+        match value {
+            FlagMetadataValue::Bool(value) => Node::Bool(*value),
+            FlagMetadataValue::Int(value) => Node::Int(*value),
+            FlagMetadataValue::Float(value) => Node::Float(*value),
+            FlagMetadataValue::String(value) => Node::String(value.clone()),
+        }
+    }
+}
+
+fn write_scalar(out: &mut String, node: &Node) {
+    match node {
+        Node::Null => out.push_str("null"),
+        Node::Redacted => write_quoted(out, REDACTED),
+        Node::Bool(value) => write!(out, "{}", value).unwrap(),
+        Node::Int(value) => write!(out, "{}", value).unwrap(),
+        // Debug keeps the decimal point, so floats never render like integers.
+        Node::Float(value) if value.is_finite() => write!(out, "{:?}", value).unwrap(),
+        Node::Float(value) => write_quoted(out, &value.to_string()),
+        Node::String(value) => write_quoted(out, value),
+        Node::Array(_) | Node::Map(_) => unreachable!("not a scalar"),
+    }
+}
+
+fn write_quoted(out: &mut String, value: &str) {
+    out.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", u32::from(c)).unwrap(),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
+
+fn write_text_entry(out: &mut String, key: &str, value: &Node, indent: usize) {
+    write!(out, "{:indent$}{}:", "", key, indent = indent).unwrap();
+    write_text_value(out, value, indent);
+}
+
+fn write_text_value(out: &mut String, value: &Node, indent: usize) {
+    match value {
+        Node::Array(items) if items.is_empty() => out.push_str(" []\n"),
+        Node::Map(fields) if fields.is_empty() => out.push_str(" {}\n"),
+        Node::Array(items) => {
+            out.push('\n');
+
+            for item in items {
+                write!(out, "{:indent$}-", "", indent = indent + 2).unwrap();
+                write_text_value(out, item, indent + 2);
+            }
+        }
+        Node::Map(fields) => {
+            out.push('\n');
+
+            for (key, value) in fields {
+                write_text_entry(out, key, value, indent + 2);
+            }
+        }
+        scalar => {
+            out.push(' ');
+            write_scalar(out, scalar);
+            out.push('\n');
+        }
+    }
+}
+
+fn write_json(out: &mut String, value: &Node, indent: usize) {
+    match value {
+        Node::Array(items) if items.is_empty() => out.push_str("[]"),
+        Node::Map(fields) if fields.is_empty() => out.push_str("{}"),
+        Node::Array(items) => {
+            out.push_str("[\n");
+
+            for (index, item) in items.iter().enumerate() {
+                write!(out, "{:indent$}", "", indent = indent + 2).unwrap();
+                write_json(out, item, indent + 2);
+                out.push_str(if index + 1 < items.len() { ",\n" } else { "\n" });
+            }
+
+            write!(out, "{:indent$}]", "", indent = indent).unwrap();
+        }
+        Node::Map(fields) => {
+            out.push_str("{\n");
+
+            for (index, (key, value)) in fields.iter().enumerate() {
+                write!(out, "{:indent$}", "", indent = indent + 2).unwrap();
+                write_quoted(out, key);
+                out.push_str(": ");
+                write_json(out, value, indent + 2);
+                out.push_str(if index + 1 < fields.len() {
+                    ",\n"
+                } else {
+                    "\n"
+                });
+            }
+
+            write!(out, "{:indent$}}}", "", indent = indent).unwrap();
+        }
+        scalar => write_scalar(out, scalar),
+    }
+}
+
+// ============================================================
+//  Tests
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EvaluationReason, FlagMetadata};
+
+    fn create_details() -> EvaluationDetails<StructValue> {
+        EvaluationDetails {
+            flag_key: "checkout-v2".to_string(),
+            value: StructValue::default()
+                .with_field("theme", "dark")
+                .with_field("limits", vec![1, 2])
+                .with_field("ratio", 1.0),
+            reason: Some(EvaluationReason::TargetingMatch),
+            variant: Some("on".to_string()),
+            flag_metadata: FlagMetadata::default()
+                .with_value("updated_at", "2024-05-29T10:00:00Z")
+                .with_value("owner", "payments"),
+        }
+    }
+
+    #[test]
+    fn render_text() {
+        let text = SnapshotFormatter::default()
+            .redact_metadata("updated_at")
+            .to_text(&create_details());
+
+        assert_eq!(
+            text,
+            r#"flag_key: "checkout-v2"
+value:
+  limits:
+    - 1
+    - 2
+  ratio: 1.0
+  theme: "dark"
+reason: "TARGETING_MATCH"
+variant: "on"
+flag_metadata:
+  owner: "payments"
+  updated_at: "[redacted]"
+"#
+        );
+    }
+
+    #[test]
+    fn render_json() {
+        let json = snapshot_json(&EvaluationDetails {
+            flag_key: "tier".to_string(),
+            value: "gold \"plus\"".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            json,
+            r#"{
+  "flag_key": "tier",
+  "value": "gold \"plus\"",
+  "reason": null,
+  "variant": null,
+  "flag_metadata": {}
+}
+"#
+        );
+    }
+
+    #[test]
+    fn render_is_stable() {
+        let details = create_details();
+
+        for _ in 0..10 {
+            assert_eq!(snapshot_text(&details), snapshot_text(&create_details()));
+        }
+    }
+}